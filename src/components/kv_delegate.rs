@@ -120,6 +120,8 @@ pub struct ZedisKvDelegate<T: ZedisKvFetcher> {
     value_states: HashMap<usize, Entity<InputState>>,
     /// Flag to ensure focus is applied only once when entering edit mode.
     edit_focus_done: bool,
+    /// Whether the server refuses writes, disabling the edit and remove actions.
+    readonly: bool,
 }
 
 impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
@@ -173,6 +175,7 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
             processing: Rc::new(Cell::new(false)),
             editing_row: Cell::new(None),
             edit_focus_done: false,
+            readonly: false,
         }
     }
 
@@ -188,6 +191,16 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
         self.processing = Rc::new(Cell::new(false));
     }
 
+    /// Whether the server refuses writes.
+    pub fn is_readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// Disables the edit and remove actions when the server refuses writes.
+    pub fn set_readonly(&mut self, readonly: bool) {
+        self.readonly = readonly;
+    }
+
     /// Updates table column layout after the editor content area changes size.
     pub fn update_column_layout(&mut self, columns: Vec<KvTableColumn>) -> bool {
         if columns.len() != self.columns.len() {
@@ -318,7 +331,7 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
                 .mr_2()
                 .icon(icon)
                 .tooltip(i18n_common(cx, "update_tooltip"))
-                .disabled(processing.get())
+                .disabled(self.readonly || processing.get())
                 .on_click(cx.listener(move |this, _, window, cx| {
                     if is_editing {
                         this.delegate_mut().handle_update_row(row_ix, window, cx);
@@ -358,7 +371,7 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
                 .ghost()
                 .icon(Icon::new(CustomIconName::FileXCorner))
                 .tooltip(i18n_common(cx, "remove_tooltip"))
-                .disabled(processing.get())
+                .disabled(self.readonly || processing.get())
                 .on_click(cx.listener(move |this, _, window, cx| {
                     let processing = this.delegate_mut().processing.clone();
                     let fetcher = fetcher.clone();
@@ -522,7 +535,7 @@ impl<T: ZedisKvFetcher + 'static> TableDelegate for ZedisKvDelegate<T> {
         menu.item(
             PopupMenuItem::new(label)
                 .icon(Icon::new(CustomIconName::FileXCorner))
-                .disabled(processing.get())
+                .disabled(self.readonly || processing.get())
                 .on_click(move |_, window, cx| {
                     let rows = rows.clone();
                     let processing = processing.clone();
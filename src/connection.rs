@@ -13,12 +13,35 @@
 // limitations under the License.
 
 mod async_connection;
+mod command_script;
 mod config;
+mod hot_keys;
+mod json_keys;
+mod known_hosts;
 mod manager;
+mod memory_analysis;
+mod pubsub;
+mod rdb;
+mod rdb_server;
+mod sentinel;
+mod ssh_auth;
 mod ssh_cluster_connection;
+mod ssh_config;
 mod ssh_stream;
 mod ssh_tunnel;
 
 pub use async_connection::{AuthSource, RedisAsyncConn};
-pub use config::{QueryMode, RedisServer, get_servers, save_servers};
-pub use manager::{KeyBackupProgress, KeyBackupProgressPhase, RedisClientDescription, get_connection_manager};
+pub use command_script::ScriptImportSummary;
+pub use config::{QueryMode, RedisServer, format_jump_hosts, get_servers, parse_jump_hosts, save_servers};
+pub use hot_keys::{HotKey, HotKeyMode, HotKeyReport, NodeHotKeys};
+pub use json_keys::KeyExportFormat;
+pub use known_hosts::{SshHostKey, remove_host_key, trust_host_key, trusted_host_keys};
+pub use manager::{
+    BulkKeyPhase, BulkKeyProgress, BulkKeys, ClusterNodeInfo, KeyBackupProgress, KeyBackupProgressPhase,
+    KeyConflictPolicy, KeyDeleteSummary, KeyTtlSummary, NodeRedirect, NodeReply, NodeRole, NodeTarget,
+    RedisClientDescription, TtlAction, TtlCondition, get_connection_manager,
+};
+pub use memory_analysis::{DEFAULT_TOP_KEYS, MemoryAnalysisOptions, MemoryReport, length_command};
+pub use pubsub::{PubSubMessage, PubSubSession, SubscriptionKind};
+pub use rdb_server::{close_rdb_file, is_rdb_file, open_rdb_file};
+pub use sentinel::{SentinelMaster, list_sentinel_masters, parse_sentinel_addrs};
@@ -18,9 +18,10 @@ use super::ssh_tunnel::open_single_ssh_tunnel_connection;
 use crate::error::Error;
 use crate::states::PresetCredential;
 use dashmap::DashMap;
-use futures::future::try_join_all;
+use futures::future::{join_all, try_join_all};
 use redis::{
-    AsyncConnectionConfig, Client, Cmd, FromRedisValue, Pipeline, RedisFuture, Value,
+    AsyncConnectionConfig, Client, Cmd, FromRedisValue, IntoConnectionInfo, Pipeline, ProtocolVersion, RedisFuture,
+    Value,
     aio::{ConnectionLike, MultiplexedConnection},
     cluster_async::ClusterConnection,
     cmd,
@@ -131,6 +132,30 @@ fn open_single_client(config: &RedisServer) -> Result<Client> {
     Ok(client)
 }
 
+/// Creates a Redis client that speaks the given protocol version.
+///
+/// Used for connections that rely on RESP3 push messages, such as Pub/Sub.
+///
+/// # Arguments
+///
+/// * `config` - Redis server configuration
+/// * `protocol` - The RESP version negotiated with `HELLO`
+///
+/// # Returns
+///
+/// A Redis client ready to establish connections
+pub(crate) fn open_single_client_with_protocol(config: &RedisServer, protocol: ProtocolVersion) -> Result<Client> {
+    let info = config.get_connection_url().into_connection_info()?;
+    let settings = info.redis_settings().clone().set_protocol(protocol);
+    let info = info.set_redis_settings(settings);
+    let client = if let Some(certificates) = config.tls_certificates() {
+        Client::build_with_tls(info, certificates)?
+    } else {
+        Client::open(info)?
+    };
+    Ok(client)
+}
+
 /// Check if an error is an authentication error
 pub fn is_auth_error(e: &Error) -> bool {
     let msg = e.to_string();
@@ -269,27 +294,59 @@ pub(crate) async fn query_async_masters<T: FromRedisValue>(
     let first_cmd = cmds.first().ok_or_else(|| Error::Invalid {
         message: "Commands are empty".to_string(),
     })?;
-    let tasks = addrs.into_iter().enumerate().map(|(index, addr)| {
-        // Clone data to move ownership into the async block.
-        // let addr = addr.to_string();
+    let tasks = addrs.iter().enumerate().map(|(index, addr)| {
         // Use the specific command for this index, or fallback to the first command.
-        let current_cmd = cmds.get(index).unwrap_or(first_cmd).clone();
-
-        async move {
-            if let Some(delay) = *DELAY {
-                smol::Timer::after(delay).await;
-            }
-            // Establish a multiplexed async connection to the specific node.
-            let mut conn = open_single_connection(&addr, db).await?;
-
-            // Execute the command asynchronously.
-            let value: T = current_cmd.query_async(&mut conn).await?;
-
-            Ok::<T, Error>(value)
-        }
+        let current_cmd = cmds.get(index).unwrap_or(first_cmd);
+        query_node(addr, db, current_cmd)
     });
 
     let values = try_join_all(tasks).await?;
 
     Ok(values)
 }
+
+/// Queries multiple Redis nodes concurrently, keeping the result of every node.
+///
+/// Unlike [`query_async_masters`], a failing node does not fail the whole query,
+/// so callers can show the reply or error of each node side by side.
+///
+/// # Arguments
+///
+/// * `addrs` - The nodes to query.
+/// * `db` - Database number to select.
+/// * `cmd` - The command executed on every node.
+///
+/// # Returns
+///
+/// The result of each node, in the order of `addrs`.
+pub(crate) async fn query_async_nodes(addrs: Vec<RedisServer>, db: usize, cmd: Cmd) -> Vec<Result<Value>> {
+    let tasks = addrs.iter().map(|addr| query_node(addr, db, &cmd));
+    join_all(tasks).await
+}
+
+/// Opens a connection to a node, on database `db`, and executes the command on it.
+async fn query_node<T: FromRedisValue>(addr: &RedisServer, db: usize, cmd: &Cmd) -> Result<T> {
+    let mut conn = node_connection(addr, db).await?;
+    let value: T = cmd.query_async(&mut conn).await?;
+    Ok(value)
+}
+
+/// Executes a pipeline on a node, through the connection of [`query_node`].
+pub(crate) async fn query_node_pipeline<T: FromRedisValue>(
+    addr: &RedisServer,
+    db: usize,
+    pipeline: &Pipeline,
+) -> Result<T> {
+    let mut conn = node_connection(addr, db).await?;
+    let value: T = pipeline.query_async(&mut conn).await?;
+    Ok(value)
+}
+
+/// Pooled connection to a node, on database `db`.
+async fn node_connection(addr: &RedisServer, db: usize) -> Result<MultiplexedConnection> {
+    if let Some(delay) = *DELAY {
+        smol::Timer::after(delay).await;
+    }
+    // Establish a multiplexed async connection to the specific node.
+    open_single_connection(addr, db).await
+}
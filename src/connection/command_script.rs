@@ -0,0 +1,431 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Replayable scripts of Redis commands.
+//!
+//! Keys are exported as the commands recreating them (`DEL`, `SET`, `RPUSH`, `SADD`,
+//! `ZADD`, `HSET`, `XADD`, `PEXPIREAT`), one command per line with the quoting of
+//! `redis-cli`, so the files can be diffed, reviewed and replayed with `redis-cli` or
+//! imported back. Empty lines and lines starting with `#` are ignored.
+
+use super::{
+    async_connection::RedisAsyncConn,
+    json_keys::{JsonKeyRecord, JsonKeyValue},
+    manager::{BulkKeyPhase, BulkKeyProgress, ConnectionManager},
+};
+use crate::{error::Error, states::PresetCredential};
+use chrono::Utc;
+use futures::channel::mpsc::UnboundedSender;
+use redis::{Cmd, Value, pipe};
+use std::{
+    io::Write,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use tracing::info;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+/// Line number and arguments of a command of a script
+type ScriptCommand = (usize, Vec<Vec<u8>>);
+
+/// Number of values written per command when a collection is exported
+const SCRIPT_CHUNK_SIZE: usize = 100;
+/// Number of commands sent in one pipeline when a script is imported
+const SCRIPT_BATCH_SIZE: usize = 500;
+/// Number of line errors kept in the import report
+const SCRIPT_ERROR_LIMIT: usize = 100;
+
+/// Result of `import_keys_script`.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptImportSummary {
+    pub file_path: String,
+    pub executed: usize,
+    pub failed: usize,
+    /// Line number and error of the first failed lines
+    pub errors: Vec<(usize, String)>,
+    /// Whether the operation was cancelled before every command was sent
+    pub cancelled: bool,
+}
+
+impl ScriptImportSummary {
+    fn add_error(&mut self, line: usize, error: String) {
+        self.failed += 1;
+        if self.errors.len() < SCRIPT_ERROR_LIMIT {
+            self.errors.push((line, error));
+        }
+    }
+}
+
+/// Quotes an argument like `redis-cli`: plain words are kept as is, anything else is
+/// double quoted with escapes for quotes, control characters and invalid UTF-8.
+fn quote_arg(arg: &[u8]) -> String {
+    let bare = !arg.is_empty()
+        && arg[0] != b'#'
+        && arg
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() && !matches!(byte, b'"' | b'\'' | b'\\'));
+    if bare {
+        return String::from_utf8_lossy(arg).into_owned();
+    }
+    let mut quoted = String::with_capacity(arg.len() + 2);
+    quoted.push('"');
+    for chunk in arg.utf8_chunks() {
+        for ch in chunk.valid().chars() {
+            match ch {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                '\x07' => quoted.push_str("\\a"),
+                '\x08' => quoted.push_str("\\b"),
+                ch if ch.is_ascii_control() => quoted.push_str(&format!("\\x{:02x}", ch as u32)),
+                ch => quoted.push(ch),
+            }
+        }
+        for byte in chunk.invalid() {
+            quoted.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Splits a line into arguments like `redis-cli`, the reverse of `quote_arg`.
+///
+/// Double quoted arguments support `\n`, `\r`, `\t`, `\a`, `\b` and `\xHH` escapes,
+/// single quoted arguments only `\'`.
+fn parse_command_line(line: &str) -> std::result::Result<Vec<Vec<u8>>, String> {
+    let bytes = line.as_bytes();
+    let mut args = Vec::new();
+    let mut i = 0;
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            return Ok(args);
+        }
+        let mut arg = Vec::new();
+        match bytes[i] {
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                loop {
+                    let Some(&byte) = bytes.get(i) else {
+                        return Err("unbalanced quotes".to_string());
+                    };
+                    match byte {
+                        b'\\' if quote == b'"' && i + 1 < bytes.len() => {
+                            i += 1;
+                            let escaped = match bytes[i] {
+                                b'n' => b'\n',
+                                b'r' => b'\r',
+                                b't' => b'\t',
+                                b'a' => 0x07,
+                                b'b' => 0x08,
+                                b'x' if i + 2 < bytes.len()
+                                    && bytes[i + 1].is_ascii_hexdigit()
+                                    && bytes[i + 2].is_ascii_hexdigit() =>
+                                {
+                                    let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                                    i += 2;
+                                    u8::from_str_radix(hex, 16).unwrap_or_default()
+                                }
+                                other => other,
+                            };
+                            arg.push(escaped);
+                        }
+                        b'\\' if quote == b'\'' && bytes.get(i + 1) == Some(&b'\'') => {
+                            i += 1;
+                            arg.push(b'\'');
+                        }
+                        byte if byte == quote => {
+                            i += 1;
+                            break;
+                        }
+                        byte => arg.push(byte),
+                    }
+                    i += 1;
+                }
+                if bytes.get(i).is_some_and(|byte| !byte.is_ascii_whitespace()) {
+                    return Err("closing quote must be followed by a space".to_string());
+                }
+            }
+            _ => {
+                while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+                    arg.push(bytes[i]);
+                    i += 1;
+                }
+            }
+        }
+        args.push(arg);
+    }
+}
+
+/// Commands recreating a key, starting with a `DEL` so the script can be replayed.
+fn record_commands(record: &JsonKeyRecord, now_ms: i64) -> Vec<Vec<Vec<u8>>> {
+    let key = &record.key.0;
+    let command = |name: &str| vec![name.as_bytes().to_vec(), key.clone()];
+    let mut commands = vec![command("DEL")];
+    match &record.value {
+        JsonKeyValue::String(value) => {
+            let mut set = command("SET");
+            set.push(value.0.clone());
+            commands.push(set);
+        }
+        JsonKeyValue::List(items) | JsonKeyValue::Set(items) => {
+            let name = if matches!(record.value, JsonKeyValue::List(_)) {
+                "RPUSH"
+            } else {
+                "SADD"
+            };
+            for chunk in items.chunks(SCRIPT_CHUNK_SIZE) {
+                let mut add = command(name);
+                add.extend(chunk.iter().map(|item| item.0.clone()));
+                commands.push(add);
+            }
+        }
+        JsonKeyValue::Zset(members) => {
+            for chunk in members.chunks(SCRIPT_CHUNK_SIZE) {
+                let mut add = command("ZADD");
+                for member in chunk {
+                    add.push(member.score.to_string().into_bytes());
+                    add.push(member.member.0.clone());
+                }
+                commands.push(add);
+            }
+        }
+        JsonKeyValue::Hash(fields) => {
+            for chunk in fields.chunks(SCRIPT_CHUNK_SIZE) {
+                let mut add = command("HSET");
+                for field in chunk {
+                    add.push(field.field.0.clone());
+                    add.push(field.value.0.clone());
+                }
+                commands.push(add);
+            }
+        }
+        JsonKeyValue::Stream(entries) => {
+            for entry in entries {
+                let mut add = command("XADD");
+                add.push(entry.id.clone().into_bytes());
+                for field in &entry.fields {
+                    add.push(field.field.0.clone());
+                    add.push(field.value.0.clone());
+                }
+                commands.push(add);
+            }
+        }
+    }
+    if let Some(ttl_ms) = record.ttl_ms.filter(|ttl_ms| *ttl_ms > 0) {
+        let mut expire = command("PEXPIREAT");
+        expire.push((now_ms + ttl_ms).to_string().into_bytes());
+        commands.push(expire);
+    }
+    commands
+}
+
+pub(super) fn write_script_header(writer: &mut impl Write, server_id: &str, db: usize) -> Result<()> {
+    writeln!(writer, "# Zedis command script")?;
+    writeln!(
+        writer,
+        "# server: {server_id}, db: {db}, exported at: {}",
+        Utc::now().to_rfc3339()
+    )?;
+    Ok(())
+}
+
+/// Writes the commands recreating a key, one per line.
+///
+/// The TTL becomes a `PEXPIREAT` relative to `now_ms`, the time of the export.
+pub(super) fn write_record_commands(writer: &mut impl Write, record: &JsonKeyRecord, now_ms: i64) -> Result<()> {
+    for command in record_commands(record, now_ms) {
+        let line: Vec<String> = command.iter().map(|arg| quote_arg(arg)).collect();
+        writeln!(writer, "{}", line.join(" "))?;
+    }
+    Ok(())
+}
+
+/// Runs the commands of a script in order and returns the result of each command.
+///
+/// Single node connections pipeline the whole batch. Cluster connections cannot pipeline
+/// commands of different slots, so consecutive commands on the same key are pipelined
+/// together.
+async fn run_script_batch(conn: &RedisAsyncConn, commands: &[ScriptCommand]) -> Result<Vec<redis::RedisResult<Value>>> {
+    let to_cmd = |args: &Vec<Vec<u8>>| {
+        let mut command = Cmd::new();
+        for arg in args {
+            command.arg(arg);
+        }
+        command
+    };
+    let runs: Vec<&[ScriptCommand]> = if let RedisAsyncConn::Single(_) = conn {
+        vec![commands]
+    } else {
+        commands.chunk_by(|(_, a), (_, b)| a.get(1) == b.get(1)).collect()
+    };
+    let mut results = Vec::with_capacity(commands.len());
+    for run in runs {
+        let mut pipeline = pipe();
+        for (_, args) in run {
+            pipeline.add_command(to_cmd(args));
+        }
+        let mut conn = conn.clone();
+        let run_results: Vec<redis::RedisResult<Value>> = pipeline.ignore_errors().query_async(&mut conn).await?;
+        results.extend(run_results);
+    }
+    Ok(results)
+}
+
+impl ConnectionManager {
+    /// Runs the commands of a script file with batched pipelines.
+    ///
+    /// Lines that cannot be parsed or whose command fails are reported with their line
+    /// number, the other commands still run.
+    /// The operation stops after the current batch once `cancelled` is set.
+    pub async fn import_keys_script(
+        &self,
+        server_id: &str,
+        db: usize,
+        preset_credentials: Vec<PresetCredential>,
+        file_path: &str,
+        progress_tx: Option<UnboundedSender<BulkKeyProgress>>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<ScriptImportSummary> {
+        let (client, _) = self.get_client(server_id, db, preset_credentials).await?;
+        let conn = client.connection();
+        let content = std::fs::read_to_string(file_path)?;
+        let mut summary = ScriptImportSummary {
+            file_path: file_path.to_string(),
+            ..Default::default()
+        };
+        let mut commands = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match parse_command_line(line) {
+                Ok(args) if !args.is_empty() => commands.push((index + 1, args)),
+                Ok(_) => {}
+                Err(e) => summary.add_error(index + 1, e),
+            }
+        }
+
+        let total = Some(commands.len());
+        let send_progress = |processed: usize| {
+            if let Some(tx) = &progress_tx {
+                let _ = tx.unbounded_send(BulkKeyProgress {
+                    phase: BulkKeyPhase::Import,
+                    processed,
+                    total,
+                });
+            }
+        };
+        info!(
+            server_id,
+            db,
+            path = file_path,
+            commands = commands.len(),
+            "start script import"
+        );
+        send_progress(0);
+
+        let mut processed = 0;
+        for batch in commands.chunks(SCRIPT_BATCH_SIZE) {
+            if cancelled.load(Ordering::Relaxed) {
+                summary.cancelled = true;
+                break;
+            }
+            let results = run_script_batch(&conn, batch).await?;
+            for ((line, _), result) in batch.iter().zip(results) {
+                match result {
+                    Ok(_) => summary.executed += 1,
+                    Err(e) => summary.add_error(*line, e.to_string()),
+                }
+            }
+            processed += batch.len();
+            send_progress(processed);
+        }
+
+        info!(
+            server_id,
+            db,
+            path = file_path,
+            executed = summary.executed,
+            failed = summary.failed,
+            cancelled = summary.cancelled,
+            "script import finished"
+        );
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::json_keys::{JsonBytes, JsonField};
+
+    #[test]
+    fn quotes_and_parses_arguments() {
+        let args: Vec<Vec<u8>> = vec![
+            b"HSET".to_vec(),
+            b"user:1".to_vec(),
+            b"".to_vec(),
+            b"two words".to_vec(),
+            b"say \"hi\"\n".to_vec(),
+            "名字".as_bytes().to_vec(),
+            vec![0xff, 0x00, b'\\'],
+            b"#tag".to_vec(),
+        ];
+        let line: Vec<String> = args.iter().map(|arg| quote_arg(arg)).collect();
+        let line = line.join(" ");
+        assert_eq!(
+            line,
+            r##"HSET user:1 "" "two words" "say \"hi\"\n" "名字" "\xff\x00\\" "#tag""##
+        );
+        assert_eq!(parse_command_line(&line).expect("parse line"), args);
+    }
+
+    #[test]
+    fn parses_redis_cli_quoting() {
+        assert_eq!(
+            parse_command_line("  SET 'it\\'s'   \"a\\tb\" ").expect("parse line"),
+            vec![b"SET".to_vec(), b"it's".to_vec(), b"a\tb".to_vec()]
+        );
+        assert!(parse_command_line("SET \"key value").is_err());
+        assert!(parse_command_line("SET \"key\"value").is_err());
+        assert!(parse_command_line("   ").expect("parse blank line").is_empty());
+    }
+
+    #[test]
+    fn writes_commands_recreating_a_key() {
+        let record = JsonKeyRecord {
+            key: JsonBytes(b"user:1".to_vec()),
+            ttl_ms: Some(5_000),
+            value: JsonKeyValue::Hash(vec![JsonField {
+                field: JsonBytes(b"name".to_vec()),
+                value: JsonBytes(b"Ada Lovelace".to_vec()),
+            }]),
+        };
+        let mut script = Vec::new();
+        write_record_commands(&mut script, &record, 1_000).expect("write commands");
+        assert_eq!(
+            String::from_utf8(script).expect("utf8 script"),
+            "DEL user:1\nHSET user:1 name \"Ada Lovelace\"\nPEXPIREAT user:1 6000\n"
+        );
+    }
+}
@@ -0,0 +1,337 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hot and cold key detection.
+//!
+//! Redis tracks the accesses of every key for its eviction policy: a logarithmic access
+//! counter with the LFU policies (`OBJECT FREQ`), the time since the last access
+//! otherwise (`OBJECT IDLETIME`). Neither command touches the key, so the keys matching a
+//! pattern are scanned on every master and ranked by the statistic of their node.
+
+use super::manager::{BulkKeyPhase, BulkKeyProgress, BulkKeys, ConnectionManager, KeyBatches, query_each};
+use crate::{error::Error, helpers::RedisKey, states::PresetCredential};
+use futures::channel::mpsc::UnboundedSender;
+use redis::cmd;
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use tracing::{info, warn};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Access statistic the keys are ranked by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HotKeyMode {
+    /// `OBJECT FREQ`, the LFU access counter: the higher, the hotter
+    Frequency,
+    /// `OBJECT IDLETIME` in seconds: the lower, the hotter
+    #[default]
+    Idle,
+}
+
+impl HotKeyMode {
+    /// Returns the mode available with a `maxmemory-policy`.
+    pub fn from_policy(policy: &str) -> Self {
+        if policy.to_ascii_lowercase().contains("lfu") {
+            HotKeyMode::Frequency
+        } else {
+            HotKeyMode::Idle
+        }
+    }
+
+    fn subcommand(&self) -> &'static str {
+        match self {
+            HotKeyMode::Frequency => "FREQ",
+            HotKeyMode::Idle => "IDLETIME",
+        }
+    }
+
+    /// Sort key ordering the hottest keys first.
+    fn heat(&self, value: u64) -> Reverse<i128> {
+        match self {
+            HotKeyMode::Frequency => Reverse(value as i128),
+            HotKeyMode::Idle => Reverse(-(value as i128)),
+        }
+    }
+}
+
+/// A ranked key.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HotKey {
+    /// Key in escaped form
+    pub key: String,
+    /// `host:port` of the master of the key
+    pub node: String,
+    /// Access counter or idle seconds, depending on the mode
+    pub value: u64,
+}
+
+/// Hottest and coldest keys of a master.
+#[derive(Debug, Clone, Default)]
+pub struct NodeHotKeys {
+    pub node: String,
+    /// `maxmemory-policy` of the node, empty when `CONFIG` is not available
+    pub policy: String,
+    pub mode: HotKeyMode,
+    /// Keys whose statistic was read
+    pub scanned: usize,
+    /// Hottest keys first
+    pub hottest: Vec<HotKey>,
+    /// Coldest keys first
+    pub coldest: Vec<HotKey>,
+}
+
+/// Result of `analyze_hot_keys`.
+#[derive(Debug, Clone, Default)]
+pub struct HotKeyReport {
+    pub pattern: String,
+    pub nodes: Vec<NodeHotKeys>,
+    /// Keys deleted between the scan and the reading of their statistic
+    pub missing: usize,
+    /// Whether the analysis was cancelled before every key was read
+    pub cancelled: bool,
+}
+
+impl HotKeyReport {
+    /// Keys whose statistic was read on every node.
+    pub fn scanned(&self) -> usize {
+        self.nodes.iter().map(|node| node.scanned).sum()
+    }
+}
+
+impl NodeHotKeys {
+    fn new(node: String, policy: String, mode: HotKeyMode) -> Self {
+        Self {
+            node,
+            policy,
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// Adds a key to the rankings, keeping at least `limit` keys in each of them.
+    ///
+    /// The candidates are trimmed once they reach twice the kept size.
+    fn add(&mut self, item: HotKey, limit: usize) {
+        let mode = self.mode;
+        self.scanned += 1;
+        self.hottest.push(item.clone());
+        self.coldest.push(item);
+        if self.hottest.len() >= limit * 2 {
+            self.hottest.sort_by_key(|item| mode.heat(item.value));
+            self.hottest.truncate(limit);
+        }
+        if self.coldest.len() >= limit * 2 {
+            self.coldest.sort_by_key(|item| Reverse(mode.heat(item.value)));
+            self.coldest.truncate(limit);
+        }
+    }
+
+    fn finish(&mut self, limit: usize) {
+        let mode = self.mode;
+        self.hottest
+            .sort_by(|a, b| mode.heat(a.value).cmp(&mode.heat(b.value)).then(a.key.cmp(&b.key)));
+        self.hottest.truncate(limit);
+        self.coldest
+            .sort_by(|a, b| mode.heat(b.value).cmp(&mode.heat(a.value)).then(a.key.cmp(&b.key)));
+        self.coldest.truncate(limit);
+    }
+}
+
+impl ConnectionManager {
+    /// Ranks the keys matching a pattern by their access statistic on every master.
+    ///
+    /// Nodes with an LFU `maxmemory-policy` are read with `OBJECT FREQ`, the others with
+    /// `OBJECT IDLETIME`. When `CONFIG GET` is disabled, as on most managed services,
+    /// `OBJECT FREQ` is tried on the first key of the node.
+    /// The analysis stops after the current batch once `cancelled` is set, and the
+    /// rankings of the keys read so far are returned.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn analyze_hot_keys(
+        &self,
+        server_id: &str,
+        db: usize,
+        preset_credentials: Vec<PresetCredential>,
+        pattern: &str,
+        limit: usize,
+        progress_tx: Option<UnboundedSender<BulkKeyProgress>>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<HotKeyReport> {
+        let (client, _) = self.get_client(server_id, db, preset_credentials).await?;
+        let limit = limit.max(1);
+        let mut config = cmd("CONFIG");
+        config.arg("GET").arg("maxmemory-policy");
+        let policies: HashMap<String, String> = match client.query_async_masters::<Vec<String>>(vec![config]).await {
+            Ok(values) => client
+                .master_addrs()
+                .into_iter()
+                .zip(values)
+                .map(|(node, value)| (node, value.get(1).cloned().unwrap_or_default()))
+                .collect(),
+            Err(e) => {
+                warn!(server_id, error = %e, "read maxmemory-policy fail");
+                HashMap::new()
+            }
+        };
+        let total = if pattern == "*" {
+            client.dbsize().await.ok().map(|size| size as usize)
+        } else {
+            None
+        };
+        let send_progress = |processed: usize| {
+            if let Some(tx) = &progress_tx {
+                let _ = tx.unbounded_send(BulkKeyProgress {
+                    phase: BulkKeyPhase::HotKeys,
+                    processed,
+                    total,
+                });
+            }
+        };
+        info!(server_id, db, pattern, ?policies, "start analyzing hot keys");
+        send_progress(0);
+
+        let mut report = HotKeyReport {
+            pattern: pattern.to_string(),
+            ..Default::default()
+        };
+        let mut processed = 0;
+        let target = BulkKeys::Pattern(pattern.to_string());
+        let mut batches = KeyBatches::new(&client, db, &target);
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                report.cancelled = true;
+                break;
+            }
+            let Some((conn, keys)) = batches.next_batch().await? else {
+                break;
+            };
+            if keys.is_empty() {
+                continue;
+            }
+            processed += keys.len();
+            let node = batches.batch_node().unwrap_or_default().to_string();
+            if report.nodes.last().is_none_or(|item| item.node != node) {
+                let policy = policies.get(&node).cloned().unwrap_or_default();
+                let mode = if policies.contains_key(&node) {
+                    HotKeyMode::from_policy(&policy)
+                } else {
+                    // Without the policy, OBJECT FREQ only succeeds with an LFU policy
+                    let probe = cmd("OBJECT").arg("FREQ").arg(&keys[0]).clone();
+                    let replies: Vec<redis::RedisResult<u64>> = query_each(&conn, vec![probe]).await?;
+                    if replies.first().is_some_and(|reply| reply.is_ok()) {
+                        HotKeyMode::Frequency
+                    } else {
+                        HotKeyMode::Idle
+                    }
+                };
+                report.nodes.push(NodeHotKeys::new(node, policy, mode));
+            }
+            let Some(node_keys) = report.nodes.last_mut() else {
+                continue;
+            };
+
+            let subcommand = node_keys.mode.subcommand();
+            let commands = keys
+                .iter()
+                .map(|key| cmd("OBJECT").arg(subcommand).arg(key).clone())
+                .collect();
+            let values: Vec<redis::RedisResult<Option<u64>>> = query_each(&conn, commands).await?;
+            for (key, value) in keys.iter().zip(values) {
+                match value {
+                    Ok(Some(value)) => node_keys.add(
+                        HotKey {
+                            key: RedisKey::escape(key),
+                            node: node_keys.node.clone(),
+                            value,
+                        },
+                        limit,
+                    ),
+                    Ok(None) => report.missing += 1,
+                    // The policy changed during the analysis or the key expired
+                    Err(e) => {
+                        warn!(error = %e, "read key access statistic fail");
+                        report.missing += 1;
+                    }
+                }
+            }
+            send_progress(processed);
+        }
+
+        for node_keys in report.nodes.iter_mut() {
+            node_keys.finish(limit);
+        }
+        info!(
+            server_id,
+            db,
+            scanned = report.scanned(),
+            missing = report.missing,
+            cancelled = report.cancelled,
+            "analyze hot keys finished"
+        );
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hot_key(key: &str, value: u64) -> HotKey {
+        HotKey {
+            key: key.to_string(),
+            node: "127.0.0.1:6379".to_string(),
+            value,
+        }
+    }
+
+    fn keys(items: &[HotKey]) -> Vec<&str> {
+        items.iter().map(|item| item.key.as_str()).collect()
+    }
+
+    #[test]
+    fn detects_mode_from_policy() {
+        assert_eq!(HotKeyMode::from_policy("allkeys-lfu"), HotKeyMode::Frequency);
+        assert_eq!(HotKeyMode::from_policy("volatile-lfu"), HotKeyMode::Frequency);
+        assert_eq!(HotKeyMode::from_policy("allkeys-lru"), HotKeyMode::Idle);
+        assert_eq!(HotKeyMode::from_policy("noeviction"), HotKeyMode::Idle);
+    }
+
+    #[test]
+    fn ranks_hottest_and_coldest_keys() {
+        let values = [("a", 5), ("b", 200), ("c", 0), ("d", 30), ("e", 12)];
+
+        let mut frequency = NodeHotKeys::new("node".to_string(), "allkeys-lfu".to_string(), HotKeyMode::Frequency);
+        for (key, value) in values {
+            frequency.add(hot_key(key, value), 2);
+        }
+        frequency.finish(2);
+        assert_eq!(frequency.scanned, 5);
+        assert_eq!(keys(&frequency.hottest), vec!["b", "d"]);
+        assert_eq!(keys(&frequency.coldest), vec!["c", "a"]);
+
+        // The most recently accessed keys are the hottest ones
+        let mut idle = NodeHotKeys::new("node".to_string(), String::new(), HotKeyMode::Idle);
+        for (key, value) in values {
+            idle.add(hot_key(key, value), 2);
+        }
+        idle.finish(2);
+        assert_eq!(keys(&idle.hottest), vec!["c", "a"]);
+        assert_eq!(keys(&idle.coldest), vec!["b", "d"]);
+    }
+}
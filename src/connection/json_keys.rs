@@ -0,0 +1,659 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Logical JSON export and import of keys.
+//!
+//! Unlike the `DUMP` based exports, every key is written with its type, TTL and decoded
+//! contents and is recreated with native commands, so the file can move between Redis,
+//! Valkey and KeyDB servers of any version. Three layouts are supported:
+//! - JSON: an array with one key per line
+//! - NDJSON: one key per line
+//! - Commands: a replayable script of Redis commands, see `command_script`
+//!
+//! Keys and values are strings when they are valid UTF-8, `{"base64": "..."}` otherwise.
+
+use super::command_script::{write_record_commands, write_script_header};
+use super::manager::{
+    BulkKeyPhase, BulkKeyProgress, BulkKeys, ConnectionManager, KeyBatches, KeyConflictPolicy, available_key_name,
+    query_all, query_each,
+};
+use crate::{error::Error, helpers::RedisKey, states::PresetCredential};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chrono::Utc;
+use futures::channel::mpsc::UnboundedSender;
+use redis::{Cmd, Pipeline, Value, cmd, pipe};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use tracing::{error, info};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Number of keys imported concurrently between two progress reports
+const IMPORT_BATCH_SIZE: usize = 100;
+/// Number of values sent per command when a collection is recreated
+const IMPORT_CHUNK_SIZE: usize = 500;
+
+/// Layout of a logical export file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyExportFormat {
+    /// An array of keys, one key per line
+    #[default]
+    Json,
+    /// One key per line, without enclosing array
+    Ndjson,
+    /// Redis commands recreating the keys, see `command_script`
+    Commands,
+}
+
+/// Result of `export_keys_logical`.
+#[derive(Debug, Clone, Default)]
+pub struct KeyExportSummary {
+    pub file_path: String,
+    pub exported: usize,
+    /// Keys of a type without logical representation, e.g. vector sets
+    pub unsupported: usize,
+    /// Keys that no longer existed
+    pub missing: usize,
+    pub failed: usize,
+    /// Whether the operation was cancelled before every key was exported
+    pub cancelled: bool,
+}
+
+/// Result of `import_keys_json`.
+#[derive(Debug, Clone, Default)]
+pub struct JsonImportSummary {
+    pub imported: usize,
+    /// Keys imported under another name because of a conflict
+    pub renamed: usize,
+    /// Keys left alone because they already existed
+    pub skipped: usize,
+    pub failed: usize,
+    /// Error of the first failed key
+    pub first_error: Option<String>,
+    /// Whether the operation was cancelled before every key was imported
+    pub cancelled: bool,
+}
+
+/// Bytes written as a string when they are valid UTF-8, as base64 otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct JsonBytes(pub(super) Vec<u8>);
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonBytesRepr {
+    Text(String),
+    Binary { base64: String },
+}
+
+impl Serialize for JsonBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match std::str::from_utf8(&self.0) {
+            Ok(text) => serializer.serialize_str(text),
+            Err(_) => JsonBytesRepr::Binary {
+                base64: BASE64.encode(&self.0),
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match JsonBytesRepr::deserialize(deserializer)? {
+            JsonBytesRepr::Text(text) => Ok(JsonBytes(text.into_bytes())),
+            JsonBytesRepr::Binary { base64 } => BASE64.decode(base64).map(JsonBytes).map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Scores are numbers, except `inf` and `-inf` which JSON cannot represent.
+mod score {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ScoreRepr {
+        Number(f64),
+        Text(String),
+    }
+
+    pub fn serialize<S: Serializer>(score: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        if score.is_finite() {
+            serializer.serialize_f64(*score)
+        } else if *score > 0.0 {
+            serializer.serialize_str("inf")
+        } else {
+            serializer.serialize_str("-inf")
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        match ScoreRepr::deserialize(deserializer)? {
+            ScoreRepr::Number(score) => Ok(score),
+            ScoreRepr::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct JsonField {
+    pub(super) field: JsonBytes,
+    pub(super) value: JsonBytes,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct JsonZsetMember {
+    pub(super) member: JsonBytes,
+    #[serde(with = "score")]
+    pub(super) score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct JsonStreamEntry {
+    pub(super) id: String,
+    pub(super) fields: Vec<JsonField>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub(super) enum JsonKeyValue {
+    String(JsonBytes),
+    List(Vec<JsonBytes>),
+    Set(Vec<JsonBytes>),
+    Zset(Vec<JsonZsetMember>),
+    Hash(Vec<JsonField>),
+    Stream(Vec<JsonStreamEntry>),
+}
+
+/// One exported key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct JsonKeyRecord {
+    pub(super) key: JsonBytes,
+    /// Remaining time to live in milliseconds, absent for persistent keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) ttl_ms: Option<i64>,
+    #[serde(flatten)]
+    pub(super) value: JsonKeyValue,
+}
+
+fn field_pairs(values: Vec<Vec<u8>>) -> Vec<JsonField> {
+    let mut fields = Vec::with_capacity(values.len() / 2);
+    let mut values = values.into_iter();
+    while let (Some(field), Some(value)) = (values.next(), values.next()) {
+        fields.push(JsonField {
+            field: JsonBytes(field),
+            value: JsonBytes(value),
+        });
+    }
+    fields
+}
+
+/// Command reading the whole value of a key, `None` for unsupported types.
+fn read_command(key: &[u8], key_type: &str) -> Option<Cmd> {
+    let mut command = match key_type {
+        "string" => cmd("GET"),
+        "list" => cmd("LRANGE"),
+        "set" => cmd("SMEMBERS"),
+        "zset" => cmd("ZRANGE"),
+        "hash" => cmd("HGETALL"),
+        "stream" => cmd("XRANGE"),
+        _ => return None,
+    };
+    command.arg(key);
+    match key_type {
+        "list" => {
+            command.arg(0).arg(-1);
+        }
+        "zset" => {
+            command.arg(0).arg(-1).arg("WITHSCORES");
+        }
+        "stream" => {
+            command.arg("-").arg("+");
+        }
+        _ => {}
+    }
+    Some(command)
+}
+
+fn parse_value(key_type: &str, value: Value) -> redis::RedisResult<JsonKeyValue> {
+    let bytes_list = |value: Value| -> redis::RedisResult<Vec<JsonBytes>> {
+        let items: Vec<Vec<u8>> = redis::from_redis_value(value)?;
+        Ok(items.into_iter().map(JsonBytes).collect())
+    };
+    let value = match key_type {
+        "string" => JsonKeyValue::String(JsonBytes(redis::from_redis_value(value)?)),
+        "list" => JsonKeyValue::List(bytes_list(value)?),
+        "set" => JsonKeyValue::Set(bytes_list(value)?),
+        "zset" => {
+            let members: Vec<(Vec<u8>, f64)> = redis::from_redis_value(value)?;
+            JsonKeyValue::Zset(
+                members
+                    .into_iter()
+                    .map(|(member, score)| JsonZsetMember {
+                        member: JsonBytes(member),
+                        score,
+                    })
+                    .collect(),
+            )
+        }
+        "hash" => {
+            let fields: Vec<(Vec<u8>, Vec<u8>)> = redis::from_redis_value(value)?;
+            JsonKeyValue::Hash(
+                fields
+                    .into_iter()
+                    .map(|(field, value)| JsonField {
+                        field: JsonBytes(field),
+                        value: JsonBytes(value),
+                    })
+                    .collect(),
+            )
+        }
+        _ => {
+            let entries: Vec<(String, Vec<Vec<u8>>)> = redis::from_redis_value(value)?;
+            JsonKeyValue::Stream(
+                entries
+                    .into_iter()
+                    .map(|(id, values)| JsonStreamEntry {
+                        id,
+                        fields: field_pairs(values),
+                    })
+                    .collect(),
+            )
+        }
+    };
+    Ok(value)
+}
+
+/// Builds the transaction recreating a key with native commands.
+fn import_pipeline(key: &[u8], record: &JsonKeyRecord, replace: bool) -> Pipeline {
+    let mut pipeline = pipe();
+    pipeline.atomic();
+    if replace {
+        pipeline.cmd("DEL").arg(key).ignore();
+    }
+    match &record.value {
+        JsonKeyValue::String(value) => {
+            pipeline.cmd("SET").arg(key).arg(&value.0).ignore();
+        }
+        JsonKeyValue::List(items) | JsonKeyValue::Set(items) => {
+            let name = if matches!(record.value, JsonKeyValue::List(_)) {
+                "RPUSH"
+            } else {
+                "SADD"
+            };
+            for chunk in items.chunks(IMPORT_CHUNK_SIZE) {
+                let command = pipeline.cmd(name).arg(key);
+                for item in chunk {
+                    command.arg(&item.0);
+                }
+                command.ignore();
+            }
+        }
+        JsonKeyValue::Zset(members) => {
+            for chunk in members.chunks(IMPORT_CHUNK_SIZE) {
+                let command = pipeline.cmd("ZADD").arg(key);
+                for member in chunk {
+                    command.arg(member.score).arg(&member.member.0);
+                }
+                command.ignore();
+            }
+        }
+        JsonKeyValue::Hash(fields) => {
+            for chunk in fields.chunks(IMPORT_CHUNK_SIZE) {
+                let command = pipeline.cmd("HSET").arg(key);
+                for field in chunk {
+                    command.arg(&field.field.0).arg(&field.value.0);
+                }
+                command.ignore();
+            }
+        }
+        JsonKeyValue::Stream(entries) => {
+            for entry in entries {
+                let command = pipeline.cmd("XADD").arg(key).arg(&entry.id);
+                for field in &entry.fields {
+                    command.arg(&field.field.0).arg(&field.value.0);
+                }
+                command.ignore();
+            }
+        }
+    }
+    if let Some(ttl_ms) = record.ttl_ms.filter(|ttl_ms| *ttl_ms > 0) {
+        pipeline.cmd("PEXPIRE").arg(key).arg(ttl_ms).ignore();
+    }
+    pipeline
+}
+
+/// Parses a JSON array or NDJSON export.
+fn parse_json_records(content: &str) -> Result<Vec<JsonKeyRecord>> {
+    if content.trim_start().starts_with('[') {
+        return Ok(serde_json::from_str(content)?);
+    }
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| Error::Invalid {
+                message: format!("Invalid record at line {}: {}", index + 1, e),
+            })
+        })
+        .collect()
+}
+
+impl ConnectionManager {
+    /// Exports keys with their type, TTL and decoded contents as JSON, NDJSON or as a
+    /// script of Redis commands.
+    ///
+    /// Vector sets have no logical representation and are counted as unsupported.
+    /// The operation stops after the current batch once `cancelled` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn export_keys_logical(
+        &self,
+        server_id: &str,
+        db: usize,
+        preset_credentials: Vec<PresetCredential>,
+        keys: &BulkKeys,
+        file_path: &str,
+        format: KeyExportFormat,
+        progress_tx: Option<UnboundedSender<BulkKeyProgress>>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<KeyExportSummary> {
+        let (client, _) = self.get_client(server_id, db, preset_credentials).await?;
+        let total = match keys {
+            BulkKeys::Keys(keys) => Some(keys.len()),
+            BulkKeys::Pattern(_) => None,
+        };
+        let send_progress = |processed: usize| {
+            if let Some(tx) = &progress_tx {
+                let _ = tx.unbounded_send(BulkKeyProgress {
+                    phase: BulkKeyPhase::Export,
+                    processed,
+                    total,
+                });
+            }
+        };
+        info!(server_id, db, ?keys, path = file_path, ?format, "start logical export");
+        send_progress(0);
+
+        let mut writer = BufWriter::new(File::create(file_path)?);
+        match format {
+            KeyExportFormat::Json => writer.write_all(b"[")?,
+            KeyExportFormat::Ndjson => {}
+            KeyExportFormat::Commands => write_script_header(&mut writer, server_id, db)?,
+        }
+        let now_ms = Utc::now().timestamp_millis();
+        let mut summary = KeyExportSummary {
+            file_path: file_path.to_string(),
+            ..Default::default()
+        };
+        let mut processed = 0;
+        let mut batches = KeyBatches::new(&client, db, keys);
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                summary.cancelled = true;
+                break;
+            }
+            let Some((conn, keys)) = batches.next_batch().await? else {
+                break;
+            };
+            if keys.is_empty() {
+                continue;
+            }
+            processed += keys.len();
+
+            let meta_commands = keys
+                .iter()
+                .flat_map(|key| [cmd("TYPE").arg(key).clone(), cmd("PTTL").arg(key).clone()])
+                .collect();
+            let meta: Vec<Value> = query_all(&conn, meta_commands).await?;
+            let mut reads = Vec::with_capacity(keys.len());
+            let mut read_commands = Vec::with_capacity(keys.len());
+            for (key, meta) in keys.iter().zip(meta.chunks(2)) {
+                let (key_type, ttl_ms): (String, i64) =
+                    redis::from_redis_value(Value::Array(meta.to_vec())).map_err(|e| Error::Invalid {
+                        message: format!("Failed to parse TYPE/PTTL result: {e}"),
+                    })?;
+                if key_type == "none" {
+                    summary.missing += 1;
+                    continue;
+                }
+                let Some(command) = read_command(key, &key_type) else {
+                    summary.unsupported += 1;
+                    continue;
+                };
+                read_commands.push(command);
+                reads.push((key, key_type, ttl_ms));
+            }
+
+            let values: Vec<redis::RedisResult<Value>> = query_each(&conn, read_commands).await?;
+            for ((key, key_type, ttl_ms), value) in reads.into_iter().zip(values) {
+                let value = match value.and_then(|value| parse_value(&key_type, value)) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        error!(key = RedisKey::escape(key), error = %e, "failed to export key");
+                        summary.failed += 1;
+                        continue;
+                    }
+                };
+                let record = JsonKeyRecord {
+                    key: JsonBytes(key.clone()),
+                    ttl_ms: (ttl_ms >= 0).then_some(ttl_ms),
+                    value,
+                };
+                match format {
+                    KeyExportFormat::Json => {
+                        writer.write_all(if summary.exported == 0 { b"\n" } else { b",\n" })?;
+                        serde_json::to_writer(&mut writer, &record)?;
+                    }
+                    KeyExportFormat::Ndjson => {
+                        serde_json::to_writer(&mut writer, &record)?;
+                        writer.write_all(b"\n")?;
+                    }
+                    KeyExportFormat::Commands => write_record_commands(&mut writer, &record, now_ms)?,
+                }
+                summary.exported += 1;
+            }
+            send_progress(processed);
+        }
+        if format == KeyExportFormat::Json {
+            writer.write_all(b"\n]\n")?;
+        }
+        writer.flush()?;
+
+        info!(
+            server_id,
+            db,
+            path = file_path,
+            exported = summary.exported,
+            unsupported = summary.unsupported,
+            failed = summary.failed,
+            cancelled = summary.cancelled,
+            "logical export finished"
+        );
+        Ok(summary)
+    }
+
+    /// Recreates the keys of a JSON or NDJSON export with native commands.
+    ///
+    /// Each key is written in a `MULTI`/`EXEC` transaction so a failed key leaves nothing
+    /// behind. Keys that already exist are handled by `policy`.
+    /// The operation stops after the current batch once `cancelled` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn import_keys_json(
+        &self,
+        server_id: &str,
+        db: usize,
+        preset_credentials: Vec<PresetCredential>,
+        file_path: &str,
+        policy: KeyConflictPolicy,
+        progress_tx: Option<UnboundedSender<BulkKeyProgress>>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<JsonImportSummary> {
+        let (client, _) = self.get_client(server_id, db, preset_credentials).await?;
+        let conn = client.connection();
+        let records = parse_json_records(&std::fs::read_to_string(file_path)?)?;
+        let total = Some(records.len());
+        let send_progress = |processed: usize| {
+            if let Some(tx) = &progress_tx {
+                let _ = tx.unbounded_send(BulkKeyProgress {
+                    phase: BulkKeyPhase::Import,
+                    processed,
+                    total,
+                });
+            }
+        };
+        info!(
+            server_id,
+            db,
+            path = file_path,
+            records = records.len(),
+            ?policy,
+            "start json import"
+        );
+        send_progress(0);
+
+        let mut summary = JsonImportSummary::default();
+        let mut processed = 0;
+        for records in records.chunks(IMPORT_BATCH_SIZE) {
+            if cancelled.load(Ordering::Relaxed) {
+                summary.cancelled = true;
+                break;
+            }
+            let exists_commands = records
+                .iter()
+                .map(|record| cmd("EXISTS").arg(&record.key.0).clone())
+                .collect();
+            let exists: Vec<bool> = query_all(&conn, exists_commands).await?;
+            let mut imports = Vec::with_capacity(records.len());
+            for (record, exists) in records.iter().zip(exists) {
+                let (key, renamed) = match policy {
+                    _ if !exists => (record.key.0.clone(), false),
+                    KeyConflictPolicy::Skip => {
+                        summary.skipped += 1;
+                        continue;
+                    }
+                    KeyConflictPolicy::Replace => (record.key.0.clone(), false),
+                    KeyConflictPolicy::Rename => (available_key_name(&conn, &record.key.0).await?, true),
+                };
+                let pipeline = import_pipeline(&key, record, exists && policy == KeyConflictPolicy::Replace);
+                let mut conn = conn.clone();
+                imports.push(async move {
+                    let result: redis::RedisResult<()> = pipeline.query_async(&mut conn).await;
+                    (key, renamed, result)
+                });
+            }
+            for (key, renamed, result) in futures::future::join_all(imports).await {
+                match result {
+                    Ok(()) => {
+                        summary.imported += 1;
+                        summary.renamed += usize::from(renamed);
+                    }
+                    Err(e) => {
+                        error!(key = RedisKey::escape(&key), error = %e, "failed to import key");
+                        summary.failed += 1;
+                        if summary.first_error.is_none() {
+                            summary.first_error = Some(e.to_string());
+                        }
+                    }
+                }
+            }
+            processed += records.len();
+            send_progress(processed);
+        }
+
+        info!(
+            server_id,
+            db,
+            path = file_path,
+            imported = summary.imported,
+            skipped = summary.skipped,
+            failed = summary.failed,
+            cancelled = summary.cancelled,
+            "json import finished"
+        );
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_binary_safe_records() {
+        let record = JsonKeyRecord {
+            key: JsonBytes(b"user:1".to_vec()),
+            ttl_ms: None,
+            value: JsonKeyValue::Hash(vec![JsonField {
+                field: JsonBytes(b"avatar".to_vec()),
+                value: JsonBytes(vec![0xff, 0x00]),
+            }]),
+        };
+        let line = serde_json::to_string(&record).expect("serialize record");
+        assert_eq!(
+            line,
+            r#"{"key":"user:1","type":"hash","value":[{"field":"avatar","value":{"base64":"/wA="}}]}"#
+        );
+        let parsed: JsonKeyRecord = serde_json::from_str(&line).expect("parse record");
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn keeps_infinite_scores() {
+        let record = JsonKeyRecord {
+            key: JsonBytes(b"ranking".to_vec()),
+            ttl_ms: Some(1_000),
+            value: JsonKeyValue::Zset(vec![
+                JsonZsetMember {
+                    member: JsonBytes(b"a".to_vec()),
+                    score: 1.5,
+                },
+                JsonZsetMember {
+                    member: JsonBytes(b"b".to_vec()),
+                    score: f64::NEG_INFINITY,
+                },
+            ]),
+        };
+        let line = serde_json::to_string(&record).expect("serialize record");
+        assert!(line.contains(r#""score":1.5"#));
+        assert!(line.contains(r#""score":"-inf""#));
+        let parsed: JsonKeyRecord = serde_json::from_str(&line).expect("parse record");
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn parses_json_and_ndjson_exports() {
+        let ndjson = "{\"key\":\"a\",\"type\":\"string\",\"value\":\"1\"}\n\n{\"key\":\"b\",\"ttl_ms\":10,\"type\":\"list\",\"value\":[\"x\",\"y\"]}\n";
+        let records = parse_json_records(ndjson).expect("parse ndjson");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].ttl_ms, Some(10));
+        assert_eq!(
+            records[1].value,
+            JsonKeyValue::List(vec![JsonBytes(b"x".to_vec()), JsonBytes(b"y".to_vec())])
+        );
+
+        let json = "[\n{\"key\":\"a\",\"type\":\"string\",\"value\":\"1\"},\n{\"key\":\"b\",\"type\":\"set\",\"value\":[]}\n]\n";
+        assert_eq!(parse_json_records(json).expect("parse json").len(), 2);
+
+        let error = parse_json_records("{\"key\":\"a\"}").expect_err("missing type");
+        assert!(error.to_string().contains("line 1"));
+    }
+}
@@ -0,0 +1,294 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SSH host key verification.
+//!
+//! The key of an SSH server is checked against `~/.ssh/known_hosts`, hashed entries
+//! included, then against the `known_hosts` file of Zedis, which keeps the keys trusted
+//! from the app. Both files use the OpenSSH format. An unknown key is refused until the
+//! user trusts its fingerprint, a changed key is always refused.
+
+use super::ssh_config::{DEFAULT_SSH_PORT, resolve_ssh_addr};
+use super::ssh_tunnel::forget_ssh_sessions;
+use crate::error::Error;
+use crate::helpers::{get_home_dir, get_or_create_config_dir};
+use russh::keys::known_hosts::{check_known_hosts_path, known_host_keys_path, learn_known_hosts_path};
+use russh::keys::{HashAlg, PublicKey, parse_public_key_base64};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A server key recorded in a known_hosts file, or presented by a server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SshHostKey {
+    pub host: String,
+    pub port: u16,
+    /// Key algorithm, e.g. `ssh-ed25519`
+    pub algorithm: String,
+    /// SHA256 fingerprint, as printed by `ssh-keygen -l`
+    pub fingerprint: String,
+    /// The public key in OpenSSH format
+    pub public_key: String,
+    /// Whether the key is stored by Zedis, the keys of `~/.ssh/known_hosts` are read only
+    pub managed: bool,
+}
+
+impl SshHostKey {
+    fn new(host: &str, port: u16, key: &PublicKey, managed: bool) -> Result<Self> {
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            algorithm: key.algorithm().to_string(),
+            fingerprint: key.fingerprint(HashAlg::Sha256).to_string(),
+            public_key: key.to_openssh().map_err(russh::keys::Error::from)?,
+            managed,
+        })
+    }
+}
+
+/// Result of the check of a key against a known_hosts file.
+#[derive(Debug, Clone, PartialEq)]
+enum HostKeyStatus {
+    Trusted,
+    /// No key of the algorithm is recorded for the host
+    Unknown,
+    /// Another key of the algorithm is recorded at the line
+    Changed(usize),
+}
+
+fn ssh_known_hosts_path() -> Option<PathBuf> {
+    get_home_dir().map(|home| home.join(".ssh").join("known_hosts"))
+}
+
+fn zedis_known_hosts_path() -> Result<PathBuf> {
+    Ok(get_or_create_config_dir()?.join("known_hosts"))
+}
+
+/// Host pattern of the known_hosts format, `[host]:port` for non-standard ports.
+fn host_pattern(host: &str, port: u16) -> String {
+    if port == DEFAULT_SSH_PORT {
+        host.to_string()
+    } else {
+        format!("[{host}]:{port}")
+    }
+}
+
+/// A missing file knows no host, an unreadable one is an error: russh would take it as
+/// empty and the key would be reported as unknown.
+fn check_host_key_path(host: &str, port: u16, key: &PublicKey, path: &Path) -> Result<HostKeyStatus> {
+    match fs::File::open(path) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HostKeyStatus::Unknown),
+        Err(e) => return Err(e.into()),
+        Ok(_) => {}
+    }
+    match check_known_hosts_path(host, port, key, path) {
+        Ok(true) => Ok(HostKeyStatus::Trusted),
+        Ok(false) => Ok(HostKeyStatus::Unknown),
+        Err(russh::keys::Error::KeyChanged { line }) => Ok(HostKeyStatus::Changed(line)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Verifies the key presented by an SSH server.
+///
+/// Returns `UnknownHostKey` when no file knows the host, so the user can trust the key,
+/// and `HostKeyChanged` when a file records another key for it. A file that cannot be
+/// read fails the verification.
+pub fn verify_host_key(host: &str, port: u16, key: &PublicKey) -> Result<()> {
+    let mut paths: Vec<PathBuf> = ssh_known_hosts_path().into_iter().collect();
+    paths.push(zedis_known_hosts_path()?);
+    for path in paths {
+        let status = check_host_key_path(host, port, key, &path).inspect_err(|e| {
+            warn!(error = %e, path = %path.display(), "read known hosts fail");
+        })?;
+        match status {
+            HostKeyStatus::Trusted => {
+                info!(host, port, path = %path.display(), "ssh host key verified");
+                return Ok(());
+            }
+            HostKeyStatus::Changed(line) => {
+                return Err(Error::HostKeyChanged {
+                    host: host.to_string(),
+                    port,
+                    fingerprint: key.fingerprint(HashAlg::Sha256).to_string(),
+                    location: format!("{}:{line}", path.display()),
+                });
+            }
+            HostKeyStatus::Unknown => {}
+        }
+    }
+    Err(Error::UnknownHostKey {
+        key: Box::new(SshHostKey::new(host, port, key, true)?),
+    })
+}
+
+/// Records a key in the known_hosts file of Zedis.
+pub fn trust_host_key(key: &SshHostKey) -> Result<()> {
+    let public_key = PublicKey::from_openssh(&key.public_key).map_err(|e| Error::Invalid {
+        message: format!("Invalid ssh host key: {e}"),
+    })?;
+    learn_known_hosts_path(&key.host, key.port, &public_key, zedis_known_hosts_path()?)?;
+    info!(
+        host = key.host,
+        port = key.port,
+        fingerprint = key.fingerprint,
+        "ssh host key trusted"
+    );
+    Ok(())
+}
+
+fn host_keys_path(host: &str, port: u16, path: &Path, managed: bool) -> Vec<SshHostKey> {
+    match known_host_keys_path(host, port, path) {
+        Ok(keys) => keys
+            .iter()
+            .filter_map(|(_, key)| SshHostKey::new(host, port, key, managed).ok())
+            .collect(),
+        Err(e) => {
+            warn!(error = %e, path = %path.display(), "read known hosts fail");
+            vec![]
+        }
+    }
+}
+
+/// Lists the keys recorded for an SSH address, those of `~/.ssh/known_hosts` first.
+///
+/// The address may be a `Host` alias of `~/.ssh/config`.
+pub fn trusted_host_keys(addr: &str) -> Vec<SshHostKey> {
+    let (host, port) = resolve_ssh_addr(addr);
+    let mut keys = vec![];
+    if let Some(path) = ssh_known_hosts_path() {
+        keys.extend(host_keys_path(&host, port, &path, false));
+    }
+    if let Ok(path) = zedis_known_hosts_path() {
+        keys.extend(host_keys_path(&host, port, &path, true));
+    }
+    keys
+}
+
+/// Removes the lines recording the key for its host from a known_hosts file.
+///
+/// Returns the number of removed lines.
+fn remove_host_key_path(key: &SshHostKey, path: &Path) -> Result<usize> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let pattern = host_pattern(&key.host, key.port);
+    let matches = |line: &str| {
+        let mut parts = line.split_whitespace();
+        let (Some(hosts), Some(_), Some(encoded)) = (parts.next(), parts.next(), parts.next()) else {
+            return false;
+        };
+        hosts.split(',').any(|item| item == pattern)
+            && parse_public_key_base64(encoded)
+                .is_ok_and(|item| item.fingerprint(HashAlg::Sha256).to_string() == key.fingerprint)
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let kept: Vec<&str> = lines.iter().copied().filter(|line| !matches(line)).collect();
+    let removed = lines.len() - kept.len();
+    if removed > 0 {
+        let mut content = kept.join("\n");
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        fs::write(path, content)?;
+    }
+    Ok(removed)
+}
+
+/// Removes a key from the known_hosts file of Zedis, the next connection asks to trust
+/// the key of the server again.
+pub fn remove_host_key(key: &SshHostKey) -> Result<()> {
+    let removed = remove_host_key_path(key, &zedis_known_hosts_path()?)?;
+    forget_ssh_sessions(&key.host, key.port);
+    info!(
+        host = key.host,
+        port = key.port,
+        fingerprint = key.fingerprint,
+        removed,
+        "ssh host key removed"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCAL_KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIJdD7y3aLq454yWBdwLWbieU1ebz9/cu7/QEXn9OIeZJ";
+    const HASHED_KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAILIG2T/B0l0gaqj3puu510tu9N1OkQ4znY3LYuEm5zCF";
+
+    fn temp_file(content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("zedis-known-hosts-{}", uuid::Uuid::now_v7()));
+        fs::write(&path, content).expect("write known hosts");
+        path
+    }
+
+    fn public_key(encoded: &str) -> PublicKey {
+        parse_public_key_base64(encoded).expect("parse public key")
+    }
+
+    #[test]
+    fn checks_host_keys() {
+        let path = temp_file(&format!(
+            "[localhost]:2222 ssh-ed25519 {LOCAL_KEY}\n\
+             |1|O33ESRMWPVkMYIwJ1Uw+n877jTo=|nuuC5vEqXlEZ/8BXQR7m619W6Ak= ssh-ed25519 {HASHED_KEY}\n"
+        ));
+        let local = public_key(LOCAL_KEY);
+        let hashed = public_key(HASHED_KEY);
+        assert_eq!(
+            check_host_key_path("localhost", 2222, &local, &path).ok(),
+            Some(HostKeyStatus::Trusted)
+        );
+        // Hashed entries are matched too
+        assert_eq!(
+            check_host_key_path("example.com", 22, &hashed, &path).ok(),
+            Some(HostKeyStatus::Trusted)
+        );
+        assert_eq!(
+            check_host_key_path("localhost", 2222, &hashed, &path).ok(),
+            Some(HostKeyStatus::Changed(1))
+        );
+        assert_eq!(
+            check_host_key_path("localhost", 22, &local, &path).ok(),
+            Some(HostKeyStatus::Unknown)
+        );
+        assert_eq!(
+            check_host_key_path("localhost", 22, &local, &path.with_extension("missing")).ok(),
+            Some(HostKeyStatus::Unknown)
+        );
+        // A directory cannot be read as a file
+        assert!(check_host_key_path("localhost", 22, &local, &std::env::temp_dir()).is_err());
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn removes_host_keys() {
+        let path = temp_file(&format!(
+            "[localhost]:2222 ssh-ed25519 {LOCAL_KEY}\nexample.com ssh-ed25519 {HASHED_KEY}\n"
+        ));
+        let key = SshHostKey::new("localhost", 2222, &public_key(LOCAL_KEY), true).expect("host key");
+        assert_eq!(remove_host_key_path(&key, &path).expect("remove"), 1);
+        assert_eq!(remove_host_key_path(&key, &path).expect("remove"), 0);
+        let keys = host_keys_path("example.com", 22, &path, true);
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].algorithm, "ssh-ed25519");
+        assert!(keys[0].fingerprint.starts_with("SHA256:"));
+        assert!(host_keys_path("localhost", 2222, &path, true).is_empty());
+        let _ = fs::remove_file(path);
+    }
+}
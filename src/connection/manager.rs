@@ -15,33 +15,45 @@
 use super::{
     async_connection::{
         AuthSource, RedisAsyncConn, clear_pool_connections_batch, is_auth_error, open_single_connection,
-        query_async_masters, try_open_with_preset_credentials,
+        query_async_masters, query_async_nodes, query_node_pipeline, try_open_with_preset_credentials,
     },
     config::{RedisServer, get_config},
+    sentinel::{SentinelTarget, open_sentinel_connection, sentinel_masters, sentinel_replicas},
     ssh_cluster_connection::SshMultiplexedConnection,
+    ssh_tunnel::open_ssh_session,
 };
 use crate::error::Error;
+use crate::helpers::RedisKey;
 use crate::states::PresetCredential;
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 use chrono::Utc;
 use dashmap::DashMap;
 use futures::channel::mpsc::UnboundedSender;
 use gpui::SharedString;
-use redis::{Cmd, FromRedisValue, InfoDict, Role, aio::MultiplexedConnection, cluster, cmd};
+use redis::{Cmd, FromRedisValue, InfoDict, Role, Value, aio::MultiplexedConnection, cluster, cmd, pipe};
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashSet,
     fs::File,
     io::{BufRead, BufReader, BufWriter, Write},
     path::{Path, PathBuf},
-    sync::LazyLock,
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicBool, AtomicI64, Ordering},
+    },
     time::Duration,
 };
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Minimum interval between two failover checks of a client resolved through Sentinel
+const FAILOVER_CHECK_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Nodes found by `get_redis_nodes`, with the Sentinel target of a Sentinel server.
+type DiscoveredNodes = (Vec<RedisNode>, ServerType, AuthSource, Option<SentinelTarget>);
+
 // Global singleton for ConnectionManager
 static CONNECTION_MANAGER: LazyLock<ConnectionManager> = LazyLock::new(ConnectionManager::new);
 
@@ -56,7 +68,7 @@ enum ServerType {
 // Wrapper for the underlying Redis client
 #[derive(Clone)]
 enum RClient {
-    Single(RedisServer),
+    Single(Box<RedisServer>),
     Cluster(cluster::ClusterClient),
     SshCluster(cluster::ClusterClient),
 }
@@ -84,16 +96,92 @@ impl RedisNode {
     pub fn host_port(&self) -> String {
         format!("{}:{}", self.server.host, self.server.port)
     }
+    fn info(&self) -> ClusterNodeInfo {
+        ClusterNodeInfo {
+            ip: self.server.host.clone(),
+            port: self.server.port,
+            role: self.role.clone(),
+        }
+    }
+}
+
+/// Selects the nodes matching the target, `None` for [`NodeTarget::Auto`].
+fn select_nodes<'a>(nodes: &'a [RedisNode], target: &NodeTarget) -> Option<Vec<&'a RedisNode>> {
+    let selected = match target {
+        NodeTarget::Auto => return None,
+        NodeTarget::AllMasters => nodes.iter().filter(|node| node.role == NodeRole::Master).collect(),
+        NodeTarget::AllNodes => nodes
+            .iter()
+            .filter(|node| matches!(node.role, NodeRole::Master | NodeRole::Slave))
+            .collect(),
+        NodeTarget::Node(addr) => nodes.iter().filter(|node| &node.host_port() == addr).collect(),
+    };
+    Some(selected)
 }
 
 // Information parsed from `CLUSTER NODES` command
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ClusterNodeInfo {
     pub ip: String,
     pub port: u16,
     pub role: NodeRole,
 }
 
+impl ClusterNodeInfo {
+    pub fn host_port(&self) -> String {
+        format!("{}:{}", self.ip, self.port)
+    }
+}
+
+/// The nodes a command is sent to.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum NodeTarget {
+    /// Let the connection route the command (slot based routing for clusters)
+    #[default]
+    Auto,
+    /// Every master node
+    AllMasters,
+    /// Every master and replica node
+    AllNodes,
+    /// A single node, identified by `host:port`
+    Node(String),
+}
+
+/// A `MOVED` or `ASK` redirection returned by a cluster node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRedirect {
+    /// Whether the slot is being migrated (`ASK`) rather than owned by another node (`MOVED`)
+    pub ask: bool,
+    pub slot: u16,
+    /// The `host:port` serving the slot
+    pub addr: String,
+}
+
+impl NodeRedirect {
+    /// Extracts the redirection from a `MOVED`/`ASK` error.
+    pub fn from_error(error: &Error) -> Option<Self> {
+        let Error::Redis { source } = error else {
+            return None;
+        };
+        let (addr, slot) = source.redirect_node()?;
+        Some(Self {
+            ask: source.code() == Some("ASK"),
+            slot,
+            addr: addr.to_string(),
+        })
+    }
+}
+
+/// The reply of one node for a command sent with a [`NodeTarget`].
+#[derive(Debug)]
+pub struct NodeReply {
+    /// The node that answered, `None` when the command was routed by the connection
+    pub node: Option<ClusterNodeInfo>,
+    /// The redirection that was followed to get this reply
+    pub redirect: Option<NodeRedirect>,
+    pub result: Result<Value>,
+}
+
 /// Parses a Redis address string like "ip:port@cport" or just "ip:port".
 fn parse_address(address_str: &str) -> Result<(String, u16, Option<u16>)> {
     // Split into address part and optional cluster bus port part
@@ -189,12 +277,18 @@ pub struct RedisClient {
     master_nodes: Vec<RedisNode>,
     version: Version,
     connection: RedisAsyncConn,
+    /// The node resolved through Sentinel, checked by `check_failover`
+    sentinel: Option<SentinelTarget>,
+    /// Time of the last failover check, in milliseconds
+    failover_checked_at: Arc<AtomicI64>,
 }
 #[derive(Debug, Clone, Default)]
 pub struct RedisClientDescription {
     pub server_type: SharedString,
     pub master_nodes: SharedString,
     pub slave_nodes: SharedString,
+    /// Whether a replica is browsed read-only
+    pub readonly: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -224,6 +318,273 @@ pub struct KeyBackupProgress {
     pub total: Option<usize>,
 }
 
+/// Keys a bulk operation applies to.
+#[derive(Debug, Clone)]
+pub enum BulkKeys {
+    /// Every key matching a SCAN MATCH pattern
+    Pattern(String),
+    /// Explicit keys, e.g. the multi-selection of the key tree
+    Keys(Vec<SharedString>),
+}
+
+/// Phase of a bulk key operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkKeyPhase {
+    /// Matching keys are counted (dry run)
+    Count,
+    Delete,
+    Ttl,
+    Copy,
+    Export,
+    Import,
+    /// Keys are analyzed by the memory analyzer
+    Analyze,
+    /// Access statistics of the keys are read by the hot key detection
+    HotKeys,
+}
+
+/// Progress of a bulk key operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkKeyProgress {
+    pub phase: BulkKeyPhase,
+    /// Keys counted, deleted or updated so far
+    pub processed: usize,
+    /// Keys expected to be processed, when known
+    pub total: Option<usize>,
+}
+
+/// Result of `delete_keys_by_pattern`.
+#[derive(Debug, Clone, Default)]
+pub struct KeyDeleteSummary {
+    pub pattern: String,
+    pub dry_run: bool,
+    /// Keys matching the pattern
+    pub matched: usize,
+    pub deleted: usize,
+    /// The first matching keys in escaped form
+    pub samples: Vec<SharedString>,
+    /// Whether the operation was cancelled before every node was scanned
+    pub cancelled: bool,
+}
+
+/// Condition of `PEXPIRE`, the `NX`/`XX`/`GT`/`LT` flags of Redis 7.0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TtlCondition {
+    #[default]
+    Always,
+    /// Only keys without expiration
+    Nx,
+    /// Only keys with an expiration
+    Xx,
+    /// Only when the new TTL is greater than the current one
+    Gt,
+    /// Only when the new TTL is less than the current one
+    Lt,
+}
+
+impl TtlCondition {
+    fn flag(&self) -> Option<&'static str> {
+        match self {
+            TtlCondition::Always => None,
+            TtlCondition::Nx => Some("NX"),
+            TtlCondition::Xx => Some("XX"),
+            TtlCondition::Gt => Some("GT"),
+            TtlCondition::Lt => Some("LT"),
+        }
+    }
+
+    /// Checks the condition on the client, for servers without the `PEXPIRE` flags.
+    ///
+    /// `pttl` is the `PTTL` reply: -1 for a key without expiration, -2 for a missing key.
+    /// As in Redis, a key without expiration has an infinite TTL for `GT` and `LT`.
+    pub fn allows(&self, pttl: i64, ttl_ms: i64) -> bool {
+        match self {
+            TtlCondition::Always => pttl != -2,
+            TtlCondition::Nx => pttl == -1,
+            TtlCondition::Xx => pttl >= 0,
+            TtlCondition::Gt => pttl >= 0 && ttl_ms > pttl,
+            TtlCondition::Lt => pttl == -1 || (pttl >= 0 && ttl_ms < pttl),
+        }
+    }
+}
+
+/// TTL change applied by `update_keys_ttl`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TtlAction {
+    Expire {
+        ttl_ms: u64,
+        /// A random delay up to this value is added to each key, so the keys do not
+        /// expire at the same time
+        jitter_ms: u64,
+        condition: TtlCondition,
+    },
+    /// Adds `delta_ms` to the current TTL of each key, or removes it when negative.
+    /// Keys without expiration are skipped.
+    Adjust {
+        delta_ms: i64,
+        jitter_ms: u64,
+    },
+    Persist,
+}
+
+/// TTL of a key extended or shortened by `delta_ms`, at least 1 ms: a key shortened by
+/// more than its TTL expires right away.
+fn adjusted_ttl_ms(pttl: i64, delta_ms: i64) -> u64 {
+    pttl.saturating_add(delta_ms).max(1) as u64
+}
+
+/// Result of `update_keys_ttl`.
+#[derive(Debug, Clone, Default)]
+pub struct KeyTtlSummary {
+    pub processed: usize,
+    pub updated: usize,
+    /// Keys left unchanged by the condition, or without TTL to remove
+    pub skipped: usize,
+    /// Keys that no longer existed
+    pub missing: usize,
+    /// The first missing keys in escaped form
+    pub missing_samples: Vec<SharedString>,
+    /// Whether the operation was cancelled before every key was processed
+    pub cancelled: bool,
+}
+
+impl KeyTtlSummary {
+    fn add_missing(&mut self, key: &[u8]) {
+        self.missing += 1;
+        if self.missing_samples.len() < KEY_BULK_SAMPLE_SIZE {
+            self.missing_samples.push(RedisKey::escape(key).into());
+        }
+    }
+}
+
+/// How `copy_keys` handles keys that already exist on the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyConflictPolicy {
+    /// Leave the existing key untouched
+    #[default]
+    Skip,
+    /// Overwrite the existing key
+    Replace,
+    /// Copy under a free name with a numeric suffix (`key-1`, `key-2`...)
+    Rename,
+}
+
+/// Result of `copy_keys`.
+#[derive(Debug, Clone, Default)]
+pub struct KeyCopySummary {
+    pub processed: usize,
+    pub copied: usize,
+    /// Keys copied under another name because of a conflict
+    pub renamed: usize,
+    /// Keys left alone because they already existed on the target
+    pub skipped: usize,
+    /// Keys that no longer existed on the source
+    pub missing: usize,
+    pub failed: usize,
+    /// Error of the first failed key, e.g. a DUMP payload the target version cannot read
+    pub first_error: Option<String>,
+    /// Whether the operation was cancelled before every key was processed
+    pub cancelled: bool,
+}
+
+impl KeyCopySummary {
+    fn add_failure(&mut self, key: &[u8], error: &redis::RedisError) {
+        error!(key = RedisKey::escape(key), error = %error, "failed to copy key");
+        self.failed += 1;
+        if self.first_error.is_none() {
+            self.first_error = Some(error.to_string());
+        }
+    }
+}
+
+/// A key copied by `copy_keys`.
+struct KeyCopy<'a> {
+    source: &'a [u8],
+    target: Vec<u8>,
+    renamed: bool,
+}
+
+/// Iterates over the keys of a bulk operation in batches.
+///
+/// Patterns are scanned on every master node, so each batch comes with the connection
+/// its keys have to be processed on.
+pub(super) struct KeyBatches<'a> {
+    client: &'a RedisClient,
+    db: usize,
+    keys: &'a BulkKeys,
+    /// Index of the scanned master node, or of the next explicit key
+    position: usize,
+    cursor: u64,
+    node_conn: Option<RedisAsyncConn>,
+    /// `host:port` of the node the last pattern batch was scanned on
+    batch_node: Option<String>,
+}
+
+impl<'a> KeyBatches<'a> {
+    pub(super) fn new(client: &'a RedisClient, db: usize, keys: &'a BulkKeys) -> Self {
+        Self {
+            client,
+            db,
+            keys,
+            position: 0,
+            cursor: 0,
+            node_conn: None,
+            batch_node: None,
+        }
+    }
+
+    /// Returns `host:port` of the node of the last batch, `None` for explicit keys.
+    pub(super) fn batch_node(&self) -> Option<&str> {
+        self.batch_node.as_deref()
+    }
+
+    pub(super) async fn next_batch(&mut self) -> Result<Option<(RedisAsyncConn, Vec<Vec<u8>>)>> {
+        match self.keys {
+            BulkKeys::Keys(keys) => {
+                if self.position >= keys.len() {
+                    return Ok(None);
+                }
+                let end = (self.position + KEY_BULK_SCAN_COUNT as usize).min(keys.len());
+                let batch = keys[self.position..end]
+                    .iter()
+                    .map(|key| RedisKey::from(key).as_bytes().to_vec())
+                    .collect();
+                self.position = end;
+                Ok(Some((self.client.connection(), batch)))
+            }
+            BulkKeys::Pattern(pattern) => {
+                let Some(node) = self.client.master_nodes.get(self.position) else {
+                    return Ok(None);
+                };
+                let mut conn = match &self.node_conn {
+                    Some(conn) => conn.clone(),
+                    None => {
+                        let conn = RedisAsyncConn::Single(open_single_connection(&node.server, self.db).await?);
+                        self.node_conn = Some(conn.clone());
+                        conn
+                    }
+                };
+                let (cursor, keys): (u64, Vec<Vec<u8>>) = cmd("SCAN")
+                    .cursor_arg(self.cursor)
+                    .arg("MATCH")
+                    .arg(RedisKey::pattern(pattern))
+                    .arg("COUNT")
+                    .arg(KEY_BULK_SCAN_COUNT)
+                    .query_async(&mut conn)
+                    .await?;
+                self.cursor = cursor;
+                self.batch_node = Some(node.host_port());
+                if cursor == 0 {
+                    debug!(node = %node.host_port(), "node pattern scan finished");
+                    self.position += 1;
+                    self.node_conn = None;
+                }
+                Ok(Some((conn, keys)))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct KeyBackupHeader {
     format: String,
@@ -245,6 +606,8 @@ const KEY_BACKUP_FORMAT: &str = "zedis-key-backup";
 const KEY_BACKUP_VERSION: u8 = 1;
 const KEY_BACKUP_SCAN_COUNT: u64 = 500;
 const KEY_BACKUP_PROGRESS_INTERVAL: usize = 100;
+const KEY_BULK_SCAN_COUNT: u64 = 1_000;
+const KEY_BULK_SAMPLE_SIZE: usize = 20;
 impl RedisClient {
     pub fn nodes(&self) -> (usize, usize) {
         (self.master_nodes.len(), self.nodes.len())
@@ -255,6 +618,10 @@ impl RedisClient {
     pub fn supports_db_selection(&self) -> bool {
         self.server_type != ServerType::Cluster
     }
+    /// Whether the client browses a Sentinel replica, which refuses writes.
+    pub fn is_readonly(&self) -> bool {
+        self.sentinel.as_ref().is_some_and(|target| target.replica)
+    }
 
     pub fn nodes_description(&self) -> RedisClientDescription {
         let master_nodes: Vec<String> = self.master_nodes.iter().map(|node| node.host_port()).collect();
@@ -268,6 +635,7 @@ impl RedisClient {
             server_type: format!("{:?}", self.server_type).into(),
             master_nodes: master_nodes.join(",").into(),
             slave_nodes: slave_nodes.join(",").into(),
+            readonly: self.is_readonly(),
         }
     }
     /// Returns the connection to the Redis server.
@@ -295,6 +663,102 @@ impl RedisClient {
         let values = query_async_masters(addrs, self.db, cmds).await?;
         Ok(values)
     }
+    /// Returns `host:port` of every master, in the order of `query_async_masters` results.
+    pub fn master_addrs(&self) -> Vec<String> {
+        self.master_nodes.iter().map(|node| node.host_port()).collect()
+    }
+    /// Returns the node used for Pub/Sub subscriptions (the first master).
+    pub(crate) fn pubsub_server(&self) -> Option<RedisServer> {
+        self.master_nodes.first().map(|node| node.server.clone())
+    }
+
+    /// Returns the nodes of a cluster, empty for other server types.
+    pub fn cluster_nodes(&self) -> Vec<ClusterNodeInfo> {
+        if self.server_type != ServerType::Cluster {
+            return vec![];
+        }
+        self.nodes.iter().map(RedisNode::info).collect()
+    }
+
+    /// Executes a command on the nodes selected by `target`.
+    ///
+    /// Every node keeps its own result. When a single node is targeted and it answers
+    /// with `MOVED` or `ASK`, the redirection is followed once (sending `ASKING` first
+    /// for `ASK`) and recorded in the reply.
+    /// # Arguments
+    /// * `target` - The nodes to send the command to.
+    /// * `command` - The command to execute.
+    /// # Returns
+    /// * `Vec<NodeReply>` - The reply of every selected node.
+    pub async fn query_async_nodes(&self, target: &NodeTarget, command: Cmd) -> Result<Vec<NodeReply>> {
+        let Some(nodes) = select_nodes(&self.nodes, target) else {
+            let mut conn = self.connection.clone();
+            let result = command.query_async(&mut conn).await.map_err(Error::from);
+            return Ok(vec![NodeReply {
+                node: None,
+                redirect: None,
+                result,
+            }]);
+        };
+        if nodes.is_empty() {
+            return Err(Error::Invalid {
+                message: "No node matches the target".to_string(),
+            });
+        }
+        let addrs = nodes.iter().map(|node| node.server.clone()).collect();
+        let results = query_async_nodes(addrs, self.db, command.clone()).await;
+        let mut replies: Vec<NodeReply> = nodes
+            .iter()
+            .zip(results)
+            .map(|(node, result)| NodeReply {
+                node: Some(node.info()),
+                redirect: None,
+                result,
+            })
+            .collect();
+        if let (NodeTarget::Node(_), [reply]) = (target, replies.as_mut_slice())
+            && let Err(e) = &reply.result
+            && let Some(redirect) = NodeRedirect::from_error(e)
+        {
+            reply.result = self.query_redirected(&redirect, &command).await;
+            reply.node = Some(self.redirected_node(&redirect.addr));
+            reply.redirect = Some(redirect);
+        }
+        Ok(replies)
+    }
+
+    /// Returns the known node for the address, or a new entry when the topology changed.
+    fn redirected_node(&self, addr: &str) -> ClusterNodeInfo {
+        if let Some(node) = self.nodes.iter().find(|node| node.host_port() == addr) {
+            return node.info();
+        }
+        let (ip, port) = addr.rsplit_once(':').unwrap_or((addr, ""));
+        ClusterNodeInfo {
+            ip: ip.to_string(),
+            port: port.parse().unwrap_or_default(),
+            role: NodeRole::Unknown,
+        }
+    }
+
+    async fn query_redirected(&self, redirect: &NodeRedirect, command: &Cmd) -> Result<Value> {
+        let node = self.redirected_node(&redirect.addr);
+        let mut server = match self.nodes.iter().find(|item| item.host_port() == redirect.addr) {
+            Some(item) => item.server.clone(),
+            None => self.nodes.first().map(|item| item.server.clone()).unwrap_or_default(),
+        };
+        server.set_addr(node.ip, node.port);
+        let mut conn = open_single_connection(&server, self.db).await?;
+        if !redirect.ask {
+            return Ok(command.query_async(&mut conn).await?);
+        }
+        let (value,): (Value,) = pipe()
+            .cmd("ASKING")
+            .ignore()
+            .add_command(command.clone())
+            .query_async(&mut conn)
+            .await?;
+        Ok(value)
+    }
     /// Calculates the total DB size across all masters.
     /// # Returns
     /// * `u64` - The total DB size.
@@ -308,6 +772,28 @@ impl RedisClient {
         let _: () = cmd("PING").query_async(&mut conn).await?;
         Ok(())
     }
+    /// Asks Sentinel whether the node of a client resolved through it failed over,
+    /// at most once every `FAILOVER_CHECK_INTERVAL`.
+    ///
+    /// An unreachable Sentinel is only logged, the data node is still usable.
+    /// # Returns
+    /// * `Option<String>` - The failover that happened, the client must be resolved again.
+    pub async fn check_failover(&self) -> Option<String> {
+        let target = self.sentinel.as_ref()?;
+        let now = Utc::now().timestamp_millis();
+        let checked_at = self.failover_checked_at.load(Ordering::Relaxed);
+        if now - checked_at < FAILOVER_CHECK_INTERVAL.as_millis() as i64 {
+            return None;
+        }
+        self.failover_checked_at.store(now, Ordering::Relaxed);
+        match target.failover().await {
+            Ok(failover) => failover,
+            Err(e) => {
+                warn!(error = %e, master_name = target.master_name, "Sentinel failover check failed");
+                None
+            }
+        }
+    }
     /// Returns the number of master nodes.
     /// # Returns
     /// * `usize` - The number of master nodes.
@@ -318,50 +804,96 @@ impl RedisClient {
     /// # Arguments
     /// * `pattern` - The pattern to match keys.
     /// * `count` - The count of keys to return.
+    /// * `key_type` - Only return keys of this type (`string`, `hash`, ...), when set.
     /// # Returns
-    /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys.
-    pub async fn first_scan(&self, pattern: &str, count: u64) -> Result<(Vec<u64>, Vec<SharedString>)> {
+    /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys in escaped form.
+    pub async fn first_scan(
+        &self,
+        pattern: &str,
+        count: u64,
+        key_type: Option<&str>,
+    ) -> Result<(Vec<u64>, Vec<SharedString>)> {
         let master_count = self.count_masters()?;
         let cursors = vec![0; master_count];
 
-        let (cursors, keys) = self.scan(cursors, pattern, count).await?;
+        let (cursors, keys) = self.scan(cursors, pattern, count, key_type).await?;
         Ok((cursors, keys))
     }
     /// Continues a SCAN operation.
+    ///
+    /// The type filter is sent as `SCAN ... TYPE` from Redis 6.0. Older servers do not
+    /// support it, so the keys of every node are checked with pipelined `TYPE` commands instead.
     /// # Arguments
     /// * `cursors` - A vector of cursors for each master.
     /// * `pattern` - The pattern to match keys.
     /// * `count` - The count of keys to return.
+    /// * `key_type` - Only return keys of this type (`string`, `hash`, ...), when set.
     /// # Returns
-    /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys.
-    pub async fn scan(&self, cursors: Vec<u64>, pattern: &str, count: u64) -> Result<(Vec<u64>, Vec<SharedString>)> {
-        debug!("scan, cursors: {cursors:?}, pattern: {pattern}, count: {count}");
+    /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys in escaped form.
+    pub async fn scan(
+        &self,
+        cursors: Vec<u64>,
+        pattern: &str,
+        count: u64,
+        key_type: Option<&str>,
+    ) -> Result<(Vec<u64>, Vec<SharedString>)> {
+        debug!("scan, cursors: {cursors:?}, pattern: {pattern}, count: {count}, type: {key_type:?}");
+        let pattern = RedisKey::pattern(pattern);
+        let server_side_type = key_type.filter(|_| self.is_at_least_version("6.0.0"));
         let cmds: Vec<Cmd> = cursors
             .iter()
             .map(|cursor| {
-                cmd("SCAN")
+                let mut command = cmd("SCAN");
+                command
                     .cursor_arg(*cursor)
                     .arg("MATCH")
-                    .arg(pattern)
+                    .arg(&pattern)
                     .arg("COUNT")
-                    .arg(count)
-                    .clone()
+                    .arg(count);
+                if let Some(key_type) = server_side_type {
+                    command.arg("TYPE").arg(key_type);
+                }
+                command
             })
             .collect();
-        let values: Vec<(u64, Vec<Vec<u8>>)> = self.query_async_masters(cmds).await?;
+        let mut values: Vec<(u64, Vec<Vec<u8>>)> = self.query_async_masters(cmds).await?;
+        if let Some(key_type) = key_type
+            && server_side_type.is_none()
+        {
+            self.retain_keys_of_type(&mut values, key_type).await?;
+        }
         let mut cursors = Vec::with_capacity(values.len());
         let mut keys = Vec::with_capacity(values[0].1.len() * values.len());
         for (cursor, keys_in_node) in values {
             cursors.push(cursor);
-            keys.extend(
-                keys_in_node
-                    .iter()
-                    .map(|k| String::from_utf8_lossy(k).to_string().into()),
-            );
+            keys.extend(keys_in_node.iter().map(|k| SharedString::from(RedisKey::escape(k))));
         }
         keys.sort_unstable();
         Ok((cursors, keys))
     }
+    /// Keeps the scanned keys of the given type, for servers without `SCAN ... TYPE`.
+    async fn retain_keys_of_type(&self, values: &mut [(u64, Vec<Vec<u8>>)], key_type: &str) -> Result<()> {
+        let tasks = self
+            .master_nodes
+            .iter()
+            .zip(values.iter())
+            .map(|(node, (_, keys))| async move {
+                if keys.is_empty() {
+                    return Ok(vec![]);
+                }
+                let mut pipeline = pipe();
+                for key in keys {
+                    pipeline.cmd("TYPE").arg(key);
+                }
+                query_node_pipeline::<Vec<String>>(&node.server, self.db, &pipeline).await
+            });
+        let types = futures::future::try_join_all(tasks).await?;
+        for ((_, keys), types) in values.iter_mut().zip(types) {
+            let mut types = types.iter();
+            keys.retain(|_| types.next().is_some_and(|t| t == key_type));
+        }
+        Ok(())
+    }
 }
 
 fn key_backup_error(message: impl Into<String>) -> Error {
@@ -447,6 +979,54 @@ fn next_available_key_backup_path(target: &Path) -> Result<PathBuf> {
     )))
 }
 
+/// Runs the commands of a batch and returns the result of each command.
+///
+/// Single node connections use a pipeline. Cluster connections cannot pipeline commands
+/// of different slots, so the commands are sent concurrently instead.
+pub(super) async fn query_each<T: FromRedisValue>(
+    conn: &RedisAsyncConn,
+    commands: Vec<Cmd>,
+) -> Result<Vec<redis::RedisResult<T>>> {
+    if commands.is_empty() {
+        return Ok(vec![]);
+    }
+    if let RedisAsyncConn::Single(_) = conn {
+        let mut pipeline = pipe();
+        for command in commands {
+            pipeline.add_command(command);
+        }
+        let mut conn = conn.clone();
+        return Ok(pipeline.ignore_errors().query_async(&mut conn).await?);
+    }
+    let queries = commands.into_iter().map(|command| {
+        let mut conn = conn.clone();
+        async move { command.query_async(&mut conn).await }
+    });
+    Ok(futures::future::join_all(queries).await)
+}
+
+/// Like `query_each`, failing as soon as one command failed.
+pub(super) async fn query_all<T: FromRedisValue>(conn: &RedisAsyncConn, commands: Vec<Cmd>) -> Result<Vec<T>> {
+    let results = query_each(conn, commands).await?;
+    Ok(results.into_iter().collect::<redis::RedisResult<Vec<T>>>()?)
+}
+
+/// Finds a free key name by appending a numeric suffix (`-1`, `-2`...).
+pub(super) async fn available_key_name(conn: &RedisAsyncConn, key: &[u8]) -> Result<Vec<u8>> {
+    let mut conn = conn.clone();
+    for i in 1..=1000 {
+        let mut candidate = key.to_vec();
+        candidate.extend_from_slice(format!("-{i}").as_bytes());
+        let exists: bool = cmd("EXISTS").arg(&candidate).query_async(&mut conn).await?;
+        if !exists {
+            return Ok(candidate);
+        }
+    }
+    Err(Error::Invalid {
+        message: "Could not find available key name after 1000 attempts".to_string(),
+    })
+}
+
 struct PendingKeyBackupFile {
     path: PathBuf,
     persisted: bool,
@@ -522,15 +1102,17 @@ impl ConnectionManager {
         }
     }
     /// Discovers Redis nodes and server type based on initial configuration.
-    async fn get_redis_nodes(
-        &self,
-        name: &str,
-        preset_credentials: Vec<PresetCredential>,
-    ) -> Result<(Vec<RedisNode>, ServerType, AuthSource)> {
+    async fn get_redis_nodes(&self, name: &str, preset_credentials: Vec<PresetCredential>) -> Result<DiscoveredNodes> {
         let config = get_config(name)?;
-        let (mut conn, server_type, auth_source) = {
+        let (mut conn, server_type, auth_source) = if config.is_sentinel() {
+            // The data nodes keep the credentials of the config
+            let conn = open_sentinel_connection(&config).await?;
+            (conn, ServerType::Sentinel, AuthSource::Config)
+        } else {
             let (conn, auth_source) = match try_open_with_preset_credentials(&config, 0, preset_credentials).await {
                 Ok((conn, auth_source)) => (conn, auth_source),
+                // The user has to trust the ssh host key first
+                Err(e @ (Error::UnknownHostKey { .. } | Error::HostKeyChanged { .. })) => return Err(e),
                 Err(e) => {
                     let is_auth = is_auth_error(&e);
                     if !is_auth {
@@ -543,6 +1125,7 @@ impl ConnectionManager {
                             }],
                             ServerType::Standalone,
                             AuthSource::None,
+                            None,
                         ));
                     }
                     // sentinel without password
@@ -566,8 +1149,7 @@ impl ConnectionManager {
                     .iter()
                     .map(|item| {
                         let mut tmp_config = config.clone();
-                        tmp_config.port = item.port;
-                        tmp_config.host = item.ip.clone();
+                        tmp_config.set_addr(item.ip.clone(), item.port);
                         // Apply preset credential to cluster nodes
                         if let AuthSource::Preset(_, ref cred) = auth_source {
                             tmp_config.username = cred.username.clone();
@@ -581,40 +1163,19 @@ impl ConnectionManager {
                         }
                     })
                     .collect();
-                Ok((nodes, server_type, auth_source))
+                Ok((nodes, server_type, auth_source, None))
             }
             ServerType::Sentinel => {
-                // let mut conn = client.get_multiplexed_async_connection().await?;
-                // Fetch masters from Sentinel
-                let masters_response: Vec<HashMap<String, String>> =
-                    cmd("SENTINEL").arg("MASTERS").query_async(&mut conn).await?;
                 let mut nodes = vec![];
-
-                for item in masters_response {
-                    let ip = item.get("ip").ok_or_else(|| Error::Invalid {
-                        message: "ip is not found".to_string(),
-                    })?;
-                    let port: u16 = item
-                        .get("port")
-                        .ok_or_else(|| Error::Invalid {
-                            message: "port is not found".to_string(),
-                        })?
-                        .parse()
-                        .map_err(|e| Error::Invalid {
-                            message: format!("Invalid port {e:?}"),
-                        })?;
-                    let name = item.get("name").ok_or_else(|| Error::Invalid {
-                        message: "master_name is not found".to_string(),
-                    })?;
+                for master in sentinel_masters(&mut conn).await? {
                     // Filter by master name if configured
                     if let Some(master_name) = &config.master_name
-                        && name != master_name
+                        && &master.name != master_name
                     {
                         continue;
                     }
                     let mut tmp_config = config.clone();
-                    tmp_config.host = ip.clone();
-                    tmp_config.port = port;
+                    tmp_config.set_addr(master.ip, master.port);
                     // Apply preset credential to sentinel nodes
                     if let AuthSource::Preset(_, ref cred) = auth_source {
                         tmp_config.username = cred.username.clone();
@@ -624,7 +1185,7 @@ impl ConnectionManager {
                     nodes.push(RedisNode {
                         server: tmp_config,
                         role: NodeRole::Master,
-                        master_name: Some(name.clone()),
+                        master_name: Some(master.name),
                     });
                 }
                 // Check for ambiguous master configuration
@@ -634,8 +1195,50 @@ impl ConnectionManager {
                         message: "Multiple masters found in Sentinel, please specify master_name".into(),
                     });
                 }
-
-                Ok((nodes, server_type, auth_source))
+                let Some(master) = nodes.first().cloned() else {
+                    return Err(Error::Invalid {
+                        message: format!(
+                            "Master {} is not monitored by sentinel",
+                            config.master_name.clone().unwrap_or_default()
+                        ),
+                    });
+                };
+                // A replica is browsed in place of the master
+                if let Some(replica_addr) = config.sentinel_replica.as_ref().filter(|addr| !addr.is_empty()) {
+                    let master_name = master.master_name.clone().unwrap_or_default();
+                    let replicas = sentinel_replicas(&mut conn, &master_name).await?;
+                    let replica = replicas
+                        .into_iter()
+                        .find(|item| &item.addr() == replica_addr)
+                        .ok_or_else(|| Error::Invalid {
+                            message: format!("Replica {replica_addr} of {master_name} is not known by sentinel"),
+                        })?;
+                    if replica.is_down() {
+                        return Err(Error::Invalid {
+                            message: format!("Replica {replica_addr} of {master_name} is down"),
+                        });
+                    }
+                    let mut server = master.server.clone();
+                    server.set_addr(replica.ip, replica.port);
+                    nodes = vec![RedisNode {
+                        server,
+                        role: NodeRole::Slave,
+                        master_name: Some(master_name),
+                    }];
+                }
+                // The credentials that reached the Sentinel are kept to check it again
+                let sentinel_config = match &auth_source {
+                    AuthSource::Preset(_, cred) if !config.is_sentinel() => config.with_credential(cred),
+                    _ => config.clone(),
+                };
+                let target = SentinelTarget {
+                    config: sentinel_config,
+                    master_name: nodes[0].master_name.clone().unwrap_or_default(),
+                    addr: nodes[0].host_port(),
+                    replica: nodes[0].role == NodeRole::Slave,
+                };
+
+                Ok((nodes, server_type, auth_source, Some(target)))
             }
             _ => {
                 // For standalone, apply preset credential
@@ -652,6 +1255,7 @@ impl ConnectionManager {
                     }],
                     server_type,
                     auth_source,
+                    None,
                 ))
             }
         }
@@ -685,7 +1289,7 @@ impl ConnectionManager {
         if let Some(client) = self.clients.get(&key) {
             return Ok((client.clone(), AuthSource::Config));
         }
-        let (nodes, server_type, auth_source) = self.get_redis_nodes(server_id, preset_credentials).await?;
+        let (nodes, server_type, auth_source, sentinel) = self.get_redis_nodes(server_id, preset_credentials).await?;
         let client = match server_type {
             ServerType::Cluster => {
                 let addrs: Vec<String> = nodes.iter().map(|n| n.server.get_connection_url()).collect();
@@ -705,15 +1309,19 @@ impl ConnectionManager {
                     RClient::Cluster(builder.build()?)
                 }
             }
-            _ => RClient::Single(nodes[0].server.clone()),
+            _ => RClient::Single(Box::new(nodes[0].server.clone())),
         };
+        // A replica browsed through Sentinel serves the keys in place of the master
         let master_nodes: Vec<RedisNode> = nodes
             .iter()
-            .filter(|node| node.role == NodeRole::Master)
+            .filter(|node| node.role == NodeRole::Master || sentinel.as_ref().is_some_and(|target| target.replica))
             .cloned()
             .collect();
         let master_nodes_description: Vec<String> = master_nodes.iter().map(|node| node.host_port()).collect();
         info!(master_nodes = ?master_nodes_description, "server master nodes");
+        if let RClient::SshCluster(_) = &client {
+            open_ssh_session(&nodes[0].server).await?;
+        }
         let connection = get_async_connection(&client, db).await?;
 
         let mut client = RedisClient {
@@ -723,6 +1331,8 @@ impl ConnectionManager {
             master_nodes,
             version: Version::new(0, 0, 0),
             connection,
+            sentinel,
+            failover_checked_at: Arc::new(AtomicI64::new(Utc::now().timestamp_millis())),
         };
         let mut conn = client.connection.clone();
         client.version = match server_type {
@@ -1000,6 +1610,430 @@ impl ConnectionManager {
         })
     }
 
+    /// Deletes the keys matching a pattern on every master using SCAN + UNLINK.
+    ///
+    /// `UNLINK` frees the memory in the background; servers older than 4.0 use `DEL`.
+    /// Keys are deleted one command per key so cluster nodes never see cross-slot commands.
+    /// With `dry_run` the matching keys are only counted and sampled.
+    /// The operation stops after the current batch once `cancelled` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn delete_keys_by_pattern(
+        &self,
+        server_id: &str,
+        db: usize,
+        preset_credentials: Vec<PresetCredential>,
+        pattern: &str,
+        dry_run: bool,
+        total: Option<usize>,
+        progress_tx: Option<UnboundedSender<BulkKeyProgress>>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<KeyDeleteSummary> {
+        let (client, _) = self.get_client(server_id, db, preset_credentials).await?;
+        let delete_cmd = if client.is_at_least_version("4.0.0") {
+            "UNLINK"
+        } else {
+            "DEL"
+        };
+        let mut summary = KeyDeleteSummary {
+            pattern: pattern.to_string(),
+            dry_run,
+            ..Default::default()
+        };
+        let phase = if dry_run {
+            BulkKeyPhase::Count
+        } else {
+            BulkKeyPhase::Delete
+        };
+        let send_progress = |processed: usize| {
+            if let Some(tx) = &progress_tx {
+                let _ = tx.unbounded_send(BulkKeyProgress {
+                    phase,
+                    processed,
+                    total,
+                });
+            }
+        };
+        info!(
+            server_id,
+            db, pattern, dry_run, delete_cmd, "start deleting keys by pattern"
+        );
+        send_progress(0);
+
+        let target = BulkKeys::Pattern(pattern.to_string());
+        let mut batches = KeyBatches::new(&client, db, &target);
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                summary.cancelled = true;
+                break;
+            }
+            let Some((mut conn, keys)) = batches.next_batch().await? else {
+                break;
+            };
+            summary.matched += keys.len();
+            let missing_samples = KEY_BULK_SAMPLE_SIZE.saturating_sub(summary.samples.len());
+            summary.samples.extend(
+                keys.iter()
+                    .take(missing_samples)
+                    .map(|key| SharedString::from(RedisKey::escape(key))),
+            );
+
+            if dry_run {
+                send_progress(summary.matched);
+            } else if !keys.is_empty() {
+                let mut pipeline = pipe();
+                for key in &keys {
+                    pipeline.cmd(delete_cmd).arg(key);
+                }
+                let deleted: Vec<i64> = pipeline.query_async(&mut conn).await?;
+                summary.deleted += deleted.iter().sum::<i64>() as usize;
+                send_progress(summary.deleted);
+            }
+        }
+
+        info!(
+            server_id,
+            db,
+            pattern,
+            matched = summary.matched,
+            deleted = summary.deleted,
+            cancelled = summary.cancelled,
+            "delete keys by pattern finished"
+        );
+        Ok(summary)
+    }
+
+    /// Sets or removes the expiration of many keys with pipelined `PEXPIRE` / `PERSIST`.
+    ///
+    /// The `NX`/`XX`/`GT`/`LT` conditions are sent to servers from 7.0; older servers get
+    /// a `PTTL` pipeline first and the condition is checked on the client.
+    /// Relative changes read the current TTLs with a `PTTL` pipeline, then set the new ones.
+    /// Keys whose command had no effect are checked with `EXISTS` to tell the keys that
+    /// no longer exist from the ones skipped by the condition.
+    /// The operation stops after the current batch once `cancelled` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_keys_ttl(
+        &self,
+        server_id: &str,
+        db: usize,
+        preset_credentials: Vec<PresetCredential>,
+        keys: &BulkKeys,
+        action: TtlAction,
+        progress_tx: Option<UnboundedSender<BulkKeyProgress>>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<KeyTtlSummary> {
+        let (client, _) = self.get_client(server_id, db, preset_credentials).await?;
+        let native_conditions = client.is_at_least_version("7.0.0");
+        let total = match keys {
+            BulkKeys::Keys(keys) => Some(keys.len()),
+            BulkKeys::Pattern(_) => None,
+        };
+        let send_progress = |processed: usize| {
+            if let Some(tx) = &progress_tx {
+                let _ = tx.unbounded_send(BulkKeyProgress {
+                    phase: BulkKeyPhase::Ttl,
+                    processed,
+                    total,
+                });
+            }
+        };
+        info!(server_id, db, ?keys, ?action, "start updating keys ttl");
+        send_progress(0);
+
+        let mut summary = KeyTtlSummary::default();
+        let mut batches = KeyBatches::new(&client, db, keys);
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                summary.cancelled = true;
+                break;
+            }
+            let Some((conn, keys)) = batches.next_batch().await? else {
+                break;
+            };
+            if keys.is_empty() {
+                continue;
+            }
+            summary.processed += keys.len();
+
+            // Keys the command is sent to, the others are already classified
+            let mut targets = Vec::with_capacity(keys.len());
+            let mut commands = Vec::with_capacity(keys.len());
+            match action {
+                TtlAction::Persist => {
+                    for key in &keys {
+                        commands.push(cmd("PERSIST").arg(key).clone());
+                        targets.push(key);
+                    }
+                }
+                TtlAction::Expire {
+                    ttl_ms,
+                    jitter_ms,
+                    condition,
+                } => {
+                    let pttls = if native_conditions || condition == TtlCondition::Always {
+                        None
+                    } else {
+                        let pttl_commands = keys.iter().map(|key| cmd("PTTL").arg(key).clone()).collect();
+                        let pttls: Vec<i64> = query_all(&conn, pttl_commands).await?;
+                        Some(pttls)
+                    };
+                    for (index, key) in keys.iter().enumerate() {
+                        let ttl_ms = ttl_ms.saturating_add(fastrand::u64(0..=jitter_ms));
+                        if let Some(pttl) = pttls.as_ref().and_then(|pttls| pttls.get(index)) {
+                            if *pttl == -2 {
+                                summary.add_missing(key);
+                                continue;
+                            }
+                            if !condition.allows(*pttl, i64::try_from(ttl_ms).unwrap_or(i64::MAX)) {
+                                summary.skipped += 1;
+                                continue;
+                            }
+                        }
+                        let mut command = cmd("PEXPIRE");
+                        command.arg(key).arg(ttl_ms);
+                        if native_conditions && let Some(flag) = condition.flag() {
+                            command.arg(flag);
+                        }
+                        commands.push(command);
+                        targets.push(key);
+                    }
+                }
+                TtlAction::Adjust { delta_ms, jitter_ms } => {
+                    let pttl_commands = keys.iter().map(|key| cmd("PTTL").arg(key).clone()).collect();
+                    let pttls: Vec<i64> = query_all(&conn, pttl_commands).await?;
+                    for (key, pttl) in keys.iter().zip(pttls) {
+                        match pttl {
+                            -2 => summary.add_missing(key),
+                            // no TTL to extend or shorten
+                            pttl if pttl < 0 => summary.skipped += 1,
+                            pttl => {
+                                let ttl_ms =
+                                    adjusted_ttl_ms(pttl, delta_ms).saturating_add(fastrand::u64(0..=jitter_ms));
+                                let mut command = cmd("PEXPIRE");
+                                command.arg(key).arg(ttl_ms);
+                                // skips the keys persisted since PTTL
+                                if native_conditions {
+                                    command.arg("XX");
+                                }
+                                commands.push(command);
+                                targets.push(key);
+                            }
+                        }
+                    }
+                }
+            }
+
+            let results: Vec<i64> = query_all(&conn, commands).await?;
+            let unchanged: Vec<_> = targets
+                .iter()
+                .zip(results)
+                .filter_map(|(key, result)| (result == 0).then_some(*key))
+                .collect();
+            summary.updated += targets.len() - unchanged.len();
+            let exists_commands = unchanged.iter().map(|key| cmd("EXISTS").arg(key).clone()).collect();
+            let exists: Vec<i64> = query_all(&conn, exists_commands).await?;
+            for (key, exists) in unchanged.iter().zip(exists) {
+                if exists == 0 {
+                    summary.add_missing(key);
+                } else {
+                    summary.skipped += 1;
+                }
+            }
+            send_progress(summary.processed);
+        }
+
+        info!(
+            server_id,
+            db,
+            updated = summary.updated,
+            skipped = summary.skipped,
+            missing = summary.missing,
+            cancelled = summary.cancelled,
+            "update keys ttl finished"
+        );
+        Ok(summary)
+    }
+
+    /// Copies keys to another database of the same server or to another server.
+    ///
+    /// Servers from 6.2 copy between their own databases with `COPY ... DB`; otherwise the
+    /// keys are moved with `DUMP` + `PTTL` on the source and `RESTORE` on the target, which
+    /// also works for cluster and SSH tunnelled targets.
+    /// Keys already on the target are handled by `policy`.
+    /// The operation stops after the current batch once `cancelled` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn copy_keys(
+        &self,
+        server_id: &str,
+        db: usize,
+        preset_credentials: Vec<PresetCredential>,
+        keys: &BulkKeys,
+        target_server_id: &str,
+        target_db: usize,
+        policy: KeyConflictPolicy,
+        progress_tx: Option<UnboundedSender<BulkKeyProgress>>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<KeyCopySummary> {
+        let same_server = server_id == target_server_id;
+        if same_server && db == target_db {
+            return Err(Error::Invalid {
+                message: "The source and the target are the same database".to_string(),
+            });
+        }
+        let (client, _) = self.get_client(server_id, db, preset_credentials.clone()).await?;
+        let (target_client, _) = self.get_client(target_server_id, target_db, preset_credentials).await?;
+        let target_conn = target_client.connection();
+        let use_copy = same_server && client.supports_db_selection() && client.is_at_least_version("6.2.0");
+        let replace = policy == KeyConflictPolicy::Replace;
+        let total = match keys {
+            BulkKeys::Keys(keys) => Some(keys.len()),
+            BulkKeys::Pattern(_) => None,
+        };
+        let send_progress = |processed: usize| {
+            if let Some(tx) = &progress_tx {
+                let _ = tx.unbounded_send(BulkKeyProgress {
+                    phase: BulkKeyPhase::Copy,
+                    processed,
+                    total,
+                });
+            }
+        };
+        info!(
+            server_id,
+            db,
+            target_server_id,
+            target_db,
+            ?keys,
+            ?policy,
+            use_copy,
+            "start copying keys"
+        );
+        send_progress(0);
+
+        let mut summary = KeyCopySummary::default();
+        let mut batches = KeyBatches::new(&client, db, keys);
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                summary.cancelled = true;
+                break;
+            }
+            let Some((conn, keys)) = batches.next_batch().await? else {
+                break;
+            };
+            if keys.is_empty() {
+                continue;
+            }
+            summary.processed += keys.len();
+
+            // Resolve the conflicts with the keys of the target
+            let exists_commands = keys.iter().map(|key| cmd("EXISTS").arg(key).clone()).collect();
+            let exists: Vec<bool> = query_all(&target_conn, exists_commands).await?;
+            let mut copies = Vec::with_capacity(keys.len());
+            for (key, exists) in keys.iter().zip(exists) {
+                let copy = match policy {
+                    _ if !exists => KeyCopy {
+                        source: key,
+                        target: key.clone(),
+                        renamed: false,
+                    },
+                    KeyConflictPolicy::Skip => {
+                        summary.skipped += 1;
+                        continue;
+                    }
+                    KeyConflictPolicy::Replace => KeyCopy {
+                        source: key,
+                        target: key.clone(),
+                        renamed: false,
+                    },
+                    KeyConflictPolicy::Rename => KeyCopy {
+                        source: key,
+                        target: available_key_name(&target_conn, key).await?,
+                        renamed: true,
+                    },
+                };
+                copies.push(copy);
+            }
+
+            if use_copy {
+                let copy_commands = copies
+                    .iter()
+                    .map(|copy| {
+                        let mut command = cmd("COPY");
+                        command.arg(copy.source).arg(&copy.target).arg("DB").arg(target_db);
+                        if replace {
+                            command.arg("REPLACE");
+                        }
+                        command
+                    })
+                    .collect();
+                let results: Vec<redis::RedisResult<i64>> = query_each(&conn, copy_commands).await?;
+                for (copy, result) in copies.iter().zip(results) {
+                    match result {
+                        Ok(1) => {
+                            summary.copied += 1;
+                            summary.renamed += usize::from(copy.renamed);
+                        }
+                        // The source key is gone (the target was checked above)
+                        Ok(_) => summary.missing += 1,
+                        Err(e) => summary.add_failure(copy.source, &e),
+                    }
+                }
+            } else {
+                let dump_commands = copies
+                    .iter()
+                    .flat_map(|copy| {
+                        [
+                            cmd("PTTL").arg(copy.source).clone(),
+                            cmd("DUMP").arg(copy.source).clone(),
+                        ]
+                    })
+                    .collect();
+                let dumps: Vec<Value> = query_all(&conn, dump_commands).await?;
+                let mut restores = Vec::with_capacity(copies.len());
+                let mut restore_commands = Vec::with_capacity(copies.len());
+                for (copy, reply) in copies.iter().zip(dumps.chunks(2)) {
+                    let [Value::Int(ttl_ms), Value::BulkString(dump)] = reply else {
+                        // DUMP replies nil for a missing key
+                        summary.missing += 1;
+                        continue;
+                    };
+                    let ttl_ms = if *ttl_ms > 0 { *ttl_ms } else { 0 };
+                    let mut command = cmd("RESTORE");
+                    command.arg(&copy.target).arg(ttl_ms).arg(dump);
+                    if replace {
+                        command.arg("REPLACE");
+                    }
+                    restore_commands.push(command);
+                    restores.push(copy);
+                }
+                let results: Vec<redis::RedisResult<()>> = query_each(&target_conn, restore_commands).await?;
+                for (copy, result) in restores.iter().zip(results) {
+                    match result {
+                        Ok(()) => {
+                            summary.copied += 1;
+                            summary.renamed += usize::from(copy.renamed);
+                        }
+                        Err(e) => summary.add_failure(copy.source, &e),
+                    }
+                }
+            }
+            send_progress(summary.processed);
+        }
+
+        info!(
+            server_id,
+            db,
+            target_server_id,
+            target_db,
+            copied = summary.copied,
+            skipped = summary.skipped,
+            missing = summary.missing,
+            failed = summary.failed,
+            cancelled = summary.cancelled,
+            "copy keys finished"
+        );
+        Ok(summary)
+    }
+
     /// Shorthand to get an async connection directly.
     /// Uses empty preset credentials since connection should already be cached.
     pub async fn get_connection(&self, server_id: &str, db: usize) -> Result<RedisAsyncConn> {
@@ -1031,6 +2065,34 @@ mod tests {
         assert_eq!(parsed.ttl_ms, -1);
     }
 
+    #[test]
+    fn ttl_condition_checked_on_client() {
+        // -1: no expiration, -2: missing key
+        assert!(TtlCondition::Always.allows(1_000, 500));
+        assert!(TtlCondition::Always.allows(-1, 500));
+        assert!(!TtlCondition::Always.allows(-2, 500));
+
+        assert!(TtlCondition::Nx.allows(-1, 500));
+        assert!(!TtlCondition::Nx.allows(1_000, 500));
+        assert!(TtlCondition::Xx.allows(1_000, 500));
+        assert!(!TtlCondition::Xx.allows(-1, 500));
+
+        assert!(TtlCondition::Gt.allows(100, 500));
+        assert!(!TtlCondition::Gt.allows(1_000, 500));
+        assert!(!TtlCondition::Gt.allows(-1, 500));
+        assert!(TtlCondition::Lt.allows(1_000, 500));
+        assert!(TtlCondition::Lt.allows(-1, 500));
+        assert!(!TtlCondition::Lt.allows(100, 500));
+        assert!(!TtlCondition::Lt.allows(-2, 500));
+    }
+
+    #[test]
+    fn adjusts_ttl_relative_to_the_current_one() {
+        assert_eq!(adjusted_ttl_ms(60_000, 30_000), 90_000);
+        assert_eq!(adjusted_ttl_ms(60_000, -30_000), 30_000);
+        assert_eq!(adjusted_ttl_ms(60_000, -90_000), 1);
+    }
+
     #[test]
     fn key_backup_temp_path_stays_next_to_target() {
         let target = Path::new("/tmp/redis.zedis-backup.jsonl");
@@ -1068,4 +2130,51 @@ mod tests {
 
         std::fs::remove_dir_all(&dir).expect("remove temp dir");
     }
+
+    fn node(port: u16, role: NodeRole) -> RedisNode {
+        RedisNode {
+            server: RedisServer {
+                host: "127.0.0.1".to_string(),
+                port,
+                ..Default::default()
+            },
+            role,
+            master_name: None,
+        }
+    }
+
+    #[test]
+    fn selects_nodes_by_target() {
+        let nodes = vec![
+            node(7000, NodeRole::Master),
+            node(7001, NodeRole::Slave),
+            node(7002, NodeRole::Fail),
+        ];
+        let ports = |target: NodeTarget| {
+            select_nodes(&nodes, &target).map(|nodes| nodes.iter().map(|node| node.server.port).collect::<Vec<_>>())
+        };
+        assert_eq!(ports(NodeTarget::Auto), None);
+        assert_eq!(ports(NodeTarget::AllMasters), Some(vec![7000]));
+        assert_eq!(ports(NodeTarget::AllNodes), Some(vec![7000, 7001]));
+        assert_eq!(ports(NodeTarget::Node("127.0.0.1:7002".to_string())), Some(vec![7002]));
+    }
+
+    #[test]
+    fn parses_cluster_redirects() {
+        let redirect = |reply: &[u8]| {
+            let value = redis::parse_redis_value(reply).expect("parse reply");
+            let error = Error::from(value.extract_error().expect_err("server error"));
+            NodeRedirect::from_error(&error)
+        };
+        assert_eq!(
+            redirect(b"-MOVED 3999 127.0.0.1:6381\r\n"),
+            Some(NodeRedirect {
+                ask: false,
+                slot: 3999,
+                addr: "127.0.0.1:6381".to_string(),
+            })
+        );
+        assert_eq!(redirect(b"-ASK 12 10.0.0.2:7000\r\n").map(|r| r.ask), Some(true));
+        assert_eq!(redirect(b"-ERR unknown command\r\n"), None);
+    }
 }
@@ -0,0 +1,560 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory analysis of the keys.
+//!
+//! Every key matching a pattern is scanned on every master, and its type, `MEMORY USAGE`,
+//! element count and TTL are read with pipelined batches. The report keeps the biggest
+//! keys of each type and aggregates the memory and key counts by key prefix, splitting
+//! the keys with the separator of the key tree.
+
+use super::manager::{BulkKeyPhase, BulkKeyProgress, BulkKeys, ConnectionManager, KeyBatches, query_each};
+use crate::{error::Error, helpers::RedisKey, states::PresetCredential};
+use chrono::Utc;
+use futures::channel::mpsc::UnboundedSender;
+use redis::{Cmd, Value, cmd, from_redis_value_ref};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use tracing::info;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Number of biggest keys kept for each type by default
+pub const DEFAULT_TOP_KEYS: usize = 20;
+/// Prefix levels aggregated, e.g. `user:`, `user:session:` and `user:session:web:`
+const MAX_PREFIX_DEPTH: usize = 3;
+/// Prefixes tracked at most, so keys with unique segments do not exhaust the memory
+const MAX_TRACKED_PREFIXES: usize = 50_000;
+/// Nested values sampled by `MEMORY USAGE`, the Redis default
+const MEMORY_USAGE_SAMPLES: u64 = 5;
+
+/// Options of `analyze_memory`.
+#[derive(Debug, Clone)]
+pub struct MemoryAnalysisOptions {
+    /// SCAN MATCH pattern of the analyzed keys
+    pub pattern: String,
+    /// Separator of the key segments aggregated as prefixes
+    pub separator: String,
+    /// Number of biggest keys kept for each type
+    pub top_keys: usize,
+}
+
+/// A key of the memory report.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct KeyMemory {
+    /// Key in escaped form
+    pub key: String,
+    pub key_type: String,
+    /// Bytes reported by `MEMORY USAGE`
+    pub memory: u64,
+    /// Length of the value: bytes of a string, elements of a collection
+    pub elements: u64,
+    /// Remaining time to live, `None` for a key without expiration
+    pub ttl_ms: Option<u64>,
+}
+
+/// Memory of the keys of one type.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TypeMemory {
+    pub key_type: String,
+    pub keys: u64,
+    pub memory: u64,
+    pub elements: u64,
+}
+
+/// Memory of the keys sharing a prefix.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PrefixMemory {
+    /// Prefix ending with the separator, e.g. `user:session:`
+    pub prefix: String,
+    /// Number of segments of the prefix, starting at 1
+    pub depth: usize,
+    pub keys: u64,
+    pub memory: u64,
+    /// Keys with an expiration
+    pub expiring: u64,
+    /// Number of prefixes directly below this one
+    pub children: usize,
+}
+
+/// Result of `analyze_memory`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MemoryReport {
+    pub pattern: String,
+    pub separator: String,
+    pub analyzed_at: String,
+    pub keys: u64,
+    pub memory: u64,
+    /// Keys with an expiration
+    pub expiring: u64,
+    /// Keys deleted between the scan and the analysis of their batch
+    pub missing: u64,
+    /// Types sorted by memory, largest first
+    pub types: Vec<TypeMemory>,
+    /// Biggest keys of each type, grouped by type and sorted by memory
+    pub top_keys: Vec<KeyMemory>,
+    /// Prefixes of every depth sorted by memory, largest first
+    pub prefixes: Vec<PrefixMemory>,
+    /// Whether some prefixes were not tracked because there were too many of them
+    pub prefixes_truncated: bool,
+    /// Whether the analysis was cancelled before every key was analyzed
+    pub cancelled: bool,
+}
+
+impl MemoryReport {
+    /// Returns the prefixes directly below `parent`, the first level when it is empty.
+    pub fn child_prefixes(&self, parent: &str) -> Vec<&PrefixMemory> {
+        let depth = self
+            .prefixes
+            .iter()
+            .find(|item| item.prefix == parent)
+            .map(|item| item.depth)
+            .unwrap_or_default();
+        self.prefixes
+            .iter()
+            .filter(|item| item.depth == depth + 1 && item.prefix.starts_with(parent))
+            .collect()
+    }
+
+    /// Returns the biggest keys of a type.
+    pub fn top_keys_of(&self, key_type: &str) -> Vec<&KeyMemory> {
+        self.top_keys.iter().filter(|item| item.key_type == key_type).collect()
+    }
+
+    /// Formats the report as CSV, one row per type, biggest key and prefix.
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec!["kind,type,name,keys,memory_bytes,elements,ttl_ms".to_string()];
+        for item in &self.types {
+            lines.push(format!(
+                "type,{},,{},{},{},",
+                csv_field(&item.key_type),
+                item.keys,
+                item.memory,
+                item.elements
+            ));
+        }
+        for item in &self.top_keys {
+            lines.push(format!(
+                "key,{},{},1,{},{},{}",
+                csv_field(&item.key_type),
+                csv_field(&item.key),
+                item.memory,
+                item.elements,
+                item.ttl_ms.map(|ttl| ttl.to_string()).unwrap_or_default()
+            ));
+        }
+        for item in &self.prefixes {
+            lines.push(format!(
+                "prefix,,{},{},{},,",
+                csv_field(&item.prefix),
+                item.keys,
+                item.memory
+            ));
+        }
+        let mut csv = lines.join("\n");
+        csv.push('\n');
+        csv
+    }
+
+    /// Writes the report as CSV when the file ends with `.csv`, as JSON otherwise.
+    pub fn export(&self, path: &Path) -> Result<()> {
+        let is_csv = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+        let content = if is_csv {
+            self.to_csv()
+        } else {
+            serde_json::to_string_pretty(self)?
+        };
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// Quotes a CSV field containing a comma, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Returns the prefixes of a key, up to `MAX_PREFIX_DEPTH` segments.
+///
+/// A prefix always ends with the separator and is followed by at least one character,
+/// so `a:b:c` gives `a:` and `a:b:`.
+fn key_prefixes(key: &str, separator: &str) -> Vec<String> {
+    if separator.is_empty() {
+        return vec![];
+    }
+    let mut prefixes = vec![];
+    let mut end = 0;
+    while prefixes.len() < MAX_PREFIX_DEPTH {
+        let Some(position) = key[end..].find(separator) else {
+            break;
+        };
+        end += position + separator.len();
+        if end >= key.len() {
+            break;
+        }
+        prefixes.push(key[..end].to_string());
+    }
+    prefixes
+}
+
+/// Returns the command reading the length of a value of the type.
+pub fn length_command(key_type: &str, key: &[u8]) -> Option<Cmd> {
+    let name = match key_type {
+        "string" => "STRLEN",
+        "list" => "LLEN",
+        "set" => "SCARD",
+        "zset" => "ZCARD",
+        "hash" => "HLEN",
+        "stream" => "XLEN",
+        "vectorset" => "VCARD",
+        _ => return None,
+    };
+    Some(cmd(name).arg(key).clone())
+}
+
+/// Accumulates the analyzed keys into a `MemoryReport`.
+struct MemoryReportBuilder {
+    report: MemoryReport,
+    top_keys: usize,
+    types: HashMap<String, TypeMemory>,
+    biggest: HashMap<String, Vec<KeyMemory>>,
+    prefixes: HashMap<String, PrefixMemory>,
+}
+
+impl MemoryReportBuilder {
+    fn new(options: &MemoryAnalysisOptions) -> Self {
+        Self {
+            report: MemoryReport {
+                pattern: options.pattern.clone(),
+                separator: options.separator.clone(),
+                ..Default::default()
+            },
+            top_keys: options.top_keys.max(1),
+            types: HashMap::new(),
+            biggest: HashMap::new(),
+            prefixes: HashMap::new(),
+        }
+    }
+
+    fn add(&mut self, item: KeyMemory) {
+        let report = &mut self.report;
+        let expiring = item.ttl_ms.is_some();
+        report.keys += 1;
+        report.memory += item.memory;
+        if expiring {
+            report.expiring += 1;
+        }
+
+        let type_memory = self.types.entry(item.key_type.clone()).or_insert_with(|| TypeMemory {
+            key_type: item.key_type.clone(),
+            ..Default::default()
+        });
+        type_memory.keys += 1;
+        type_memory.memory += item.memory;
+        type_memory.elements += item.elements;
+
+        let mut parent: Option<String> = None;
+        for (index, prefix) in key_prefixes(&item.key, &report.separator).into_iter().enumerate() {
+            if !self.prefixes.contains_key(&prefix) {
+                if self.prefixes.len() >= MAX_TRACKED_PREFIXES {
+                    report.prefixes_truncated = true;
+                    break;
+                }
+                let prefix_memory = PrefixMemory {
+                    prefix: prefix.clone(),
+                    depth: index + 1,
+                    ..Default::default()
+                };
+                self.prefixes.insert(prefix.clone(), prefix_memory);
+                if let Some(parent) = parent.as_ref().and_then(|parent| self.prefixes.get_mut(parent)) {
+                    parent.children += 1;
+                }
+            }
+            let Some(prefix_memory) = self.prefixes.get_mut(&prefix) else {
+                continue;
+            };
+            prefix_memory.keys += 1;
+            prefix_memory.memory += item.memory;
+            if expiring {
+                prefix_memory.expiring += 1;
+            }
+            parent = Some(prefix);
+        }
+
+        // The candidates are trimmed once they reach twice the kept size
+        let top_keys = self.top_keys;
+        let biggest = self.biggest.entry(item.key_type.clone()).or_default();
+        biggest.push(item);
+        if biggest.len() >= top_keys * 2 {
+            biggest.sort_by_key(|item| std::cmp::Reverse(item.memory));
+            biggest.truncate(top_keys);
+        }
+    }
+
+    fn finish(self, cancelled: bool) -> MemoryReport {
+        let mut report = self.report;
+        report.cancelled = cancelled;
+        report.analyzed_at = Utc::now().to_rfc3339();
+
+        report.types = self.types.into_values().collect();
+        report
+            .types
+            .sort_by(|a, b| b.memory.cmp(&a.memory).then(a.key_type.cmp(&b.key_type)));
+
+        let mut biggest = self.biggest;
+        for item in &report.types {
+            let Some(mut keys) = biggest.remove(&item.key_type) else {
+                continue;
+            };
+            keys.sort_by(|a, b| b.memory.cmp(&a.memory).then(a.key.cmp(&b.key)));
+            keys.truncate(self.top_keys);
+            report.top_keys.extend(keys);
+        }
+
+        report.prefixes = self.prefixes.into_values().collect();
+        report
+            .prefixes
+            .sort_by(|a, b| b.memory.cmp(&a.memory).then(a.prefix.cmp(&b.prefix)));
+        report
+    }
+}
+
+impl ConnectionManager {
+    /// Analyzes the memory used by the keys matching a pattern on every master.
+    ///
+    /// For each SCAN batch, `TYPE`, `MEMORY USAGE` and `PTTL` are pipelined, then the
+    /// length of every value with the command of its type. Requires Redis 4.0.
+    /// The analysis stops after the current batch once `cancelled` is set, and the report
+    /// of the keys analyzed so far is returned.
+    pub async fn analyze_memory(
+        &self,
+        server_id: &str,
+        db: usize,
+        preset_credentials: Vec<PresetCredential>,
+        options: MemoryAnalysisOptions,
+        progress_tx: Option<UnboundedSender<BulkKeyProgress>>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<MemoryReport> {
+        let (client, _) = self.get_client(server_id, db, preset_credentials).await?;
+        if !client.is_at_least_version("4.0.0") {
+            return Err(Error::Invalid {
+                message: "MEMORY USAGE requires Redis 4.0 or later".to_string(),
+            });
+        }
+        // The database size is only the number of analyzed keys without pattern
+        let total = if options.pattern == "*" {
+            client.dbsize().await.ok().map(|size| size as usize)
+        } else {
+            None
+        };
+        let send_progress = |processed: usize| {
+            if let Some(tx) = &progress_tx {
+                let _ = tx.unbounded_send(BulkKeyProgress {
+                    phase: BulkKeyPhase::Analyze,
+                    processed,
+                    total,
+                });
+            }
+        };
+        info!(server_id, db, ?options, "start analyzing memory");
+        send_progress(0);
+
+        let mut builder = MemoryReportBuilder::new(&options);
+        let mut processed = 0;
+        let mut was_cancelled = false;
+        let target = BulkKeys::Pattern(options.pattern.clone());
+        let mut batches = KeyBatches::new(&client, db, &target);
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                was_cancelled = true;
+                break;
+            }
+            let Some((conn, keys)) = batches.next_batch().await? else {
+                break;
+            };
+            if keys.is_empty() {
+                continue;
+            }
+            processed += keys.len();
+
+            let mut commands = Vec::with_capacity(keys.len() * 3);
+            for key in &keys {
+                commands.push(cmd("TYPE").arg(key).clone());
+                commands.push(
+                    cmd("MEMORY")
+                        .arg("USAGE")
+                        .arg(key)
+                        .arg("SAMPLES")
+                        .arg(MEMORY_USAGE_SAMPLES)
+                        .clone(),
+                );
+                commands.push(cmd("PTTL").arg(key).clone());
+            }
+            let results: Vec<redis::RedisResult<Value>> = query_each(&conn, commands).await?;
+            let mut analyzed = Vec::with_capacity(keys.len());
+            for (key, replies) in keys.iter().zip(results.chunks(3)) {
+                let [key_type, memory, pttl] = replies else {
+                    continue;
+                };
+                let key_type = key_type
+                    .as_ref()
+                    .ok()
+                    .and_then(|value| from_redis_value_ref::<String>(value).ok())
+                    .unwrap_or_default();
+                let memory = memory
+                    .as_ref()
+                    .ok()
+                    .and_then(|value| from_redis_value_ref::<Option<u64>>(value).ok())
+                    .flatten();
+                let (Some(memory), false) = (memory, key_type.is_empty() || key_type == "none") else {
+                    builder.report.missing += 1;
+                    continue;
+                };
+                let pttl = pttl
+                    .as_ref()
+                    .ok()
+                    .and_then(|value| from_redis_value_ref::<i64>(value).ok())
+                    .unwrap_or(-1);
+                analyzed.push((
+                    key,
+                    KeyMemory {
+                        key: RedisKey::escape(key),
+                        key_type,
+                        memory,
+                        elements: 0,
+                        ttl_ms: (pttl >= 0).then_some(pttl as u64),
+                    },
+                ));
+            }
+
+            // Keys of an unknown type, e.g. module types, keep no element count
+            let (targets, length_commands): (Vec<_>, Vec<_>) = analyzed
+                .iter()
+                .enumerate()
+                .filter_map(|(index, (key, item))| length_command(&item.key_type, key).map(|command| (index, command)))
+                .unzip();
+            let lengths: Vec<redis::RedisResult<u64>> = query_each(&conn, length_commands).await?;
+            for (index, length) in targets.into_iter().zip(lengths) {
+                if let (Some((_, item)), Ok(length)) = (analyzed.get_mut(index), length) {
+                    item.elements = length;
+                }
+            }
+            for (_, item) in analyzed {
+                builder.add(item);
+            }
+            send_progress(processed);
+        }
+
+        let report = builder.finish(was_cancelled);
+        info!(
+            server_id,
+            db,
+            keys = report.keys,
+            memory = report.memory,
+            cancelled = report.cancelled,
+            "analyze memory finished"
+        );
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key: &str, key_type: &str, memory: u64, ttl_ms: Option<u64>) -> KeyMemory {
+        KeyMemory {
+            key: key.to_string(),
+            key_type: key_type.to_string(),
+            memory,
+            elements: 1,
+            ttl_ms,
+        }
+    }
+
+    #[test]
+    fn splits_key_prefixes() {
+        assert_eq!(key_prefixes("user:1:name", ":"), vec!["user:", "user:1:"]);
+        assert_eq!(key_prefixes("a::b", "::"), vec!["a::"]);
+        assert_eq!(key_prefixes("a:b:c:d:e", ":"), vec!["a:", "a:b:", "a:b:c:"]);
+        assert!(key_prefixes("plain", ":").is_empty());
+        assert!(key_prefixes("trailing:", ":").is_empty());
+        assert!(key_prefixes("user:1", "").is_empty());
+    }
+
+    #[test]
+    fn aggregates_biggest_keys_and_prefixes() {
+        let options = MemoryAnalysisOptions {
+            pattern: "*".to_string(),
+            separator: ":".to_string(),
+            top_keys: 2,
+        };
+        let mut builder = MemoryReportBuilder::new(&options);
+        builder.add(key("user:1:name", "string", 100, None));
+        builder.add(key("user:2:name", "string", 300, Some(1000)));
+        builder.add(key("user:2:tags", "set", 50, None));
+        builder.add(key("cache:a", "string", 200, Some(500)));
+        builder.add(key("plain", "string", 10, None));
+        let report = builder.finish(false);
+
+        assert_eq!(report.keys, 5);
+        assert_eq!(report.memory, 660);
+        assert_eq!(report.expiring, 2);
+        assert_eq!(report.types[0].key_type, "string");
+        assert_eq!(report.types[0].keys, 4);
+        assert_eq!(report.types[1].memory, 50);
+
+        let strings: Vec<_> = report
+            .top_keys_of("string")
+            .iter()
+            .map(|item| item.key.as_str())
+            .collect();
+        assert_eq!(strings, vec!["user:2:name", "cache:a"]);
+        assert_eq!(report.top_keys_of("set").len(), 1);
+
+        let first_level: Vec<_> = report
+            .child_prefixes("")
+            .iter()
+            .map(|item| (item.prefix.as_str(), item.keys, item.memory))
+            .collect();
+        assert_eq!(first_level, vec![("user:", 3, 450), ("cache:", 1, 200)]);
+        let user_level: Vec<_> = report
+            .child_prefixes("user:")
+            .iter()
+            .map(|item| (item.prefix.as_str(), item.memory))
+            .collect();
+        assert_eq!(user_level, vec![("user:2:", 350), ("user:1:", 100)]);
+        assert_eq!(report.child_prefixes("")[0].children, 2);
+        assert_eq!(report.child_prefixes("user:")[0].children, 0);
+
+        let csv = report.to_csv();
+        assert!(csv.starts_with("kind,type,name,keys,memory_bytes,elements,ttl_ms\ntype,string,,4,610,4,\n"));
+        assert!(csv.contains("key,string,user:2:name,1,300,1,1000\n"));
+        assert!(csv.contains("prefix,,user:,3,450,,\n"));
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,347 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dedicated Pub/Sub connections.
+//!
+//! A subscribed connection cannot be shared with regular commands, so every
+//! session opens its own connections:
+//! - Servers from 6.0 use RESP3, messages arrive as push replies and sharded
+//!   channels (`SSUBSCRIBE`, 7.0+) are supported
+//! - Older servers use a RESP2 Pub/Sub connection
+//!
+//! Sharded channels of a cluster live on the node owning their slot, the session
+//! follows `MOVED` redirections and keeps one connection per node.
+
+use super::{
+    async_connection::open_single_client_with_protocol,
+    config::RedisServer,
+    manager::{NodeRedirect, get_connection_manager},
+    ssh_tunnel::{open_ssh_tunnel_pubsub, open_ssh_tunnel_push_connection},
+};
+use crate::error::Error;
+use chrono::Utc;
+use futures::{
+    StreamExt,
+    channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
+};
+use redis::{
+    AsyncConnectionConfig, Msg, ProtocolVersion, PushInfo, PushKind,
+    aio::{MultiplexedConnection, PubSubSink},
+    cmd,
+};
+use std::{collections::HashMap, time::Duration};
+use tracing::info;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The kind of a subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum SubscriptionKind {
+    /// `SUBSCRIBE` to channels
+    #[default]
+    Channel,
+    /// `PSUBSCRIBE` to glob-style patterns
+    Pattern,
+    /// `SSUBSCRIBE` to sharded channels (Redis 7.0+)
+    Shard,
+}
+
+impl SubscriptionKind {
+    pub fn subscribe_command(&self) -> &'static str {
+        match self {
+            SubscriptionKind::Channel => "SUBSCRIBE",
+            SubscriptionKind::Pattern => "PSUBSCRIBE",
+            SubscriptionKind::Shard => "SSUBSCRIBE",
+        }
+    }
+    pub fn unsubscribe_command(&self) -> &'static str {
+        match self {
+            SubscriptionKind::Channel => "UNSUBSCRIBE",
+            SubscriptionKind::Pattern => "PUNSUBSCRIBE",
+            SubscriptionKind::Shard => "SUNSUBSCRIBE",
+        }
+    }
+    /// The command publishing to this kind of channel.
+    pub fn publish_command(&self) -> &'static str {
+        match self {
+            SubscriptionKind::Shard => "SPUBLISH",
+            _ => "PUBLISH",
+        }
+    }
+}
+
+/// A message received on a subscription.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PubSubMessage {
+    pub kind: SubscriptionKind,
+    pub channel: Vec<u8>,
+    /// The matching pattern for pattern subscriptions
+    pub pattern: Option<Vec<u8>>,
+    pub payload: Vec<u8>,
+    /// Unix timestamp in milliseconds
+    pub received_at: i64,
+}
+
+impl PubSubMessage {
+    fn new(msg: &Msg, kind: SubscriptionKind) -> Self {
+        Self {
+            kind,
+            channel: msg.get_channel().unwrap_or_default(),
+            pattern: msg.get_pattern().unwrap_or_default(),
+            payload: msg.get_payload_bytes().to_vec(),
+            received_at: Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Converts a RESP3 push reply, ignoring everything but messages.
+    fn from_push(info: PushInfo) -> Option<Self> {
+        let kind = match info.kind {
+            PushKind::Message => SubscriptionKind::Channel,
+            PushKind::PMessage => SubscriptionKind::Pattern,
+            PushKind::SMessage => SubscriptionKind::Shard,
+            _ => return None,
+        };
+        Msg::from_push_info(info).map(|msg| Self::new(&msg, kind))
+    }
+
+    /// Converts a message of a RESP2 Pub/Sub connection.
+    fn from_msg(msg: &Msg) -> Self {
+        let kind = if msg.from_pattern() {
+            SubscriptionKind::Pattern
+        } else {
+            SubscriptionKind::Channel
+        };
+        Self::new(msg, kind)
+    }
+}
+
+enum Subscriber {
+    Resp3(MultiplexedConnection),
+    Resp2(PubSubSink),
+}
+
+impl Subscriber {
+    async fn send(&mut self, command: &str, names: &[String]) -> Result<()> {
+        match self {
+            Subscriber::Resp3(conn) => {
+                cmd(command).arg(names).exec_async(conn).await?;
+            }
+            Subscriber::Resp2(sink) => match command {
+                "SUBSCRIBE" => sink.subscribe(names).await?,
+                "UNSUBSCRIBE" => sink.unsubscribe(names).await?,
+                "PSUBSCRIBE" => sink.psubscribe(names).await?,
+                "PUNSUBSCRIBE" => sink.punsubscribe(names).await?,
+                _ => {
+                    return Err(Error::Invalid {
+                        message: format!("{command} requires Redis 7.0 or later"),
+                    });
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Opens a subscriber connection forwarding its messages to `sender`.
+async fn open_subscriber(
+    config: &RedisServer,
+    resp3: bool,
+    sender: UnboundedSender<PubSubMessage>,
+) -> Result<Subscriber> {
+    if resp3 {
+        let push_sender = move |info: PushInfo| match PubSubMessage::from_push(info) {
+            Some(message) => sender.unbounded_send(message),
+            None => Ok(()),
+        };
+        let conn = if config.is_ssh_tunnel() {
+            open_ssh_tunnel_push_connection(config, push_sender).await?
+        } else {
+            let client = open_single_client_with_protocol(config, ProtocolVersion::RESP3)?;
+            let cfg = AsyncConnectionConfig::new()
+                .set_connection_timeout(Some(CONNECTION_TIMEOUT))
+                .set_push_sender(push_sender);
+            client.get_multiplexed_async_connection_with_config(&cfg).await?
+        };
+        return Ok(Subscriber::Resp3(conn));
+    }
+    let pubsub = if config.is_ssh_tunnel() {
+        open_ssh_tunnel_pubsub(config).await?
+    } else {
+        let client = open_single_client_with_protocol(config, ProtocolVersion::RESP2)?;
+        client.get_async_pubsub().await?
+    };
+    let (sink, mut stream) = pubsub.split();
+    smol::spawn(async move {
+        while let Some(msg) = stream.next().await {
+            if sender.unbounded_send(PubSubMessage::from_msg(&msg)).is_err() {
+                break;
+            }
+        }
+        info!("pubsub stream finished");
+    })
+    .detach();
+    Ok(Subscriber::Resp2(sink))
+}
+
+/// A set of subscriptions sharing one message receiver.
+///
+/// Connections are closed when the session is dropped.
+pub struct PubSubSession {
+    /// Configuration of the node used for channel and pattern subscriptions
+    base: RedisServer,
+    resp3: bool,
+    /// Subscriber connections keyed by `host:port`
+    subscribers: HashMap<String, Subscriber>,
+    /// The node serving each sharded channel
+    shard_nodes: HashMap<String, String>,
+    sender: UnboundedSender<PubSubMessage>,
+}
+
+impl PubSubSession {
+    /// Opens a session on the given server.
+    ///
+    /// # Arguments
+    /// * `server_id` - The server to subscribe on
+    /// * `db` - The selected database, only used to reuse the cached client
+    ///
+    /// # Returns
+    /// The session and the receiver of every message of its subscriptions.
+    pub async fn open(server_id: &str, db: usize) -> Result<(Self, UnboundedReceiver<PubSubMessage>)> {
+        let (client, _) = get_connection_manager().get_client(server_id, db, vec![]).await?;
+        let base = client.pubsub_server().ok_or_else(|| Error::Invalid {
+            message: "No master node available for Pub/Sub".to_string(),
+        })?;
+        let (sender, receiver) = unbounded();
+        let session = Self {
+            base,
+            resp3: client.is_at_least_version("6.0.0"),
+            subscribers: HashMap::new(),
+            shard_nodes: HashMap::new(),
+            sender,
+        };
+        Ok((session, receiver))
+    }
+
+    fn base_addr(&self) -> String {
+        format!("{}:{}", self.base.host, self.base.port)
+    }
+
+    /// Returns the subscriber of a node, connecting on first use.
+    async fn subscriber(&mut self, addr: &str) -> Result<&mut Subscriber> {
+        if !self.subscribers.contains_key(addr) {
+            let mut config = self.base.clone();
+            // The base node keeps its address, it may be a unix socket
+            if addr != self.base_addr()
+                && let Some((host, port)) = addr.rsplit_once(':')
+            {
+                config.set_addr(host, port.parse().unwrap_or(config.port));
+            }
+            let subscriber = open_subscriber(&config, self.resp3, self.sender.clone()).await?;
+            self.subscribers.insert(addr.to_string(), subscriber);
+        }
+        self.subscribers.get_mut(addr).ok_or_else(|| Error::Invalid {
+            message: format!("Subscriber of {addr} is not available"),
+        })
+    }
+
+    /// Subscribes to channels, patterns or sharded channels.
+    pub async fn subscribe(&mut self, kind: SubscriptionKind, names: &[String]) -> Result<()> {
+        if kind != SubscriptionKind::Shard {
+            let addr = self.base_addr();
+            return self
+                .subscriber(&addr)
+                .await?
+                .send(kind.subscribe_command(), names)
+                .await;
+        }
+        // Sharded channels may live on different nodes, subscribe one by one
+        for name in names {
+            let mut addr = self.base_addr();
+            let channel = [name.clone()];
+            if let Err(e) = self
+                .subscriber(&addr)
+                .await?
+                .send(kind.subscribe_command(), &channel)
+                .await
+            {
+                let Some(redirect) = NodeRedirect::from_error(&e) else {
+                    return Err(e);
+                };
+                addr = redirect.addr;
+                self.subscriber(&addr)
+                    .await?
+                    .send(kind.subscribe_command(), &channel)
+                    .await?;
+            }
+            self.shard_nodes.insert(name.clone(), addr);
+        }
+        Ok(())
+    }
+
+    /// Unsubscribes from channels, patterns or sharded channels.
+    pub async fn unsubscribe(&mut self, kind: SubscriptionKind, names: &[String]) -> Result<()> {
+        if kind != SubscriptionKind::Shard {
+            let addr = self.base_addr();
+            return self
+                .subscriber(&addr)
+                .await?
+                .send(kind.unsubscribe_command(), names)
+                .await;
+        }
+        for name in names {
+            let addr = self.shard_nodes.remove(name).unwrap_or_else(|| self.base_addr());
+            self.subscriber(&addr)
+                .await?
+                .send(kind.unsubscribe_command(), std::slice::from_ref(name))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use redis::Value;
+
+    #[test]
+    fn converts_push_messages() {
+        let bulk = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+        let message = PubSubMessage::from_push(PushInfo {
+            kind: PushKind::PMessage,
+            data: vec![bulk("news.*"), bulk("news.tech"), bulk("hello")],
+        })
+        .expect("pattern message");
+        assert_eq!(message.kind, SubscriptionKind::Pattern);
+        assert_eq!(message.pattern.as_deref(), Some(&b"news.*"[..]));
+        assert_eq!(message.channel, b"news.tech");
+        assert_eq!(message.payload, b"hello");
+
+        let message = PubSubMessage::from_push(PushInfo {
+            kind: PushKind::SMessage,
+            data: vec![bulk("orders"), bulk("1")],
+        })
+        .expect("shard message");
+        assert_eq!(message.kind, SubscriptionKind::Shard);
+        assert_eq!(message.pattern, None);
+
+        let subscribed = PushInfo {
+            kind: PushKind::Subscribe,
+            data: vec![bulk("orders"), Value::Int(1)],
+        };
+        assert_eq!(PubSubMessage::from_push(subscribed), None);
+    }
+}
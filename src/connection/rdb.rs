@@ -0,0 +1,1043 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parser of RDB snapshot files.
+//!
+//! Supports strings, lists, sets, sorted sets, hashes and streams in every encoding
+//! written since Redis 2.x (linked lists, ziplists, listpacks, quicklists, intsets and
+//! zipmaps), LZF compressed strings and expiries. Module values are skipped but keep
+//! their type name and serialized payload, so they can still be dumped.
+
+use crate::error::Error;
+use std::{collections::BTreeMap, ops::Range};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+const RDB_OPCODE_SLOT_INFO: u8 = 0xF4;
+const RDB_OPCODE_FUNCTION2: u8 = 0xF5;
+const RDB_OPCODE_FUNCTION_PRE_GA: u8 = 0xF6;
+const RDB_OPCODE_MODULE_AUX: u8 = 0xF7;
+const RDB_OPCODE_IDLE: u8 = 0xF8;
+const RDB_OPCODE_FREQ: u8 = 0xF9;
+const RDB_OPCODE_AUX: u8 = 0xFA;
+const RDB_OPCODE_RESIZEDB: u8 = 0xFB;
+const RDB_OPCODE_EXPIRETIME_MS: u8 = 0xFC;
+const RDB_OPCODE_EXPIRETIME: u8 = 0xFD;
+const RDB_OPCODE_SELECTDB: u8 = 0xFE;
+const RDB_OPCODE_EOF: u8 = 0xFF;
+
+const RDB_TYPE_STRING: u8 = 0;
+const RDB_TYPE_LIST: u8 = 1;
+const RDB_TYPE_SET: u8 = 2;
+const RDB_TYPE_ZSET: u8 = 3;
+const RDB_TYPE_HASH: u8 = 4;
+const RDB_TYPE_ZSET_2: u8 = 5;
+const RDB_TYPE_MODULE_2: u8 = 7;
+const RDB_TYPE_HASH_ZIPMAP: u8 = 9;
+const RDB_TYPE_LIST_ZIPLIST: u8 = 10;
+const RDB_TYPE_SET_INTSET: u8 = 11;
+const RDB_TYPE_ZSET_ZIPLIST: u8 = 12;
+const RDB_TYPE_HASH_ZIPLIST: u8 = 13;
+const RDB_TYPE_LIST_QUICKLIST: u8 = 14;
+const RDB_TYPE_STREAM_LISTPACKS: u8 = 15;
+const RDB_TYPE_HASH_LISTPACK: u8 = 16;
+const RDB_TYPE_ZSET_LISTPACK: u8 = 17;
+const RDB_TYPE_LIST_QUICKLIST_2: u8 = 18;
+const RDB_TYPE_STREAM_LISTPACKS_2: u8 = 19;
+const RDB_TYPE_SET_LISTPACK: u8 = 20;
+const RDB_TYPE_STREAM_LISTPACKS_3: u8 = 21;
+const RDB_TYPE_HASH_METADATA: u8 = 24;
+const RDB_TYPE_HASH_LISTPACK_EX: u8 = 25;
+
+const RDB_ENC_INT8: u8 = 0;
+const RDB_ENC_INT16: u8 = 1;
+const RDB_ENC_INT32: u8 = 2;
+const RDB_ENC_LZF: u8 = 3;
+
+const RDB_MODULE_OPCODE_EOF: u64 = 0;
+const RDB_MODULE_OPCODE_SINT: u64 = 1;
+const RDB_MODULE_OPCODE_UINT: u64 = 2;
+const RDB_MODULE_OPCODE_FLOAT: u64 = 3;
+const RDB_MODULE_OPCODE_DOUBLE: u64 = 4;
+const RDB_MODULE_OPCODE_STRING: u64 = 5;
+
+const QUICKLIST_NODE_CONTAINER_PLAIN: u64 = 1;
+const STREAM_ITEM_FLAG_DELETED: i64 = 1;
+const STREAM_ITEM_FLAG_SAMEFIELDS: i64 = 2;
+
+/// Characters of the 9 letters module type names encoded in module ids
+const MODULE_NAME_CHARSET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Reflected polynomial of the CRC-64/Jones checksum used by RDB files and `DUMP` payloads
+const CRC64_POLY: u64 = 0x95ac_9329_ac4b_c9b5;
+const CRC64_TABLE: [u64; 256] = {
+    let mut table = [0_u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ CRC64_POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+pub(super) fn crc64(crc: u64, data: &[u8]) -> u64 {
+    data.iter().fold(crc, |crc, byte| {
+        CRC64_TABLE[((crc ^ *byte as u64) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn rdb_error(message: impl AsRef<str>, offset: usize) -> Error {
+    Error::Invalid {
+        message: format!("Invalid RDB file: {} at offset {offset}", message.as_ref()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) struct StreamId {
+    pub(super) ms: u64,
+    pub(super) seq: u64,
+}
+
+impl StreamId {
+    fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+        let ms = u64::from_be_bytes(bytes.get(0..8)?.try_into().ok()?);
+        let seq = u64::from_be_bytes(bytes.get(8..16)?.try_into().ok()?);
+        Some(Self { ms, seq })
+    }
+}
+
+impl std::fmt::Display for StreamId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+/// Id and field-value pairs of a stream entry
+pub(super) type StreamEntry = (StreamId, Vec<(Vec<u8>, Vec<u8>)>);
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct RdbStreamGroup {
+    pub(super) name: Vec<u8>,
+    pub(super) last_id: StreamId,
+    pub(super) consumers: usize,
+    pub(super) pending: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct RdbStream {
+    pub(super) entries: Vec<StreamEntry>,
+    pub(super) last_id: StreamId,
+    pub(super) groups: Vec<RdbStreamGroup>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum RdbValue {
+    String(Vec<u8>),
+    List(Vec<Vec<u8>>),
+    Set(Vec<Vec<u8>>),
+    /// Members ordered by score, then member
+    Zset(Vec<(Vec<u8>, f64)>),
+    Hash(Vec<(Vec<u8>, Vec<u8>)>),
+    Stream(RdbStream),
+    /// A module value, only its type name is known
+    Module(String),
+}
+
+impl RdbValue {
+    /// The name returned by `TYPE`.
+    pub(super) fn type_name(&self) -> &str {
+        match self {
+            RdbValue::String(_) => "string",
+            RdbValue::List(_) => "list",
+            RdbValue::Set(_) => "set",
+            RdbValue::Zset(_) => "zset",
+            RdbValue::Hash(_) => "hash",
+            RdbValue::Stream(_) => "stream",
+            RdbValue::Module(name) => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct RdbKey {
+    pub(super) key: Vec<u8>,
+    pub(super) value: RdbValue,
+    /// Encoding of the value in the file
+    pub(super) encoding: &'static str,
+    pub(super) expire_at_ms: Option<i64>,
+    /// RDB type of the value
+    value_type: u8,
+    /// Position of the serialized value in the file
+    value_range: Range<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct RdbDatabase {
+    /// Keys ordered by name
+    pub(super) keys: Vec<RdbKey>,
+}
+
+impl RdbDatabase {
+    pub(super) fn get(&self, key: &[u8]) -> Option<&RdbKey> {
+        self.keys
+            .binary_search_by(|item| item.key.as_slice().cmp(key))
+            .ok()
+            .map(|index| &self.keys[index])
+    }
+    pub(super) fn expires(&self) -> usize {
+        self.keys.iter().filter(|key| key.expire_at_ms.is_some()).count()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct RdbSnapshot {
+    pub(super) version: u16,
+    /// Auxiliary fields (`redis-ver`, `ctime`, `used-mem`...)
+    pub(super) aux: Vec<(String, String)>,
+    pub(super) databases: BTreeMap<usize, RdbDatabase>,
+    /// Keys already expired when the snapshot was taken, skipped like Redis does on load
+    pub(super) expired: usize,
+    data: Vec<u8>,
+}
+
+impl RdbSnapshot {
+    pub(super) fn aux(&self, name: &str) -> Option<&str> {
+        self.aux
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Creation time of the snapshot, from the `ctime` field.
+    pub(super) fn created_at_ms(&self) -> Option<i64> {
+        self.aux("ctime")
+            .and_then(|ctime| ctime.parse::<i64>().ok())
+            .map(|ctime| ctime * 1000)
+    }
+
+    /// The payload `DUMP` returns for the key: the serialized value followed by the
+    /// RDB version and a CRC64 checksum.
+    pub(super) fn dump_payload(&self, key: &RdbKey) -> Vec<u8> {
+        let value = &self.data[key.value_range.clone()];
+        let mut payload = Vec::with_capacity(value.len() + 11);
+        payload.push(key.value_type);
+        payload.extend_from_slice(value);
+        payload.extend_from_slice(&self.version.to_le_bytes());
+        let checksum = crc64(0, &payload);
+        payload.extend_from_slice(&checksum.to_le_bytes());
+        payload
+    }
+
+    /// Size of the serialized value, reported by `MEMORY USAGE`.
+    pub(super) fn serialized_size(&self, key: &RdbKey) -> usize {
+        key.key.len() + key.value_range.len() + 1
+    }
+
+    /// Parses a snapshot.
+    ///
+    /// `now_ms` is used when the file has no creation time: keys expired before it
+    /// are skipped.
+    pub(super) fn parse(data: Vec<u8>, now_ms: i64) -> Result<Self> {
+        let mut reader = Reader { data: &data, pos: 0 };
+        if reader.bytes(5)? != b"REDIS" {
+            return Err(rdb_error("missing REDIS signature", 0));
+        }
+        let version = std::str::from_utf8(reader.bytes(4)?)
+            .ok()
+            .and_then(|version| version.parse::<u16>().ok())
+            .ok_or_else(|| rdb_error("invalid version", 5))?;
+
+        let mut aux = Vec::new();
+        let mut databases: BTreeMap<usize, RdbDatabase> = BTreeMap::new();
+        let mut db = 0;
+        let mut expire_at_ms = None;
+        let mut keys = Vec::new();
+        loop {
+            let opcode_pos = reader.pos;
+            let opcode = reader.u8()?;
+            match opcode {
+                RDB_OPCODE_EOF => break,
+                RDB_OPCODE_AUX => {
+                    let field = reader.string()?;
+                    let value = reader.string()?;
+                    aux.push((
+                        String::from_utf8_lossy(&field).into_owned(),
+                        String::from_utf8_lossy(&value).into_owned(),
+                    ));
+                }
+                RDB_OPCODE_SELECTDB => {
+                    db = reader.len()?;
+                }
+                RDB_OPCODE_RESIZEDB => {
+                    let size = reader.len()?;
+                    let _ = reader.len()?;
+                    keys.reserve(size.min(1 << 16));
+                }
+                RDB_OPCODE_SLOT_INFO => {
+                    for _ in 0..3 {
+                        let _ = reader.len()?;
+                    }
+                }
+                RDB_OPCODE_FUNCTION2 => {
+                    let _ = reader.string()?;
+                }
+                RDB_OPCODE_FUNCTION_PRE_GA => {
+                    return Err(rdb_error("pre-release functions are not supported", opcode_pos));
+                }
+                RDB_OPCODE_MODULE_AUX => {
+                    let _ = reader.length_u64()?;
+                    let _ = reader.length_u64()?;
+                    reader.skip_module_value()?;
+                }
+                RDB_OPCODE_IDLE => {
+                    let _ = reader.length_u64()?;
+                }
+                RDB_OPCODE_FREQ => {
+                    let _ = reader.u8()?;
+                }
+                RDB_OPCODE_EXPIRETIME_MS => {
+                    expire_at_ms = Some(reader.i64_le()?);
+                }
+                RDB_OPCODE_EXPIRETIME => {
+                    expire_at_ms = Some(reader.u32_le()? as i64 * 1000);
+                }
+                value_type => {
+                    let key = reader.string()?;
+                    let value_start = reader.pos;
+                    let (value, encoding) = reader.value(value_type)?;
+                    keys.push((
+                        db,
+                        RdbKey {
+                            key,
+                            value,
+                            encoding,
+                            expire_at_ms: expire_at_ms.take(),
+                            value_type,
+                            value_range: value_start..reader.pos,
+                        },
+                    ));
+                }
+            }
+        }
+        if version >= 5 {
+            let end = reader.pos;
+            let checksum = reader.u64_le()?;
+            if checksum != 0 && checksum != crc64(0, &data[..end]) {
+                return Err(rdb_error("checksum mismatch", end));
+            }
+        }
+
+        let mut snapshot = RdbSnapshot {
+            version,
+            aux,
+            ..Default::default()
+        };
+        let snapshot_ms = snapshot.created_at_ms().unwrap_or(now_ms);
+        for (db, key) in keys {
+            if key.expire_at_ms.is_some_and(|expire_at_ms| expire_at_ms <= snapshot_ms) {
+                snapshot.expired += 1;
+                continue;
+            }
+            databases.entry(db).or_default().keys.push(key);
+        }
+        for database in databases.values_mut() {
+            database.keys.sort_by(|a, b| a.key.cmp(&b.key));
+        }
+        snapshot.databases = databases;
+        snapshot.data = data;
+        Ok(snapshot)
+    }
+}
+
+enum Length {
+    Len(u64),
+    Encoded(u8),
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| rdb_error("unexpected end of file", self.pos))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+    fn u32_le(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+    fn u64_le(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+    fn i64_le(&mut self) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+    fn length(&mut self) -> Result<Length> {
+        let first = self.u8()?;
+        let length = match first >> 6 {
+            0 => Length::Len((first & 0x3f) as u64),
+            1 => Length::Len((((first & 0x3f) as u64) << 8) | self.u8()? as u64),
+            2 => match first {
+                0x80 => Length::Len(u32::from_be_bytes(self.array()?) as u64),
+                0x81 => Length::Len(u64::from_be_bytes(self.array()?)),
+                _ => return Err(rdb_error("invalid length encoding", self.pos - 1)),
+            },
+            _ => Length::Encoded(first & 0x3f),
+        };
+        Ok(length)
+    }
+    fn length_u64(&mut self) -> Result<u64> {
+        match self.length()? {
+            Length::Len(len) => Ok(len),
+            Length::Encoded(_) => Err(rdb_error("unexpected string encoding", self.pos - 1)),
+        }
+    }
+    fn len(&mut self) -> Result<usize> {
+        let pos = self.pos;
+        usize::try_from(self.length_u64()?).map_err(|_| rdb_error("length too large", pos))
+    }
+    fn string(&mut self) -> Result<Vec<u8>> {
+        let pos = self.pos;
+        match self.length()? {
+            Length::Len(len) => {
+                let len = usize::try_from(len).map_err(|_| rdb_error("length too large", pos))?;
+                Ok(self.bytes(len)?.to_vec())
+            }
+            Length::Encoded(RDB_ENC_INT8) => Ok((self.u8()? as i8).to_string().into_bytes()),
+            Length::Encoded(RDB_ENC_INT16) => Ok(i16::from_le_bytes(self.array()?).to_string().into_bytes()),
+            Length::Encoded(RDB_ENC_INT32) => Ok(i32::from_le_bytes(self.array()?).to_string().into_bytes()),
+            Length::Encoded(RDB_ENC_LZF) => {
+                let compressed_len = self.len()?;
+                let len = self.len()?;
+                let compressed = self.bytes(compressed_len)?;
+                lzf_decompress(compressed, len).ok_or_else(|| rdb_error("invalid LZF string", pos))
+            }
+            Length::Encoded(_) => Err(rdb_error("unknown string encoding", pos)),
+        }
+    }
+    /// Score of the first sorted set encoding: a length prefixed decimal string.
+    fn string_double(&mut self) -> Result<f64> {
+        let pos = self.pos;
+        match self.u8()? {
+            253 => Ok(f64::NAN),
+            254 => Ok(f64::INFINITY),
+            255 => Ok(f64::NEG_INFINITY),
+            len => {
+                let text = self.bytes(len as usize)?;
+                parse_double(text).ok_or_else(|| rdb_error("invalid score", pos))
+            }
+        }
+    }
+    fn binary_double(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+    fn strings(&mut self) -> Result<Vec<Vec<u8>>> {
+        let len = self.len()?;
+        let mut items = Vec::with_capacity(len.min(1 << 16));
+        for _ in 0..len {
+            items.push(self.string()?);
+        }
+        Ok(items)
+    }
+    /// Skips the opcodes of a module value until its end marker.
+    fn skip_module_value(&mut self) -> Result<()> {
+        loop {
+            let pos = self.pos;
+            match self.length_u64()? {
+                RDB_MODULE_OPCODE_EOF => return Ok(()),
+                RDB_MODULE_OPCODE_SINT | RDB_MODULE_OPCODE_UINT => {
+                    let _ = self.length_u64()?;
+                }
+                RDB_MODULE_OPCODE_FLOAT => {
+                    let _ = self.bytes(4)?;
+                }
+                RDB_MODULE_OPCODE_DOUBLE => {
+                    let _ = self.bytes(8)?;
+                }
+                RDB_MODULE_OPCODE_STRING => {
+                    let _ = self.string()?;
+                }
+                _ => return Err(rdb_error("unknown module opcode", pos)),
+            }
+        }
+    }
+
+    fn value(&mut self, value_type: u8) -> Result<(RdbValue, &'static str)> {
+        let pos = self.pos;
+        let value = match value_type {
+            RDB_TYPE_STRING => {
+                let value = self.string()?;
+                let encoding =
+                    if value.len() <= 20 && std::str::from_utf8(&value).is_ok_and(|s| s.parse::<i64>().is_ok()) {
+                        "int"
+                    } else if value.len() <= 44 {
+                        "embstr"
+                    } else {
+                        "raw"
+                    };
+                return Ok((RdbValue::String(value), encoding));
+            }
+            RDB_TYPE_LIST => (RdbValue::List(self.strings()?), "linkedlist"),
+            RDB_TYPE_SET => (RdbValue::Set(self.strings()?), "hashtable"),
+            RDB_TYPE_ZSET | RDB_TYPE_ZSET_2 => {
+                let len = self.len()?;
+                let mut members = Vec::with_capacity(len.min(1 << 16));
+                for _ in 0..len {
+                    let member = self.string()?;
+                    let score = if value_type == RDB_TYPE_ZSET {
+                        self.string_double()?
+                    } else {
+                        self.binary_double()?
+                    };
+                    members.push((member, score));
+                }
+                (zset(members), "skiplist")
+            }
+            RDB_TYPE_HASH => {
+                let len = self.len()?;
+                let mut fields = Vec::with_capacity(len.min(1 << 16));
+                for _ in 0..len {
+                    fields.push((self.string()?, self.string()?));
+                }
+                (RdbValue::Hash(fields), "hashtable")
+            }
+            RDB_TYPE_MODULE_2 => {
+                let module_id = self.length_u64()?;
+                self.skip_module_value()?;
+                (RdbValue::Module(module_type_name(module_id)), "raw")
+            }
+            RDB_TYPE_HASH_ZIPMAP => {
+                let blob = self.string()?;
+                let fields = zipmap_pairs(&blob).ok_or_else(|| rdb_error("invalid zipmap", pos))?;
+                (RdbValue::Hash(fields), "zipmap")
+            }
+            RDB_TYPE_LIST_ZIPLIST => (RdbValue::List(self.ziplist(pos)?), "ziplist"),
+            RDB_TYPE_SET_INTSET => {
+                let blob = self.string()?;
+                let members = intset_entries(&blob).ok_or_else(|| rdb_error("invalid intset", pos))?;
+                (RdbValue::Set(members), "intset")
+            }
+            RDB_TYPE_ZSET_ZIPLIST => (zset_pairs(self.ziplist(pos)?, pos)?, "ziplist"),
+            RDB_TYPE_HASH_ZIPLIST => (RdbValue::Hash(pairs(self.ziplist(pos)?)), "ziplist"),
+            RDB_TYPE_LIST_QUICKLIST => {
+                let len = self.len()?;
+                let mut items = Vec::new();
+                for _ in 0..len {
+                    items.extend(self.ziplist(pos)?);
+                }
+                (RdbValue::List(items), "quicklist")
+            }
+            RDB_TYPE_HASH_LISTPACK => (RdbValue::Hash(pairs(self.listpack(pos)?)), "listpack"),
+            RDB_TYPE_ZSET_LISTPACK => (zset_pairs(self.listpack(pos)?, pos)?, "listpack"),
+            RDB_TYPE_LIST_QUICKLIST_2 => {
+                let len = self.len()?;
+                let mut items = Vec::new();
+                for _ in 0..len {
+                    if self.length_u64()? == QUICKLIST_NODE_CONTAINER_PLAIN {
+                        items.push(self.string()?);
+                    } else {
+                        items.extend(self.listpack(pos)?);
+                    }
+                }
+                (RdbValue::List(items), "quicklist")
+            }
+            RDB_TYPE_SET_LISTPACK => (RdbValue::Set(self.listpack(pos)?), "listpack"),
+            RDB_TYPE_STREAM_LISTPACKS | RDB_TYPE_STREAM_LISTPACKS_2 | RDB_TYPE_STREAM_LISTPACKS_3 => {
+                (RdbValue::Stream(self.stream(value_type)?), "stream")
+            }
+            RDB_TYPE_HASH_METADATA => {
+                // minimum expire time of the fields, followed by (ttl, field, value)
+                let _ = self.i64_le()?;
+                let len = self.len()?;
+                let mut fields = Vec::with_capacity(len.min(1 << 16));
+                for _ in 0..len {
+                    let _ = self.length_u64()?;
+                    fields.push((self.string()?, self.string()?));
+                }
+                (RdbValue::Hash(fields), "hashtable")
+            }
+            RDB_TYPE_HASH_LISTPACK_EX => {
+                // minimum expire time of the fields, followed by (field, value, ttl)
+                let _ = self.i64_le()?;
+                let items = self.listpack(pos)?;
+                let fields = items
+                    .chunks_exact(3)
+                    .map(|chunk| (chunk[0].clone(), chunk[1].clone()))
+                    .collect();
+                (RdbValue::Hash(fields), "listpackex")
+            }
+            _ => return Err(rdb_error(format!("unsupported value type {value_type}"), pos - 1)),
+        };
+        Ok(value)
+    }
+    fn ziplist(&mut self, pos: usize) -> Result<Vec<Vec<u8>>> {
+        let blob = self.string()?;
+        ziplist_entries(&blob).ok_or_else(|| rdb_error("invalid ziplist", pos))
+    }
+    fn listpack(&mut self, pos: usize) -> Result<Vec<Vec<u8>>> {
+        let blob = self.string()?;
+        listpack_entries(&blob).ok_or_else(|| rdb_error("invalid listpack", pos))
+    }
+    fn stream(&mut self, value_type: u8) -> Result<RdbStream> {
+        let mut stream = RdbStream::default();
+        let nodes = self.len()?;
+        for _ in 0..nodes {
+            let pos = self.pos;
+            let master_id =
+                StreamId::from_be_bytes(&self.string()?).ok_or_else(|| rdb_error("invalid stream id", pos))?;
+            let items = self.listpack(pos)?;
+            stream_entries(master_id, &items, &mut stream.entries)
+                .ok_or_else(|| rdb_error("invalid stream node", pos))?;
+        }
+        let _length = self.length_u64()?;
+        stream.last_id = StreamId {
+            ms: self.length_u64()?,
+            seq: self.length_u64()?,
+        };
+        if value_type >= RDB_TYPE_STREAM_LISTPACKS_2 {
+            // first id, max deleted id and entries added
+            for _ in 0..5 {
+                let _ = self.length_u64()?;
+            }
+        }
+        let groups = self.len()?;
+        for _ in 0..groups {
+            let name = self.string()?;
+            let last_id = StreamId {
+                ms: self.length_u64()?,
+                seq: self.length_u64()?,
+            };
+            if value_type >= RDB_TYPE_STREAM_LISTPACKS_2 {
+                let _entries_read = self.length_u64()?;
+            }
+            let pending = self.len()?;
+            for _ in 0..pending {
+                // id, delivery time and delivery count
+                let _ = self.bytes(16)?;
+                let _ = self.i64_le()?;
+                let _ = self.length_u64()?;
+            }
+            let consumers = self.len()?;
+            for _ in 0..consumers {
+                let _name = self.string()?;
+                let _seen_time = self.i64_le()?;
+                if value_type >= RDB_TYPE_STREAM_LISTPACKS_3 {
+                    let _active_time = self.i64_le()?;
+                }
+                let consumer_pending = self.len()?;
+                let _ = self.bytes(consumer_pending.saturating_mul(16))?;
+            }
+            stream.groups.push(RdbStreamGroup {
+                name,
+                last_id,
+                consumers,
+                pending,
+            });
+        }
+        Ok(stream)
+    }
+}
+
+fn parse_double(text: &[u8]) -> Option<f64> {
+    let text = std::str::from_utf8(text).ok()?;
+    match text {
+        "inf" | "+inf" => Some(f64::INFINITY),
+        "-inf" => Some(f64::NEG_INFINITY),
+        _ => text.parse().ok(),
+    }
+}
+
+fn zset(mut members: Vec<(Vec<u8>, f64)>) -> RdbValue {
+    members.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    RdbValue::Zset(members)
+}
+
+fn pairs(items: Vec<Vec<u8>>) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut items = items.into_iter();
+    let mut pairs = Vec::with_capacity(items.len() / 2);
+    while let (Some(first), Some(second)) = (items.next(), items.next()) {
+        pairs.push((first, second));
+    }
+    pairs
+}
+
+fn zset_pairs(items: Vec<Vec<u8>>, pos: usize) -> Result<RdbValue> {
+    let members = pairs(items)
+        .into_iter()
+        .map(|(member, score)| Some((member, parse_double(&score)?)))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| rdb_error("invalid score", pos))?;
+    Ok(zset(members))
+}
+
+fn module_type_name(module_id: u64) -> String {
+    (0..9)
+        .map(|i| {
+            let index = (module_id >> (64 - 6 * (i + 1))) & 0x3f;
+            MODULE_NAME_CHARSET[index as usize] as char
+        })
+        .collect()
+}
+
+/// Decompresses an LZF block of a known uncompressed length.
+fn lzf_decompress(input: &[u8], len: usize) -> Option<Vec<u8>> {
+    // the length comes from the file, the vec grows with the output instead
+    let mut output = Vec::with_capacity(len.min(1 << 16));
+    let mut i = 0;
+    while i < input.len() {
+        if output.len() > len {
+            return None;
+        }
+        let ctrl = input[i] as usize;
+        i += 1;
+        if ctrl < 32 {
+            let literal = input.get(i..i + ctrl + 1)?;
+            output.extend_from_slice(literal);
+            i += ctrl + 1;
+        } else {
+            let mut run = ctrl >> 5;
+            if run == 7 {
+                run += *input.get(i)? as usize;
+                i += 1;
+            }
+            let offset = ((ctrl & 0x1f) << 8) + *input.get(i)? as usize + 1;
+            i += 1;
+            let start = output.len().checked_sub(offset)?;
+            for index in start..start + run + 2 {
+                output.push(output[index]);
+            }
+        }
+    }
+    (output.len() == len).then_some(output)
+}
+
+fn read_int(data: &[u8], pos: usize, size: usize) -> Option<i64> {
+    let bytes = data.get(pos..pos + size)?;
+    let mut value = 0_i64;
+    for (i, byte) in bytes.iter().enumerate() {
+        value |= (*byte as i64) << (8 * i);
+    }
+    // sign extend
+    let shift = 64 - 8 * size as u32;
+    Some((value << shift) >> shift)
+}
+
+fn ziplist_entries(data: &[u8]) -> Option<Vec<Vec<u8>>> {
+    let mut entries = Vec::new();
+    let mut pos = 10;
+    loop {
+        let prev_len = *data.get(pos)?;
+        if prev_len == 0xff {
+            return Some(entries);
+        }
+        pos += if prev_len == 0xfe { 5 } else { 1 };
+        let encoding = *data.get(pos)?;
+        let entry = match encoding >> 6 {
+            0 => {
+                let len = (encoding & 0x3f) as usize;
+                pos += 1;
+                data.get(pos..pos + len)?.to_vec()
+            }
+            1 => {
+                let len = (((encoding & 0x3f) as usize) << 8) | *data.get(pos + 1)? as usize;
+                pos += 2;
+                data.get(pos..pos + len)?.to_vec()
+            }
+            2 => {
+                let len = u32::from_be_bytes(data.get(pos + 1..pos + 5)?.try_into().ok()?) as usize;
+                pos += 5;
+                data.get(pos..pos + len)?.to_vec()
+            }
+            _ => {
+                pos += 1;
+                let (value, size) = match encoding {
+                    0xc0 => (read_int(data, pos, 2)?, 2),
+                    0xd0 => (read_int(data, pos, 4)?, 4),
+                    0xe0 => (read_int(data, pos, 8)?, 8),
+                    0xf0 => (read_int(data, pos, 3)?, 3),
+                    0xfe => (read_int(data, pos, 1)?, 1),
+                    0xf1..=0xfd => ((encoding & 0x0f) as i64 - 1, 0),
+                    _ => return None,
+                };
+                pos += size;
+                entries.push(value.to_string().into_bytes());
+                continue;
+            }
+        };
+        pos += entry.len();
+        entries.push(entry);
+    }
+}
+
+fn listpack_entries(data: &[u8]) -> Option<Vec<Vec<u8>>> {
+    let mut entries = Vec::new();
+    let mut pos = 6;
+    loop {
+        let encoding = *data.get(pos)?;
+        if encoding == 0xff {
+            return Some(entries);
+        }
+        let (entry, size) = if encoding & 0x80 == 0 {
+            ((encoding & 0x7f).to_string().into_bytes(), 1)
+        } else if encoding & 0xc0 == 0x80 {
+            let len = (encoding & 0x3f) as usize;
+            (data.get(pos + 1..pos + 1 + len)?.to_vec(), 1 + len)
+        } else if encoding & 0xe0 == 0xc0 {
+            let value = (((encoding & 0x1f) as i64) << 8) | *data.get(pos + 1)? as i64;
+            let value = if value >= 1 << 12 { value - (1 << 13) } else { value };
+            (value.to_string().into_bytes(), 2)
+        } else if encoding & 0xf0 == 0xe0 {
+            let len = (((encoding & 0x0f) as usize) << 8) | *data.get(pos + 1)? as usize;
+            (data.get(pos + 2..pos + 2 + len)?.to_vec(), 2 + len)
+        } else {
+            match encoding {
+                0xf0 => {
+                    let len = u32::from_le_bytes(data.get(pos + 1..pos + 5)?.try_into().ok()?) as usize;
+                    (data.get(pos + 5..pos + 5 + len)?.to_vec(), 5 + len)
+                }
+                0xf1 => (read_int(data, pos + 1, 2)?.to_string().into_bytes(), 3),
+                0xf2 => (read_int(data, pos + 1, 3)?.to_string().into_bytes(), 4),
+                0xf3 => (read_int(data, pos + 1, 4)?.to_string().into_bytes(), 5),
+                0xf4 => (read_int(data, pos + 1, 8)?.to_string().into_bytes(), 9),
+                _ => return None,
+            }
+        };
+        let backlen = match size {
+            0..=127 => 1,
+            128..=16382 => 2,
+            16383..=2097150 => 3,
+            2097151..=268435454 => 4,
+            _ => 5,
+        };
+        pos += size + backlen;
+        entries.push(entry);
+    }
+}
+
+fn intset_entries(data: &[u8]) -> Option<Vec<Vec<u8>>> {
+    let size = u32::from_le_bytes(data.get(0..4)?.try_into().ok()?) as usize;
+    let len = u32::from_le_bytes(data.get(4..8)?.try_into().ok()?) as usize;
+    if !matches!(size, 2 | 4 | 8) {
+        return None;
+    }
+    (0..len)
+        .map(|i| read_int(data, 8 + i * size, size).map(|value| value.to_string().into_bytes()))
+        .collect()
+}
+
+fn zipmap_pairs(data: &[u8]) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+    let mut pairs = Vec::new();
+    let mut pos = 1;
+    let read_len = |pos: &mut usize| -> Option<Option<usize>> {
+        let first = *data.get(*pos)?;
+        *pos += 1;
+        match first {
+            0xff => Some(None),
+            0xfe => {
+                let len = u32::from_le_bytes(data.get(*pos..*pos + 4)?.try_into().ok()?) as usize;
+                *pos += 4;
+                Some(Some(len))
+            }
+            len => Some(Some(len as usize)),
+        }
+    };
+    loop {
+        let Some(field_len) = read_len(&mut pos)? else {
+            return Some(pairs);
+        };
+        let field = data.get(pos..pos + field_len)?.to_vec();
+        pos += field_len;
+        let value_len = read_len(&mut pos)??;
+        let free = *data.get(pos)? as usize;
+        pos += 1;
+        let value = data.get(pos..pos + value_len)?.to_vec();
+        pos += value_len + free;
+        pairs.push((field, value));
+    }
+}
+
+/// Reads the entries of a stream listpack node, whose ids are relative to `master_id`.
+fn stream_entries(master_id: StreamId, items: &[Vec<u8>], entries: &mut Vec<StreamEntry>) -> Option<()> {
+    let int = |index: usize| -> Option<i64> { std::str::from_utf8(items.get(index)?).ok()?.parse().ok() };
+    // count, deleted count, master fields, then the master entry terminator
+    let master_fields_count = usize::try_from(int(2)?).ok()?;
+    let master_fields = items.get(3..3 + master_fields_count)?;
+    let mut index = 3 + master_fields_count + 1;
+    while index < items.len() {
+        let flags = int(index)?;
+        let id = StreamId {
+            ms: master_id.ms.checked_add_signed(int(index + 1)?)?,
+            seq: master_id.seq.checked_add_signed(int(index + 2)?)?,
+        };
+        index += 3;
+        let fields: Vec<(Vec<u8>, Vec<u8>)> = if flags & STREAM_ITEM_FLAG_SAMEFIELDS != 0 {
+            let values = items.get(index..index + master_fields_count)?;
+            index += master_fields_count;
+            master_fields.iter().cloned().zip(values.iter().cloned()).collect()
+        } else {
+            let count = usize::try_from(int(index)?).ok()?;
+            let values = items.get(index + 1..index + 1 + count * 2)?;
+            index += 1 + count * 2;
+            pairs(values.to_vec())
+        };
+        // number of listpack items of the entry
+        index += 1;
+        if flags & STREAM_ITEM_FLAG_DELETED == 0 {
+            entries.push((id, fields));
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds an RDB file from the body that follows the header.
+    fn rdb_file(version: &str, body: &[u8]) -> Vec<u8> {
+        let mut data = format!("REDIS{version}").into_bytes();
+        data.extend_from_slice(body);
+        data.push(RDB_OPCODE_EOF);
+        let checksum = crc64(0, &data);
+        data.extend_from_slice(&checksum.to_le_bytes());
+        data
+    }
+
+    fn string(value: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![value.len() as u8];
+        encoded.extend_from_slice(value);
+        encoded
+    }
+
+    #[test]
+    fn computes_redis_crc64() {
+        assert_eq!(crc64(0, b"123456789"), 0xe9c6_d914_c4b8_d9ca);
+    }
+
+    #[test]
+    fn parses_keys_encodings_and_expiries() {
+        let mut body = Vec::new();
+        body.push(RDB_OPCODE_AUX);
+        body.extend(string(b"redis-ver"));
+        body.extend(string(b"7.2.4"));
+        body.push(RDB_OPCODE_AUX);
+        body.extend(string(b"ctime"));
+        body.extend(string(b"1700000000"));
+        body.extend([RDB_OPCODE_SELECTDB, 0, RDB_OPCODE_RESIZEDB, 4, 1]);
+        // a string stored as an 8 bits integer
+        body.push(RDB_TYPE_STRING);
+        body.extend(string(b"counter"));
+        body.extend([0xc0, 0xf6]);
+        // an intset with -3 and 2 as 16 bits integers
+        body.push(RDB_TYPE_SET_INTSET);
+        body.extend(string(b"ids"));
+        body.extend(string(&[2, 0, 0, 0, 2, 0, 0, 0, 0xfd, 0xff, 2, 0]));
+        // a listpack hash: 7 bits uint and 6 bits string entries
+        body.push(RDB_TYPE_HASH_LISTPACK);
+        body.extend(string(b"user:1"));
+        body.extend(string(&[
+            18, 0, 0, 0, 2, 0, 0x84, b'n', b'a', b'm', b'e', 5, 0x83, b'a', b'd', b'a', 4, 0xff,
+        ]));
+        // a key with an expiry after the snapshot
+        body.push(RDB_OPCODE_EXPIRETIME_MS);
+        body.extend(1_800_000_000_000_i64.to_le_bytes());
+        body.push(RDB_TYPE_LIST);
+        body.extend(string(b"queue"));
+        body.push(2);
+        body.extend(string(b"a"));
+        body.extend(string(b"b"));
+        // an expired key
+        body.push(RDB_OPCODE_EXPIRETIME);
+        body.extend(1_600_000_000_u32.to_le_bytes());
+        body.push(RDB_TYPE_STRING);
+        body.extend(string(b"old"));
+        body.extend(string(b"value"));
+
+        let snapshot = RdbSnapshot::parse(rdb_file("0011", &body), 0).expect("parse snapshot");
+        assert_eq!(snapshot.version, 11);
+        assert_eq!(snapshot.aux("redis-ver"), Some("7.2.4"));
+        assert_eq!(snapshot.expired, 1);
+        let db = snapshot.databases.get(&0).expect("db 0");
+        let names: Vec<&[u8]> = db.keys.iter().map(|key| key.key.as_slice()).collect();
+        assert_eq!(names, vec![&b"counter"[..], b"ids", b"queue", b"user:1"]);
+
+        let counter = db.get(b"counter").expect("counter");
+        assert!(matches!(&counter.value, RdbValue::String(value) if value == b"-10"));
+        assert_eq!(counter.encoding, "int");
+        let ids = db.get(b"ids").expect("ids");
+        assert!(matches!(&ids.value, RdbValue::Set(members) if members == &vec![b"-3".to_vec(), b"2".to_vec()]));
+        let user = db.get(b"user:1").expect("user:1");
+        assert!(matches!(&user.value, RdbValue::Hash(fields) if fields == &vec![(b"name".to_vec(), b"ada".to_vec())]));
+        let queue = db.get(b"queue").expect("queue");
+        assert_eq!(queue.expire_at_ms, Some(1_800_000_000_000));
+
+        // DUMP payload: type, serialized value, RDB version and checksum
+        let payload = snapshot.dump_payload(counter);
+        assert_eq!(&payload[..5], &[RDB_TYPE_STRING, 0xc0, 0xf6, 11, 0]);
+        assert_eq!(
+            u64::from_le_bytes(payload[5..].try_into().expect("checksum")),
+            crc64(0, &payload[..5])
+        );
+    }
+
+    #[test]
+    fn rejects_corrupted_files() {
+        let mut data = rdb_file("0011", &[RDB_TYPE_STRING, 1, b'k', 1, b'v']);
+        let len = data.len();
+        data[len - 1] ^= 0xff;
+        assert!(RdbSnapshot::parse(data, 0).is_err());
+        assert!(RdbSnapshot::parse(b"REDIS0011\x00\x01k".to_vec(), 0).is_err());
+        // a crafted RESIZEDB size doesn't allocate up front
+        let mut data = b"REDIS0011".to_vec();
+        data.extend([RDB_OPCODE_SELECTDB, 0, RDB_OPCODE_RESIZEDB, 0x81]);
+        data.extend(u64::MAX.to_be_bytes());
+        data.push(0);
+        assert!(RdbSnapshot::parse(data, 0).is_err());
+    }
+
+    #[test]
+    fn decompresses_lzf() {
+        // literal "abc" followed by a back reference copying it twice
+        let compressed = [2, b'a', b'b', b'c', 0x80, 2];
+        assert_eq!(lzf_decompress(&compressed, 9), Some(b"abcabcabc".to_vec()));
+        assert_eq!(lzf_decompress(&compressed, 8), None);
+        // a crafted length doesn't allocate up front
+        assert_eq!(lzf_decompress(&compressed, usize::MAX), None);
+    }
+}
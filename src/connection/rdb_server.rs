@@ -0,0 +1,1126 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Read-only server for RDB snapshot files.
+//!
+//! An opened snapshot is served on a loopback port by a small RESP2 server answering
+//! the read commands of the key browser and the value editors, so the snapshot is
+//! browsed, searched and exported exactly like a live server. Write commands fail with
+//! a `READONLY` error.
+//!
+//! Each opened file gets a random password: connections must `AUTH` before any other
+//! command, so other local processes can't read the snapshot through the port.
+//!
+//! TTLs are relative to the creation time of the snapshot, and keys that had already
+//! expired when it was taken are skipped, like Redis does when loading the file.
+
+use super::{
+    config::RedisServer,
+    rdb::{RdbKey, RdbSnapshot, RdbStream, RdbStreamGroup, RdbValue, StreamEntry, StreamId},
+};
+use crate::error::Error;
+use chrono::Utc;
+use dashmap::DashMap;
+use smol::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+use std::{
+    io,
+    path::Path,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Prefix of the ids of the servers serving RDB files
+const RDB_SERVER_ID_PREFIX: &str = "rdb-";
+/// Number of databases reported when the snapshot uses fewer
+const RDB_DATABASES: usize = 16;
+const DEFAULT_SCAN_COUNT: usize = 10;
+/// Pause after a failed accept, so a lasting error doesn't spin the loop
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+/// Limits of a request, like the `proto-max-bulk-len` and multibulk limits of Redis
+const MAX_INLINE_LEN: usize = 64 * 1024;
+const MAX_ARGS: usize = 1024 * 1024;
+const MAX_BULK_LEN: usize = 512 * 1024 * 1024;
+
+const WRONG_TYPE: &str = "WRONGTYPE Operation against a key holding the wrong kind of value";
+const NOT_INTEGER: &str = "ERR value is not an integer or out of range";
+const SYNTAX_ERROR: &str = "ERR syntax error";
+
+/// Commands modifying data, rejected with a `READONLY` error.
+const WRITE_COMMANDS: &[&str] = &[
+    "APPEND",
+    "COPY",
+    "DECR",
+    "DECRBY",
+    "DEL",
+    "EVAL",
+    "EVALSHA",
+    "EXPIRE",
+    "EXPIREAT",
+    "FCALL",
+    "FLUSHALL",
+    "FLUSHDB",
+    "GETDEL",
+    "GETEX",
+    "GETSET",
+    "HDEL",
+    "HINCRBY",
+    "HINCRBYFLOAT",
+    "HMSET",
+    "HSET",
+    "HSETNX",
+    "INCR",
+    "INCRBY",
+    "INCRBYFLOAT",
+    "LINSERT",
+    "LMOVE",
+    "LPOP",
+    "LPUSH",
+    "LPUSHX",
+    "LREM",
+    "LSET",
+    "LTRIM",
+    "MOVE",
+    "MSET",
+    "MSETNX",
+    "PERSIST",
+    "PEXPIRE",
+    "PEXPIREAT",
+    "PSETEX",
+    "RENAME",
+    "RENAMENX",
+    "RESTORE",
+    "RPOP",
+    "RPOPLPUSH",
+    "RPUSH",
+    "RPUSHX",
+    "SADD",
+    "SET",
+    "SETEX",
+    "SETNX",
+    "SETRANGE",
+    "SMOVE",
+    "SPOP",
+    "SREM",
+    "UNLINK",
+    "VADD",
+    "VREM",
+    "VSETATTR",
+    "XACK",
+    "XADD",
+    "XAUTOCLAIM",
+    "XCLAIM",
+    "XDEL",
+    "XGROUP",
+    "XTRIM",
+    "ZADD",
+    "ZINCRBY",
+    "ZPOPMAX",
+    "ZPOPMIN",
+    "ZREM",
+    "ZREMRANGEBYRANK",
+    "ZREMRANGEBYSCORE",
+];
+
+/// An opened RDB file and the task serving it.
+struct RdbFile {
+    server: RedisServer,
+    /// Accept loop, stopped when dropped
+    _task: smol::Task<()>,
+}
+
+static RDB_FILES: LazyLock<DashMap<String, RdbFile>> = LazyLock::new(DashMap::new);
+
+/// Whether the server id is an opened RDB file.
+pub fn is_rdb_file(server_id: &str) -> bool {
+    server_id.starts_with(RDB_SERVER_ID_PREFIX)
+}
+
+/// Returns the configuration of the server serving an opened RDB file.
+pub(crate) fn get_rdb_config(server_id: &str) -> Option<RedisServer> {
+    RDB_FILES.get(server_id).map(|file| file.server.clone())
+}
+
+/// Parses an RDB file and serves it read-only on a loopback port.
+///
+/// The returned configuration connects to it like any server with the generated
+/// password, it is not saved.
+pub async fn open_rdb_file(path: &str) -> Result<RedisServer> {
+    let content = smol::fs::read(path).await?;
+    let now_ms = Utc::now().timestamp_millis();
+    let snapshot = smol::unblock(move || RdbSnapshot::parse(content, now_ms)).await?;
+    let snapshot_ms = snapshot.created_at_ms().unwrap_or(now_ms);
+
+    let password = Uuid::new_v4().simple().to_string();
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+    let port = listener.local_addr()?.port();
+    let name = Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string());
+    let key_count: usize = snapshot.databases.values().map(|db| db.keys.len()).sum();
+    info!(
+        path,
+        port,
+        version = snapshot.version,
+        keys = key_count,
+        expired = snapshot.expired,
+        "serve rdb file"
+    );
+    let server = RedisServer {
+        id: format!("{RDB_SERVER_ID_PREFIX}{}", Uuid::now_v7()),
+        name,
+        host: "127.0.0.1".to_string(),
+        port,
+        password: Some(password.clone()),
+        description: Some(path.to_string()),
+        ..Default::default()
+    };
+    let state = Arc::new(RdbServerState {
+        password,
+        snapshot,
+        snapshot_ms,
+        path: path.to_string(),
+    });
+    let task = smol::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                // e.g. too many open files, the next connections may be accepted
+                Err(e) => {
+                    warn!(error = %e, "accept rdb connection fail");
+                    smol::Timer::after(ACCEPT_RETRY_DELAY).await;
+                    continue;
+                }
+            };
+            let state = state.clone();
+            smol::spawn(async move {
+                if let Err(e) = handle_connection(stream, state).await {
+                    debug!(error = %e, "rdb connection closed");
+                }
+            })
+            .detach();
+        }
+    });
+    RDB_FILES.insert(
+        server.id.clone(),
+        RdbFile {
+            server: server.clone(),
+            _task: task,
+        },
+    );
+    Ok(server)
+}
+
+/// Stops serving an opened RDB file.
+pub fn close_rdb_file(server_id: &str) {
+    if RDB_FILES.remove(server_id).is_some() {
+        info!(server_id, "close rdb file");
+    }
+}
+
+struct RdbServerState {
+    /// Password connections must send before any other command
+    password: String,
+    snapshot: RdbSnapshot,
+    /// Time TTLs are relative to
+    snapshot_ms: i64,
+    path: String,
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+async fn read_line(reader: &mut BufReader<TcpStream>) -> io::Result<Option<Vec<u8>>> {
+    let mut line = Vec::new();
+    // one more byte than the limit tells a line at the limit from a longer one
+    let read = (&mut *reader)
+        .take(MAX_INLINE_LEN as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .await?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if line.last() != Some(&b'\n') && line.len() > MAX_INLINE_LEN {
+        return Err(invalid_data("too big inline request"));
+    }
+    while line.last().is_some_and(|byte| *byte == b'\n' || *byte == b'\r') {
+        line.pop();
+    }
+    Ok(Some(line))
+}
+
+/// Parses the length of a `*N` or `$N` header, rejecting lengths above `max`.
+fn parse_header_len(line: &[u8], max: usize) -> io::Result<usize> {
+    let len: usize = std::str::from_utf8(line.get(1..).unwrap_or_default())
+        .ok()
+        .and_then(|len| len.parse().ok())
+        .ok_or_else(|| invalid_data("invalid RESP length"))?;
+    if len > max {
+        return Err(invalid_data("RESP length exceeds the limit"));
+    }
+    Ok(len)
+}
+
+/// Reads a command sent as an array of bulk strings, or as an inline command.
+async fn read_command(reader: &mut BufReader<TcpStream>) -> io::Result<Option<Vec<Vec<u8>>>> {
+    let Some(line) = read_line(reader).await? else {
+        return Ok(None);
+    };
+    if line.first() != Some(&b'*') {
+        let args = line
+            .split(|byte| byte.is_ascii_whitespace())
+            .filter(|arg| !arg.is_empty())
+            .map(<[u8]>::to_vec)
+            .collect();
+        return Ok(Some(args));
+    }
+    let count = parse_header_len(&line, MAX_ARGS)?;
+    let mut args = Vec::with_capacity(count.min(1024));
+    for _ in 0..count {
+        let header = read_line(reader)
+            .await?
+            .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+        if header.first() != Some(&b'$') {
+            return Err(invalid_data("expected a bulk string"));
+        }
+        let len = parse_header_len(&header, MAX_BULK_LEN)? + 2;
+        // grow with the data received instead of trusting the announced length
+        let mut arg = Vec::new();
+        if (&mut *reader).take(len as u64).read_to_end(&mut arg).await? != len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+        arg.truncate(len - 2);
+        args.push(arg);
+    }
+    Ok(Some(args))
+}
+
+async fn handle_connection(stream: TcpStream, state: Arc<RdbServerState>) -> io::Result<()> {
+    let mut writer = stream.clone();
+    let mut reader = BufReader::new(stream);
+    let mut session = Session::default();
+    let mut output = Vec::new();
+    while let Some(args) = read_command(&mut reader).await? {
+        if args.is_empty() {
+            continue;
+        }
+        state.execute(&mut session, &args).write(&mut output);
+        // answer pipelined commands together
+        if reader.buffer().is_empty() {
+            writer.write_all(&output).await?;
+            output.clear();
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Reply {
+    Status(&'static str),
+    Error(String),
+    Int(i64),
+    Bulk(Vec<u8>),
+    Nil,
+    Array(Vec<Reply>),
+}
+
+impl Reply {
+    fn ok() -> Self {
+        Reply::Status("OK")
+    }
+    fn error(message: &str) -> Self {
+        Reply::Error(message.to_string())
+    }
+    fn bulk(value: impl AsRef<[u8]>) -> Self {
+        Reply::Bulk(value.as_ref().to_vec())
+    }
+    fn len(len: usize) -> Self {
+        Reply::Int(len as i64)
+    }
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Reply::Status(status) => out.extend_from_slice(format!("+{status}\r\n").as_bytes()),
+            Reply::Error(message) => {
+                let message = message.replace(['\r', '\n'], " ");
+                out.extend_from_slice(format!("-{message}\r\n").as_bytes());
+            }
+            Reply::Int(value) => out.extend_from_slice(format!(":{value}\r\n").as_bytes()),
+            Reply::Bulk(value) => {
+                out.extend_from_slice(format!("${}\r\n", value.len()).as_bytes());
+                out.extend_from_slice(value);
+                out.extend_from_slice(b"\r\n");
+            }
+            Reply::Nil => out.extend_from_slice(b"$-1\r\n"),
+            Reply::Array(items) => {
+                out.extend_from_slice(format!("*{}\r\n", items.len()).as_bytes());
+                for item in items {
+                    item.write(out);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Session {
+    db: usize,
+    authenticated: bool,
+}
+
+/// Matches a glob-style pattern like Redis: `*`, `?`, `[...]` classes and `\` escapes.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let Some((&first, rest)) = pattern.split_first() else {
+        return text.is_empty();
+    };
+    match first {
+        b'*' => (0..=text.len()).any(|skip| glob_match(rest, &text[skip..])),
+        b'?' => !text.is_empty() && glob_match(rest, &text[1..]),
+        b'[' => {
+            let Some((&byte, text_rest)) = text.split_first() else {
+                return false;
+            };
+            let (negate, mut class) = match rest.split_first() {
+                Some((b'^', class)) => (true, class),
+                _ => (false, rest),
+            };
+            let mut matched = false;
+            loop {
+                match class {
+                    [] => break,
+                    [b']', tail @ ..] => {
+                        class = tail;
+                        break;
+                    }
+                    [b'\\', escaped, tail @ ..] => {
+                        matched |= *escaped == byte;
+                        class = tail;
+                    }
+                    [start, b'-', end, tail @ ..] if *end != b']' => {
+                        let (low, high) = if start <= end { (start, end) } else { (end, start) };
+                        matched |= (*low..=*high).contains(&byte);
+                        class = tail;
+                    }
+                    [item, tail @ ..] => {
+                        matched |= *item == byte;
+                        class = tail;
+                    }
+                }
+            }
+            matched != negate && glob_match(class, text_rest)
+        }
+        b'\\' if !rest.is_empty() => text.first() == Some(&rest[0]) && glob_match(&rest[1..], &text[1..]),
+        _ => text.first() == Some(&first) && glob_match(rest, &text[1..]),
+    }
+}
+
+fn parse_int(arg: &[u8]) -> Option<i64> {
+    std::str::from_utf8(arg).ok()?.parse().ok()
+}
+
+/// Resolves a `start stop` index range like `LRANGE`, `None` when empty.
+fn index_range(len: usize, start: i64, stop: i64) -> Option<std::ops::RangeInclusive<usize>> {
+    let len = len as i64;
+    let start = if start < 0 { (len + start).max(0) } else { start };
+    let stop = if stop < 0 { len + stop } else { stop.min(len - 1) };
+    (start <= stop && start < len).then_some(start as usize..=stop as usize)
+}
+
+/// Parses a stream id bound of `XRANGE`: `-`, `+`, `ms`, `ms-seq`, optionally exclusive.
+fn parse_stream_bound(arg: &[u8], is_start: bool) -> Option<StreamId> {
+    let text = std::str::from_utf8(arg).ok()?;
+    let (exclusive, text) = match text.strip_prefix('(') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let id = match text {
+        "-" => StreamId::default(),
+        "+" => StreamId {
+            ms: u64::MAX,
+            seq: u64::MAX,
+        },
+        _ => match text.split_once('-') {
+            Some((ms, seq)) => StreamId {
+                ms: ms.parse().ok()?,
+                seq: seq.parse().ok()?,
+            },
+            None => StreamId {
+                ms: text.parse().ok()?,
+                seq: if is_start { 0 } else { u64::MAX },
+            },
+        },
+    };
+    if !exclusive {
+        return Some(id);
+    }
+    // exclusive bounds move to the next id in the direction of the range
+    if is_start {
+        match id.seq.checked_add(1) {
+            Some(seq) => Some(StreamId { ms: id.ms, seq }),
+            None => Some(StreamId {
+                ms: id.ms.checked_add(1)?,
+                seq: 0,
+            }),
+        }
+    } else {
+        match id.seq.checked_sub(1) {
+            Some(seq) => Some(StreamId { ms: id.ms, seq }),
+            None => Some(StreamId {
+                ms: id.ms.checked_sub(1)?,
+                seq: u64::MAX,
+            }),
+        }
+    }
+}
+
+fn stream_entry_reply(id: &StreamId, fields: &[(Vec<u8>, Vec<u8>)]) -> Reply {
+    let fields = fields
+        .iter()
+        .flat_map(|(field, value)| [Reply::bulk(field), Reply::bulk(value)])
+        .collect();
+    Reply::Array(vec![Reply::bulk(id.to_string()), Reply::Array(fields)])
+}
+
+/// Options shared by the `SCAN` family: `MATCH`, `COUNT`, `TYPE` and `NOVALUES`.
+#[derive(Debug, Default)]
+struct ScanOptions<'a> {
+    pattern: Option<&'a [u8]>,
+    count: usize,
+    key_type: Option<String>,
+    no_values: bool,
+}
+
+impl<'a> ScanOptions<'a> {
+    fn parse(args: &'a [Vec<u8>]) -> Result<Self, Reply> {
+        let mut options = ScanOptions {
+            count: DEFAULT_SCAN_COUNT,
+            ..Default::default()
+        };
+        let mut args = args.iter();
+        while let Some(option) = args.next() {
+            match option.to_ascii_uppercase().as_slice() {
+                b"MATCH" => options.pattern = Some(args.next().ok_or_else(|| Reply::error(SYNTAX_ERROR))?),
+                b"COUNT" => {
+                    let count = args.next().and_then(|count| parse_int(count));
+                    options.count = count
+                        .filter(|count| *count > 0)
+                        .ok_or_else(|| Reply::error(SYNTAX_ERROR))? as usize;
+                }
+                b"TYPE" => {
+                    let key_type = args.next().ok_or_else(|| Reply::error(SYNTAX_ERROR))?;
+                    options.key_type = Some(String::from_utf8_lossy(key_type).to_lowercase());
+                }
+                b"NOVALUES" => options.no_values = true,
+                _ => return Err(Reply::error(SYNTAX_ERROR)),
+            }
+        }
+        Ok(options)
+    }
+    fn matches(&self, item: &[u8]) -> bool {
+        self.pattern.is_none_or(|pattern| glob_match(pattern, item))
+    }
+}
+
+/// Runs one step of a cursor based scan over `items`, the cursor being an index.
+fn scan_items<T>(
+    items: &[T],
+    cursor: &[u8],
+    options: &ScanOptions,
+    mut reply: impl FnMut(&T, &mut Vec<Reply>),
+) -> Reply {
+    let Some(start) = parse_int(cursor).and_then(|cursor| usize::try_from(cursor).ok()) else {
+        return Reply::error("ERR invalid cursor");
+    };
+    let end = start.saturating_add(options.count).min(items.len());
+    let mut replies = Vec::new();
+    for item in items.get(start..end).unwrap_or_default() {
+        reply(item, &mut replies);
+    }
+    let next = if end >= items.len() { 0 } else { end };
+    Reply::Array(vec![Reply::bulk(next.to_string()), Reply::Array(replies)])
+}
+
+impl RdbServerState {
+    fn database_count(&self) -> usize {
+        self.snapshot
+            .databases
+            .keys()
+            .last()
+            .map(|db| db + 1)
+            .unwrap_or_default()
+            .max(RDB_DATABASES)
+    }
+
+    fn keys(&self, session: &Session) -> &[RdbKey] {
+        self.snapshot
+            .databases
+            .get(&session.db)
+            .map(|db| db.keys.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn lookup(&self, session: &Session, key: &[u8]) -> Option<&RdbKey> {
+        self.snapshot.databases.get(&session.db)?.get(key)
+    }
+
+    fn info(&self) -> String {
+        let version = self.snapshot.aux("redis-ver").unwrap_or("0.0.0");
+        let used_memory = self.snapshot.aux("used-mem").unwrap_or("0");
+        let mut info = format!(
+            "# Server\r\nredis_version:{version}\r\nredis_mode:standalone\r\nrdb_version:{}\r\nrdb_file:{}\r\n\r\n\
+             # Clients\r\nconnected_clients:1\r\n\r\n\
+             # Memory\r\nused_memory:{used_memory}\r\n\r\n\
+             # Persistence\r\nloading:0\r\nrdb_expired_keys_skipped:{}\r\n\r\n\
+             # Replication\r\nrole:master\r\nconnected_slaves:0\r\n\r\n\
+             # Cluster\r\ncluster_enabled:0\r\n\r\n# Keyspace\r\n",
+            self.snapshot.version, self.path, self.snapshot.expired
+        );
+        for (db, database) in &self.snapshot.databases {
+            info.push_str(&format!(
+                "db{db}:keys={},expires={},avg_ttl=0\r\n",
+                database.keys.len(),
+                database.expires()
+            ));
+        }
+        info
+    }
+
+    fn ttl_ms(&self, key: Option<&RdbKey>) -> i64 {
+        match key {
+            None => -2,
+            Some(key) => key
+                .expire_at_ms
+                .map(|expire_at_ms| (expire_at_ms - self.snapshot_ms).max(0))
+                .unwrap_or(-1),
+        }
+    }
+
+    fn execute(&self, session: &mut Session, args: &[Vec<u8>]) -> Reply {
+        let name = String::from_utf8_lossy(&args[0]).to_uppercase();
+        let args = &args[1..];
+        let arg = |index: usize| args.get(index).map(Vec::as_slice);
+        let key = || arg(0).and_then(|key| self.lookup(session, key));
+        macro_rules! value_of {
+            ($variant:ident) => {
+                match key().map(|key| &key.value) {
+                    None => None,
+                    Some(RdbValue::$variant(value)) => Some(value),
+                    Some(_) => return Reply::error(WRONG_TYPE),
+                }
+            };
+        }
+        if !session.authenticated {
+            match name.as_str() {
+                "AUTH" => {}
+                "HELLO" => return Reply::error("NOPROTO the RDB snapshot only speaks RESP2"),
+                "QUIT" => return Reply::ok(),
+                _ => return Reply::error("NOAUTH Authentication required."),
+            }
+        }
+        let min_args = match name.as_str() {
+            "ECHO" | "SELECT" | "TYPE" | "TTL" | "PTTL" | "EXPIRETIME" | "PEXPIRETIME" | "DUMP" | "GET" | "STRLEN"
+            | "LLEN" | "SCARD" | "SMEMBERS" | "ZCARD" | "HLEN" | "HGETALL" | "HKEYS" | "HVALS" | "XLEN" | "EXISTS"
+            | "MGET" | "SCAN" | "KEYS" => 1,
+            "LINDEX" | "SISMEMBER" | "ZSCORE" | "HGET" | "HEXISTS" | "HSTRLEN" | "HMGET" | "SMISMEMBER" | "SSCAN"
+            | "ZSCAN" | "HSCAN" | "XINFO" | "OBJECT" | "MEMORY" => 2,
+            "GETRANGE" | "SUBSTR" | "LRANGE" | "ZRANGE" | "ZREVRANGE" | "XRANGE" | "XREVRANGE" => 3,
+            _ => 0,
+        };
+        if args.len() < min_args {
+            return Reply::Error(format!(
+                "ERR wrong number of arguments for '{}' command",
+                name.to_lowercase()
+            ));
+        }
+
+        match name.as_str() {
+            "PING" => arg(0).map(Reply::bulk).unwrap_or(Reply::Status("PONG")),
+            "ECHO" => Reply::bulk(&args[0]),
+            // `AUTH password` or `AUTH username password`, the username is ignored
+            "AUTH" => match args.last() {
+                Some(password) if args.len() <= 2 && password.as_slice() == self.password.as_bytes() => {
+                    session.authenticated = true;
+                    Reply::ok()
+                }
+                Some(_) if args.len() <= 2 => {
+                    Reply::error("WRONGPASS invalid username-password pair or user is disabled.")
+                }
+                _ => Reply::error(SYNTAX_ERROR),
+            },
+            "READONLY" | "READWRITE" | "RESET" => Reply::ok(),
+            "QUIT" => Reply::ok(),
+            "HELLO" => Reply::error("NOPROTO the RDB snapshot only speaks RESP2"),
+            "CLIENT" => match arg(0).map(|sub| sub.to_ascii_uppercase()).as_deref() {
+                Some(b"ID") => Reply::Int(1),
+                Some(b"GETNAME") => Reply::Nil,
+                Some(b"LIST") | Some(b"INFO") => Reply::bulk(""),
+                _ => Reply::ok(),
+            },
+            "SELECT" => match parse_int(&args[0]).and_then(|db| usize::try_from(db).ok()) {
+                Some(db) if db < self.database_count() => {
+                    session.db = db;
+                    Reply::ok()
+                }
+                _ => Reply::error("ERR DB index is out of range"),
+            },
+            "ROLE" => Reply::Array(vec![Reply::bulk("master"), Reply::Int(0), Reply::Array(vec![])]),
+            "INFO" => Reply::bulk(self.info()),
+            "CONFIG" => match (arg(0).map(|sub| sub.to_ascii_uppercase()).as_deref(), arg(1)) {
+                (Some(b"GET"), Some(pattern)) if glob_match(pattern, b"databases") => Reply::Array(vec![
+                    Reply::bulk("databases"),
+                    Reply::bulk(self.database_count().to_string()),
+                ]),
+                (Some(b"GET"), _) => Reply::Array(vec![]),
+                _ => Reply::error("READONLY You can't write against a read only RDB snapshot"),
+            },
+            "COMMAND" | "SLOWLOG" | "LATENCY" | "PUBSUB" => {
+                match arg(0).map(|sub| sub.to_ascii_uppercase()).as_deref() {
+                    Some(b"COUNT") | Some(b"LEN") | Some(b"NUMPAT") | Some(b"RESET") => Reply::Int(0),
+                    Some(b"DOCTOR") => Reply::bulk("RDB snapshots have no latency samples."),
+                    _ => Reply::Array(vec![]),
+                }
+            }
+            "DBSIZE" => Reply::len(self.keys(session).len()),
+            "SCAN" => {
+                let options = match ScanOptions::parse(&args[1..]) {
+                    Ok(options) => options,
+                    Err(reply) => return reply,
+                };
+                scan_items(self.keys(session), &args[0], &options, |key, replies| {
+                    let type_matches = options
+                        .key_type
+                        .as_ref()
+                        .is_none_or(|key_type| key.value.type_name().eq_ignore_ascii_case(key_type));
+                    if type_matches && options.matches(&key.key) {
+                        replies.push(Reply::bulk(&key.key));
+                    }
+                })
+            }
+            "KEYS" => Reply::Array(
+                self.keys(session)
+                    .iter()
+                    .filter(|key| glob_match(&args[0], &key.key))
+                    .map(|key| Reply::bulk(&key.key))
+                    .collect(),
+            ),
+            "RANDOMKEY" => self
+                .keys(session)
+                .first()
+                .map(|key| Reply::bulk(&key.key))
+                .unwrap_or(Reply::Nil),
+            "EXISTS" => Reply::len(args.iter().filter(|key| self.lookup(session, key).is_some()).count()),
+            "TYPE" => match key() {
+                Some(key) => Reply::Status(match key.value.type_name() {
+                    "string" => "string",
+                    "list" => "list",
+                    "set" => "set",
+                    "zset" => "zset",
+                    "hash" => "hash",
+                    "stream" => "stream",
+                    // module type names are not static
+                    _ => return Reply::Bulk(key.value.type_name().as_bytes().to_vec()),
+                }),
+                None => Reply::Status("none"),
+            },
+            "PTTL" => Reply::Int(self.ttl_ms(key())),
+            "TTL" => match self.ttl_ms(key()) {
+                ttl if ttl < 0 => Reply::Int(ttl),
+                ttl => Reply::Int((ttl + 500) / 1000),
+            },
+            "PEXPIRETIME" | "EXPIRETIME" => match key() {
+                None => Reply::Int(-2),
+                Some(key) => match key.expire_at_ms {
+                    None => Reply::Int(-1),
+                    Some(ms) if name == "PEXPIRETIME" => Reply::Int(ms),
+                    Some(ms) => Reply::Int(ms / 1000),
+                },
+            },
+            "MEMORY" => match arg(0).map(|sub| sub.to_ascii_uppercase()).as_deref() {
+                Some(b"USAGE") => match arg(1).and_then(|key| self.lookup(session, key)) {
+                    Some(key) => Reply::len(self.snapshot.serialized_size(key)),
+                    None => Reply::Nil,
+                },
+                _ => Reply::error("ERR unknown subcommand"),
+            },
+            "OBJECT" => {
+                let object = arg(1).and_then(|key| self.lookup(session, key));
+                match (arg(0).map(|sub| sub.to_ascii_uppercase()).as_deref(), object) {
+                    (_, None) => Reply::Nil,
+                    (Some(b"ENCODING"), Some(key)) => Reply::bulk(key.encoding),
+                    (Some(b"REFCOUNT"), Some(_)) => Reply::Int(1),
+                    (Some(b"IDLETIME") | Some(b"FREQ"), Some(_)) => Reply::Int(0),
+                    _ => Reply::error("ERR unknown subcommand"),
+                }
+            }
+            "DUMP" => match key() {
+                Some(key) => Reply::Bulk(self.snapshot.dump_payload(key)),
+                None => Reply::Nil,
+            },
+            "GET" => value_of!(String).map(Reply::bulk).unwrap_or(Reply::Nil),
+            "STRLEN" => Reply::len(value_of!(String).map(Vec::len).unwrap_or_default()),
+            "GETRANGE" | "SUBSTR" => {
+                let value = value_of!(String).map(Vec::as_slice).unwrap_or_default();
+                let (Some(start), Some(end)) = (parse_int(&args[1]), parse_int(&args[2])) else {
+                    return Reply::error(NOT_INTEGER);
+                };
+                match index_range(value.len(), start, end) {
+                    Some(range) => Reply::bulk(&value[range]),
+                    None => Reply::bulk(""),
+                }
+            }
+            "MGET" => Reply::Array(
+                args.iter()
+                    .map(|key| match self.lookup(session, key).map(|key| &key.value) {
+                        Some(RdbValue::String(value)) => Reply::bulk(value),
+                        _ => Reply::Nil,
+                    })
+                    .collect(),
+            ),
+            "LLEN" => Reply::len(value_of!(List).map(Vec::len).unwrap_or_default()),
+            "LRANGE" => {
+                let items = value_of!(List).map(Vec::as_slice).unwrap_or_default();
+                let (Some(start), Some(stop)) = (parse_int(&args[1]), parse_int(&args[2])) else {
+                    return Reply::error(NOT_INTEGER);
+                };
+                let range = index_range(items.len(), start, stop);
+                Reply::Array(
+                    range
+                        .map(|range| items[range].iter().map(Reply::bulk).collect())
+                        .unwrap_or_default(),
+                )
+            }
+            "LINDEX" => {
+                let items = value_of!(List).map(Vec::as_slice).unwrap_or_default();
+                let Some(index) = parse_int(&args[1]) else {
+                    return Reply::error(NOT_INTEGER);
+                };
+                let index = if index < 0 { items.len() as i64 + index } else { index };
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|index| items.get(index))
+                    .map(Reply::bulk)
+                    .unwrap_or(Reply::Nil)
+            }
+            "SCARD" => Reply::len(value_of!(Set).map(Vec::len).unwrap_or_default()),
+            "SMEMBERS" => Reply::Array(value_of!(Set).into_iter().flatten().map(Reply::bulk).collect()),
+            "SISMEMBER" => {
+                let members = value_of!(Set).map(Vec::as_slice).unwrap_or_default();
+                Reply::Int(members.contains(&args[1]) as i64)
+            }
+            "SMISMEMBER" => {
+                let members = value_of!(Set).map(Vec::as_slice).unwrap_or_default();
+                Reply::Array(
+                    args[1..]
+                        .iter()
+                        .map(|member| Reply::Int(members.contains(member) as i64))
+                        .collect(),
+                )
+            }
+            "SSCAN" => {
+                let members = value_of!(Set).map(Vec::as_slice).unwrap_or_default();
+                let options = match ScanOptions::parse(&args[2..]) {
+                    Ok(options) => options,
+                    Err(reply) => return reply,
+                };
+                scan_items(members, &args[1], &options, |member, replies| {
+                    if options.matches(member) {
+                        replies.push(Reply::bulk(member));
+                    }
+                })
+            }
+            "ZCARD" => Reply::len(value_of!(Zset).map(Vec::len).unwrap_or_default()),
+            "ZSCORE" => value_of!(Zset)
+                .and_then(|members| members.iter().find(|(member, _)| *member == args[1]))
+                .map(|(_, score)| Reply::bulk(format_score(*score)))
+                .unwrap_or(Reply::Nil),
+            "ZRANGE" | "ZREVRANGE" => {
+                let members = value_of!(Zset).map(Vec::as_slice).unwrap_or_default();
+                let (Some(start), Some(stop)) = (parse_int(&args[1]), parse_int(&args[2])) else {
+                    return Reply::error(NOT_INTEGER);
+                };
+                let mut reverse = name == "ZREVRANGE";
+                let mut with_scores = false;
+                for option in &args[3..] {
+                    match option.to_ascii_uppercase().as_slice() {
+                        b"WITHSCORES" => with_scores = true,
+                        b"REV" => reverse = true,
+                        _ => return Reply::error("ERR only index ranges are supported by RDB snapshots"),
+                    }
+                }
+                let Some(range) = index_range(members.len(), start, stop) else {
+                    return Reply::Array(vec![]);
+                };
+                let len = members.len();
+                let mut replies = Vec::new();
+                for index in range {
+                    let (member, score) = &members[if reverse { len - 1 - index } else { index }];
+                    replies.push(Reply::bulk(member));
+                    if with_scores {
+                        replies.push(Reply::bulk(format_score(*score)));
+                    }
+                }
+                Reply::Array(replies)
+            }
+            "ZSCAN" => {
+                let members = value_of!(Zset).map(Vec::as_slice).unwrap_or_default();
+                let options = match ScanOptions::parse(&args[2..]) {
+                    Ok(options) => options,
+                    Err(reply) => return reply,
+                };
+                scan_items(members, &args[1], &options, |(member, score), replies| {
+                    if options.matches(member) {
+                        replies.push(Reply::bulk(member));
+                        replies.push(Reply::bulk(format_score(*score)));
+                    }
+                })
+            }
+            "HLEN" => Reply::len(value_of!(Hash).map(Vec::len).unwrap_or_default()),
+            "HGET" | "HEXISTS" | "HSTRLEN" => {
+                let value = value_of!(Hash)
+                    .and_then(|fields| fields.iter().find(|(field, _)| *field == args[1]))
+                    .map(|(_, value)| value);
+                match name.as_str() {
+                    "HGET" => value.map(Reply::bulk).unwrap_or(Reply::Nil),
+                    "HEXISTS" => Reply::Int(value.is_some() as i64),
+                    _ => Reply::len(value.map(Vec::len).unwrap_or_default()),
+                }
+            }
+            "HMGET" => {
+                let fields = value_of!(Hash).map(Vec::as_slice).unwrap_or_default();
+                Reply::Array(
+                    args[1..]
+                        .iter()
+                        .map(|name| {
+                            fields
+                                .iter()
+                                .find(|(field, _)| field == name)
+                                .map(|(_, value)| Reply::bulk(value))
+                                .unwrap_or(Reply::Nil)
+                        })
+                        .collect(),
+                )
+            }
+            "HGETALL" | "HKEYS" | "HVALS" => {
+                let fields = value_of!(Hash).map(Vec::as_slice).unwrap_or_default();
+                let replies = fields.iter().flat_map(|(field, value)| match name.as_str() {
+                    "HKEYS" => vec![Reply::bulk(field)],
+                    "HVALS" => vec![Reply::bulk(value)],
+                    _ => vec![Reply::bulk(field), Reply::bulk(value)],
+                });
+                Reply::Array(replies.collect())
+            }
+            "HSCAN" => {
+                let fields = value_of!(Hash).map(Vec::as_slice).unwrap_or_default();
+                let options = match ScanOptions::parse(&args[2..]) {
+                    Ok(options) => options,
+                    Err(reply) => return reply,
+                };
+                scan_items(fields, &args[1], &options, |(field, value), replies| {
+                    if options.matches(field) {
+                        replies.push(Reply::bulk(field));
+                        if !options.no_values {
+                            replies.push(Reply::bulk(value));
+                        }
+                    }
+                })
+            }
+            "XLEN" => Reply::len(value_of!(Stream).map(|stream| stream.entries.len()).unwrap_or_default()),
+            "XRANGE" | "XREVRANGE" => {
+                let stream = value_of!(Stream);
+                let reverse = name == "XREVRANGE";
+                let (start, end) = if reverse {
+                    (&args[2], &args[1])
+                } else {
+                    (&args[1], &args[2])
+                };
+                let (Some(start), Some(end)) = (parse_stream_bound(start, true), parse_stream_bound(end, false)) else {
+                    return Reply::error("ERR Invalid stream ID specified as stream command argument");
+                };
+                let count = match args.get(3..) {
+                    Some([option, count]) if option.eq_ignore_ascii_case(b"COUNT") => match parse_int(count) {
+                        Some(count) => usize::try_from(count).unwrap_or_default(),
+                        None => return Reply::error(NOT_INTEGER),
+                    },
+                    Some([]) | None => usize::MAX,
+                    _ => return Reply::error(SYNTAX_ERROR),
+                };
+                let entries = stream
+                    .map(|stream| stream.entries.as_slice())
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|(id, _)| *id >= start && *id <= end);
+                let replies: Vec<Reply> = if reverse {
+                    entries
+                        .rev()
+                        .take(count)
+                        .map(|(id, fields)| stream_entry_reply(id, fields))
+                        .collect()
+                } else {
+                    entries
+                        .take(count)
+                        .map(|(id, fields)| stream_entry_reply(id, fields))
+                        .collect()
+                };
+                Reply::Array(replies)
+            }
+            "XINFO" => {
+                let sub = args[0].to_ascii_uppercase();
+                let stream = match self.lookup(session, &args[1]).map(|key| &key.value) {
+                    Some(RdbValue::Stream(stream)) => stream,
+                    Some(_) => return Reply::error(WRONG_TYPE),
+                    None => return Reply::error("ERR no such key"),
+                };
+                match sub.as_slice() {
+                    b"GROUPS" => Reply::Array(stream.groups.iter().map(stream_group_reply).collect()),
+                    b"STREAM" => stream_info_reply(stream),
+                    _ => Reply::Array(vec![]),
+                }
+            }
+            "XPENDING" if args.len() <= 2 => {
+                Reply::Array(vec![Reply::Int(0), Reply::Nil, Reply::Nil, Reply::Array(vec![])])
+            }
+            "XPENDING" => Reply::Array(vec![]),
+            name if WRITE_COMMANDS.contains(&name) => {
+                Reply::error("READONLY You can't write against a read only RDB snapshot")
+            }
+            name => Reply::Error(format!("ERR unknown command '{}'", name.to_lowercase())),
+        }
+    }
+}
+
+fn format_score(score: f64) -> String {
+    if score.is_infinite() {
+        return if score > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+    score.to_string()
+}
+
+fn stream_group_reply(group: &RdbStreamGroup) -> Reply {
+    Reply::Array(vec![
+        Reply::bulk("name"),
+        Reply::bulk(&group.name),
+        Reply::bulk("consumers"),
+        Reply::len(group.consumers),
+        Reply::bulk("pending"),
+        Reply::len(group.pending),
+        Reply::bulk("last-delivered-id"),
+        Reply::bulk(group.last_id.to_string()),
+    ])
+}
+
+fn stream_info_reply(stream: &RdbStream) -> Reply {
+    let entry = |entry: Option<&StreamEntry>| {
+        entry
+            .map(|(id, fields)| stream_entry_reply(id, fields))
+            .unwrap_or(Reply::Nil)
+    };
+    Reply::Array(vec![
+        Reply::bulk("length"),
+        Reply::len(stream.entries.len()),
+        Reply::bulk("last-generated-id"),
+        Reply::bulk(stream.last_id.to_string()),
+        Reply::bulk("groups"),
+        Reply::len(stream.groups.len()),
+        Reply::bulk("first-entry"),
+        entry(stream.entries.first()),
+        Reply::bulk("last-entry"),
+        entry(stream.entries.last()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_redis_glob_patterns() {
+        assert!(glob_match(b"user:*", b"user:1"));
+        assert!(glob_match(b"*", b""));
+        assert!(glob_match(b"h?llo", b"hello"));
+        assert!(glob_match(b"h[ae]llo", b"hallo"));
+        assert!(!glob_match(b"h[^e]llo", b"hello"));
+        assert!(glob_match(b"h[a-c]llo", b"hbllo"));
+        assert!(glob_match(b"a\\*b", b"a*b"));
+        assert!(!glob_match(b"a\\*b", b"axb"));
+        assert!(!glob_match(b"user:*", b"order:1"));
+    }
+
+    #[test]
+    fn resolves_index_ranges_and_stream_bounds() {
+        assert_eq!(index_range(5, 0, -1), Some(0..=4));
+        assert_eq!(index_range(5, -2, 10), Some(3..=4));
+        assert_eq!(index_range(5, 3, 1), None);
+        assert_eq!(index_range(0, 0, -1), None);
+        assert_eq!(parse_stream_bound(b"-", true), Some(StreamId::default()));
+        assert_eq!(parse_stream_bound(b"5", false), Some(StreamId { ms: 5, seq: u64::MAX }));
+        assert_eq!(parse_stream_bound(b"(5-1", true), Some(StreamId { ms: 5, seq: 2 }));
+        assert_eq!(
+            parse_stream_bound(b"(5-0", false),
+            Some(StreamId { ms: 4, seq: u64::MAX })
+        );
+        assert_eq!(parse_stream_bound(b"x", true), None);
+    }
+
+    #[test]
+    fn encodes_resp_replies() {
+        let mut out = Vec::new();
+        Reply::Array(vec![Reply::bulk("0"), Reply::Array(vec![Reply::bulk("k"), Reply::Nil])]).write(&mut out);
+        Reply::Error("ERR a\r\nb".to_string()).write(&mut out);
+        Reply::Int(-2).write(&mut out);
+        assert_eq!(
+            out,
+            b"*2\r\n$1\r\n0\r\n*2\r\n$1\r\nk\r\n$-1\r\n-ERR a  b\r\n:-2\r\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn requires_auth_before_commands() {
+        let state = RdbServerState {
+            password: "secret".to_string(),
+            snapshot: RdbSnapshot::default(),
+            snapshot_ms: 0,
+            path: "dump.rdb".to_string(),
+        };
+        let command = |args: &[&str]| args.iter().map(|arg| arg.as_bytes().to_vec()).collect::<Vec<_>>();
+        let mut session = Session::default();
+        assert!(matches!(
+            state.execute(&mut session, &command(&["DBSIZE"])),
+            Reply::Error(message) if message.starts_with("NOAUTH")
+        ));
+        assert!(matches!(
+            state.execute(&mut session, &command(&["AUTH", "wrong"])),
+            Reply::Error(message) if message.starts_with("WRONGPASS")
+        ));
+        assert_eq!(
+            state.execute(&mut session, &command(&["AUTH", "default", "secret"])),
+            Reply::ok()
+        );
+        assert_eq!(state.execute(&mut session, &command(&["DBSIZE"])), Reply::Int(0));
+    }
+
+    #[test]
+    fn rejects_oversized_headers() {
+        assert_eq!(parse_header_len(b"*3", MAX_ARGS).ok(), Some(3));
+        assert!(parse_header_len(b"*9999999999", MAX_ARGS).is_err());
+        assert!(parse_header_len(b"$-1", MAX_BULK_LEN).is_err());
+        assert!(parse_header_len(b"$536870913", MAX_BULK_LEN).is_err());
+    }
+}
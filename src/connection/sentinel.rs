@@ -0,0 +1,306 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redis Sentinel support.
+//!
+//! The Sentinel nodes are the address of the server followed by `sentinel_nodes`, the
+//! first reachable one is used. They authenticate with the Sentinel credentials when set,
+//! otherwise with the credentials of the data nodes and then without password, as older
+//! configurations expect.
+//!
+//! A client resolved through Sentinel keeps a [`SentinelTarget`], the heartbeat checks it
+//! against Sentinel so the client is dropped and resolved again after a failover.
+
+use super::async_connection::{is_auth_error, open_single_connection};
+use super::config::RedisServer;
+use crate::error::Error;
+use redis::{aio::MultiplexedConnection, cmd};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+const DEFAULT_SENTINEL_PORT: u16 = 26379;
+
+/// A master or replica reported by `SENTINEL MASTERS` or `SENTINEL REPLICAS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentinelNode {
+    /// Name of the master, `ip:port` for a replica
+    pub name: String,
+    pub ip: String,
+    pub port: u16,
+    /// Flags of the node, e.g. `master`, `slave`, `s_down`, `disconnected`
+    pub flags: Vec<String>,
+}
+
+impl SentinelNode {
+    pub fn addr(&self) -> String {
+        format!("{}:{}", self.ip, self.port)
+    }
+    /// Whether Sentinel considers the node down or unreachable.
+    pub fn is_down(&self) -> bool {
+        self.flags
+            .iter()
+            .any(|flag| matches!(flag.as_str(), "s_down" | "o_down" | "disconnected"))
+    }
+}
+
+/// A master monitored by Sentinel with its replicas, listed by the server form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentinelMaster {
+    pub master: SentinelNode,
+    pub replicas: Vec<SentinelNode>,
+}
+
+/// The node a client was resolved to through Sentinel.
+#[derive(Debug, Clone)]
+pub(crate) struct SentinelTarget {
+    /// The server config, used to reach the Sentinel nodes again
+    pub config: RedisServer,
+    pub master_name: String,
+    /// `host:port` of the node the client is connected to
+    pub addr: String,
+    /// Whether the node is a replica browsed read-only
+    pub replica: bool,
+}
+
+impl SentinelTarget {
+    /// Checks whether the node is still the one Sentinel resolves to.
+    ///
+    /// # Returns
+    /// The failover that happened: the master was switched or the browsed replica was
+    /// promoted. `None` when the node is still current.
+    pub async fn failover(&self) -> Result<Option<String>> {
+        let mut conn = open_sentinel_connection(&self.config).await?;
+        let master = master_addr(&mut conn, &self.master_name).await?;
+        if self.replica && master == self.addr {
+            return Ok(Some(format!(
+                "Replica {} of {} was promoted to master",
+                self.addr, self.master_name
+            )));
+        }
+        if !self.replica && master != self.addr {
+            return Ok(Some(format!(
+                "Master of {} was switched from {} to {master}",
+                self.master_name, self.addr
+            )));
+        }
+        Ok(None)
+    }
+}
+
+/// Splits Sentinel addresses separated by commas or whitespace.
+pub fn parse_sentinel_addrs(value: &str) -> Vec<String> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect()
+}
+
+/// The configs of the Sentinel nodes, in the order they are tried.
+fn sentinel_endpoints(config: &RedisServer) -> Vec<RedisServer> {
+    let mut endpoints = vec![config.clone()];
+    for addr in config.sentinel_nodes.iter().flatten() {
+        let mut endpoint = config.clone();
+        match addr.rsplit_once(':') {
+            Some((host, port)) => endpoint.set_addr(host, port.parse().unwrap_or(DEFAULT_SENTINEL_PORT)),
+            None => endpoint.set_addr(addr.as_str(), DEFAULT_SENTINEL_PORT),
+        }
+        endpoints.push(endpoint);
+    }
+    endpoints
+}
+
+/// The credentials tried on a Sentinel node, in order.
+fn sentinel_credentials(endpoint: &RedisServer) -> Vec<RedisServer> {
+    if endpoint.sentinel_username.is_some() || endpoint.sentinel_password.is_some() {
+        let mut server = endpoint.clone();
+        server.username = endpoint.sentinel_username.clone();
+        server.password = endpoint.sentinel_password.clone();
+        return vec![server];
+    }
+    let mut servers = vec![endpoint.clone()];
+    if endpoint.password.is_some() {
+        let mut server = endpoint.clone();
+        server.password = None;
+        servers.push(server);
+    }
+    servers
+}
+
+/// Connects to the first reachable Sentinel node.
+pub(crate) async fn open_sentinel_connection(config: &RedisServer) -> Result<MultiplexedConnection> {
+    let mut last_error = None;
+    for endpoint in sentinel_endpoints(config) {
+        for server in sentinel_credentials(&endpoint) {
+            match open_single_connection(&server, 0).await {
+                Ok(conn) => {
+                    info!(addr = server.addr(), "sentinel connected");
+                    return Ok(conn);
+                }
+                // The user has to trust the ssh host key first
+                Err(e @ (Error::UnknownHostKey { .. } | Error::HostKeyChanged { .. })) => return Err(e),
+                Err(e) => {
+                    warn!(addr = server.addr(), error = %e, "connect sentinel fail");
+                    let is_auth = is_auth_error(&e);
+                    last_error = Some(e);
+                    // Other credentials are only worth a try on an authentication error
+                    if !is_auth {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| Error::Invalid {
+        message: "No sentinel node available".to_string(),
+    }))
+}
+
+/// Parses the maps returned by `SENTINEL MASTERS` and `SENTINEL REPLICAS`.
+fn parse_sentinel_reply(items: Vec<HashMap<String, String>>) -> Result<Vec<SentinelNode>> {
+    items
+        .into_iter()
+        .map(|item| {
+            let field = |name: &str| {
+                item.get(name).cloned().ok_or_else(|| Error::Invalid {
+                    message: format!("{name} is not found"),
+                })
+            };
+            let port = field("port")?;
+            Ok(SentinelNode {
+                name: field("name")?,
+                ip: field("ip")?,
+                port: port.parse().map_err(|e| Error::Invalid {
+                    message: format!("Invalid port {port}: {e:?}"),
+                })?,
+                flags: item
+                    .get("flags")
+                    .map(|flags| flags.split(',').map(|flag| flag.to_string()).collect())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Lists the masters monitored by the Sentinel.
+pub(crate) async fn sentinel_masters(conn: &mut MultiplexedConnection) -> Result<Vec<SentinelNode>> {
+    let items: Vec<HashMap<String, String>> = cmd("SENTINEL").arg("MASTERS").query_async(conn).await?;
+    parse_sentinel_reply(items)
+}
+
+/// Lists the replicas of a master, `SENTINEL SLAVES` on Redis before 5.0.
+pub(crate) async fn sentinel_replicas(
+    conn: &mut MultiplexedConnection,
+    master_name: &str,
+) -> Result<Vec<SentinelNode>> {
+    let items: Vec<HashMap<String, String>> =
+        match cmd("SENTINEL").arg("REPLICAS").arg(master_name).query_async(conn).await {
+            Ok(items) => items,
+            Err(_) => cmd("SENTINEL").arg("SLAVES").arg(master_name).query_async(conn).await?,
+        };
+    parse_sentinel_reply(items)
+}
+
+/// Returns `ip:port` of the current master.
+async fn master_addr(conn: &mut MultiplexedConnection, master_name: &str) -> Result<String> {
+    let addr: Option<(String, String)> = cmd("SENTINEL")
+        .arg("GET-MASTER-ADDR-BY-NAME")
+        .arg(master_name)
+        .query_async(conn)
+        .await?;
+    let (ip, port) = addr.ok_or_else(|| Error::Invalid {
+        message: format!("Master {master_name} is not monitored by sentinel"),
+    })?;
+    Ok(format!("{ip}:{port}"))
+}
+
+/// Lists the masters of the Sentinel with their replicas, for the master name picker.
+pub async fn list_sentinel_masters(config: &RedisServer) -> Result<Vec<SentinelMaster>> {
+    let mut conn = open_sentinel_connection(config).await?;
+    let mut masters = vec![];
+    for master in sentinel_masters(&mut conn).await? {
+        let replicas = sentinel_replicas(&mut conn, &master.name).await?;
+        masters.push(SentinelMaster { master, replicas });
+    }
+    masters.sort_by(|a, b| a.master.name.cmp(&b.master.name));
+    Ok(masters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(fields: &[(&str, &str)]) -> HashMap<String, String> {
+        fields
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_sentinel_reply() {
+        let nodes = parse_sentinel_reply(vec![
+            reply(&[
+                ("name", "mymaster"),
+                ("ip", "10.0.0.1"),
+                ("port", "6379"),
+                ("flags", "master"),
+            ]),
+            reply(&[
+                ("name", "10.0.0.2:6379"),
+                ("ip", "10.0.0.2"),
+                ("port", "6379"),
+                ("flags", "slave,s_down,disconnected"),
+            ]),
+        ])
+        .expect("parse sentinel reply");
+        assert_eq!(nodes[0].addr(), "10.0.0.1:6379");
+        assert!(!nodes[0].is_down());
+        assert_eq!(nodes[1].flags, vec!["slave", "s_down", "disconnected"]);
+        assert!(nodes[1].is_down());
+        assert!(parse_sentinel_reply(vec![reply(&[("name", "mymaster"), ("ip", "10.0.0.1")])]).is_err());
+    }
+
+    #[test]
+    fn lists_sentinel_endpoints() {
+        let config = RedisServer {
+            host: "10.0.0.1".to_string(),
+            port: 26379,
+            password: Some("data".to_string()),
+            sentinel_nodes: Some(parse_sentinel_addrs("10.0.0.2:26380, 10.0.0.3\n")),
+            ..Default::default()
+        };
+        let addrs: Vec<String> = sentinel_endpoints(&config).iter().map(RedisServer::addr).collect();
+        assert_eq!(addrs, vec!["10.0.0.1:26379", "10.0.0.2:26380", "10.0.0.3:26379"]);
+
+        // Without Sentinel credentials, the data password is tried first
+        let passwords: Vec<Option<String>> = sentinel_credentials(&config)
+            .into_iter()
+            .map(|server| server.password)
+            .collect();
+        assert_eq!(passwords, vec![Some("data".to_string()), None]);
+
+        let config = RedisServer {
+            sentinel_username: Some("sentinel".to_string()),
+            sentinel_password: Some("secret".to_string()),
+            ..config
+        };
+        let servers = sentinel_credentials(&config);
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].username.as_deref(), Some("sentinel"));
+        assert_eq!(servers[0].password.as_deref(), Some("secret"));
+    }
+}
@@ -62,8 +62,7 @@ impl Connect for SshMultiplexedConnection {
                     )));
                 }
             };
-            config.host = target_host.to_string();
-            config.port = *target_port;
+            config.set_addr(target_host, *target_port);
             let connection = open_single_ssh_tunnel_connection(&config).await.map_err(|e| {
                 (
                     ErrorKind::InvalidClientConfig,
@@ -0,0 +1,602 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SSH tunnel hops and `~/.ssh/config` lookup.
+//!
+//! The SSH address of a server, like the address of every jump host, may be a `Host`
+//! alias of `~/.ssh/config`. Its HostName, Port, User, IdentityFile and ProxyJump are
+//! used for the settings left empty in Zedis. As with OpenSSH, the first value of a
+//! keyword wins, `Include` is followed and `Match` blocks are skipped.
+
+use super::config::{RedisServer, SshJumpHost};
+use super::ssh_auth::SshCredentials;
+use crate::error::Error;
+use crate::helpers::get_home_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+pub(super) const DEFAULT_SSH_PORT: u16 = 22;
+
+/// Jump hosts followed before giving up, which also stops ProxyJump loops
+const MAX_SSH_HOPS: usize = 8;
+
+/// Nested `Include` files read before giving up
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Settings of a `Host` alias of `~/.ssh/config`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SshHostConfig {
+    pub host_name: Option<String>,
+    pub port: Option<u16>,
+    pub user: Option<String>,
+    pub identity_files: Vec<String>,
+    /// `none` disables the jump hosts of a more generic block
+    pub proxy_jump: Option<String>,
+}
+
+/// An SSH server of a tunnel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SshHop {
+    pub host: String,
+    pub port: u16,
+    pub credentials: SshCredentials,
+}
+
+impl SshHop {
+    /// Identifier of the hop in the session cache, `user@host:port`.
+    pub fn id(&self) -> String {
+        format!("{}@{}:{}", self.credentials.user, self.host, self.port)
+    }
+}
+
+/// Matches a host against a pattern of `~/.ssh/config`, `*` and `?` being wildcards.
+fn pattern_matches(pattern: &str, host: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let host: Vec<char> = host.to_lowercase().chars().collect();
+    let (mut p, mut h) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while h < host.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == host[h]) {
+            p += 1;
+            h += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, h));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            h = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|item| *item == '*')
+}
+
+/// Whether a `Host` line applies: a pattern matches and no negated `!pattern` does.
+fn host_matches(patterns: &[String], host: &str) -> bool {
+    let mut matched = false;
+    for pattern in patterns {
+        if let Some(negated) = pattern.strip_prefix('!') {
+            if pattern_matches(negated, host) {
+                return false;
+            }
+        } else if pattern_matches(pattern, host) {
+            matched = true;
+        }
+    }
+    matched
+}
+
+/// Splits a line into its lowercase keyword and arguments, `Keyword=value` included.
+fn parse_line(line: &str) -> Option<(String, Vec<String>)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let split = line.find(|c: char| c.is_whitespace() || c == '=')?;
+    let keyword = line[..split].to_lowercase();
+    let rest = line[split..].trim_start();
+    let rest = rest.strip_prefix('=').unwrap_or(rest);
+    let mut args = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in rest.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    Some((keyword, args))
+}
+
+/// Files of an `Include` argument, relative paths are in `~/.ssh` and `*` is allowed
+/// in the file name.
+fn include_paths(arg: &str, ssh_dir: &Path) -> Vec<PathBuf> {
+    let path = match arg.strip_prefix("~/") {
+        Some(rest) => get_home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| PathBuf::from(arg)),
+        None => ssh_dir.join(arg),
+    };
+    let file_name = path.file_name().map(|name| name.to_string_lossy().to_string());
+    let Some(file_name) = file_name.filter(|name| name.contains(['*', '?'])) else {
+        return vec![path];
+    };
+    let Some(dir) = path.parent() else {
+        return vec![];
+    };
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|item| {
+                    item.file_name()
+                        .is_some_and(|name| pattern_matches(&file_name, &name.to_string_lossy()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+    paths
+}
+
+/// Reads the settings of a host from the content of a config file.
+fn lookup_in(content: &str, ssh_dir: &Path, host: &str, config: &mut SshHostConfig, depth: usize) {
+    // Lines before the first Host block apply to every host
+    let mut active = true;
+    for line in content.lines() {
+        let Some((keyword, args)) = parse_line(line) else {
+            continue;
+        };
+        match keyword.as_str() {
+            "host" => {
+                active = host_matches(&args, host);
+                continue;
+            }
+            "match" => {
+                active = false;
+                continue;
+            }
+            _ => {}
+        }
+        if !active {
+            continue;
+        }
+        let Some(value) = args.first().cloned() else {
+            continue;
+        };
+        match keyword.as_str() {
+            "hostname" => {
+                config.host_name.get_or_insert(value);
+            }
+            "port" if config.port.is_none() => {
+                config.port = value.parse().ok();
+            }
+            "user" => {
+                config.user.get_or_insert(value);
+            }
+            "identityfile" => config.identity_files.push(value),
+            "proxyjump" => {
+                config.proxy_jump.get_or_insert(value);
+            }
+            "include" if depth < MAX_INCLUDE_DEPTH => {
+                for path in args.iter().flat_map(|arg| include_paths(arg, ssh_dir)) {
+                    match fs::read_to_string(&path) {
+                        Ok(content) => lookup_in(&content, ssh_dir, host, config, depth + 1),
+                        Err(e) => debug!(error = %e, path = %path.display(), "read ssh config include fail"),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reads the settings of a host alias from `~/.ssh/config`.
+pub fn lookup_ssh_config(host: &str) -> SshHostConfig {
+    let mut config = SshHostConfig::default();
+    let Some(ssh_dir) = get_home_dir().map(|home| home.join(".ssh")) else {
+        return config;
+    };
+    match fs::read_to_string(ssh_dir.join("config")) {
+        Ok(content) => lookup_in(&content, &ssh_dir, host, &mut config, 0),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!(error = %e, "read ssh config fail"),
+    }
+    config
+}
+
+/// Parses jump hosts in ProxyJump form: `[user@]host[:port]` or
+/// `ssh://[user@]host[:port]`, separated by commas.
+pub fn parse_proxy_jump(value: &str) -> Vec<SshJumpHost> {
+    if value.trim().eq_ignore_ascii_case("none") {
+        return vec![];
+    }
+    value
+        .split(',')
+        .map(|item| item.trim())
+        .map(|item| item.strip_prefix("ssh://").unwrap_or(item))
+        .filter(|item| !item.is_empty())
+        .map(|item| match item.rsplit_once('@') {
+            Some((username, addr)) => SshJumpHost {
+                addr: addr.to_string(),
+                username: Some(username.to_string()),
+                ..Default::default()
+            },
+            None => SshJumpHost {
+                addr: item.to_string(),
+                ..Default::default()
+            },
+        })
+        .collect()
+}
+
+/// Name of the local user, the default SSH user like with OpenSSH.
+fn local_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default()
+}
+
+/// Replaces the tokens of an IdentityFile value.
+fn expand_identity_file(path: &str, host: &str, user: &str) -> String {
+    let home = get_home_dir()
+        .map(|home| home.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let path = match path.strip_prefix('~') {
+        Some(rest) => format!("{home}{rest}"),
+        None => path.to_string(),
+    };
+    let mut expanded = String::new();
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            expanded.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('d') => expanded.push_str(&home),
+            Some('h') => expanded.push_str(host),
+            Some('r') => expanded.push_str(user),
+            Some('u') => expanded.push_str(&local_user()),
+            Some('%') => expanded.push('%'),
+            Some(other) => {
+                expanded.push('%');
+                expanded.push(other);
+            }
+            None => expanded.push('%'),
+        }
+    }
+    expanded
+}
+
+/// Splits `host:port` or `[host]:port`, the port is `None` when missing or invalid.
+fn split_addr(addr: &str) -> (&str, Option<u16>) {
+    if let Some(rest) = addr.strip_prefix('[')
+        && let Some((host, rest)) = rest.split_once(']')
+    {
+        return (host, rest.strip_prefix(':').and_then(|port| port.parse::<u16>().ok()));
+    }
+    match addr.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) => (host, Some(port)),
+            Err(_) => (addr, None),
+        },
+        None => (addr, None),
+    }
+}
+
+/// Splits an SSH address in `host:port`, `[host]:port` or `host` form, the port
+/// defaults to 22.
+pub fn parse_ssh_addr(addr: &str) -> (String, u16) {
+    let (host, port) = split_addr(addr.trim());
+    (host.to_string(), port.unwrap_or(DEFAULT_SSH_PORT))
+}
+
+/// Host and port of an SSH address whose alias has the given settings.
+fn resolve_addr(addr: &str, config: &SshHostConfig) -> (String, u16) {
+    let (alias, port) = split_addr(addr.trim());
+    let host = config
+        .host_name
+        .as_deref()
+        .map(|name| name.replace("%h", alias))
+        .unwrap_or_else(|| alias.to_string());
+    (host, port.or(config.port).unwrap_or(DEFAULT_SSH_PORT))
+}
+
+/// Host and port of an SSH address, a `Host` alias of `~/.ssh/config` included.
+pub fn resolve_ssh_addr(addr: &str) -> (String, u16) {
+    let (alias, _) = split_addr(addr.trim());
+    resolve_addr(addr, &lookup_ssh_config(alias))
+}
+
+/// Resolves the hops reaching an SSH server, its jump hosts first.
+///
+/// # Arguments
+///
+/// * `target` - The SSH server and its credentials
+/// * `jump_hosts` - Jump hosts of the server, the ProxyJump of its alias when `None`
+/// * `lookup` - Reads the settings of a host alias
+/// * `depth` - Number of jumps leading to the server
+/// * `hops` - Receives the hops
+fn resolve_hops(
+    target: &SshJumpHost,
+    jump_hosts: Option<&[SshJumpHost]>,
+    lookup: &dyn Fn(&str) -> SshHostConfig,
+    depth: usize,
+    hops: &mut Vec<SshHop>,
+) -> Result<()> {
+    if depth > MAX_SSH_HOPS || hops.len() > MAX_SSH_HOPS {
+        return Err(Error::Invalid {
+            message: format!("Too many ssh jump hosts, check the ProxyJump of {}", target.addr),
+        });
+    }
+    let (alias, _) = split_addr(target.addr.trim());
+    let config = lookup(alias);
+    let (host, port) = resolve_addr(&target.addr, &config);
+    let mut credentials = SshCredentials::new(target);
+    if credentials.user.is_empty() {
+        credentials.user = config.user.clone().unwrap_or_else(local_user);
+    }
+    if credentials.identities.is_empty() {
+        credentials.identities = config
+            .identity_files
+            .iter()
+            .map(|path| expand_identity_file(path, &host, &credentials.user))
+            .collect();
+    }
+
+    let jump_hosts = match jump_hosts {
+        Some(hosts) => hosts.to_vec(),
+        None => config.proxy_jump.as_deref().map(parse_proxy_jump).unwrap_or_default(),
+    };
+    // As with `ssh -J a,b`, b is reached through a and only a follows its own ProxyJump
+    for (index, jump_host) in jump_hosts.iter().enumerate() {
+        let nested = if index == 0 { None } else { Some(&[][..]) };
+        resolve_hops(jump_host, nested, lookup, depth + 1, hops)?;
+    }
+    hops.push(SshHop {
+        host,
+        port,
+        credentials,
+    });
+    Ok(())
+}
+
+/// Resolves the hops of the SSH tunnel of a server, the SSH server of `ssh_addr` last.
+pub fn ssh_hops(server: &RedisServer) -> Result<Vec<SshHop>> {
+    let mut hops = vec![];
+    // Without jump hosts in Zedis, the ProxyJump of the alias is followed
+    let jump_hosts = server.ssh_jump_hosts.as_deref().filter(|hosts| !hosts.is_empty());
+    resolve_hops(&server.ssh_target(), jump_hosts, &lookup_ssh_config, 0, &mut hops)?;
+    Ok(hops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::{format_jump_hosts, parse_jump_hosts};
+
+    const CONFIG: &str = r#"
+# Defaults of every host are read last
+Host redis-*.internal !redis-legacy.internal
+    User ops
+    ProxyJump inner
+
+Host inner
+    HostName 10.0.1.5
+    Port 2222
+    ProxyJump admin@bastion
+
+Host bastion
+    HostName=bastion.example.com
+    IdentityFile "~/.ssh/id bastion"
+
+Match host *
+    User ignored
+
+Host *
+    User deploy
+    IdentityFile %d/.ssh/id_%h
+    Port 22
+"#;
+
+    fn lookup(host: &str) -> SshHostConfig {
+        let mut config = SshHostConfig::default();
+        lookup_in(CONFIG, Path::new("/nonexistent"), host, &mut config, 0);
+        config
+    }
+
+    fn addrs(hops: &[SshHop]) -> Vec<String> {
+        hops.iter().map(SshHop::id).collect()
+    }
+
+    #[test]
+    fn parses_ssh_addr() {
+        assert_eq!(parse_ssh_addr("bastion"), ("bastion".to_string(), 22));
+        assert_eq!(parse_ssh_addr("10.0.0.1:2222"), ("10.0.0.1".to_string(), 2222));
+        assert_eq!(parse_ssh_addr("bastion:abc"), ("bastion:abc".to_string(), 22));
+        assert_eq!(parse_ssh_addr("[::1]:2222"), ("::1".to_string(), 2222));
+        assert_eq!(parse_ssh_addr("[::1]"), ("::1".to_string(), 22));
+    }
+
+    #[test]
+    fn matches_host_patterns() {
+        assert!(pattern_matches("*", "anything"));
+        assert!(pattern_matches("redis-*.internal", "REDIS-1.internal"));
+        assert!(pattern_matches("10.0.?.5", "10.0.1.5"));
+        assert!(!pattern_matches("10.0.?.5", "10.0.12.5"));
+        assert!(pattern_matches("*a*b", "xaxxab"));
+        assert!(!pattern_matches("bastion", "bastion2"));
+        let patterns = vec!["redis-*".to_string(), "!redis-legacy".to_string()];
+        assert!(host_matches(&patterns, "redis-1"));
+        assert!(!host_matches(&patterns, "redis-legacy"));
+    }
+
+    #[test]
+    fn parses_config_lines() {
+        assert_eq!(parse_line("  # comment"), None);
+        assert_eq!(
+            parse_line("HostName=bastion"),
+            Some(("hostname".to_string(), vec!["bastion".to_string()]))
+        );
+        assert_eq!(
+            parse_line(r#"IdentityFile "~/my keys/id""#),
+            Some(("identityfile".to_string(), vec!["~/my keys/id".to_string()]))
+        );
+        assert_eq!(
+            parse_line("Host a b"),
+            Some(("host".to_string(), vec!["a".to_string(), "b".to_string()]))
+        );
+    }
+
+    #[test]
+    fn looks_up_hosts() {
+        let inner = lookup("inner");
+        assert_eq!(inner.host_name.as_deref(), Some("10.0.1.5"));
+        // The first value wins, the Port of `Host *` is ignored
+        assert_eq!(inner.port, Some(2222));
+        assert_eq!(inner.user.as_deref(), Some("deploy"));
+        assert_eq!(inner.proxy_jump.as_deref(), Some("admin@bastion"));
+
+        let bastion = lookup("bastion");
+        assert_eq!(bastion.identity_files, vec!["~/.ssh/id bastion", "%d/.ssh/id_%h"]);
+
+        let legacy = lookup("redis-legacy.internal");
+        assert_eq!(legacy.user.as_deref(), Some("deploy"));
+        assert_eq!(legacy.proxy_jump, None);
+    }
+
+    #[test]
+    fn follows_includes() {
+        let dir = std::env::temp_dir().join(format!("zedis-ssh-config-{}", uuid::Uuid::now_v7()));
+        fs::create_dir_all(dir.join("config.d")).expect("create config dir");
+        fs::write(
+            dir.join("config.d").join("10-cache"),
+            "Host cache\n    HostName 10.0.2.9\n",
+        )
+        .expect("write include");
+        let mut config = SshHostConfig::default();
+        lookup_in(
+            "Include config.d/*\nHost *\n    Port 2200\n",
+            &dir,
+            "cache",
+            &mut config,
+            0,
+        );
+        assert_eq!(config.host_name.as_deref(), Some("10.0.2.9"));
+        assert_eq!(config.port, Some(2200));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn parses_proxy_jump() {
+        let hosts = parse_proxy_jump("admin@bastion:2200, ssh://inner ,");
+        assert_eq!(hosts.len(), 2);
+        assert_eq!(hosts[0].addr, "bastion:2200");
+        assert_eq!(hosts[0].username.as_deref(), Some("admin"));
+        assert_eq!(hosts[1].addr, "inner");
+        assert_eq!(hosts[1].username, None);
+        assert!(parse_proxy_jump("none").is_empty());
+    }
+
+    #[test]
+    fn keeps_jump_host_credentials() {
+        let existing = vec![SshJumpHost {
+            addr: "bastion".to_string(),
+            username: Some("admin".to_string()),
+            key: Some("/keys/bastion".to_string()),
+            ..Default::default()
+        }];
+        assert_eq!(format_jump_hosts(&existing), "admin@bastion");
+        let hosts = parse_jump_hosts("admin@bastion,inner:2222", &existing);
+        assert_eq!(hosts[0], existing[0]);
+        assert_eq!(hosts[1].addr, "inner:2222");
+        assert_eq!(hosts[1].key, None);
+    }
+
+    #[test]
+    fn resolves_hops_through_config() {
+        let target = SshJumpHost {
+            addr: "redis-1.internal".to_string(),
+            key: Some("/keys/redis".to_string()),
+            ..Default::default()
+        };
+        let mut hops = vec![];
+        resolve_hops(&target, None, &lookup, 0, &mut hops).expect("resolve hops");
+        // bastion, then inner through bastion, then the target through inner
+        assert_eq!(
+            addrs(&hops),
+            vec![
+                "admin@bastion.example.com:22",
+                "deploy@10.0.1.5:2222",
+                "ops@redis-1.internal:22"
+            ]
+        );
+        assert_eq!(hops[2].credentials.identities, vec!["/keys/redis"]);
+        if let Some(home) = get_home_dir() {
+            let home = home.to_string_lossy();
+            assert_eq!(hops[1].credentials.identities, vec![format!("{home}/.ssh/id_10.0.1.5")]);
+        }
+    }
+
+    #[test]
+    fn resolves_explicit_jump_hosts() {
+        let target = SshJumpHost {
+            addr: "10.0.3.1".to_string(),
+            username: Some("redis".to_string()),
+            ..Default::default()
+        };
+        let jump_hosts = parse_proxy_jump("inner,root@gateway:2022");
+        let mut hops = vec![];
+        resolve_hops(&target, Some(&jump_hosts), &lookup, 0, &mut hops).expect("resolve hops");
+        // Only the first jump host follows its own ProxyJump
+        assert_eq!(
+            addrs(&hops),
+            vec![
+                "admin@bastion.example.com:22",
+                "deploy@10.0.1.5:2222",
+                "root@gateway:2022",
+                "redis@10.0.3.1:22"
+            ]
+        );
+    }
+
+    #[test]
+    fn stops_proxy_jump_loops() {
+        let lookup = |_: &str| SshHostConfig {
+            proxy_jump: Some("loop".to_string()),
+            ..Default::default()
+        };
+        let target = SshJumpHost {
+            addr: "loop".to_string(),
+            ..Default::default()
+        };
+        let mut hops = vec![];
+        assert!(resolve_hops(&target, None, &lookup, 0, &mut hops).is_err());
+    }
+}
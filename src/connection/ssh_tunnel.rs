@@ -13,15 +13,20 @@
 // limitations under the License.
 
 use super::config::RedisServer;
+use super::known_hosts::verify_host_key;
+use super::ssh_auth::authenticate;
+use super::ssh_config::{SshHop, parse_ssh_addr, ssh_hops};
 use super::ssh_stream::SshRedisStream;
 use crate::error::Error;
-use crate::helpers::get_home_dir;
 use dashmap::DashMap;
-use redis::{RedisConnectionInfo, aio::MultiplexedConnection, cmd};
+use redis::{
+    ProtocolVersion, RedisConnectionInfo,
+    aio::{AsyncPushSender, MultiplexedConnection, PubSub},
+    cmd,
+};
 use russh::client::{Handle, Handler};
 use russh::keys::ssh_key::PublicKey;
-use russh::keys::{PrivateKeyWithHashAlg, decode_secret_key, load_secret_key};
-use std::path::Path;
+use std::ops::Deref;
 use std::sync::Arc;
 use std::sync::{LazyLock, OnceLock};
 use std::time::Duration;
@@ -94,35 +99,72 @@ pub struct ClientHandler {
     port: u16,
 }
 
+impl ClientHandler {
+    pub fn new(host: &str, port: u16) -> Self {
+        Self {
+            host: host.to_string(),
+            port,
+        }
+    }
+}
+
 impl Handler for ClientHandler {
-    type Error = russh::Error;
+    type Error = Error;
 
     /// Verifies the SSH server's public key during connection establishment.
     ///
+    /// The key must be recorded in `~/.ssh/known_hosts` or in the known hosts of Zedis.
+    ///
     /// # Arguments
     ///
-    /// * `_server_public_key` - The server's public key to validate
+    /// * `server_public_key` - The server's public key to validate
     ///
     /// # Returns
     ///
-    /// `Ok(true)` to accept the connection, `Ok(false)` to reject it
-    ///
-    /// # Note
-    ///
-    /// Currently accepts all server keys without validation.
-    /// TODO: Implement proper validation against ~/.ssh/known_hosts
-    async fn check_server_key(&mut self, _server_public_key: &PublicKey) -> Result<bool, Self::Error> {
-        // TODO validate server public key (~/.ssh/known_hosts)
+    /// `Ok(true)` to accept the connection, `UnknownHostKey` for a key the user has not
+    /// trusted yet and `HostKeyChanged` for a key that differs from the recorded one
+    async fn check_server_key(&mut self, server_public_key: &PublicKey) -> Result<bool, Self::Error> {
         info!(host = self.host, port = self.port, "check server key");
+        verify_host_key(&self.host, self.port, server_public_key)?;
         Ok(true)
     }
 }
 
 type SshHandle = Handle<ClientHandler>;
 
-/// Global cache of SSH sessions keyed by "user@host:port" identifier.
+/// An SSH session, keeping alive the session of the jump host it goes through.
+pub struct SshSession {
+    handle: SshHandle,
+    _jump: Option<Arc<SshSession>>,
+}
+
+impl Deref for SshSession {
+    type Target = SshHandle;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handle
+    }
+}
+
+/// Global cache of SSH sessions keyed by the "user@host:port" identifiers of their hops.
 /// This prevents creating duplicate SSH connections to the same server.
-static SSH_SESSION: LazyLock<DashMap<String, Arc<SshHandle>>> = LazyLock::new(DashMap::new);
+static SSH_SESSION: LazyLock<DashMap<String, Arc<SshSession>>> = LazyLock::new(DashMap::new);
+
+/// Drops the cached SSH sessions going through a server, e.g. once its host key is no
+/// longer trusted.
+pub(crate) fn forget_ssh_sessions(host: &str, port: u16) {
+    SSH_SESSION.retain(|id, _| {
+        !id.split(',').any(|hop| {
+            let addr = hop.rsplit_once('@').map(|(_, addr)| addr).unwrap_or(hop);
+            parse_ssh_addr(addr) == (host.to_string(), port)
+        })
+    });
+}
+
+/// Identifier of the session reaching the last of the hops.
+fn session_id(hops: &[SshHop]) -> String {
+    hops.iter().map(SshHop::id).collect::<Vec<_>>().join(",")
+}
 
 /// Checks if an SSH session is still alive and functional.
 ///
@@ -137,7 +179,7 @@ static SSH_SESSION: LazyLock<DashMap<String, Arc<SshHandle>>> = LazyLock::new(Da
 /// # Returns
 ///
 /// `true` if the session is alive, `false` otherwise
-async fn is_alive(session: Arc<SshHandle>) -> bool {
+async fn is_alive(session: Arc<SshSession>) -> bool {
     match session.channel_open_session().await {
         Ok(channel) => {
             let _ = channel.close().await;
@@ -150,56 +192,54 @@ async fn is_alive(session: Arc<SshHandle>) -> bool {
 /// Gets an existing SSH session from the cache or creates a new one.
 ///
 /// This function first attempts to retrieve a cached SSH session for the
-/// specified address and user. If found, it validates the session is still
-/// alive before returning it. If no valid cached session exists, a new
-/// SSH connection is established and cached for future use.
+/// specified hops. If found, it validates the session is still alive before
+/// returning it. Otherwise the hops are connected one by one, each through a
+/// `direct-tcpip` channel of the previous one, reusing the cached sessions of
+/// the jump hosts that are still alive.
 ///
 /// # Arguments
 ///
-/// * `addr` - SSH server address in "host:port" or "host" format (defaults to port 22)
-/// * `user` - SSH username for authentication
-/// * `key` - Optional SSH private key (file path or key content)
-/// * `password` - Optional password for key decryption or password authentication
+/// * `hops` - The jump hosts then the SSH server, see [`ssh_hops`]
 ///
 /// # Returns
 ///
-/// An Arc-wrapped SSH session handle ready for use
-pub async fn get_or_init_ssh_session(
-    addr: &str,
-    user: &str,
-    key: Option<&str>,
-    password: Option<&str>,
-) -> Result<Arc<SshHandle>> {
-    // Generate unique identifier for this SSH connection
-    let id = format!("{user}@{addr}");
+/// An Arc-wrapped SSH session handle of the last hop ready for use
+pub async fn get_or_init_ssh_session(hops: &[SshHop]) -> Result<Arc<SshSession>> {
     // Check cache for existing session
-    let cached_session = SSH_SESSION.get(&id).map(|entry| entry.value().clone());
+    let cached_session = SSH_SESSION.get(&session_id(hops)).map(|entry| entry.value().clone());
     if let Some(session) = cached_session {
         // Validate the cached session is still alive
         if is_alive(session.clone()).await {
             return Ok(session);
         }
     }
-    // Create new session if none exists or cached session is dead
-    let session = new_ssh_session(addr, user, key, password).await?;
-    let session = Arc::new(session);
-    // Cache the new session for future reuse
-    SSH_SESSION.insert(id, session.clone());
-    Ok(session)
+    let mut jump: Option<Arc<SshSession>> = None;
+    for index in 0..hops.len() {
+        let id = session_id(&hops[..=index]);
+        let cached_session = SSH_SESSION.get(&id).map(|entry| entry.value().clone());
+        let session = match cached_session {
+            Some(session) if index + 1 < hops.len() && is_alive(session.clone()).await => session,
+            // Create new session if none exists or cached session is dead
+            _ => {
+                let session = Arc::new(new_ssh_session(&hops[index], jump.take()).await?);
+                // Cache the new session for future reuse
+                SSH_SESSION.insert(id, session.clone());
+                session
+            }
+        };
+        jump = Some(session);
+    }
+    jump.ok_or_else(|| Error::Invalid {
+        message: "Ssh address is empty".to_string(),
+    })
 }
 
-/// Creates a new SSH session with the specified authentication method.
-///
-/// This function establishes a new SSH connection to the remote server using
-/// either public key authentication or password authentication. It supports
-/// SSH keys provided as file paths or direct key content.
+/// Creates a new SSH session authenticated with the first accepted method.
 ///
 /// # Arguments
 ///
-/// * `addr` - SSH server address in "host:port" or "host" format (defaults to port 22)
-/// * `user` - SSH username for authentication
-/// * `key` - Optional SSH private key (file path or PEM/OpenSSH format content)
-/// * `password` - Optional password for key decryption or password authentication
+/// * `hop` - SSH server and the credentials tried to authenticate
+/// * `jump` - Session of the jump host the server is reached through
 ///
 /// # Returns
 ///
@@ -207,12 +247,11 @@ pub async fn get_or_init_ssh_session(
 ///
 /// # Authentication Methods
 ///
-/// 1. Public Key: If `key` is provided, attempts public key authentication
-///    - If key is a valid file path, loads the key from disk
-///    - Otherwise, decodes the key from the string content
-/// 2. Password: If only `password` is provided, uses password authentication
-/// 3. Error: If neither key nor password is provided, returns an error
-async fn new_ssh_session(addr: &str, user: &str, key: Option<&str>, password: Option<&str>) -> Result<SshHandle> {
+/// Tried in order, see [`authenticate`]:
+/// 1. Identities of the ssh-agent listening on `SSH_AUTH_SOCK`
+/// 2. Configured private keys, or the default keys of `~/.ssh`, decrypted with the passphrase
+/// 3. Password, with the `password` then the `keyboard-interactive` method
+async fn new_ssh_session(hop: &SshHop, jump: Option<Arc<SshSession>>) -> Result<SshSession> {
     // Configure SSH client with keepalive to maintain connection
     let config = russh::client::Config {
         keepalive_interval: Some(Duration::from_secs(30)),
@@ -220,62 +259,78 @@ async fn new_ssh_session(addr: &str, user: &str, key: Option<&str>, password: Op
     };
     let config = Arc::new(config);
 
-    // Parse host and port from address string
-    let (host, port) = if let Some((host, port)) = addr.split_once(':') {
-        let host = host.to_string();
-        let port = port.parse::<u16>().unwrap_or(22);
-        (host.to_string(), port)
-    } else {
-        (addr.to_string(), 22)
-    };
+    let handler = ClientHandler::new(&hop.host, hop.port);
 
-    let handler = ClientHandler {
-        host: host.clone(),
-        port,
+    // Establish SSH connection, directly or through the jump host
+    let mut handle = match &jump {
+        Some(jump) => {
+            let channel = jump
+                .channel_open_direct_tcpip(hop.host.clone(), hop.port as u32, "127.0.0.1", 0)
+                .await?;
+            russh::client::connect_stream(config, channel.into_stream(), handler).await?
+        }
+        None => russh::client::connect(config, (hop.host.clone(), hop.port), handler).await?,
     };
 
-    // Establish SSH connection
-    let mut session = russh::client::connect(config, (host, port), handler).await?;
+    authenticate(&mut handle, &hop.credentials).await?;
+    info!(
+        host = hop.host,
+        port = hop.port,
+        user = hop.credentials.user,
+        jump = jump.is_some(),
+        "ssh session established"
+    );
 
-    // Authenticate using provided credentials
-    let auth_res = if let Some(key) = key {
-        let key = if key.starts_with("~")
-            && let Some(home_dir) = get_home_dir()
-        {
-            format!("{}{}", home_dir.to_string_lossy(), &key[1..])
-        } else {
-            key.to_string()
-        };
-        // Public key authentication
-        let key_pair = if Path::new(&key).exists() {
-            // Load key from file path
-            load_secret_key(key, None)?
-        } else {
-            // Decode key from string content
-            decode_secret_key(&key, password)?
-        };
-        let key = Arc::new(key_pair);
-        let key_with_alg = PrivateKeyWithHashAlg::new(key, None);
-        session.authenticate_publickey(user, key_with_alg).await?
-    } else if let Some(password) = password {
-        // Password authentication
-        session.authenticate_password(user, password).await?
-    } else {
-        // TODO: Add SSH agent support
-        return Err(Error::Invalid {
-            message: "Ssh authentication failed".to_string(),
-        });
+    Ok(SshSession { handle, _jump: jump })
+}
+
+/// Opens the SSH session a server is reached through, or checks the cached one.
+///
+/// The cluster connection turns the errors of its tunnels into strings, opening the
+/// session first reports an untrusted host key as `UnknownHostKey`.
+pub async fn open_ssh_session(config: &RedisServer) -> Result<()> {
+    let hops = ssh_hops(config)?;
+    run_in_tokio(async move { get_or_init_ssh_session(&hops).await.map(|_| ()) }).await
+}
+
+/// Opens a direct TCP channel through the SSH tunnel to the Redis server.
+///
+/// A unix socket of the SSH server is reached with a `direct-streamlocal` channel instead.
+/// Must be called from the SSH tunnel Tokio runtime (see [`run_in_tokio`]).
+async fn open_direct_tcpip_stream(config: &RedisServer) -> Result<SshRedisStream> {
+    let ssh_addr = config.ssh_addr.clone().unwrap_or_default();
+    let hops = ssh_hops(config)?;
+    // Get or initialize an SSH session
+    let session = get_or_init_ssh_session(&hops).await?;
+    let channel = match config.socket_path.as_ref().filter(|path| !path.is_empty()) {
+        Some(path) => {
+            let channel = session.channel_open_direct_streamlocal(path.as_str()).await?;
+            info!(ssh_addr, path, hops = hops.len(), "open direct streamlocal success");
+            channel
+        }
+        None => {
+            // Open a direct TCP channel through the SSH tunnel to the Redis server
+            let channel = session
+                .channel_open_direct_tcpip(&config.host, config.port as u32, "127.0.0.1", 0)
+                .await?;
+            info!(ssh_addr, hops = hops.len(), "open direct tcpip success");
+            channel
+        }
     };
+    // Wrap the SSH channel in a Redis-compatible stream
+    Ok(SshRedisStream::new(channel.into_stream()))
+}
 
-    // Verify authentication succeeded
-    if !auth_res.success() {
-        return Err(Error::Invalid {
-            message: format!("Ssh authentication failed, {auth_res:?}"),
-        });
+/// Builds the handshake settings of a tunneled connection, authenticating during the handshake.
+fn redis_connection_info(config: &RedisServer, protocol: ProtocolVersion) -> RedisConnectionInfo {
+    let mut info = RedisConnectionInfo::default().set_protocol(protocol);
+    if let Some(password) = &config.password {
+        info = info.set_password(password);
+        if let Some(username) = &config.username {
+            info = info.set_username(username);
+        }
     }
-    info!(addr, user, "ssh session established");
-
-    Ok(session)
+    info
 }
 
 /// Opens a Redis connection through an SSH tunnel.
@@ -292,26 +347,9 @@ async fn new_ssh_session(addr: &str, user: &str, key: Option<&str>, password: Op
 ///
 /// A multiplexed Redis connection ready for use
 pub async fn open_single_ssh_tunnel_connection(config: &RedisServer) -> Result<MultiplexedConnection> {
-    // Extract SSH tunnel configuration
-    let ssh_addr = config.ssh_addr.clone().unwrap_or_default();
-    let ssh_user = config.ssh_username.clone().unwrap_or_default();
-    let ssh_key = config.ssh_key.clone().unwrap_or_default();
-    let ssh_password = config.ssh_password.clone().unwrap_or_default();
-    // Extract Redis server details
-    let host = config.host.to_string();
-    let port = config.port;
-    let username = config.username.clone();
-    let password = config.password.clone();
+    let config = config.clone();
     run_in_tokio(async move {
-        // Get or initialize an SSH session
-        let session = get_or_init_ssh_session(&ssh_addr, &ssh_user, Some(&ssh_key), Some(&ssh_password)).await?;
-        // Open a direct TCP channel through the SSH tunnel to the Redis server
-        let channel = session
-            .channel_open_direct_tcpip(&host, port as u32, "127.0.0.1", 0)
-            .await?;
-        info!(ssh_addr, ssh_user, "open direct tcpip success");
-        // Wrap the SSH channel in a Redis-compatible stream
-        let compat_stream = SshRedisStream::new(channel.into_stream());
+        let compat_stream = open_direct_tcpip_stream(&config).await?;
         let info = RedisConnectionInfo::default();
         let conn_config = redis::AsyncConnectionConfig::new()
             .set_connection_timeout(Some(Duration::from_secs(10)))
@@ -325,10 +363,10 @@ pub async fn open_single_ssh_tunnel_connection(config: &RedisServer) -> Result<M
             info!("Redis driver task finished");
         });
         // Authenticate with Redis if password is provided
-        if let Some(password) = password {
+        if let Some(password) = config.password {
             let mut auth_cmd = cmd("AUTH");
             // Use ACL authentication (username + password) if username is provided
-            if let Some(user) = username {
+            if let Some(user) = config.username {
                 auth_cmd.arg(user);
             }
             auth_cmd.arg(password);
@@ -339,3 +377,54 @@ pub async fn open_single_ssh_tunnel_connection(config: &RedisServer) -> Result<M
     })
     .await
 }
+
+/// Opens a RESP3 connection through an SSH tunnel whose push messages
+/// (Pub/Sub messages) are delivered to `push_sender`.
+///
+/// # Arguments
+///
+/// * `config` - Redis server configuration containing SSH and Redis connection details
+/// * `push_sender` - Receiver of the push messages
+///
+/// # Returns
+///
+/// A multiplexed RESP3 connection, already authenticated by the `HELLO` handshake
+pub async fn open_ssh_tunnel_push_connection(
+    config: &RedisServer,
+    push_sender: impl AsyncPushSender,
+) -> Result<MultiplexedConnection> {
+    let config = config.clone();
+    run_in_tokio(async move {
+        let compat_stream = open_direct_tcpip_stream(&config).await?;
+        let info = redis_connection_info(&config, ProtocolVersion::RESP3);
+        let conn_config = redis::AsyncConnectionConfig::new()
+            .set_connection_timeout(Some(Duration::from_secs(10)))
+            .set_push_sender(push_sender);
+        let (connection, driver) = MultiplexedConnection::new_with_config(&info, compat_stream, conn_config).await?;
+        tokio::spawn(async move {
+            driver.await;
+            info!("Redis push driver task finished");
+        });
+        Ok(connection)
+    })
+    .await
+}
+
+/// Opens a RESP2 Pub/Sub connection through an SSH tunnel.
+///
+/// # Arguments
+///
+/// * `config` - Redis server configuration containing SSH and Redis connection details
+///
+/// # Returns
+///
+/// A Pub/Sub connection, already authenticated
+pub async fn open_ssh_tunnel_pubsub(config: &RedisServer) -> Result<PubSub> {
+    let config = config.clone();
+    run_in_tokio(async move {
+        let compat_stream = open_direct_tcpip_stream(&config).await?;
+        let info = redis_connection_info(&config, ProtocolVersion::RESP2);
+        Ok(PubSub::new(&info, compat_stream).await?)
+    })
+    .await
+}
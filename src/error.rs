@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::connection::SshHostKey;
 use snafu::Snafu;
 
 #[derive(Debug, Snafu)]
@@ -32,6 +33,17 @@ pub enum Error {
     Ssh { source: russh::Error },
     #[snafu(display("Key error: {source}"))]
     Key { source: russh::keys::Error },
+    #[snafu(display("Unknown ssh host key of {}:{} ({})", key.host, key.port, key.fingerprint))]
+    UnknownHostKey { key: Box<SshHostKey> },
+    #[snafu(display(
+        "Ssh host key of {host}:{port} has changed ({fingerprint}) and does not match {location}, the connection is refused as it may be intercepted. Remove the old key if the change is expected"
+    ))]
+    HostKeyChanged {
+        host: String,
+        port: u16,
+        fingerprint: String,
+        location: String,
+    },
     #[snafu(display("Http error: {source}"))]
     Http { source: reqwest::Error },
     #[snafu(display("Update error: {message}"))]
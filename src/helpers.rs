@@ -15,23 +15,27 @@
 use std::env;
 
 mod action;
+mod cli;
 pub mod codec;
 mod common;
 mod font;
 mod fs;
 mod protobuf;
+mod redis_key;
 mod string;
 mod time;
 mod updater;
 mod validate;
 
 pub use action::*;
+pub use cli::{format_error, format_reply, split_args};
 pub use common::*;
 pub use font::get_font_family;
 #[cfg(target_os = "macos")]
 pub use fs::get_app_bundle_path;
 pub use fs::{get_home_dir, get_or_create_config_dir, is_app_store_build};
 pub use protobuf::{decode_raw_to_json, is_likely_protobuf};
+pub use redis_key::RedisKey;
 pub use string::*;
 pub use time::unix_ts;
 pub use updater::install_update;
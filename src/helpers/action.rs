@@ -39,6 +39,11 @@ pub enum ServersAction {
     Filter,
 }
 
+#[derive(Clone, Copy, PartialEq, Debug, Deserialize, JsonSchema, Action)]
+pub enum ConsoleAction {
+    ReverseSearch,
+}
+
 #[derive(Clone, Copy, PartialEq, Debug, Deserialize, JsonSchema, Action)]
 pub enum KeyTreeAction {
     SelectAll,
@@ -136,5 +141,6 @@ pub fn new_hot_keys() -> Vec<KeyBinding> {
         KeyBinding::new("secondary-a", KeyTreeAction::SelectAll, None),
         KeyBinding::new("delete", KeyTreeAction::DeleteSelected, Some("KeyTree")),
         KeyBinding::new("backspace", KeyTreeAction::DeleteSelected, Some("KeyTree")),
+        KeyBinding::new("ctrl-r", ConsoleAction::ReverseSearch, Some("ZedisConsole")),
     ]
 }
@@ -0,0 +1,328 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! redis-cli compatible command line helpers.
+//!
+//! This module provides:
+//! - Argument splitting that follows redis-cli quoting and escaping rules
+//! - Reply formatting in redis-cli's terminal style, including RESP3 types
+//!   (maps, sets, attributes, doubles, booleans, big numbers and verbatim strings)
+
+use crate::error::Error;
+use redis::Value;
+use std::fmt::Write;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+fn unbalanced_quotes() -> Error {
+    Error::Invalid {
+        message: "unbalanced quotes in command".to_string(),
+    }
+}
+
+/// A closing quote must be followed by whitespace or the end of the line.
+fn check_closing_quote(bytes: &[u8], index: usize) -> Result<()> {
+    match bytes.get(index + 1) {
+        Some(c) if !c.is_ascii_whitespace() => Err(unbalanced_quotes()),
+        _ => Ok(()),
+    }
+}
+
+fn hex_value(c: Option<&u8>) -> Option<u8> {
+    c.and_then(|c| (*c as char).to_digit(16)).map(|d| d as u8)
+}
+
+/// Splits a command line into arguments using redis-cli quoting rules.
+///
+/// Double quoted arguments support the `\n`, `\r`, `\t`, `\b`, `\a` and `\xNN`
+/// escapes (any other escaped character is taken literally), single quoted
+/// arguments only support `\'`.
+///
+/// # Arguments
+/// * `line` - The command line typed by the user
+///
+/// # Returns
+/// The raw bytes of every argument, or an error for unbalanced quotes.
+pub fn split_args(line: &str) -> Result<Vec<Vec<u8>>> {
+    let bytes = line.as_bytes();
+    let mut args = Vec::new();
+    let mut i = 0;
+    loop {
+        while bytes.get(i).is_some_and(|c| c.is_ascii_whitespace()) {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            return Ok(args);
+        }
+        let mut current = Vec::new();
+        let mut quote = None;
+        while let Some(&c) = bytes.get(i) {
+            match quote {
+                None => match c {
+                    b'"' | b'\'' => quote = Some(c),
+                    c if c.is_ascii_whitespace() => break,
+                    c => current.push(c),
+                },
+                Some(b'"') => match c {
+                    b'\\' => {
+                        if bytes.get(i + 1) == Some(&b'x')
+                            && let Some(hi) = hex_value(bytes.get(i + 2))
+                            && let Some(lo) = hex_value(bytes.get(i + 3))
+                        {
+                            current.push(hi * 16 + lo);
+                            i += 3;
+                        } else if let Some(&next) = bytes.get(i + 1) {
+                            current.push(match next {
+                                b'n' => b'\n',
+                                b'r' => b'\r',
+                                b't' => b'\t',
+                                b'b' => 0x08,
+                                b'a' => 0x07,
+                                other => other,
+                            });
+                            i += 1;
+                        }
+                    }
+                    b'"' => {
+                        check_closing_quote(bytes, i)?;
+                        quote = None;
+                        i += 1;
+                        break;
+                    }
+                    c => current.push(c),
+                },
+                Some(_) => match c {
+                    b'\\' if bytes.get(i + 1) == Some(&b'\'') => {
+                        current.push(b'\'');
+                        i += 1;
+                    }
+                    b'\'' => {
+                        check_closing_quote(bytes, i)?;
+                        quote = None;
+                        i += 1;
+                        break;
+                    }
+                    c => current.push(c),
+                },
+            }
+            i += 1;
+        }
+        if quote.is_some() {
+            return Err(unbalanced_quotes());
+        }
+        args.push(current);
+    }
+}
+
+/// Quotes a bulk string the way redis-cli does, escaping quotes, backslashes,
+/// control characters and invalid UTF-8 bytes.
+pub fn quote_bytes(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() + 2);
+    out.push('"');
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                '\u{7}' => out.push_str("\\a"),
+                '\u{8}' => out.push_str("\\b"),
+                c if c.is_control() => {
+                    let mut buf = [0; 4];
+                    for byte in c.encode_utf8(&mut buf).bytes() {
+                        let _ = write!(out, "\\x{byte:02x}");
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            let _ = write!(out, "\\x{byte:02x}");
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Formats a reply the way redis-cli prints it in a terminal.
+///
+/// Nested aggregates are indented under their index, map entries are shown as
+/// `1# key => value`, set members as `1~ member` and attributes as a `|` prefixed map.
+pub fn format_reply(value: &Value) -> String {
+    let mut out = String::new();
+    write_reply(value, "", &mut out);
+    if out.ends_with('\n') {
+        out.pop();
+    }
+    out
+}
+
+/// Formats an error returned by a command in redis-cli style (`(error) ERR ...`).
+pub fn format_error(error: &Error) -> String {
+    if let Error::Redis { source } = error
+        && let Some(code) = source.code()
+    {
+        return match source.detail() {
+            Some(detail) => format!("(error) {code} {detail}"),
+            None => format!("(error) {code}"),
+        };
+    }
+    format!("(error) {error}")
+}
+
+fn write_reply(value: &Value, prefix: &str, out: &mut String) {
+    match value {
+        Value::Nil => out.push_str("(nil)\n"),
+        Value::Int(i) => {
+            let _ = writeln!(out, "(integer) {i}");
+        }
+        Value::Double(d) => {
+            let _ = writeln!(out, "(double) {d}");
+        }
+        Value::Boolean(b) => {
+            let _ = writeln!(out, "({b})");
+        }
+        Value::BigNumber(n) => {
+            let _ = writeln!(out, "(big number) {n}");
+        }
+        Value::Okay => out.push_str("OK\n"),
+        Value::SimpleString(s) => {
+            out.push_str(s);
+            out.push('\n');
+        }
+        Value::BulkString(bytes) => {
+            out.push_str(&quote_bytes(bytes));
+            out.push('\n');
+        }
+        Value::VerbatimString { text, .. } => {
+            out.push_str(text);
+            if !text.ends_with('\n') {
+                out.push('\n');
+            }
+        }
+        Value::ServerError(e) => {
+            let _ = match e.details() {
+                Some(detail) => writeln!(out, "(error) {} {detail}", e.code()),
+                None => writeln!(out, "(error) {}", e.code()),
+            };
+        }
+        Value::Array(items) | Value::Push { data: items, .. } => {
+            let entries: Vec<_> = items.iter().map(|item| (item, None)).collect();
+            write_aggregate(&entries, ')', "(empty array)", prefix, out);
+        }
+        Value::Set(items) => {
+            let entries: Vec<_> = items.iter().map(|item| (item, None)).collect();
+            write_aggregate(&entries, '~', "(empty set)", prefix, out);
+        }
+        Value::Map(items) => {
+            let entries: Vec<_> = items.iter().map(|(k, v)| (k, Some(v))).collect();
+            write_aggregate(&entries, '#', "(empty hash)", prefix, out);
+        }
+        Value::Attribute { data, attributes } => {
+            let entries: Vec<_> = attributes.iter().map(|(k, v)| (k, Some(v))).collect();
+            out.push('|');
+            write_aggregate(&entries, '#', "(empty attributes)", prefix, out);
+            out.push_str(prefix);
+            write_reply(data, prefix, out);
+        }
+        _ => out.push_str("(unsupported reply)\n"),
+    }
+}
+
+fn write_aggregate(entries: &[(&Value, Option<&Value>)], separator: char, empty: &str, prefix: &str, out: &mut String) {
+    if entries.is_empty() {
+        out.push_str(empty);
+        out.push('\n');
+        return;
+    }
+    let width = entries.len().to_string().len();
+    // Nested replies are aligned after the index column
+    let nested_prefix = format!("{prefix}{}", " ".repeat(width + 2));
+    for (index, (value, map_value)) in entries.iter().enumerate() {
+        // The caller already wrote the prefix for the first entry
+        if index > 0 {
+            out.push_str(prefix);
+        }
+        let _ = write!(out, "{:>width$}{separator} ", index + 1);
+        write_reply(value, &nested_prefix, out);
+        if let Some(map_value) = map_value {
+            out.pop();
+            out.push_str(" => ");
+            write_reply(map_value, &nested_prefix, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_reply, split_args};
+    use redis::Value;
+
+    fn args(line: &str) -> Vec<Vec<u8>> {
+        split_args(line).expect("valid command line")
+    }
+
+    #[test]
+    fn splits_quoted_arguments() {
+        assert_eq!(args("  SET k  v "), vec![b"SET".to_vec(), b"k".to_vec(), b"v".to_vec()]);
+        assert_eq!(args(r#"SET k "hello world""#)[2], b"hello world");
+        assert_eq!(args(r#"SET k "a\"b\n\x41\xff""#)[2], b"a\"b\nA\xff");
+        assert_eq!(args(r"SET k 'it\'s \n'")[2], b"it's \\n");
+        assert_eq!(args(r#"SET k """#)[2], b"");
+        assert_eq!(args("SET 用户 值")[1], "用户".as_bytes());
+        assert!(args("   ").is_empty());
+    }
+
+    #[test]
+    fn rejects_unbalanced_quotes() {
+        assert!(split_args(r#"SET k "abc"#).is_err());
+        assert!(split_args("SET k 'abc").is_err());
+        assert!(split_args(r#"SET k "abc"def"#).is_err());
+    }
+
+    #[test]
+    fn formats_nested_replies() {
+        let value = Value::Array(vec![
+            Value::BulkString(b"a\"b".to_vec()),
+            Value::Int(3),
+            Value::Array(vec![Value::Nil, Value::Double(1.5)]),
+            Value::Array(vec![]),
+        ]);
+        assert_eq!(
+            format_reply(&value),
+            "1) \"a\\\"b\"\n2) (integer) 3\n3) 1) (nil)\n   2) (double) 1.5\n4) (empty array)"
+        );
+        assert_eq!(format_reply(&Value::Okay), "OK");
+        assert_eq!(format_reply(&Value::Boolean(true)), "(true)");
+    }
+
+    #[test]
+    fn formats_resp3_aggregates() {
+        let map = Value::Map(vec![
+            (Value::SimpleString("name".into()), Value::BulkString(b"zedis".to_vec())),
+            (
+                Value::SimpleString("tags".into()),
+                Value::Set(vec![Value::BulkString(b"x".to_vec())]),
+            ),
+        ]);
+        assert_eq!(format_reply(&map), "1# name => \"zedis\"\n2# tags => 1~ \"x\"");
+        let attribute = Value::Attribute {
+            data: Box::new(Value::Int(1)),
+            attributes: vec![(Value::SimpleString("ttl".into()), Value::Int(10))],
+        };
+        assert_eq!(format_reply(&attribute), "|1# ttl => (integer) 10\n(integer) 1");
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Binary-safe Redis key names.
+//!
+//! Redis keys are arbitrary byte strings. The UI identifies keys by an escaped
+//! form in which a backslash is written as `\\` and every byte that is not part
+//! of printable UTF-8 is written as `\xNN`. The escaped form round-trips to the
+//! original bytes, so it can be shown in the key tree, typed by the user and
+//! converted back with [`RedisKey`] whenever a key is sent to Redis.
+
+use bytes::Bytes;
+use gpui::SharedString;
+use redis::{RedisWrite, ToRedisArgs};
+use std::fmt;
+
+/// Bytes that have a special meaning in SCAN MATCH glob patterns
+const GLOB_SPECIAL_BYTES: &[u8] = b"*?[]\\";
+
+/// A Redis key backed by its raw bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RedisKey(Bytes);
+
+fn push_escaped_byte(out: &mut String, byte: u8) {
+    out.push_str(&format!("\\x{byte:02x}"));
+}
+
+/// Parses the two hex digits following `\x`.
+fn parse_hex_byte(hi: Option<char>, lo: Option<char>) -> Option<u8> {
+    let hi = hi?.to_digit(16)?;
+    let lo = lo?.to_digit(16)?;
+    Some((hi * 16 + lo) as u8)
+}
+
+/// Decodes the escaped form; `on_byte` pushes a `\xNN` byte, `on_backslash` a `\\` pair.
+fn unescape(input: &str, on_byte: impl Fn(&mut Vec<u8>, u8), on_backslash: impl Fn(&mut Vec<u8>)) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.peek() {
+            Some('\\') => {
+                chars.next();
+                on_backslash(&mut out);
+            }
+            Some('x') => {
+                let mut lookahead = chars.clone();
+                lookahead.next();
+                match parse_hex_byte(lookahead.next(), lookahead.next()) {
+                    Some(byte) => {
+                        chars = lookahead;
+                        on_byte(&mut out, byte);
+                    }
+                    // Not a valid escape, keep the backslash as typed
+                    None => out.push(b'\\'),
+                }
+            }
+            _ => out.push(b'\\'),
+        }
+    }
+    out
+}
+
+impl RedisKey {
+    /// Parses a key from its escaped form. Unknown escape sequences are kept literally.
+    pub fn parse(input: &str) -> Self {
+        Self(unescape(input, |out, byte| out.push(byte), |out| out.push(b'\\')).into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Escapes raw key bytes for display.
+    pub fn escape(bytes: &[u8]) -> String {
+        let mut out = String::with_capacity(bytes.len());
+        for chunk in bytes.utf8_chunks() {
+            for c in chunk.valid().chars() {
+                if c == '\\' {
+                    out.push_str("\\\\");
+                } else if c.is_control() {
+                    let mut buf = [0; 4];
+                    for byte in c.encode_utf8(&mut buf).bytes() {
+                        push_escaped_byte(&mut out, byte);
+                    }
+                } else {
+                    out.push(c);
+                }
+            }
+            for byte in chunk.invalid() {
+                push_escaped_byte(&mut out, *byte);
+            }
+        }
+        out
+    }
+
+    /// Returns the escaped form used to identify the key in the UI.
+    pub fn escaped(&self) -> SharedString {
+        Self::escape(&self.0).into()
+    }
+
+    /// Converts a SCAN MATCH pattern typed in escaped form into raw bytes.
+    ///
+    /// `\xNN` escapes are decoded (and glob-escaped when they produce a glob special
+    /// character), while `\\` and other backslash sequences are passed through so they
+    /// keep their glob meaning.
+    pub fn pattern(input: &str) -> Vec<u8> {
+        unescape(
+            input,
+            |out, byte| {
+                if GLOB_SPECIAL_BYTES.contains(&byte) {
+                    out.push(b'\\');
+                }
+                out.push(byte);
+            },
+            |out| out.extend_from_slice(b"\\\\"),
+        )
+    }
+}
+
+impl<T: AsRef<str> + ?Sized> From<&T> for RedisKey {
+    fn from(value: &T) -> Self {
+        Self::parse(value.as_ref())
+    }
+}
+
+impl fmt::Display for RedisKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Self::escape(&self.0))
+    }
+}
+
+impl ToRedisArgs for RedisKey {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RedisKey;
+
+    #[test]
+    fn escapes_binary_and_control_bytes() {
+        assert_eq!(RedisKey::escape(b"user:1"), "user:1");
+        assert_eq!(RedisKey::escape("用户:1".as_bytes()), "用户:1");
+        assert_eq!(RedisKey::escape(b"a\xff\x00b"), "a\\xff\\x00b");
+        assert_eq!(RedisKey::escape(b"a\\b\n"), "a\\\\b\\x0a");
+    }
+
+    #[test]
+    fn round_trips_escaped_keys() {
+        let samples: [&[u8]; 4] = [
+            b"plain",
+            b"\x01\x02packed\xfe",
+            b"back\\slash\\x41",
+            "混合\x7f".as_bytes(),
+        ];
+        for bytes in samples {
+            let escaped = RedisKey::escape(bytes);
+            assert_eq!(RedisKey::parse(&escaped).as_bytes(), bytes, "escaped: {escaped}");
+        }
+    }
+
+    #[test]
+    fn parses_typed_escapes_leniently() {
+        assert_eq!(RedisKey::parse("\\x41\\X41").as_bytes(), b"A\\X41");
+        assert_eq!(RedisKey::parse("a\\xZ1").as_bytes(), b"a\\xZ1");
+        assert_eq!(RedisKey::parse("tail\\").as_bytes(), b"tail\\");
+    }
+
+    #[test]
+    fn converts_scan_patterns() {
+        assert_eq!(RedisKey::pattern("*\\xff*"), b"*\xff*");
+        assert_eq!(RedisKey::pattern("\\x2a"), b"\\*");
+        assert_eq!(RedisKey::pattern("a\\\\b\\*"), b"a\\\\b\\*");
+    }
+}
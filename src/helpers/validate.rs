@@ -12,6 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+
+/// Parses a TTL given in seconds or as a human readable duration, e.g. `1h 30m`.
+pub fn parse_ttl(s: &str) -> Option<Duration> {
+    if let Ok(secs) = s.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    humantime::parse_duration(s).ok()
+}
+
 pub fn validate_ttl(s: &str) -> bool {
     if s.is_empty() || s.parse::<usize>().is_ok() {
         return true;
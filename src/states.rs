@@ -20,21 +20,43 @@ pub mod update;
 
 pub use app::*;
 pub use i18n::i18n_about;
+pub use i18n::i18n_bulk;
+pub use i18n::i18n_clients;
 pub use i18n::i18n_common;
+pub use i18n::i18n_console;
+pub use i18n::i18n_diagnostics;
 pub use i18n::i18n_editor;
 pub use i18n::i18n_hash_editor;
+pub use i18n::i18n_hot_keys;
 pub use i18n::i18n_key_tree;
 pub use i18n::i18n_kv_table;
 pub use i18n::i18n_list_editor;
+pub use i18n::i18n_memory;
+pub use i18n::i18n_metrics;
+pub use i18n::i18n_pubsub;
 pub use i18n::i18n_servers;
 pub use i18n::i18n_set_editor;
 pub use i18n::i18n_settings;
 pub use i18n::i18n_sidebar;
 pub use i18n::i18n_status_bar;
+pub use i18n::i18n_stream_editor;
 pub use i18n::i18n_update;
+pub use i18n::i18n_vector_set_editor;
 pub use i18n::i18n_zset_editor;
 pub use server::ErrorMessage;
+pub use server::ServerPanel;
 pub use server::ZedisServerState;
+pub use server::clients::{ClientSort, filter_clients};
+pub use server::console::{
+    ConsoleHistory, common_prefix, complete, execute_console_command, find_command_doc, next_history_sequence,
+    save_console_history,
+};
+pub use server::diagnostics::{SlowLogSort, filter_slowlog};
 pub use server::event::ServerEvent;
 pub use server::event::ServerTask;
+pub use server::hot_keys::{DEFAULT_HOT_KEYS, HotKeyCategory, HotKeySort, filter_hot_keys};
+pub use server::metrics::{METRICS_INTERVALS, Metric};
+pub use server::pubsub::decode_pubsub_payload;
+pub use server::stream::StreamTrimStrategy;
 pub use server::value::*;
+pub use server::vector_set::{VectorSetQuery, parse_vector};
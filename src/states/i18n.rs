@@ -72,6 +72,56 @@ pub fn i18n_hash_editor<'a>(cx: &'a App, key: &'a str) -> SharedString {
     t!(format!("hash_editor.{key}"), locale = locale).into()
 }
 
+pub fn i18n_stream_editor<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("stream_editor.{key}"), locale = locale).into()
+}
+
+pub fn i18n_vector_set_editor<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("vector_set_editor.{key}"), locale = locale).into()
+}
+
+pub fn i18n_console<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("console.{key}"), locale = locale).into()
+}
+
+pub fn i18n_pubsub<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("pubsub.{key}"), locale = locale).into()
+}
+
+pub fn i18n_metrics<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("metrics.{key}"), locale = locale).into()
+}
+
+pub fn i18n_diagnostics<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("diagnostics.{key}"), locale = locale).into()
+}
+
+pub fn i18n_bulk<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("bulk.{key}"), locale = locale).into()
+}
+
+pub fn i18n_clients<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("clients.{key}"), locale = locale).into()
+}
+
+pub fn i18n_hot_keys<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("hot_keys.{key}"), locale = locale).into()
+}
+
+pub fn i18n_memory<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("memory.{key}"), locale = locale).into()
+}
+
 pub fn i18n_settings<'a>(cx: &'a App, key: &'a str) -> SharedString {
     let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
     t!(format!("settings.{key}"), locale = locale).into()
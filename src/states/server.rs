@@ -13,12 +13,14 @@
 // limitations under the License.
 
 use crate::connection::{
-    AuthSource, QueryMode, RedisClientDescription, RedisServer, get_connection_manager, save_servers,
+    AuthSource, ClusterNodeInfo, HotKeyReport, MemoryReport, QueryMode, RedisClientDescription, RedisServer,
+    close_rdb_file, get_connection_manager, is_rdb_file, open_rdb_file, save_servers,
 };
 use crate::error::Error;
 use crate::helpers::unix_ts;
 use crate::states::server::event::{ServerEvent, ServerTask};
 use crate::states::server::stat::RedisInfo;
+use crate::states::{Route, ZedisGlobalStore};
 use ahash::AHashMap;
 use ahash::AHashSet;
 use chrono::Local;
@@ -27,27 +29,44 @@ use gpui::prelude::*;
 use parking_lot::RwLock;
 use protobuf::ProtobufSchema;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
 use tracing::{debug, error};
 use uuid::Uuid;
 use value::{KeyType, RedisValue, RedisValueData};
 
 pub mod backup;
+pub mod bulk;
+pub mod clients;
+pub mod console;
+pub mod diagnostics;
 pub mod event;
 pub mod hash;
+pub mod host_keys;
+pub mod hot_keys;
 pub mod key;
 pub mod list;
+pub mod memory;
+pub mod metrics;
 pub mod protobuf;
+pub mod pubsub;
 pub mod set;
 pub mod stat;
+pub mod stream;
+pub mod stream_group;
 pub mod string;
 pub mod value;
+pub mod vector_set;
 pub mod zset;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
 // Constants for state management
 const MAX_ERROR_MESSAGES: usize = 10; // Maximum error messages to keep in memory
+/// Error of the write tasks refused on a read-only replica
+const READONLY_MESSAGE: &str = "The server is a replica browsed read-only, writes are disabled";
 /// Error message with categorization and timestamp
 #[derive(Debug, Clone)]
 pub struct ErrorMessage {
@@ -72,6 +91,28 @@ pub enum RedisServerStatus {
     Loading,
 }
 
+/// Panel shown next to the key tree
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ServerPanel {
+    /// Value editor of the selected key
+    #[default]
+    Editor,
+    /// redis-cli style console
+    Terminal,
+    /// Pub/Sub monitor and publisher
+    PubSub,
+    /// Slow log and latency diagnostics
+    Diagnostics,
+    /// Live metrics dashboard
+    Metrics,
+    /// Connected clients
+    Clients,
+    /// Memory analyzer
+    Memory,
+    /// Hottest and coldest keys
+    HotKeys,
+}
+
 /// Main state management for Redis server operations
 ///
 /// This struct manages:
@@ -84,8 +125,8 @@ pub enum RedisServerStatus {
 pub struct ZedisServerState {
     redis_info: Option<RedisInfo>,
 
-    /// Whether the terminal is open
-    terminal: bool,
+    /// Panel shown next to the key tree
+    panel: ServerPanel,
 
     /// Currently selected server id
     server_id: SharedString,
@@ -99,6 +140,9 @@ pub struct ZedisServerState {
     /// Query mode (All/Prefix/Exact) for key filtering
     query_mode: QueryMode,
 
+    /// Only keys of this type are scanned, every type when `None`
+    key_type_filter: Option<KeyType>,
+
     /// Whether to soft wrap the editor
     soft_wrap: bool,
 
@@ -112,6 +156,8 @@ pub struct ZedisServerState {
     nodes: (usize, usize),
     /// Description of the nodes
     nodes_description: Arc<RedisClientDescription>,
+    /// Nodes of the cluster, empty for other server types
+    cluster_nodes: Arc<Vec<ClusterNodeInfo>>,
 
     /// Redis server version string
     version: SharedString,
@@ -177,6 +223,30 @@ pub struct ZedisServerState {
 
     /// Set of currently opened server IDs (shown in sidebar)
     opened_servers: AHashSet<SharedString>,
+
+    // ===== Console state =====
+    /// Command documentation used for console hints and completion
+    command_docs: Option<Arc<Vec<console::CommandDoc>>>,
+    /// Active Pub/Sub channels loaded by the Pub/Sub panel
+    pubsub_channels: Option<Arc<Vec<pubsub::PubSubChannel>>>,
+    /// Slow log and latency diagnostics of every master
+    diagnostics: Option<Arc<diagnostics::ServerDiagnostics>>,
+    diagnostics_loading: bool,
+    /// Rolling history of server metrics collected by the dashboard
+    metrics: Arc<metrics::MetricsHistory>,
+    /// Clients of every node loaded by the clients panel
+    clients: Option<Arc<Vec<clients::ClientInfo>>>,
+    clients_loading: bool,
+    /// Whether no-evict mode was turned on for the Zedis connections
+    client_no_evict: bool,
+    /// Cancellation flag of the running bulk operation
+    bulk_cancel: Option<Arc<AtomicBool>>,
+    /// Report of the last memory analysis
+    memory_report: Option<Arc<MemoryReport>>,
+    memory_analyzing: bool,
+    /// Rankings of the last hot key detection
+    hot_key_report: Option<Arc<HotKeyReport>>,
+    hot_keys_analyzing: bool,
 }
 
 impl ZedisServerState {
@@ -214,12 +284,29 @@ impl ZedisServerState {
         self.keys.clear();
         self.key_tree_id = SharedString::default();
         self.nodes_description = Arc::new(RedisClientDescription::default());
+        self.cluster_nodes = Arc::new(vec![]);
         self.dbsize = None;
         self.key = None;
         self.redis_info = None;
         self.value = None;
         self.reset_scan();
-        self.terminal = false;
+        self.panel = ServerPanel::Editor;
+        self.command_docs = None;
+        self.pubsub_channels = None;
+        self.diagnostics = None;
+        self.diagnostics_loading = false;
+        self.metrics = Arc::new(metrics::MetricsHistory::default());
+        self.clients = None;
+        self.clients_loading = false;
+        self.client_no_evict = false;
+        self.memory_report = None;
+        self.memory_analyzing = false;
+        self.hot_key_report = None;
+        self.hot_keys_analyzing = false;
+        // Stop the bulk operation of the previous server
+        if let Some(cancelled) = self.bulk_cancel.take() {
+            cancelled.store(true, Ordering::Relaxed);
+        }
         // Clear protobuf schema when switching servers
         self.protobuf_schema.clear();
         // Clear preset credentials
@@ -234,11 +321,13 @@ impl ZedisServerState {
     fn extend_keys(&mut self, keys: Vec<SharedString>) {
         self.keys.reserve(keys.len());
         let mut insert_count = 0;
+        // Keys scanned with a type filter are known to be of that type
+        let key_type = self.key_type_filter.unwrap_or_default();
 
         for key in keys {
             self.keys.entry(key).or_insert_with(|| {
                 insert_count += 1;
-                KeyType::Unknown
+                key_type
             });
         }
 
@@ -275,6 +364,8 @@ impl ZedisServerState {
     /// 3. Captures errors and adds them to error history
     /// 4. Calls the callback with the result
     ///
+    /// Write tasks fail right away on a read-only replica, without reaching the server.
+    ///
     /// # Type Parameters
     /// * `T` - The success return type of the task
     /// * `Fut` - The future type returned by the task closure
@@ -295,6 +386,14 @@ impl ZedisServerState {
         Fut: Future<Output = Result<T>> + Send + 'static,
     {
         cx.emit(ServerEvent::TaskStarted(name.clone()));
+        if name.is_write() && self.is_readonly() {
+            let e = Error::Invalid {
+                message: READONLY_MESSAGE.to_string(),
+            };
+            self.add_error_message(name.as_str().to_string(), e.to_string(), cx);
+            callback(self, Err(e), cx);
+            return;
+        }
         debug!(name = name.as_str(), "Spawning background task");
 
         cx.spawn(async move |handle, cx| {
@@ -308,6 +407,11 @@ impl ZedisServerState {
                     let message = format!("{} failed", name.as_str());
                     error!(error = %e, message);
                     this.add_error_message(name.as_str().to_string(), e.to_string(), cx);
+                    // Ask to trust the key of the ssh server, whatever the connection
+                    // (standalone, cluster or sentinel) it was met on
+                    if let Error::UnknownHostKey { key } = e {
+                        cx.emit(ServerEvent::HostKeyPrompt(Arc::new(key.as_ref().clone())));
+                    }
                 }
                 callback(this, result, cx);
             })
@@ -413,19 +517,38 @@ impl ZedisServerState {
             KeyType::Hash => {
                 self.filter_hash_value(keyword, cx);
             }
+            KeyType::Stream => {
+                self.filter_stream_value(keyword, cx);
+            }
+            KeyType::Vectorset => {
+                self.filter_vector_set_value(keyword, cx);
+            }
             _ => {}
         }
     }
 
     // ===== Public accessor methods =====
 
+    pub fn panel(&self) -> ServerPanel {
+        self.panel
+    }
+
     pub fn is_terminal(&self) -> bool {
-        self.terminal
+        self.panel == ServerPanel::Terminal
     }
 
     pub fn toggle_terminal(&mut self, cx: &mut Context<Self>) {
-        self.terminal = !self.terminal;
-        cx.emit(ServerEvent::TerminalToggled(self.terminal));
+        self.toggle_panel(ServerPanel::Terminal, cx);
+    }
+
+    /// Shows the panel, or goes back to the value editor when it is already shown.
+    pub fn toggle_panel(&mut self, panel: ServerPanel, cx: &mut Context<Self>) {
+        self.panel = if self.panel == panel {
+            ServerPanel::Editor
+        } else {
+            panel
+        };
+        cx.emit(ServerEvent::PanelToggled(self.panel));
     }
 
     /// Check if the server is currently busy with an operation
@@ -465,6 +588,20 @@ impl ZedisServerState {
         self.query_mode
     }
 
+    /// Set the type of the keys listed by the key browser and scan again
+    pub fn set_key_type_filter(&mut self, key_type: Option<KeyType>, cx: &mut Context<Self>) {
+        self.key_type_filter = key_type;
+        self.update_and_save_server_config(ServerTask::UpdateServerKeyTypeFilter, cx, move |server| {
+            server.key_type_filter = key_type.map(|key_type| key_type.type_name().to_string());
+        });
+        self.handle_filter(self.keyword.clone(), cx);
+    }
+
+    /// Get the type of the keys listed by the key browser, `None` for every type
+    pub fn key_type_filter(&self) -> Option<KeyType> {
+        self.key_type_filter
+    }
+
     /// Check if the current scan has completed
     pub fn scan_completed(&self) -> bool {
         self.scan_completed
@@ -498,6 +635,14 @@ impl ZedisServerState {
     pub fn nodes_description(&self) -> Arc<RedisClientDescription> {
         self.nodes_description.clone()
     }
+    /// Whether the server is a replica browsed read-only, which refuses writes
+    pub fn is_readonly(&self) -> bool {
+        self.nodes_description.readonly
+    }
+    /// Get the nodes of the cluster, empty for other server types
+    pub fn cluster_nodes(&self) -> Arc<Vec<ClusterNodeInfo>> {
+        self.cluster_nodes.clone()
+    }
 
     /// Get the Redis server version string
     pub fn version(&self) -> &str {
@@ -641,6 +786,15 @@ impl ZedisServerState {
             self.reset();
         }
 
+        // Opened RDB files are forgotten once closed
+        if is_rdb_file(server_id) {
+            close_rdb_file(server_id);
+            get_connection_manager().remove_client(server_id);
+            if let Some(servers) = self.servers.as_mut() {
+                servers.retain(|server| server.id != server_id);
+            }
+        }
+
         cx.emit(ServerEvent::ServerListUpdated);
         cx.notify();
     }
@@ -669,6 +823,31 @@ impl ZedisServerState {
         );
     }
 
+    /// Open an RDB file read-only and browse it like a server
+    ///
+    /// The snapshot is listed with the servers until it is closed, it is never saved.
+    pub fn open_rdb_file(&mut self, path: String, cx: &mut Context<Self>) {
+        self.spawn(
+            ServerTask::OpenRdbFile,
+            move || async move { open_rdb_file(&path).await },
+            move |this, result, cx| {
+                if let Ok(server) = result {
+                    let server_id: SharedString = server.id.clone().into();
+                    this.servers.get_or_insert_default().push(server);
+                    cx.emit(ServerEvent::ServerListUpdated);
+                    this.select(server_id, 0, vec![], cx);
+                    cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
+                        store.update(cx, |state, cx| {
+                            state.go_to(Route::Editor, cx);
+                        });
+                    });
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
     /// Add new server or update existing server configuration
     ///
     /// # Arguments
@@ -740,7 +919,7 @@ impl ZedisServerState {
             self.opened_servers.insert(server_id.clone());
             // Save preset credentials for subsequent operations
             self.preset_credentials = preset_credentials.clone();
-            let (query_mode, soft_wrap) = self
+            let (query_mode, soft_wrap, key_type_filter) = self
                 .server(server_id.as_str())
                 .map(|server_config| {
                     let mode = server_config
@@ -751,12 +930,19 @@ impl ZedisServerState {
 
                     let wrap = server_config.soft_wrap.unwrap_or(true);
 
+                    let key_type = server_config
+                        .key_type_filter
+                        .as_deref()
+                        .map(KeyType::from)
+                        .filter(|key_type| *key_type != KeyType::Unknown);
+
                     // 返回一个元组，包含所有需要更新的值
-                    (mode, wrap)
+                    (mode, wrap, key_type)
                 })
-                .unwrap_or((QueryMode::All, true));
+                .unwrap_or((QueryMode::All, true, None));
             self.query_mode = query_mode;
             self.soft_wrap = soft_wrap;
+            self.key_type_filter = key_type_filter;
 
             debug!(server_id = self.server_id.as_str(), "Selecting server");
             cx.emit(ServerEvent::ServerSelected(server_id, db));
@@ -787,11 +973,12 @@ impl ZedisServerState {
                     let version = client.version().to_string();
                     let nodes = client.nodes();
                     let nodes_description = client.nodes_description();
+                    let cluster_nodes = client.cluster_nodes();
                     let supports_db_selection = client.supports_db_selection();
                     Ok((
                         dbsize,
                         nodes,
-                        nodes_description,
+                        (nodes_description, cluster_nodes),
                         version,
                         supports_db_selection,
                         auth_source,
@@ -804,11 +991,19 @@ impl ZedisServerState {
                     }
 
                     // Update metadata if successful
-                    if let Ok((dbsize, nodes, nodes_description, version, supports_db_selection, auth_source)) = result
+                    if let Ok((
+                        dbsize,
+                        nodes,
+                        (nodes_description, cluster_nodes),
+                        version,
+                        supports_db_selection,
+                        auth_source,
+                    )) = result
                     {
                         this.dbsize = Some(dbsize);
                         this.nodes = nodes;
                         this.nodes_description = Arc::new(nodes_description);
+                        this.cluster_nodes = Arc::new(cluster_nodes);
                         this.version = version.into();
                         this.supports_db_selection = supports_db_selection;
 
@@ -0,0 +1,491 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bulk key operations.
+//!
+//! Operations on every key matching a pattern, scanned on every master node, or on the
+//! selected keys. They run one at a time, report their progress through a channel and
+//! can be cancelled between two batches.
+
+use super::{READONLY_MESSAGE, ServerTask, ZedisServerState};
+use crate::{
+    connection::{BulkKeyProgress, BulkKeys, KeyConflictPolicy, KeyExportFormat, TtlAction, get_connection_manager},
+    states::{NotificationAction, ServerEvent, ZedisGlobalStore, i18n_bulk},
+};
+use futures::{StreamExt, channel::mpsc::UnboundedReceiver};
+use gpui::{SharedString, prelude::*};
+use rust_i18n::t;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+impl ZedisServerState {
+    /// Whether a bulk operation is running.
+    pub fn is_bulk_running(&self) -> bool {
+        self.bulk_cancel.is_some()
+    }
+
+    /// Asks the running bulk operation to stop after its current batch.
+    pub fn cancel_bulk_operation(&mut self, cx: &mut Context<Self>) {
+        if let Some(cancelled) = &self.bulk_cancel {
+            cancelled.store(true, Ordering::Relaxed);
+            cx.notify();
+        }
+    }
+
+    /// Registers a new bulk operation, `None` when another one is still running.
+    pub(super) fn start_bulk_operation(&mut self, cx: &mut Context<Self>) -> Option<Arc<AtomicBool>> {
+        if self.bulk_cancel.is_some() {
+            let message = i18n_bulk(cx, "already_running");
+            cx.emit(ServerEvent::Notification(NotificationAction::new_warning(message)));
+            return None;
+        }
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.bulk_cancel = Some(cancelled.clone());
+        cx.notify();
+        Some(cancelled)
+    }
+
+    pub(super) fn watch_bulk_progress(&self, mut rx: UnboundedReceiver<BulkKeyProgress>, cx: &mut Context<Self>) {
+        cx.spawn(async move |handle, cx| {
+            while let Some(progress) = rx.next().await {
+                let _ = handle.update(cx, |_, cx| {
+                    cx.emit(ServerEvent::BulkKeyProgress(progress));
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Counts the keys matching the pattern without deleting them (dry run).
+    ///
+    /// Emits `KeyDeletePreviewed` with the count and a sample of the keys, so the
+    /// deletion can be confirmed.
+    pub fn preview_delete_by_pattern(&mut self, pattern: SharedString, cx: &mut Context<Self>) {
+        self.run_delete_by_pattern(pattern, true, None, cx);
+    }
+
+    /// Deletes every key matching the pattern.
+    ///
+    /// # Arguments
+    /// * `pattern` - SCAN MATCH pattern
+    /// * `total` - Keys counted by the dry run, used to report the progress
+    pub fn delete_by_pattern(&mut self, pattern: SharedString, total: usize, cx: &mut Context<Self>) {
+        self.run_delete_by_pattern(pattern, false, Some(total), cx);
+    }
+
+    fn run_delete_by_pattern(
+        &mut self,
+        pattern: SharedString,
+        dry_run: bool,
+        total: Option<usize>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(cancelled) = self.start_bulk_operation(cx) else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let preset_credentials = self.preset_credentials.clone();
+        let (progress_tx, progress_rx) = futures::channel::mpsc::unbounded();
+        self.watch_bulk_progress(progress_rx, cx);
+        let task = if dry_run {
+            ServerTask::PreviewDeleteByPattern
+        } else {
+            ServerTask::DeleteByPattern
+        };
+
+        self.spawn(
+            task,
+            move || async move {
+                get_connection_manager()
+                    .delete_keys_by_pattern(
+                        &server_id,
+                        db,
+                        preset_credentials,
+                        &pattern,
+                        dry_run,
+                        total,
+                        Some(progress_tx),
+                        cancelled,
+                    )
+                    .await
+            },
+            move |this, result, cx| {
+                this.bulk_cancel = None;
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                match result {
+                    Ok(summary) if summary.dry_run => {
+                        if summary.cancelled {
+                            let message = i18n_bulk(cx, "cancelled");
+                            cx.emit(ServerEvent::Notification(NotificationAction::new_warning(message)));
+                        } else if summary.matched == 0 {
+                            let message = i18n_bulk(cx, "no_matching_keys");
+                            cx.emit(ServerEvent::Notification(NotificationAction::new_info(message)));
+                        }
+                        cx.emit(ServerEvent::KeyDeletePreviewed(Arc::new(summary)));
+                    }
+                    Ok(summary) => {
+                        let key = if summary.cancelled {
+                            "bulk.delete_cancelled"
+                        } else {
+                            "bulk.deleted"
+                        };
+                        let message = t!(key, count = summary.deleted, locale = locale).to_string();
+                        let action = if summary.cancelled {
+                            NotificationAction::new_warning(message.into())
+                        } else {
+                            NotificationAction::new_success(message.into())
+                        };
+                        cx.emit(ServerEvent::KeysDeleted(summary.deleted));
+                        cx.emit(ServerEvent::Notification(action));
+                        // The deleted keys can be anywhere in the tree, so the keys are reloaded
+                        let keyword = this.keyword.clone();
+                        this.scan(keyword, cx);
+                    }
+                    Err(_) => {}
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Sets or removes the TTL of the keys matching a pattern or of explicit keys.
+    ///
+    /// Emits `KeysTtlUpdated` with a summary, including the keys that no longer existed.
+    pub fn update_keys_ttl(&mut self, keys: BulkKeys, action: TtlAction, cx: &mut Context<Self>) {
+        let Some(cancelled) = self.start_bulk_operation(cx) else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let preset_credentials = self.preset_credentials.clone();
+        let (progress_tx, progress_rx) = futures::channel::mpsc::unbounded();
+        self.watch_bulk_progress(progress_rx, cx);
+
+        self.spawn(
+            ServerTask::UpdateKeysTtl,
+            move || async move {
+                get_connection_manager()
+                    .update_keys_ttl(
+                        &server_id,
+                        db,
+                        preset_credentials,
+                        &keys,
+                        action,
+                        Some(progress_tx),
+                        cancelled,
+                    )
+                    .await
+            },
+            move |this, result, cx| {
+                this.bulk_cancel = None;
+                if let Ok(summary) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let key = if summary.cancelled {
+                        "bulk.ttl_cancelled"
+                    } else {
+                        "bulk.ttl_updated"
+                    };
+                    let message = t!(
+                        key,
+                        updated = summary.updated,
+                        skipped = summary.skipped,
+                        missing = summary.missing,
+                        locale = locale
+                    )
+                    .to_string();
+                    let action = if summary.cancelled || summary.missing > 0 {
+                        NotificationAction::new_warning(message.into())
+                    } else {
+                        NotificationAction::new_success(message.into())
+                    };
+                    cx.emit(ServerEvent::Notification(action));
+                    cx.emit(ServerEvent::KeysTtlUpdated(Arc::new(summary)));
+                    // The TTL shown for the current key may have changed
+                    if let Some(key) = this.key.clone() {
+                        this.select_key(key, cx);
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Copies the keys matching a pattern or explicit keys to another database or server.
+    ///
+    /// # Arguments
+    /// * `target_server_id` - Configured server receiving the keys, may be the current one
+    /// * `target_db` - Database receiving the keys
+    /// * `policy` - How keys already on the target are handled
+    pub fn copy_keys(
+        &mut self,
+        keys: BulkKeys,
+        target_server_id: SharedString,
+        target_db: usize,
+        policy: KeyConflictPolicy,
+        cx: &mut Context<Self>,
+    ) {
+        if target_server_id == self.server_id && self.is_readonly() {
+            self.add_error_message(
+                ServerTask::CopyKeys.as_str().to_string(),
+                READONLY_MESSAGE.to_string(),
+                cx,
+            );
+            return;
+        }
+        let Some(cancelled) = self.start_bulk_operation(cx) else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let preset_credentials = self.preset_credentials.clone();
+        let (progress_tx, progress_rx) = futures::channel::mpsc::unbounded();
+        self.watch_bulk_progress(progress_rx, cx);
+
+        self.spawn(
+            ServerTask::CopyKeys,
+            move || async move {
+                get_connection_manager()
+                    .copy_keys(
+                        &server_id,
+                        db,
+                        preset_credentials,
+                        &keys,
+                        &target_server_id,
+                        target_db,
+                        policy,
+                        Some(progress_tx),
+                        cancelled,
+                    )
+                    .await
+            },
+            move |this, result, cx| {
+                this.bulk_cancel = None;
+                if let Ok(summary) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let key = if summary.cancelled {
+                        "bulk.copy_cancelled"
+                    } else {
+                        "bulk.copied"
+                    };
+                    let mut message = t!(
+                        key,
+                        copied = summary.copied,
+                        renamed = summary.renamed,
+                        skipped = summary.skipped,
+                        missing = summary.missing,
+                        failed = summary.failed,
+                        locale = locale
+                    )
+                    .to_string();
+                    if let Some(error) = &summary.first_error {
+                        message = format!("{message}\n{error}");
+                    }
+                    let action = if summary.failed > 0 {
+                        NotificationAction::new_error(message.into())
+                    } else if summary.cancelled || summary.missing > 0 {
+                        NotificationAction::new_warning(message.into())
+                    } else {
+                        NotificationAction::new_success(message.into())
+                    };
+                    cx.emit(ServerEvent::Notification(action));
+                    cx.emit(ServerEvent::KeysCopied);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Exports the keys matching a pattern or explicit keys with their decoded contents.
+    pub fn export_keys_logical(
+        &mut self,
+        keys: BulkKeys,
+        file_path: String,
+        format: KeyExportFormat,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(cancelled) = self.start_bulk_operation(cx) else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let preset_credentials = self.preset_credentials.clone();
+        let (progress_tx, progress_rx) = futures::channel::mpsc::unbounded();
+        self.watch_bulk_progress(progress_rx, cx);
+
+        self.spawn(
+            ServerTask::ExportKeysLogical,
+            move || async move {
+                get_connection_manager()
+                    .export_keys_logical(
+                        &server_id,
+                        db,
+                        preset_credentials,
+                        &keys,
+                        &file_path,
+                        format,
+                        Some(progress_tx),
+                        cancelled,
+                    )
+                    .await
+            },
+            move |this, result, cx| {
+                this.bulk_cancel = None;
+                if let Ok(summary) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!(
+                        "bulk.exported",
+                        count = summary.exported,
+                        path = summary.file_path,
+                        unsupported = summary.unsupported,
+                        failed = summary.failed,
+                        locale = locale
+                    )
+                    .to_string();
+                    let action = if summary.cancelled || summary.unsupported > 0 || summary.failed > 0 {
+                        NotificationAction::new_warning(message.into())
+                    } else {
+                        NotificationAction::new_success(message.into())
+                    };
+                    cx.emit(ServerEvent::KeysExported(summary.exported));
+                    cx.emit(ServerEvent::Notification(action));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Recreates the keys of a JSON or NDJSON export with native commands.
+    ///
+    /// # Arguments
+    /// * `file_path` - Export written by `export_keys_logical`
+    /// * `policy` - How keys that already exist are handled
+    pub fn import_keys_json(&mut self, file_path: String, policy: KeyConflictPolicy, cx: &mut Context<Self>) {
+        let Some(cancelled) = self.start_bulk_operation(cx) else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let preset_credentials = self.preset_credentials.clone();
+        let (progress_tx, progress_rx) = futures::channel::mpsc::unbounded();
+        self.watch_bulk_progress(progress_rx, cx);
+
+        self.spawn(
+            ServerTask::ImportKeysJson,
+            move || async move {
+                get_connection_manager()
+                    .import_keys_json(
+                        &server_id,
+                        db,
+                        preset_credentials,
+                        &file_path,
+                        policy,
+                        Some(progress_tx),
+                        cancelled,
+                    )
+                    .await
+            },
+            move |this, result, cx| {
+                this.bulk_cancel = None;
+                if let Ok(summary) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let mut message = t!(
+                        "bulk.json_imported",
+                        imported = summary.imported,
+                        renamed = summary.renamed,
+                        skipped = summary.skipped,
+                        failed = summary.failed,
+                        locale = locale
+                    )
+                    .to_string();
+                    if let Some(error) = &summary.first_error {
+                        message = format!("{message}\n{error}");
+                    }
+                    let action = if summary.failed > 0 {
+                        NotificationAction::new_error(message.into())
+                    } else if summary.cancelled {
+                        NotificationAction::new_warning(message.into())
+                    } else {
+                        NotificationAction::new_success(message.into())
+                    };
+                    cx.emit(ServerEvent::KeysImported(summary.imported, summary.failed));
+                    cx.emit(ServerEvent::Notification(action));
+                    let keyword = this.keyword.clone();
+                    this.scan(keyword, cx);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Runs a script of Redis commands, reporting the lines that failed.
+    pub fn import_keys_script(&mut self, file_path: String, cx: &mut Context<Self>) {
+        let Some(cancelled) = self.start_bulk_operation(cx) else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let preset_credentials = self.preset_credentials.clone();
+        let (progress_tx, progress_rx) = futures::channel::mpsc::unbounded();
+        self.watch_bulk_progress(progress_rx, cx);
+
+        self.spawn(
+            ServerTask::ImportKeysScript,
+            move || async move {
+                get_connection_manager()
+                    .import_keys_script(
+                        &server_id,
+                        db,
+                        preset_credentials,
+                        &file_path,
+                        Some(progress_tx),
+                        cancelled,
+                    )
+                    .await
+            },
+            move |this, result, cx| {
+                this.bulk_cancel = None;
+                if let Ok(summary) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!(
+                        "bulk.script_imported",
+                        executed = summary.executed,
+                        failed = summary.failed,
+                        locale = locale
+                    );
+                    let action = if summary.failed > 0 {
+                        NotificationAction::new_error(message.into())
+                    } else if summary.cancelled {
+                        NotificationAction::new_warning(message.into())
+                    } else {
+                        NotificationAction::new_success(message.into())
+                    };
+                    cx.emit(ServerEvent::Notification(action));
+                    cx.emit(ServerEvent::ScriptImported(Arc::new(summary)));
+                    let keyword = this.keyword.clone();
+                    this.scan(keyword, cx);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+}
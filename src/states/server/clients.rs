@@ -0,0 +1,382 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Connected clients.
+//!
+//! This module lists the clients of every node (`CLIENT LIST`) and provides the client
+//! management commands:
+//! - `CLIENT KILL ID` on the node the client is connected to
+//! - `CLIENT NO-EVICT` for the connections of Zedis itself
+//! - `CLIENT PAUSE` / `CLIENT UNPAUSE` on every master
+
+use super::{ServerTask, ZedisServerState};
+use crate::{
+    connection::{NodeTarget, get_connection_manager},
+    error::Error,
+    states::{NotificationAction, ServerEvent, ZedisGlobalStore, i18n_clients},
+};
+use gpui::{SharedString, prelude::*};
+use redis::{Value, cmd};
+use rust_i18n::t;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// A client of `CLIENT LIST`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientInfo {
+    /// `host:port` of the node the client is connected to
+    pub node: SharedString,
+    pub id: u64,
+    pub addr: SharedString,
+    pub name: SharedString,
+    /// ACL user, only reported from Redis 6.0
+    pub user: SharedString,
+    pub db: i64,
+    /// Connection age in seconds
+    pub age: u64,
+    /// Idle time in seconds
+    pub idle: u64,
+    /// Last command executed
+    pub cmd: SharedString,
+    pub flags: SharedString,
+    /// Memory used by the client in bytes (`tot-mem`, `omem` before Redis 6.0)
+    pub memory: u64,
+}
+
+/// The column the client list is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClientSort {
+    Addr,
+    Age,
+    #[default]
+    Idle,
+    Memory,
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::BulkString(bytes) => String::from_utf8_lossy(bytes).to_string(),
+        Value::SimpleString(s) => s.clone(),
+        Value::VerbatimString { text, .. } => text.clone(),
+        _ => String::new(),
+    }
+}
+
+/// Parses a `CLIENT LIST` reply of a node.
+fn parse_client_list(node: &str, text: &str) -> Vec<ClientInfo> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut client = ClientInfo {
+                node: node.to_string().into(),
+                ..Default::default()
+            };
+            let mut omem = 0;
+            for (field, value) in line.split_whitespace().filter_map(|item| item.split_once('=')) {
+                match field {
+                    "id" => client.id = value.parse().unwrap_or_default(),
+                    "addr" => client.addr = value.to_string().into(),
+                    "name" => client.name = value.to_string().into(),
+                    "user" => client.user = value.to_string().into(),
+                    "db" => client.db = value.parse().unwrap_or_default(),
+                    "age" => client.age = value.parse().unwrap_or_default(),
+                    "idle" => client.idle = value.parse().unwrap_or_default(),
+                    "cmd" => client.cmd = value.to_string().into(),
+                    "flags" => client.flags = value.to_string().into(),
+                    "tot-mem" => client.memory = value.parse().unwrap_or_default(),
+                    "omem" => omem = value.parse().unwrap_or_default(),
+                    _ => {}
+                }
+            }
+            if client.memory == 0 {
+                client.memory = omem;
+            }
+            client
+        })
+        .collect()
+}
+
+/// Filters and sorts the clients.
+///
+/// # Arguments
+/// * `clients` - Clients of every node
+/// * `keyword` - Case-insensitive substring of the address, name, user, command or node
+/// * `sort` - Sort column
+/// * `descending` - Whether the largest values come first
+///
+/// # Returns
+/// The indexes of the matching clients in display order.
+pub fn filter_clients(clients: &[ClientInfo], keyword: &str, sort: ClientSort, descending: bool) -> Vec<usize> {
+    let keyword = keyword.to_lowercase();
+    let mut indexes: Vec<usize> = clients
+        .iter()
+        .enumerate()
+        .filter(|(_, client)| {
+            keyword.is_empty()
+                || [&client.addr, &client.name, &client.user, &client.cmd, &client.node]
+                    .iter()
+                    .any(|value| value.to_lowercase().contains(&keyword))
+        })
+        .map(|(index, _)| index)
+        .collect();
+    indexes.sort_by(|a, b| {
+        let (a, b) = (&clients[*a], &clients[*b]);
+        match sort {
+            ClientSort::Addr => (&a.node, &a.addr).cmp(&(&b.node, &b.addr)),
+            ClientSort::Age => a.age.cmp(&b.age),
+            ClientSort::Idle => a.idle.cmp(&b.idle),
+            ClientSort::Memory => a.memory.cmp(&b.memory),
+        }
+    });
+    if descending {
+        indexes.reverse();
+    }
+    indexes
+}
+
+impl ZedisServerState {
+    /// Returns the clients loaded by `load_clients`.
+    pub fn clients(&self) -> Option<Arc<Vec<ClientInfo>>> {
+        self.clients.clone()
+    }
+
+    /// Whether the clients are being loaded.
+    pub fn is_clients_loading(&self) -> bool {
+        self.clients_loading
+    }
+
+    /// Loads the clients of every master and replica (CLIENT LIST).
+    ///
+    /// Nodes that cannot be reached are skipped, the load fails only when no node answers.
+    pub fn load_clients(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.clients_loading = true;
+        cx.notify();
+        self.spawn(
+            ServerTask::LoadClients,
+            move || async move {
+                let (client, _) = get_connection_manager().get_client(&server_id, db, vec![]).await?;
+                let mut command = cmd("CLIENT");
+                command.arg("LIST");
+                let replies = client.query_async_nodes(&NodeTarget::AllNodes, command).await?;
+                let mut clients = vec![];
+                let mut last_error = None;
+                for reply in replies {
+                    let node = reply.node.map(|node| node.host_port()).unwrap_or_default();
+                    match reply.result {
+                        Ok(value) => clients.extend(parse_client_list(&node, &value_to_string(&value))),
+                        Err(e) => {
+                            warn!(node, error = %e, "Load client list fail");
+                            last_error = Some(e);
+                        }
+                    }
+                }
+                if clients.is_empty()
+                    && let Some(e) = last_error
+                {
+                    return Err(e);
+                }
+                info!(count = clients.len(), "Clients loaded");
+                Ok(clients)
+            },
+            move |this, result, cx| {
+                this.clients_loading = false;
+                if let Ok(clients) = result {
+                    this.clients = Some(Arc::new(clients));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Closes the connection of a client (CLIENT KILL ID) and reloads the clients.
+    ///
+    /// # Arguments
+    /// * `node` - `host:port` of the node the client is connected to
+    /// * `id` - Client id
+    pub fn kill_client(&mut self, node: SharedString, id: u64, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::KillClient,
+            move || async move {
+                let (client, _) = get_connection_manager().get_client(&server_id, db, vec![]).await?;
+                let mut command = cmd("CLIENT");
+                command.arg("KILL").arg("ID").arg(id);
+                let replies = client
+                    .query_async_nodes(&NodeTarget::Node(node.to_string()), command)
+                    .await?;
+                for reply in replies {
+                    if let Value::Int(0) = reply.result? {
+                        return Err(Error::Invalid {
+                            message: format!("Client {id} is no longer connected"),
+                        });
+                    }
+                }
+                Ok(())
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    let message = i18n_clients(cx, "killed");
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(message)));
+                }
+                this.load_clients(cx);
+            },
+            cx,
+        );
+    }
+
+    /// Turns the no-evict mode of the Zedis connections on or off (CLIENT NO-EVICT).
+    ///
+    /// The mode only applies to the connection sending the command, so it is sent on the
+    /// pooled connection of every node. Requires Redis 7.0.
+    pub fn set_client_no_evict(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::SetClientNoEvict,
+            move || async move {
+                let (client, _) = get_connection_manager().get_client(&server_id, db, vec![]).await?;
+                if !client.is_at_least_version("7.0.0") {
+                    return Err(Error::Invalid {
+                        message: "CLIENT NO-EVICT requires Redis 7.0 or later".to_string(),
+                    });
+                }
+                let mut command = cmd("CLIENT");
+                command.arg("NO-EVICT").arg(if enabled { "ON" } else { "OFF" });
+                for reply in client.query_async_nodes(&NodeTarget::AllNodes, command).await? {
+                    reply.result?;
+                }
+                Ok(())
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    this.client_no_evict = enabled;
+                    let key = if enabled { "no_evict_on" } else { "no_evict_off" };
+                    let message = i18n_clients(cx, key);
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(message)));
+                    this.load_clients(cx);
+                }
+            },
+            cx,
+        );
+    }
+
+    /// Whether no-evict mode was turned on for the Zedis connections.
+    pub fn client_no_evict(&self) -> bool {
+        self.client_no_evict
+    }
+
+    /// Suspends the clients of every master (CLIENT PAUSE).
+    ///
+    /// # Arguments
+    /// * `timeout` - Pause duration in milliseconds
+    /// * `write_only` - Only pause write commands, requires Redis 6.2
+    pub fn pause_clients(&mut self, timeout: u64, write_only: bool, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::PauseClients,
+            move || async move {
+                let (client, _) = get_connection_manager().get_client(&server_id, db, vec![]).await?;
+                let mut command = cmd("CLIENT");
+                command.arg("PAUSE").arg(timeout);
+                if write_only {
+                    if !client.is_at_least_version("6.2.0") {
+                        return Err(Error::Invalid {
+                            message: "CLIENT PAUSE WRITE requires Redis 6.2 or later".to_string(),
+                        });
+                    }
+                    command.arg("WRITE");
+                }
+                for reply in client.query_async_nodes(&NodeTarget::AllMasters, command).await? {
+                    reply.result?;
+                }
+                Ok(())
+            },
+            move |_, result, cx| {
+                if result.is_ok() {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!("clients.paused", timeout = timeout, locale = locale).to_string();
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
+                }
+            },
+            cx,
+        );
+    }
+
+    /// Resumes the clients paused by `pause_clients` (CLIENT UNPAUSE), requires Redis 6.2.
+    pub fn unpause_clients(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::UnpauseClients,
+            move || async move {
+                let (client, _) = get_connection_manager().get_client(&server_id, db, vec![]).await?;
+                if !client.is_at_least_version("6.2.0") {
+                    return Err(Error::Invalid {
+                        message: "CLIENT UNPAUSE requires Redis 6.2 or later".to_string(),
+                    });
+                }
+                let mut command = cmd("CLIENT");
+                command.arg("UNPAUSE");
+                for reply in client.query_async_nodes(&NodeTarget::AllMasters, command).await? {
+                    reply.result?;
+                }
+                Ok(())
+            },
+            move |_, result, cx| {
+                if result.is_ok() {
+                    let message = i18n_clients(cx, "unpaused");
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(message)));
+                }
+            },
+            cx,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_filters_client_list() {
+        let text = "id=3 addr=127.0.0.1:52000 laddr=127.0.0.1:6379 fd=8 name=worker age=120 idle=5 flags=N db=0 \
+                    sub=0 psub=0 multi=-1 qbuf=26 omem=0 tot-mem=22298 events=r cmd=client|list user=default\n\
+                    id=9 addr=10.0.0.2:41000 fd=9 name= age=30 idle=30 flags=S db=2 omem=4096 cmd=replconf\n";
+        let clients = parse_client_list("10.0.0.1:6379", text);
+        assert_eq!(clients.len(), 2);
+        assert_eq!(clients[0].id, 3);
+        assert_eq!(clients[0].name, "worker");
+        assert_eq!(clients[0].user, "default");
+        assert_eq!(clients[0].cmd, "client|list");
+        assert_eq!(clients[0].memory, 22298);
+        assert_eq!(clients[0].node, "10.0.0.1:6379");
+        // Redis 5.0 reports no tot-mem nor user
+        assert_eq!(clients[1].memory, 4096);
+        assert_eq!(clients[1].user, "");
+        assert_eq!(clients[1].db, 2);
+
+        assert_eq!(filter_clients(&clients, "", ClientSort::Idle, true), vec![1, 0]);
+        assert_eq!(filter_clients(&clients, "", ClientSort::Memory, true), vec![0, 1]);
+        assert_eq!(filter_clients(&clients, "", ClientSort::Age, false), vec![1, 0]);
+        assert_eq!(filter_clients(&clients, "WORK", ClientSort::Idle, true), vec![0]);
+        assert_eq!(filter_clients(&clients, "replconf", ClientSort::Idle, true), vec![1]);
+    }
+}
@@ -0,0 +1,901 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Console support: command documentation, autocompletion and history.
+//!
+//! This module provides:
+//! - `COMMAND DOCS` parsing (with a `COMMAND` fallback for servers before 7.0)
+//! - Command and key name completion driven by the argument specs
+//! - Persistent per-server command history with navigation and reverse search
+//! - Command execution for the console
+
+use super::{ServerTask, ZedisServerState};
+use crate::{
+    connection::{NodeReply, NodeTarget, RedisAsyncConn, get_connection_manager},
+    error::Error,
+    helpers::{get_or_create_config_dir, redis_value_to_string, split_args},
+};
+use gpui::{SharedString, prelude::*};
+use parking_lot::Mutex;
+use redis::{Value, cmd};
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tracing::{debug, error};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Maximum number of commands kept in the history file
+const MAX_HISTORY_ENTRIES: usize = 1000;
+/// Maximum number of completion candidates offered at once
+const MAX_COMPLETION_CANDIDATES: usize = 50;
+const HISTORY_DIR: &str = "console_history";
+/// `CONFIG SET` parameters holding secrets, like redis-cli
+const SENSITIVE_CONFIGS: &[&str] = &[
+    "masterauth",
+    "masteruser",
+    "requirepass",
+    "tls-key-file-pass",
+    "tls-client-key-file-pass",
+];
+
+/// Orders the history saves by the time they were scheduled
+static HISTORY_SAVE_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+/// Sequence of the last history written for each server
+static HISTORY_SAVED: LazyLock<Mutex<HashMap<String, u64>>> = LazyLock::new(Default::default);
+
+/// An argument of a command as described by `COMMAND DOCS`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandArgument {
+    pub name: String,
+    /// Argument type, e.g. `key`, `string`, `integer`, `pure-token`, `oneof` or `block`
+    pub kind: String,
+    pub token: Option<String>,
+    pub optional: bool,
+    pub multiple: bool,
+    /// Nested arguments of `oneof` and `block` arguments
+    pub arguments: Vec<CommandArgument>,
+}
+
+impl CommandArgument {
+    fn is_key(&self) -> bool {
+        self.kind == "key"
+    }
+
+    /// Renders the argument in the notation used by the Redis documentation.
+    fn syntax(&self) -> String {
+        let nested = |separator: &str| {
+            self.arguments
+                .iter()
+                .map(|argument| argument.syntax())
+                .collect::<Vec<_>>()
+                .join(separator)
+        };
+        let mut body = match self.kind.as_str() {
+            "pure-token" => self.token.clone().unwrap_or_else(|| self.name.to_uppercase()),
+            "oneof" => nested(" | "),
+            "block" => nested(" "),
+            _ => self.name.clone(),
+        };
+        if let Some(token) = &self.token
+            && self.kind != "pure-token"
+        {
+            body = format!("{token} {body}");
+        }
+        if self.multiple {
+            body = format!("{body} [{body} ...]");
+        }
+        if self.optional {
+            format!("[{body}]")
+        } else if self.kind == "oneof" {
+            format!("<{body}>")
+        } else {
+            body
+        }
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a str>) {
+        if let Some(token) = &self.token {
+            tokens.push(token);
+        }
+        for argument in &self.arguments {
+            argument.collect_tokens(tokens);
+        }
+    }
+}
+
+/// Documentation of a command, used for hints and completion.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandDoc {
+    /// Upper-case command name, subcommands include their container (e.g. `CONFIG GET`)
+    pub name: SharedString,
+    pub summary: SharedString,
+    pub since: SharedString,
+    pub group: SharedString,
+    pub arguments: Vec<CommandArgument>,
+}
+
+impl CommandDoc {
+    /// Returns the full syntax, e.g. `GET key`.
+    pub fn syntax(&self) -> String {
+        let mut syntax = self.name.to_string();
+        for argument in &self.arguments {
+            syntax.push(' ');
+            syntax.push_str(&argument.syntax());
+        }
+        syntax
+    }
+
+    /// Returns whether the positional argument at `index` (after the command name) is a key.
+    ///
+    /// Positions are only tracked up to the first token or choice argument, after which the
+    /// layout depends on what the user typed.
+    pub fn is_key_argument(&self, index: usize) -> bool {
+        let mut position = 0;
+        for argument in &self.arguments {
+            if argument.token.is_some() || matches!(argument.kind.as_str(), "pure-token" | "oneof") {
+                return false;
+            }
+            let block = argument.kind == "block" && !argument.arguments.is_empty();
+            let width = if block { argument.arguments.len() } else { 1 };
+            if argument.multiple || index < position + width {
+                let offset = (index - position) % width;
+                return if block {
+                    argument.arguments[offset].is_key()
+                } else {
+                    argument.is_key()
+                };
+            }
+            position += width;
+        }
+        false
+    }
+
+    fn tokens(&self) -> Vec<&str> {
+        let mut tokens = vec![];
+        for argument in &self.arguments {
+            argument.collect_tokens(&mut tokens);
+        }
+        tokens
+    }
+}
+
+/// Field-value pairs of a reply that is a flat array in RESP2 and a map in RESP3.
+fn pairs(value: &Value) -> Vec<(&Value, &Value)> {
+    match value {
+        Value::Map(items) => items.iter().map(|(field, value)| (field, value)).collect(),
+        Value::Array(items) => items
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [field, value] => Some((field, value)),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn parse_arguments(value: &Value) -> Vec<CommandArgument> {
+    let (Value::Array(items) | Value::Set(items)) = value else {
+        return vec![];
+    };
+    items
+        .iter()
+        .map(|item| {
+            let mut argument = CommandArgument::default();
+            for (field, value) in pairs(item) {
+                match redis_value_to_string(field).as_str() {
+                    "name" => argument.name = redis_value_to_string(value),
+                    "type" => argument.kind = redis_value_to_string(value),
+                    "token" => argument.token = Some(redis_value_to_string(value)),
+                    "flags" => {
+                        if let Value::Array(flags) | Value::Set(flags) = value {
+                            for flag in flags {
+                                match redis_value_to_string(flag).as_str() {
+                                    "optional" => argument.optional = true,
+                                    "multiple" => argument.multiple = true,
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                    "arguments" => argument.arguments = parse_arguments(value),
+                    _ => {}
+                }
+            }
+            argument
+        })
+        .collect()
+}
+
+fn push_command_doc(docs: &mut Vec<CommandDoc>, name: &Value, value: &Value) {
+    let mut doc = CommandDoc {
+        name: redis_value_to_string(name).replace('|', " ").to_uppercase().into(),
+        ..Default::default()
+    };
+    for (field, value) in pairs(value) {
+        match redis_value_to_string(field).as_str() {
+            "summary" => doc.summary = redis_value_to_string(value).into(),
+            "since" => doc.since = redis_value_to_string(value).into(),
+            "group" => doc.group = redis_value_to_string(value).into(),
+            "arguments" => doc.arguments = parse_arguments(value),
+            "subcommands" => {
+                for (name, value) in pairs(value) {
+                    push_command_doc(docs, name, value);
+                }
+            }
+            _ => {}
+        }
+    }
+    docs.push(doc);
+}
+
+/// Parses the `COMMAND DOCS` reply (Redis 7.0+), including subcommands.
+pub fn parse_command_docs(value: &Value) -> Vec<CommandDoc> {
+    let mut docs = vec![];
+    for (name, value) in pairs(value) {
+        push_command_doc(&mut docs, name, value);
+    }
+    docs.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    docs
+}
+
+/// Parses the `COMMAND` reply of older servers, deriving key positions from the key specs.
+pub fn parse_command_list(value: &Value) -> Vec<CommandDoc> {
+    let Value::Array(items) = value else {
+        return vec![];
+    };
+    let int = |value: Option<&Value>| match value {
+        Some(Value::Int(value)) => *value,
+        _ => 0,
+    };
+    let mut docs: Vec<CommandDoc> = items
+        .iter()
+        .filter_map(|item| {
+            let Value::Array(info) = item else {
+                return None;
+            };
+            let name = redis_value_to_string(info.first()?).to_uppercase();
+            let (first_key, last_key, step) = (int(info.get(3)), int(info.get(4)), int(info.get(5)));
+            let mut arguments = vec![];
+            if first_key > 0 {
+                let argument = |name: &str, kind: &str| CommandArgument {
+                    name: name.to_string(),
+                    kind: kind.to_string(),
+                    ..Default::default()
+                };
+                for _ in 1..first_key {
+                    arguments.push(argument("arg", "string"));
+                }
+                if last_key == first_key {
+                    arguments.push(argument("key", "key"));
+                } else if step <= 1 {
+                    arguments.push(CommandArgument {
+                        multiple: true,
+                        ..argument("key", "key")
+                    });
+                } else {
+                    let mut block = vec![argument("key", "key")];
+                    block.extend((1..step).map(|_| argument("value", "string")));
+                    arguments.push(CommandArgument {
+                        multiple: true,
+                        arguments: block,
+                        ..argument("data", "block")
+                    });
+                }
+            }
+            Some(CommandDoc {
+                name: name.into(),
+                arguments,
+                ..Default::default()
+            })
+        })
+        .collect();
+    docs.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    docs.dedup_by(|a, b| a.name == b.name);
+    docs
+}
+
+/// Finds the documentation for the typed words, preferring `CONTAINER SUBCOMMAND` matches.
+///
+/// # Returns
+/// The documentation and the number of words that make up the command name.
+pub fn find_command_doc<'a>(docs: &'a [CommandDoc], words: &[&str]) -> Option<(&'a CommandDoc, usize)> {
+    let first = words.first()?.to_uppercase();
+    if let Some(second) = words.get(1) {
+        let name = format!("{first} {}", second.to_uppercase());
+        if let Ok(index) = docs.binary_search_by(|doc| doc.name.as_str().cmp(&name)) {
+            return Some((&docs[index], 2));
+        }
+    }
+    let index = docs.binary_search_by(|doc| doc.name.as_str().cmp(&first)).ok()?;
+    Some((&docs[index], 1))
+}
+
+/// Completion candidates for the word under the cursor.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsoleCompletion {
+    /// Byte offset in the input where the completed word starts
+    pub start: usize,
+    /// Replacement text for the word, already quoted when necessary
+    pub candidates: Vec<SharedString>,
+}
+
+/// Quotes a key for the command line when it contains whitespace, quotes or escapes.
+///
+/// Keys use the escaped form of [`crate::helpers::RedisKey`], whose `\\` and `\xNN`
+/// sequences decode to the original bytes inside a double quoted argument.
+fn quote_key(key: &str) -> SharedString {
+    if key.is_empty() || key.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '\\') {
+        format!("\"{}\"", key.replace('"', "\\\"")).into()
+    } else {
+        key.to_string().into()
+    }
+}
+
+/// Computes completion candidates for the end of the input.
+///
+/// The first word completes to command names, subsequent words to subcommands, key
+/// names (for key arguments) or the tokens accepted by the command.
+///
+/// # Arguments
+/// * `input` - The current input line
+/// * `docs` - Command documentation sorted by name
+/// * `keys` - Known key names in escaped form
+pub fn complete<'a>(
+    input: &str,
+    docs: &[CommandDoc],
+    keys: impl Iterator<Item = &'a SharedString>,
+) -> ConsoleCompletion {
+    let start = input.rfind(char::is_whitespace).map(|index| index + 1).unwrap_or(0);
+    let word = &input[start..];
+    let mut completion = ConsoleCompletion {
+        start,
+        candidates: vec![],
+    };
+    // Quoted words are not completed
+    if word.starts_with(['"', '\'']) {
+        return completion;
+    }
+    let upper_word = word.to_uppercase();
+    let words: Vec<&str> = input[..start].split_whitespace().collect();
+    let mut candidates: Vec<SharedString> = match words.as_slice() {
+        [] => {
+            let mut names: Vec<SharedString> = docs
+                .iter()
+                .filter_map(|doc| doc.name.split(' ').next())
+                .filter(|name| name.starts_with(&upper_word))
+                .map(|name| name.to_string().into())
+                .collect();
+            names.dedup();
+            names
+        }
+        [container] => {
+            let prefix = format!("{} {upper_word}", container.to_uppercase());
+            let subcommands: Vec<SharedString> = docs
+                .iter()
+                .filter(|doc| doc.name.starts_with(&prefix))
+                .filter_map(|doc| doc.name.split(' ').nth(1))
+                .map(|name| name.to_string().into())
+                .collect();
+            if subcommands.is_empty() {
+                complete_arguments(docs, &words, word, &upper_word, keys)
+            } else {
+                subcommands
+            }
+        }
+        _ => complete_arguments(docs, &words, word, &upper_word, keys),
+    };
+    candidates.truncate(MAX_COMPLETION_CANDIDATES);
+    completion.candidates = candidates;
+    completion
+}
+
+fn complete_arguments<'a>(
+    docs: &[CommandDoc],
+    words: &[&str],
+    word: &str,
+    upper_word: &str,
+    keys: impl Iterator<Item = &'a SharedString>,
+) -> Vec<SharedString> {
+    let Some((doc, name_words)) = find_command_doc(docs, words) else {
+        return vec![];
+    };
+    let index = words.len() - name_words;
+    if doc.is_key_argument(index) {
+        let mut matched: Vec<&SharedString> = keys.filter(|key| key.starts_with(word)).collect();
+        matched.sort_unstable();
+        return matched
+            .into_iter()
+            .take(MAX_COMPLETION_CANDIDATES)
+            .map(|key| quote_key(key))
+            .collect();
+    }
+    let mut tokens: Vec<SharedString> = doc
+        .tokens()
+        .into_iter()
+        .filter(|token| token.to_uppercase().starts_with(upper_word))
+        .map(|token| token.to_uppercase().into())
+        .collect();
+    tokens.sort_unstable();
+    tokens.dedup();
+    tokens
+}
+
+/// Returns the longest common prefix of the candidates.
+pub fn common_prefix(candidates: &[SharedString]) -> &str {
+    let Some(first) = candidates.first() else {
+        return "";
+    };
+    let mut len = first.len();
+    for candidate in &candidates[1..] {
+        len = first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map(|((index, c), _)| index + c.len_utf8())
+            .unwrap_or(0)
+            .min(len);
+    }
+    &first[..len]
+}
+
+fn history_path(server_id: &str) -> Result<PathBuf> {
+    let dir = get_or_create_config_dir()?.join(HISTORY_DIR);
+    if !dir.exists() {
+        fs::create_dir_all(&dir)?;
+    }
+    let name: String = server_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    Ok(dir.join(format!("{name}.history")))
+}
+
+/// Returns the sequence of a history save, taken when the save is scheduled.
+pub fn next_history_sequence() -> u64 {
+    HISTORY_SAVE_SEQUENCE.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+/// Saves the history of a server, one command per line.
+///
+/// Saves run one at a time and a save older than the last written one is skipped, so
+/// saves finishing out of order never overwrite newer entries. The file is replaced
+/// through a rename, readers never see a partial history.
+pub fn save_console_history(server_id: &str, entries: &[String], sequence: u64) -> Result<()> {
+    let mut saved = HISTORY_SAVED.lock();
+    if saved.get(server_id).is_some_and(|last| *last > sequence) {
+        return Ok(());
+    }
+    let path = history_path(server_id)?;
+    let tmp_path = path.with_extension("history.tmp");
+    let mut value = entries.join("\n");
+    value.push('\n');
+    fs::write(&tmp_path, value)?;
+    fs::rename(&tmp_path, &path)?;
+    saved.insert(server_id.to_string(), sequence);
+    Ok(())
+}
+
+/// Whether the command carries a secret and is kept out of the history, like redis-cli:
+/// `AUTH`, `ACL SETUSER`, `CONFIG SET` of a password, `HELLO ... AUTH` and
+/// `MIGRATE ... AUTH`.
+pub fn is_sensitive_command(command: &str) -> bool {
+    let args: Vec<String> = match split_args(command) {
+        Ok(args) => args
+            .iter()
+            .map(|arg| String::from_utf8_lossy(arg).to_lowercase())
+            .collect(),
+        Err(_) => command.split_whitespace().map(str::to_lowercase).collect(),
+    };
+    let arg = |index: usize| args.get(index).map(String::as_str);
+    match arg(0) {
+        Some("auth") => true,
+        Some("acl") => arg(1) == Some("setuser"),
+        Some("config") => {
+            arg(1) == Some("set")
+                && args
+                    .iter()
+                    .skip(2)
+                    .step_by(2)
+                    .any(|name| SENSITIVE_CONFIGS.contains(&name.as_str()))
+        }
+        Some("hello") => args.iter().skip(2).any(|arg| arg == "auth"),
+        Some("migrate") => args.iter().skip(6).any(|arg| arg == "auth" || arg == "auth2"),
+        _ => false,
+    }
+}
+
+/// Command history of a server with up/down navigation and reverse search.
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleHistory {
+    entries: Vec<String>,
+    /// Entry shown while navigating, `None` when editing a new line
+    cursor: Option<usize>,
+    /// The line being edited before navigation started
+    draft: String,
+}
+
+impl ConsoleHistory {
+    /// Loads the persisted history of a server; a missing file yields an empty history.
+    pub fn load(server_id: &str) -> Self {
+        let entries = history_path(server_id)
+            .and_then(|path| {
+                if path.exists() {
+                    Ok(fs::read_to_string(path)?)
+                } else {
+                    Ok(String::new())
+                }
+            })
+            .map(|value| {
+                value
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_else(|e| {
+                error!(error = %e, "Failed to load console history");
+                vec![]
+            });
+        Self {
+            entries,
+            ..Default::default()
+        }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Appends a command, skipping consecutive duplicates and sensitive commands,
+    /// and resets navigation.
+    ///
+    /// # Returns
+    /// Whether the command was added.
+    pub fn push(&mut self, command: &str) -> bool {
+        self.reset_navigation();
+        let command = command.trim();
+        if command.is_empty()
+            || self.entries.last().is_some_and(|last| last == command)
+            || is_sensitive_command(command)
+        {
+            return false;
+        }
+        self.entries.push(command.to_string());
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            let overflow = self.entries.len() - MAX_HISTORY_ENTRIES;
+            self.entries.drain(..overflow);
+        }
+        true
+    }
+
+    pub fn reset_navigation(&mut self) {
+        self.cursor = None;
+        self.draft.clear();
+    }
+
+    /// Moves to the previous (older) entry, remembering the current line as draft.
+    pub fn previous(&mut self, current: &str) -> Option<&str> {
+        let index = match self.cursor {
+            None => {
+                self.draft = current.to_string();
+                self.entries.len().checked_sub(1)?
+            }
+            Some(index) => index.checked_sub(1)?,
+        };
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Moves to the next (newer) entry, returning the draft after the newest one.
+    pub fn next(&mut self) -> Option<&str> {
+        let index = self.cursor? + 1;
+        if index >= self.entries.len() {
+            self.cursor = None;
+            return Some(&self.draft);
+        }
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Searches backwards for an entry containing `query`, starting before `before`.
+    ///
+    /// # Returns
+    /// The index and the matching entry.
+    pub fn search(&self, query: &str, before: usize) -> Option<(usize, &str)> {
+        let end = before.min(self.entries.len());
+        self.entries[..end]
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, entry)| entry.contains(query))
+            .map(|(index, entry)| (index, entry.as_str()))
+    }
+}
+
+/// Fetches command documentation, falling back to `COMMAND` for servers before 7.0.
+async fn fetch_command_docs(conn: &mut RedisAsyncConn) -> Result<Vec<CommandDoc>> {
+    match cmd("COMMAND").arg("DOCS").query_async::<Value>(conn).await {
+        Ok(value) => {
+            let docs = parse_command_docs(&value);
+            if !docs.is_empty() {
+                return Ok(docs);
+            }
+        }
+        Err(e) => debug!(error = %e, "COMMAND DOCS is not supported, fallback to COMMAND"),
+    }
+    let value: Value = cmd("COMMAND").query_async(conn).await?;
+    Ok(parse_command_list(&value))
+}
+
+/// Executes a console command given as raw arguments.
+///
+/// # Arguments
+/// * `server_id` - The server to run the command on
+/// * `db` - The selected database
+/// * `target` - The cluster nodes the command is sent to
+/// * `args` - The command name followed by its arguments
+///
+/// # Returns
+/// The reply of every targeted node (a single reply for [`NodeTarget::Auto`]).
+pub async fn execute_console_command(
+    server_id: &str,
+    db: usize,
+    target: NodeTarget,
+    args: Vec<Vec<u8>>,
+) -> Result<Vec<NodeReply>> {
+    let Some((name, args)) = args.split_first() else {
+        return Ok(vec![]);
+    };
+    let (client, _) = get_connection_manager().get_client(server_id, db, vec![]).await?;
+    let mut command = cmd(&String::from_utf8_lossy(name));
+    command.arg(args);
+    client.query_async_nodes(&target, command).await
+}
+
+impl ZedisServerState {
+    /// Returns the command documentation of the current server, if loaded.
+    pub fn command_docs(&self) -> Option<Arc<Vec<CommandDoc>>> {
+        self.command_docs.clone()
+    }
+
+    /// Loads the command documentation of the current server once.
+    pub fn load_command_docs(&mut self, cx: &mut Context<Self>) {
+        if self.command_docs.is_some() || self.server_id.is_empty() {
+            return;
+        }
+        // Mark as loaded to avoid concurrent requests
+        self.command_docs = Some(Arc::new(vec![]));
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::LoadCommandDocs,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                fetch_command_docs(&mut conn).await
+            },
+            move |this, result, cx| {
+                match result {
+                    Ok(docs) => {
+                        debug!(count = docs.len(), "Command docs loaded");
+                        this.command_docs = Some(Arc::new(docs));
+                    }
+                    // Allow retrying the next time the console is opened
+                    Err(_) => this.command_docs = None,
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(value: &str) -> Value {
+        Value::BulkString(value.as_bytes().to_vec())
+    }
+
+    fn argument(name: &str, kind: &str, flags: &[&str], token: Option<&str>) -> Value {
+        let mut fields = vec![bulk("name"), bulk(name), bulk("type"), bulk(kind)];
+        if let Some(token) = token {
+            fields.extend([bulk("token"), bulk(token)]);
+        }
+        if !flags.is_empty() {
+            fields.extend([
+                bulk("flags"),
+                Value::Array(flags.iter().map(|flag| bulk(flag)).collect()),
+            ]);
+        }
+        Value::Array(fields)
+    }
+
+    fn sample_docs() -> Vec<CommandDoc> {
+        let reply = Value::Array(vec![
+            bulk("set"),
+            Value::Array(vec![
+                bulk("summary"),
+                bulk("Sets the string value of a key."),
+                bulk("group"),
+                bulk("string"),
+                bulk("arguments"),
+                Value::Array(vec![
+                    argument("key", "key", &[], None),
+                    argument("value", "string", &[], None),
+                    Value::Array(vec![
+                        bulk("name"),
+                        bulk("condition"),
+                        bulk("type"),
+                        bulk("oneof"),
+                        bulk("flags"),
+                        Value::Array(vec![bulk("optional")]),
+                        bulk("arguments"),
+                        Value::Array(vec![
+                            argument("nx", "pure-token", &[], Some("NX")),
+                            argument("xx", "pure-token", &[], Some("XX")),
+                        ]),
+                    ]),
+                ]),
+            ]),
+            bulk("del"),
+            Value::Map(vec![(
+                bulk("arguments"),
+                Value::Array(vec![argument("key", "key", &["multiple"], None)]),
+            )]),
+            bulk("config"),
+            Value::Array(vec![
+                bulk("subcommands"),
+                Value::Array(vec![
+                    bulk("config|get"),
+                    Value::Array(vec![
+                        bulk("arguments"),
+                        Value::Array(vec![argument("parameter", "string", &["multiple"], None)]),
+                    ]),
+                ]),
+            ]),
+        ]);
+        parse_command_docs(&reply)
+    }
+
+    #[test]
+    fn parses_command_docs() {
+        let docs = sample_docs();
+        let names: Vec<&str> = docs.iter().map(|doc| doc.name.as_str()).collect();
+        assert_eq!(names, vec!["CONFIG", "CONFIG GET", "DEL", "SET"]);
+        let (set, words) = find_command_doc(&docs, &["set", "k"]).expect("command doc");
+        assert_eq!(words, 1);
+        assert_eq!(set.syntax(), "SET key value [NX | XX]");
+        assert!(set.is_key_argument(0));
+        assert!(!set.is_key_argument(1));
+        let (del, _) = find_command_doc(&docs, &["DEL"]).expect("command doc");
+        assert_eq!(del.syntax(), "DEL key [key ...]");
+        assert!(del.is_key_argument(3));
+        assert_eq!(find_command_doc(&docs, &["config", "get"]).expect("command doc").1, 2);
+    }
+
+    #[test]
+    fn parses_legacy_command_list() {
+        let reply = Value::Array(vec![
+            Value::Array(vec![
+                bulk("mset"),
+                Value::Int(-3),
+                Value::Array(vec![]),
+                Value::Int(1),
+                Value::Int(-1),
+                Value::Int(2),
+            ]),
+            Value::Array(vec![
+                bulk("ping"),
+                Value::Int(-1),
+                Value::Array(vec![]),
+                Value::Int(0),
+                Value::Int(0),
+                Value::Int(0),
+            ]),
+        ]);
+        let docs = parse_command_list(&reply);
+        let (mset, _) = find_command_doc(&docs, &["mset"]).expect("command doc");
+        assert!(mset.is_key_argument(0));
+        assert!(!mset.is_key_argument(1));
+        assert!(mset.is_key_argument(2));
+        assert!(
+            find_command_doc(&docs, &["ping"])
+                .expect("command doc")
+                .0
+                .arguments
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn completes_commands_keys_and_tokens() {
+        let docs = sample_docs();
+        let keys: Vec<SharedString> = vec!["user:1".into(), "user:2".into(), "order 1".into()];
+        let completion = complete("se", &docs, keys.iter());
+        assert_eq!(completion.candidates, vec![SharedString::from("SET")]);
+        let completion = complete("config g", &docs, keys.iter());
+        assert_eq!(completion.start, 7);
+        assert_eq!(completion.candidates, vec![SharedString::from("GET")]);
+        let completion = complete("UNKNOWN use", &docs, keys.iter());
+        assert!(completion.candidates.is_empty());
+        let completion = complete("DEL user:1 use", &docs, keys.iter());
+        assert_eq!(
+            completion.candidates,
+            vec![SharedString::from("user:1"), "user:2".into()]
+        );
+        assert_eq!(common_prefix(&completion.candidates), "user:");
+        let completion = complete("del or", &docs, keys.iter());
+        assert_eq!(completion.candidates, vec![SharedString::from("\"order 1\"")]);
+        let completion = complete("set k v n", &docs, keys.iter());
+        assert_eq!(completion.candidates, vec![SharedString::from("NX")]);
+    }
+
+    #[test]
+    fn navigates_and_searches_history() {
+        let mut history = ConsoleHistory::default();
+        for command in ["GET a", "GET a", "SET b 1", "DEL b"] {
+            history.push(command);
+        }
+        assert_eq!(history.entries().len(), 3);
+        assert_eq!(history.previous("draft"), Some("DEL b"));
+        assert_eq!(history.previous(""), Some("SET b 1"));
+        assert_eq!(history.previous(""), Some("GET a"));
+        assert_eq!(history.previous(""), None);
+        assert_eq!(history.next(), Some("SET b 1"));
+        assert_eq!(history.next(), Some("DEL b"));
+        assert_eq!(history.next(), Some("draft"));
+        assert_eq!(history.next(), None);
+        assert_eq!(history.search("b", 3), Some((2, "DEL b")));
+        assert_eq!(history.search("b", 2), Some((1, "SET b 1")));
+        assert_eq!(history.search("zzz", 3), None);
+    }
+
+    #[test]
+    fn skips_sensitive_commands() {
+        for command in [
+            "AUTH secret",
+            "auth default secret",
+            "ACL SETUSER app on >secret",
+            "CONFIG SET maxmemory 1gb requirepass secret",
+            "config set masterauth secret",
+            "HELLO 3 AUTH default secret",
+            "MIGRATE host 6379 \"\" 0 5000 AUTH secret KEYS a",
+        ] {
+            assert!(is_sensitive_command(command), "{command}");
+        }
+        for command in [
+            "GET auth",
+            "CONFIG SET maxmemory 1gb",
+            "ACL LIST",
+            "HELLO 3",
+            "CONFIG GET requirepass",
+        ] {
+            assert!(!is_sensitive_command(command), "{command}");
+        }
+        let mut history = ConsoleHistory::default();
+        assert!(!history.push("AUTH secret"));
+        assert!(history.push("GET a"));
+        assert_eq!(history.entries(), ["GET a"]);
+    }
+}
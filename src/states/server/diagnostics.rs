@@ -0,0 +1,384 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Slow log and latency diagnostics.
+//!
+//! This module collects from every master node:
+//! - `SLOWLOG GET` entries
+//! - `LATENCY LATEST` events and their `LATENCY HISTORY` samples
+//! - The `LATENCY DOCTOR` report
+//!
+//! Every record keeps the node it comes from, so the results of a cluster can be merged
+//! into a single list.
+
+use super::{ServerTask, ZedisServerState};
+use crate::{
+    connection::get_connection_manager,
+    states::{NotificationAction, ServerEvent, i18n_diagnostics},
+};
+use gpui::{SharedString, prelude::*};
+use redis::{Value, cmd};
+use std::{collections::BTreeSet, sync::Arc};
+use tracing::info;
+
+/// Number of slow log entries requested from each node
+const SLOWLOG_COUNT: usize = 128;
+
+/// An entry of `SLOWLOG GET`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlowLogEntry {
+    /// `host:port` of the node
+    pub node: SharedString,
+    pub id: u64,
+    /// Unix timestamp in seconds
+    pub timestamp: i64,
+    /// Execution time in microseconds
+    pub duration: u64,
+    pub command: SharedString,
+    /// Client address and name, only reported from Redis 4.0
+    pub client: SharedString,
+}
+
+/// An event of `LATENCY LATEST`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyEvent {
+    pub node: SharedString,
+    pub event: SharedString,
+    /// Unix timestamp of the latest spike
+    pub timestamp: i64,
+    /// Latest and maximum latency in milliseconds
+    pub latest: u64,
+    pub max: u64,
+    /// Samples of `LATENCY HISTORY`, (timestamp, latency in milliseconds)
+    pub history: Vec<(i64, u64)>,
+}
+
+/// The `LATENCY DOCTOR` report of a node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyReport {
+    pub node: SharedString,
+    pub report: SharedString,
+}
+
+/// Diagnostics of every master node.
+#[derive(Debug, Clone, Default)]
+pub struct ServerDiagnostics {
+    pub slowlog: Vec<SlowLogEntry>,
+    pub latency: Vec<LatencyEvent>,
+    pub reports: Vec<LatencyReport>,
+}
+
+/// The column the slow log is sorted by, always descending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SlowLogSort {
+    #[default]
+    Duration,
+    Time,
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::BulkString(bytes) => String::from_utf8_lossy(bytes).to_string(),
+        Value::SimpleString(s) => s.clone(),
+        Value::VerbatimString { text, .. } => text.clone(),
+        Value::Int(i) => i.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn value_to_i64(value: &Value) -> i64 {
+    match value {
+        Value::Int(i) => *i,
+        other => value_to_string(other).parse().unwrap_or_default(),
+    }
+}
+
+fn value_items(value: &Value) -> &[Value] {
+    match value {
+        Value::Array(items) | Value::Set(items) => items,
+        _ => &[],
+    }
+}
+
+/// Parses a `SLOWLOG GET` reply of a node.
+fn parse_slowlog(node: &str, value: &Value) -> Vec<SlowLogEntry> {
+    value_items(value)
+        .iter()
+        .filter_map(|entry| {
+            let fields = value_items(entry);
+            if fields.len() < 4 {
+                return None;
+            }
+            let command = value_items(&fields[3])
+                .iter()
+                .map(value_to_string)
+                .collect::<Vec<_>>()
+                .join(" ");
+            let client = fields[4..]
+                .iter()
+                .map(value_to_string)
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            Some(SlowLogEntry {
+                node: node.to_string().into(),
+                id: value_to_i64(&fields[0]) as u64,
+                timestamp: value_to_i64(&fields[1]),
+                duration: value_to_i64(&fields[2]) as u64,
+                command: command.into(),
+                client: client.into(),
+            })
+        })
+        .collect()
+}
+
+/// Parses a `LATENCY LATEST` reply of a node.
+fn parse_latency_latest(node: &str, value: &Value) -> Vec<LatencyEvent> {
+    value_items(value)
+        .iter()
+        .filter_map(|event| {
+            let fields = value_items(event);
+            if fields.len() < 4 {
+                return None;
+            }
+            Some(LatencyEvent {
+                node: node.to_string().into(),
+                event: value_to_string(&fields[0]).into(),
+                timestamp: value_to_i64(&fields[1]),
+                latest: value_to_i64(&fields[2]) as u64,
+                max: value_to_i64(&fields[3]) as u64,
+                history: vec![],
+            })
+        })
+        .collect()
+}
+
+/// Parses a `LATENCY HISTORY` reply.
+fn parse_latency_history(value: &Value) -> Vec<(i64, u64)> {
+    value_items(value)
+        .iter()
+        .filter_map(|sample| match value_items(sample) {
+            [timestamp, latency, ..] => Some((value_to_i64(timestamp), value_to_i64(latency) as u64)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Filters and sorts the slow log.
+///
+/// # Arguments
+/// * `entries` - Slow log entries of every node
+/// * `keyword` - Case-insensitive substring of the command, ignored when empty
+/// * `min_duration` - Minimum execution time in microseconds
+/// * `sort` - Sort column, the slowest or most recent entries come first
+///
+/// # Returns
+/// The indexes of the matching entries in display order.
+pub fn filter_slowlog(entries: &[SlowLogEntry], keyword: &str, min_duration: u64, sort: SlowLogSort) -> Vec<usize> {
+    let keyword = keyword.to_lowercase();
+    let mut indexes: Vec<usize> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| {
+            entry.duration >= min_duration && (keyword.is_empty() || entry.command.to_lowercase().contains(&keyword))
+        })
+        .map(|(index, _)| index)
+        .collect();
+    match sort {
+        SlowLogSort::Duration => indexes.sort_by(|a, b| entries[*b].duration.cmp(&entries[*a].duration)),
+        SlowLogSort::Time => indexes
+            .sort_by(|a, b| (entries[*b].timestamp, entries[*b].id).cmp(&(entries[*a].timestamp, entries[*a].id))),
+    }
+    indexes
+}
+
+impl ZedisServerState {
+    /// Returns the diagnostics loaded by `load_diagnostics`.
+    pub fn diagnostics(&self) -> Option<Arc<ServerDiagnostics>> {
+        self.diagnostics.clone()
+    }
+
+    /// Whether the diagnostics are being loaded.
+    pub fn is_diagnostics_loading(&self) -> bool {
+        self.diagnostics_loading
+    }
+
+    /// Loads the slow log and latency diagnostics of every master.
+    pub fn load_diagnostics(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.diagnostics_loading = true;
+        cx.notify();
+        self.spawn(
+            ServerTask::LoadDiagnostics,
+            move || async move {
+                let (client, _) = get_connection_manager().get_client(&server_id, db, vec![]).await?;
+                let nodes = client.master_addrs();
+                let mut command = cmd("SLOWLOG");
+                command.arg("GET").arg(SLOWLOG_COUNT);
+                let slowlogs: Vec<Value> = client.query_async_masters(vec![command]).await?;
+                let mut command = cmd("LATENCY");
+                command.arg("LATEST");
+                let latest: Vec<Value> = client.query_async_masters(vec![command]).await?;
+                let mut command = cmd("LATENCY");
+                command.arg("DOCTOR");
+                let reports: Vec<Value> = client.query_async_masters(vec![command]).await?;
+
+                let mut diagnostics = ServerDiagnostics::default();
+                for (node, (slowlog, (latest, report))) in
+                    nodes.iter().zip(slowlogs.iter().zip(latest.iter().zip(&reports)))
+                {
+                    diagnostics.slowlog.extend(parse_slowlog(node, slowlog));
+                    diagnostics.latency.extend(parse_latency_latest(node, latest));
+                    diagnostics.reports.push(LatencyReport {
+                        node: node.clone().into(),
+                        report: value_to_string(report).into(),
+                    });
+                }
+                // Every node answers LATENCY HISTORY, nodes without the event return no samples
+                let events: BTreeSet<SharedString> =
+                    diagnostics.latency.iter().map(|event| event.event.clone()).collect();
+                for event in events {
+                    let mut command = cmd("LATENCY");
+                    command.arg("HISTORY").arg(event.as_str());
+                    let histories: Vec<Value> = client.query_async_masters(vec![command]).await?;
+                    for (node, history) in nodes.iter().zip(&histories) {
+                        if let Some(latency) = diagnostics
+                            .latency
+                            .iter_mut()
+                            .find(|latency| latency.event == event && latency.node == node.as_str())
+                        {
+                            latency.history = parse_latency_history(history);
+                        }
+                    }
+                }
+                info!(
+                    slowlog = diagnostics.slowlog.len(),
+                    latency = diagnostics.latency.len(),
+                    "Diagnostics loaded"
+                );
+                Ok(diagnostics)
+            },
+            move |this, result, cx| {
+                this.diagnostics_loading = false;
+                if let Ok(diagnostics) = result {
+                    this.diagnostics = Some(Arc::new(diagnostics));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Clears the slow log of every master (SLOWLOG RESET) and reloads the diagnostics.
+    pub fn reset_slowlog(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::ResetSlowlog,
+            move || async move {
+                let (client, _) = get_connection_manager().get_client(&server_id, db, vec![]).await?;
+                let mut command = cmd("SLOWLOG");
+                command.arg("RESET");
+                let _: Vec<()> = client.query_async_masters(vec![command]).await?;
+                Ok(())
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    let message = i18n_diagnostics(cx, "slowlog_reset");
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(message)));
+                    this.load_diagnostics(cx);
+                }
+            },
+            cx,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> Value {
+        Value::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn parses_slowlog_and_latency_replies() {
+        let slowlog = Value::Array(vec![
+            Value::Array(vec![
+                Value::Int(7),
+                Value::Int(1_700_000_000),
+                Value::Int(15_000),
+                Value::Array(vec![bulk("KEYS"), bulk("*")]),
+                bulk("127.0.0.1:52000"),
+                bulk(""),
+            ]),
+            // Redis 2.x/3.x entries have no client fields
+            Value::Array(vec![
+                Value::Int(6),
+                Value::Int(1_699_999_999),
+                Value::Int(12),
+                Value::Array(vec![bulk("GET"), bulk("a")]),
+            ]),
+        ]);
+        let entries = parse_slowlog("10.0.0.1:6379", &slowlog);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command, "KEYS *");
+        assert_eq!(entries[0].duration, 15_000);
+        assert_eq!(entries[0].client, "127.0.0.1:52000");
+        assert_eq!(entries[1].client, "");
+        assert_eq!(entries[1].node, "10.0.0.1:6379");
+
+        let latest = Value::Array(vec![Value::Array(vec![
+            bulk("command"),
+            Value::Int(1_700_000_000),
+            Value::Int(250),
+            Value::Int(1000),
+        ])]);
+        let events = parse_latency_latest("10.0.0.1:6379", &latest);
+        assert_eq!(events[0].event, "command");
+        assert_eq!((events[0].latest, events[0].max), (250, 1000));
+
+        let history = Value::Array(vec![
+            Value::Array(vec![Value::Int(1_700_000_000), Value::Int(250)]),
+            Value::Array(vec![Value::Int(1_700_000_010), Value::Int(300)]),
+        ]);
+        assert_eq!(
+            parse_latency_history(&history),
+            vec![(1_700_000_000, 250), (1_700_000_010, 300)]
+        );
+    }
+
+    #[test]
+    fn filters_and_sorts_slowlog() {
+        let entry = |node: &str, id: u64, timestamp: i64, duration: u64, command: &str| SlowLogEntry {
+            node: node.to_string().into(),
+            id,
+            timestamp,
+            duration,
+            command: command.to_string().into(),
+            client: SharedString::default(),
+        };
+        let entries = vec![
+            entry("a", 1, 100, 500, "GET foo"),
+            entry("b", 1, 150, 20_000, "KEYS *"),
+            entry("a", 2, 200, 9_000, "HGETALL big"),
+        ];
+        assert_eq!(filter_slowlog(&entries, "", 0, SlowLogSort::Duration), vec![1, 2, 0]);
+        assert_eq!(filter_slowlog(&entries, "", 0, SlowLogSort::Time), vec![2, 1, 0]);
+        assert_eq!(filter_slowlog(&entries, "get", 0, SlowLogSort::Duration), vec![2, 0]);
+        assert_eq!(filter_slowlog(&entries, "", 1_000, SlowLogSort::Time), vec![2, 1]);
+    }
+}
@@ -14,11 +14,15 @@
 
 use crate::helpers::EditorAction;
 use crate::{
-    connection::KeyBackupProgress,
-    states::{ErrorMessage, NotificationAction, PresetCredential, ZedisServerState},
+    connection::{
+        BulkKeyProgress, HotKeyReport, KeyBackupProgress, KeyDeleteSummary, KeyTtlSummary, MemoryReport,
+        ScriptImportSummary, SshHostKey,
+    },
+    states::{ErrorMessage, NotificationAction, PresetCredential, ServerPanel, ZedisServerState},
 };
 use gpui::prelude::*;
 use gpui::{EventEmitter, SharedString};
+use std::sync::Arc;
 
 /// Background task types for Redis operations
 ///
@@ -40,15 +44,30 @@ pub enum ServerTask {
     /// Update the server soft wrap
     UpdateServerSoftWrap,
 
+    /// Update the key type filter of the key browser
+    UpdateServerKeyTypeFilter,
+
     /// Add new server or update existing server configuration
     UpdateOrInsertServer,
 
+    /// Record the key of an SSH server in the known hosts of Zedis
+    TrustHostKey,
+
+    /// Remove an SSH host key from the known hosts of Zedis
+    RemoveHostKey,
+
+    /// Open an RDB file read-only
+    OpenRdbFile,
+
     /// Fill in key types for unknown keys
     FillKeyTypes,
 
     /// Load value data for a selected key
     Selectkey,
 
+    /// Read the internal metadata of the selected key
+    InspectKey,
+
     /// Delete a key from Redis
     DeleteKey,
 
@@ -103,6 +122,42 @@ pub enum ServerTask {
     /// Update a value in a hash
     UpdateHashValue,
 
+    /// Add an entry to a stream
+    AddStreamValue,
+
+    /// Remove entries from a stream
+    RemoveStreamValues,
+
+    /// Trim a stream by length or minimum ID
+    TrimStream,
+
+    /// Load consumer groups of a stream
+    LoadStreamGroups,
+
+    /// Create, reposition or destroy a stream consumer group, or remove a consumer
+    UpdateStreamGroup,
+
+    /// Acknowledge pending stream entries
+    AckStreamEntries,
+
+    /// Claim pending stream entries for a consumer
+    ClaimStreamEntries,
+
+    /// Automatically claim idle pending stream entries for a consumer
+    AutoClaimStreamEntries,
+
+    /// Add an element to a vector set
+    AddVectorSetValue,
+
+    /// Remove elements from a vector set
+    RemoveVectorSetValues,
+
+    /// Update the attributes of a vector set element
+    UpdateVectorSetAttributes,
+
+    /// Run a similarity search on a vector set
+    SearchVectorSet,
+
     /// Save edited value back to Redis
     SaveValue,
 
@@ -120,9 +175,121 @@ pub enum ServerTask {
 
     /// Import keys from a file
     ImportKeys,
+
+    /// Load command documentation for the console
+    LoadCommandDocs,
+
+    /// List active Pub/Sub channels
+    LoadPubSubChannels,
+
+    /// Publish a Pub/Sub message
+    PublishMessage,
+
+    /// Write the Pub/Sub message log to a file
+    ExportPubSubLog,
+
+    /// Load slow log and latency diagnostics
+    LoadDiagnostics,
+
+    /// Reset the slow log of every master
+    ResetSlowlog,
+
+    /// Collect a metrics sample of every master
+    SampleMetrics,
+
+    /// Write the collected metrics to a CSV file
+    ExportMetrics,
+
+    /// Load the clients of every node
+    LoadClients,
+
+    /// Close the connection of a client
+    KillClient,
+
+    /// Toggle the no-evict mode of the Zedis connections
+    SetClientNoEvict,
+
+    /// Suspend the clients of every master
+    PauseClients,
+
+    /// Resume the paused clients
+    UnpauseClients,
+
+    /// Count the keys matching a pattern before deleting them
+    PreviewDeleteByPattern,
+
+    /// Delete the keys matching a pattern
+    DeleteByPattern,
+
+    /// Update the TTL of the keys matching a pattern or of selected keys
+    UpdateKeysTtl,
+
+    /// Copy keys to another database or server
+    CopyKeys,
+
+    /// Export keys with their decoded contents as JSON, NDJSON or commands
+    ExportKeysLogical,
+
+    /// Import keys from a JSON or NDJSON export
+    ImportKeysJson,
+
+    /// Run a script of Redis commands
+    ImportKeysScript,
+
+    /// Analyze the memory of the keys
+    AnalyzeMemory,
+
+    /// Write the memory report to a CSV or JSON file
+    ExportMemoryReport,
+
+    /// Rank the keys by their access frequency or idle time
+    AnalyzeHotKeys,
 }
 
 impl ServerTask {
+    /// Whether the task writes to the keys of the server, refused on a read-only replica.
+    ///
+    /// `CopyKeys` reads from the current server, copying into it is checked by `copy_keys`.
+    pub fn is_write(&self) -> bool {
+        matches!(
+            self,
+            ServerTask::DeleteKey
+                | ServerTask::DeleteKeys
+                | ServerTask::AddKey
+                | ServerTask::UpdateKeyTtl
+                | ServerTask::RenameKey
+                | ServerTask::RemoveListValue
+                | ServerTask::RemoveListValues
+                | ServerTask::UpdateListValue
+                | ServerTask::PushListValue
+                | ServerTask::AddSetValue
+                | ServerTask::RemoveSetValue
+                | ServerTask::AddZsetValue
+                | ServerTask::RemoveZsetValue
+                | ServerTask::RemoveHashValue
+                | ServerTask::RemoveHashValues
+                | ServerTask::UpdateHashValue
+                | ServerTask::AddStreamValue
+                | ServerTask::RemoveStreamValues
+                | ServerTask::TrimStream
+                | ServerTask::UpdateStreamGroup
+                | ServerTask::AckStreamEntries
+                | ServerTask::ClaimStreamEntries
+                | ServerTask::AutoClaimStreamEntries
+                | ServerTask::AddVectorSetValue
+                | ServerTask::RemoveVectorSetValues
+                | ServerTask::UpdateVectorSetAttributes
+                | ServerTask::SaveValue
+                | ServerTask::DuplicateKey
+                | ServerTask::RestoreKeyBackup
+                | ServerTask::ImportKeys
+                | ServerTask::DeleteByPattern
+                | ServerTask::UpdateKeysTtl
+                | ServerTask::ImportKeysJson
+                | ServerTask::ImportKeysScript
+        )
+    }
+
     /// Get string representation of task (for logging and error messages)
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -130,8 +297,12 @@ impl ServerTask {
             ServerTask::SelectServer => "select_server",
             ServerTask::RemoveServer => "remove_server",
             ServerTask::UpdateOrInsertServer => "update_or_insert_server",
+            ServerTask::TrustHostKey => "trust_host_key",
+            ServerTask::RemoveHostKey => "remove_host_key",
+            ServerTask::OpenRdbFile => "open_rdb_file",
             ServerTask::FillKeyTypes => "fill_key_types",
             ServerTask::Selectkey => "select_key",
+            ServerTask::InspectKey => "inspect_key",
             ServerTask::DeleteKey => "delete_key",
             ServerTask::DeleteKeys => "delete_keys",
             ServerTask::ScanKeys => "scan_keys",
@@ -146,6 +317,7 @@ impl ServerTask {
             ServerTask::SaveValue => "save_value",
             ServerTask::UpdateServerQueryMode => "update_server_query_mode",
             ServerTask::UpdateServerSoftWrap => "update_server_soft_wrap",
+            ServerTask::UpdateServerKeyTypeFilter => "update_server_key_type_filter",
             ServerTask::PushListValue => "push_list_value",
             ServerTask::AddSetValue => "add_set_value",
             ServerTask::RemoveSetValue => "remove_set_value",
@@ -154,11 +326,46 @@ impl ServerTask {
             ServerTask::RemoveHashValue => "remove_hash_value",
             ServerTask::RemoveHashValues => "remove_hash_values",
             ServerTask::UpdateHashValue => "update_hash_value",
+            ServerTask::AddStreamValue => "add_stream_value",
+            ServerTask::RemoveStreamValues => "remove_stream_values",
+            ServerTask::TrimStream => "trim_stream",
+            ServerTask::LoadStreamGroups => "load_stream_groups",
+            ServerTask::UpdateStreamGroup => "update_stream_group",
+            ServerTask::AckStreamEntries => "ack_stream_entries",
+            ServerTask::ClaimStreamEntries => "claim_stream_entries",
+            ServerTask::AutoClaimStreamEntries => "auto_claim_stream_entries",
+            ServerTask::AddVectorSetValue => "add_vector_set_value",
+            ServerTask::RemoveVectorSetValues => "remove_vector_set_values",
+            ServerTask::UpdateVectorSetAttributes => "update_vector_set_attributes",
+            ServerTask::SearchVectorSet => "search_vector_set",
             ServerTask::DuplicateKey => "duplicate_key",
             ServerTask::ExportKeys => "export_keys",
             ServerTask::ExportKeyBackup => "export_key_backup",
             ServerTask::RestoreKeyBackup => "restore_key_backup",
             ServerTask::ImportKeys => "import_keys",
+            ServerTask::LoadCommandDocs => "load_command_docs",
+            ServerTask::LoadPubSubChannels => "load_pubsub_channels",
+            ServerTask::PublishMessage => "publish_message",
+            ServerTask::ExportPubSubLog => "export_pubsub_log",
+            ServerTask::LoadDiagnostics => "load_diagnostics",
+            ServerTask::ResetSlowlog => "reset_slowlog",
+            ServerTask::SampleMetrics => "sample_metrics",
+            ServerTask::ExportMetrics => "export_metrics",
+            ServerTask::LoadClients => "load_clients",
+            ServerTask::KillClient => "kill_client",
+            ServerTask::SetClientNoEvict => "set_client_no_evict",
+            ServerTask::PauseClients => "pause_clients",
+            ServerTask::UnpauseClients => "unpause_clients",
+            ServerTask::PreviewDeleteByPattern => "preview_delete_by_pattern",
+            ServerTask::DeleteByPattern => "delete_by_pattern",
+            ServerTask::UpdateKeysTtl => "update_keys_ttl",
+            ServerTask::CopyKeys => "copy_keys",
+            ServerTask::ExportKeysLogical => "export_keys_logical",
+            ServerTask::ImportKeysJson => "import_keys_json",
+            ServerTask::ImportKeysScript => "import_keys_script",
+            ServerTask::AnalyzeMemory => "analyze_memory",
+            ServerTask::ExportMemoryReport => "export_memory_report",
+            ServerTask::AnalyzeHotKeys => "analyze_hot_keys",
         }
     }
 }
@@ -171,8 +378,8 @@ pub enum ServerEvent {
     /// A background task has completed.
     TaskFinished(SharedString),
 
-    /// Terminal toggled
-    TerminalToggled(bool),
+    /// The panel next to the key tree changed
+    PanelToggled(ServerPanel),
 
     /// A key has been selected for viewing/editing
     KeySelected(SharedString),
@@ -200,6 +407,8 @@ pub enum ServerEvent {
 
     /// User selected a different server
     ServerSelected(SharedString, usize),
+    /// The key of the SSH server is unknown, the user is asked to trust it.
+    HostKeyPrompt(Arc<SshHostKey>),
     /// Server list config has been modified (add/remove/edit).
     ServerListUpdated,
     /// Server metadata (info/dbsize) has been refreshed.
@@ -253,6 +462,30 @@ pub enum ServerEvent {
 
     /// Keys have been imported (success_count, fail_count)
     KeysImported(usize, usize),
+
+    /// The keys matching a pattern have been counted (dry run of a delete by pattern).
+    KeyDeletePreviewed(Arc<KeyDeleteSummary>),
+
+    /// Progress of a bulk key operation has changed.
+    BulkKeyProgress(BulkKeyProgress),
+
+    /// The TTL of many keys has been updated.
+    KeysTtlUpdated(Arc<KeyTtlSummary>),
+
+    /// Keys have been copied to another database or server.
+    KeysCopied,
+
+    /// A script of Redis commands has been run.
+    ScriptImported(Arc<ScriptImportSummary>),
+
+    /// The memory analysis has finished.
+    MemoryAnalyzed(Arc<MemoryReport>),
+
+    /// The hot key detection has finished.
+    HotKeysAnalyzed(Arc<HotKeyReport>),
+
+    /// The key tree should list the keys starting with a prefix.
+    KeyFilterRequested(SharedString),
 }
 
 impl EventEmitter<ServerEvent> for ZedisServerState {}
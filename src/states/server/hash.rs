@@ -28,7 +28,10 @@ use super::{
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
     error::Error,
-    helpers::codec::{CompressionFormat, MAX_DECOMPRESS_BYTES, decompress, detect},
+    helpers::{
+        RedisKey,
+        codec::{CompressionFormat, MAX_DECOMPRESS_BYTES, decompress, detect},
+    },
     states::{NotificationAction, ServerEvent, i18n_hash_editor},
 };
 use bytes::Bytes;
@@ -94,7 +97,7 @@ async fn get_redis_hash_value(
 
     // Execute HSCAN with MATCH and COUNT options
     let (next_cursor, raw_values): HashScanValue = cmd("HSCAN")
-        .arg(key)
+        .arg(RedisKey::from(key))
         .arg(cursor)
         .arg("MATCH")
         .arg(pattern)
@@ -135,7 +138,7 @@ async fn get_redis_hash_value(
 /// A `RedisValue` containing HASH metadata and initial field-value pairs
 pub(crate) async fn first_load_hash_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
     // Get total number of fields in the HASH
-    let size: usize = cmd("HLEN").arg(key).query_async(conn).await?;
+    let size: usize = cmd("HLEN").arg(RedisKey::from(key)).query_async(conn).await?;
 
     // Load first batch of field-value pairs (up to 100)
     let (cursor, values) = get_redis_hash_value(conn, key, None, 0, 100).await?;
@@ -203,7 +206,7 @@ impl ZedisServerState {
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 let bytes: Vec<u8> = cmd("HGET")
-                    .arg(key_for_task.as_str())
+                    .arg(RedisKey::from(&key_for_task))
                     .arg(field.as_str())
                     .query_async(&mut conn)
                     .await?;
@@ -310,7 +313,7 @@ impl ZedisServerState {
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 let _: () = cmd("HSET")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(field.as_str())
                     .arg(new_bytes_vec)
                     .query_async(&mut conn)
@@ -392,7 +395,7 @@ impl ZedisServerState {
 
                 // HSET returns 1 if new field created, 0 if existing field updated
                 let count: usize = cmd("HSET")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(new_field.as_str())
                     .arg(new_value.as_str())
                     .query_async(&mut conn)
@@ -509,7 +512,7 @@ impl ZedisServerState {
 
                 // HDEL returns number of fields removed (0 if doesn't exist, 1 if removed)
                 let count: usize = cmd("HDEL")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(remove_field.as_str())
                     .query_async(&mut conn)
                     .await?;
@@ -577,7 +580,7 @@ impl ZedisServerState {
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 let mut binding = cmd("HDEL");
-                binding.arg(key.as_str());
+                binding.arg(RedisKey::from(&key));
                 for field in &fields_for_task {
                     binding.arg(field.as_str());
                 }
@@ -0,0 +1,80 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SSH host keys trusted from the app.
+//!
+//! A connection through an SSH tunnel fails with `UnknownHostKey` until the key of the
+//! SSH server is trusted, the failed task then emits `HostKeyPrompt` so the user can
+//! check the fingerprint. Trusting the key reconnects to the server.
+
+use super::{ServerTask, ZedisServerState};
+use crate::{
+    connection::{SshHostKey, remove_host_key, trust_host_key},
+    states::{NotificationAction, ServerEvent, ZedisGlobalStore},
+};
+use gpui::prelude::*;
+use rust_i18n::t;
+use std::sync::Arc;
+
+impl ZedisServerState {
+    /// Records the key of an SSH server and reconnects to the current server.
+    pub fn trust_host_key(&mut self, key: Arc<SshHostKey>, cx: &mut Context<Self>) {
+        let host_key = key.clone();
+        self.spawn(
+            ServerTask::TrustHostKey,
+            move || async move { trust_host_key(&host_key) },
+            move |this, result, cx| {
+                if result.is_err() {
+                    return;
+                }
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                let message = t!(
+                    "servers.host_key_trusted",
+                    host = format!("{}:{}", key.host, key.port),
+                    locale = locale
+                )
+                .to_string();
+                cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                    message.into(),
+                )));
+                this.reconnect(this.preset_credentials.clone(), cx);
+            },
+            cx,
+        );
+    }
+
+    /// Removes a key trusted from the app, the next connection asks to trust it again.
+    pub fn remove_host_key(&mut self, key: SshHostKey, cx: &mut Context<Self>) {
+        self.spawn(
+            ServerTask::RemoveHostKey,
+            move || async move {
+                remove_host_key(&key)?;
+                Ok(key)
+            },
+            move |_this, result, cx| {
+                if let Ok(key) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!(
+                        "servers.host_key_removed",
+                        fingerprint = key.fingerprint,
+                        locale = locale
+                    )
+                    .to_string();
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_info(message.into())));
+                }
+            },
+            cx,
+        );
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hot key detection.
+//!
+//! Like the memory analyzer, the detection runs as a bulk operation with progress and
+//! cancellation. The hottest and coldest keys of every master are kept until another
+//! server is selected.
+
+use super::{ServerTask, ZedisServerState};
+use crate::{
+    connection::{HotKey, HotKeyReport, get_connection_manager},
+    states::{NotificationAction, ServerEvent, i18n_hot_keys},
+};
+use gpui::{SharedString, prelude::*};
+use std::sync::Arc;
+
+/// Number of hottest and coldest keys kept for each node by default
+pub const DEFAULT_HOT_KEYS: usize = 50;
+
+/// Ranking shown by the hot keys panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HotKeyCategory {
+    #[default]
+    Hottest,
+    Coldest,
+}
+
+/// The column the hot keys are sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HotKeySort {
+    Node,
+    Key,
+    /// Access counter or idle time
+    Value,
+    /// The ranking of each node, hottest or coldest first
+    #[default]
+    Rank,
+}
+
+/// Filters and sorts the ranked keys of every node.
+///
+/// # Arguments
+/// * `report` - Result of the hot key detection
+/// * `category` - Hottest or coldest keys
+/// * `keyword` - Case-insensitive substring of the key or node
+/// * `sort` - Sort column
+/// * `descending` - Whether the order of the column is reversed
+pub fn filter_hot_keys<'a>(
+    report: &'a HotKeyReport,
+    category: HotKeyCategory,
+    keyword: &str,
+    sort: HotKeySort,
+    descending: bool,
+) -> Vec<(usize, &'a HotKey)> {
+    let keyword = keyword.to_lowercase();
+    let mut items: Vec<(usize, &HotKey)> = report
+        .nodes
+        .iter()
+        .flat_map(|node| {
+            let keys = match category {
+                HotKeyCategory::Hottest => &node.hottest,
+                HotKeyCategory::Coldest => &node.coldest,
+            };
+            keys.iter().enumerate()
+        })
+        .filter(|(_, item)| {
+            keyword.is_empty()
+                || item.key.to_lowercase().contains(&keyword)
+                || item.node.to_lowercase().contains(&keyword)
+        })
+        .collect();
+    items.sort_by(|(a_rank, a), (b_rank, b)| match sort {
+        HotKeySort::Node => (&a.node, a_rank).cmp(&(&b.node, b_rank)),
+        HotKeySort::Key => a.key.cmp(&b.key),
+        HotKeySort::Value => (a.value, &a.key).cmp(&(b.value, &b.key)),
+        HotKeySort::Rank => (a_rank, &a.node).cmp(&(b_rank, &b.node)),
+    });
+    if descending {
+        items.reverse();
+    }
+    items
+}
+
+impl ZedisServerState {
+    /// Returns the result of the last hot key detection.
+    pub fn hot_key_report(&self) -> Option<Arc<HotKeyReport>> {
+        self.hot_key_report.clone()
+    }
+
+    /// Whether the hot key detection is running.
+    pub fn is_hot_keys_analyzing(&self) -> bool {
+        self.hot_keys_analyzing
+    }
+
+    /// Ranks the keys matching a pattern by `OBJECT FREQ` or `OBJECT IDLETIME`.
+    ///
+    /// Emits `HotKeysAnalyzed` with the rankings, also when the detection was cancelled.
+    ///
+    /// # Arguments
+    /// * `pattern` - SCAN MATCH pattern, every key when empty
+    /// * `limit` - Number of hottest and coldest keys kept for each node
+    pub fn analyze_hot_keys(&mut self, pattern: SharedString, limit: usize, cx: &mut Context<Self>) {
+        let Some(cancelled) = self.start_bulk_operation(cx) else {
+            return;
+        };
+        let pattern = if pattern.trim().is_empty() {
+            "*".to_string()
+        } else {
+            pattern.trim().to_string()
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let preset_credentials = self.preset_credentials.clone();
+        let (progress_tx, progress_rx) = futures::channel::mpsc::unbounded();
+        self.watch_bulk_progress(progress_rx, cx);
+        self.hot_keys_analyzing = true;
+
+        self.spawn(
+            ServerTask::AnalyzeHotKeys,
+            move || async move {
+                get_connection_manager()
+                    .analyze_hot_keys(
+                        &server_id,
+                        db,
+                        preset_credentials,
+                        &pattern,
+                        limit,
+                        Some(progress_tx),
+                        cancelled,
+                    )
+                    .await
+            },
+            move |this, result, cx| {
+                this.bulk_cancel = None;
+                this.hot_keys_analyzing = false;
+                if let Ok(report) = result {
+                    if report.cancelled {
+                        let message = i18n_hot_keys(cx, "cancelled");
+                        cx.emit(ServerEvent::Notification(NotificationAction::new_warning(message)));
+                    }
+                    let report = Arc::new(report);
+                    this.hot_key_report = Some(report.clone());
+                    cx.emit(ServerEvent::HotKeysAnalyzed(report));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::NodeHotKeys;
+
+    fn node(node: &str, hottest: &[&str]) -> NodeHotKeys {
+        let keys: Vec<HotKey> = hottest
+            .iter()
+            .map(|key| HotKey {
+                key: key.to_string(),
+                node: node.to_string(),
+                value: 0,
+            })
+            .collect();
+        NodeHotKeys {
+            node: node.to_string(),
+            coldest: keys.iter().rev().cloned().collect(),
+            hottest: keys,
+            ..Default::default()
+        }
+    }
+
+    fn keys(items: Vec<(usize, &HotKey)>) -> Vec<String> {
+        items
+            .iter()
+            .map(|(_, item)| format!("{}/{}", item.node, item.key))
+            .collect()
+    }
+
+    #[test]
+    fn filters_and_sorts_hot_keys() {
+        let report = HotKeyReport {
+            nodes: vec![node("n1", &["user:1", "cart:9"]), node("n2", &["user:7"])],
+            ..Default::default()
+        };
+        let hottest = filter_hot_keys(&report, HotKeyCategory::Hottest, "", HotKeySort::Rank, false);
+        assert_eq!(keys(hottest), vec!["n1/user:1", "n2/user:7", "n1/cart:9"]);
+        let coldest = filter_hot_keys(&report, HotKeyCategory::Coldest, "", HotKeySort::Node, false);
+        assert_eq!(keys(coldest), vec!["n1/cart:9", "n1/user:1", "n2/user:7"]);
+        let by_key = filter_hot_keys(&report, HotKeyCategory::Hottest, "", HotKeySort::Key, true);
+        assert_eq!(keys(by_key), vec!["n2/user:7", "n1/user:1", "n1/cart:9"]);
+        let filtered = filter_hot_keys(&report, HotKeyCategory::Hottest, "USER", HotKeySort::Rank, false);
+        assert_eq!(keys(filtered), vec!["n1/user:1", "n2/user:7"]);
+        let by_node = filter_hot_keys(&report, HotKeyCategory::Hottest, "n2", HotKeySort::Value, false);
+        assert_eq!(keys(by_node), vec!["n2/user:7"]);
+    }
+}
@@ -13,14 +13,17 @@
 // limitations under the License.
 
 use super::{
-    ServerEvent, ServerTask, ZedisServerState,
+    ServerEvent, ServerPanel, ServerTask, ZedisServerState,
     hash::first_load_hash_value,
     list::first_load_list_value,
     set::first_load_set_value,
+    stream::first_load_stream_value,
     string::get_redis_value,
     value::{KeyType, RedisValue, RedisValueStatus, SortOrder},
+    vector_set::first_load_vector_set_value,
     zset::first_load_zset_value,
 };
+use crate::helpers::RedisKey;
 use crate::helpers::codec::{bytes_to_compact_hex, hex_to_bytes};
 use crate::states::{NotificationAction, ZedisGlobalStore};
 use crate::{
@@ -92,7 +95,7 @@ impl ZedisServerState {
                         let key = key.clone();
                         async move {
                             let t: String = cmd("TYPE")
-                                .arg(key.as_str())
+                                .arg(RedisKey::from(&key))
                                 .query_async(&mut conn_clone)
                                 .await
                                 .unwrap_or_default();
@@ -137,6 +140,7 @@ impl ZedisServerState {
         let processing_keyword = keyword.clone();
         let db = self.db;
         let preset_credentials = self.preset_credentials.clone();
+        let key_type = self.key_type_filter.map(|key_type| key_type.type_name());
         self.spawn(
             ServerTask::ScanKeys,
             move || async move {
@@ -151,9 +155,9 @@ impl ZedisServerState {
                 // Adjust count based on keyword specificity
                 let count = if keyword.is_empty() { 2_000 } else { 10_000 };
                 if let Some(cursors) = cursors {
-                    client.scan(cursors, &pattern, count).await
+                    client.scan(cursors, &pattern, count, key_type).await
                 } else {
-                    client.first_scan(&pattern, count).await
+                    client.first_scan(&pattern, count, key_type).await
                 }
             },
             move |this, result, cx| {
@@ -201,7 +205,8 @@ impl ZedisServerState {
         self.keyword = keyword.clone();
         match self.query_mode {
             QueryMode::Prefix => self.scan_prefix(keyword, cx),
-            QueryMode::Exact => self.select_key(keyword, cx),
+            // Normalize typed escapes so the key matches the scanned (escaped) form
+            QueryMode::Exact => self.select_key(RedisKey::from(&keyword).escaped(), cx),
             _ => self.scan(keyword, cx),
         }
     }
@@ -262,6 +267,7 @@ impl ZedisServerState {
         let db = self.db;
         let pattern = format!("{}*", prefix);
         let preset_credentials = self.preset_credentials.clone();
+        let key_type = self.key_type_filter.map(|key_type| key_type.type_name());
         self.spawn(
             ServerTask::ScanPrefix,
             move || async move {
@@ -277,9 +283,9 @@ impl ZedisServerState {
                 // to gather a sufficient amount without blocking for too long.
                 for _ in 0..20 {
                     let (new_cursor, keys) = if let Some(cursors) = cursors.clone() {
-                        client.scan(cursors, &pattern, count).await?
+                        client.scan(cursors, &pattern, count, key_type).await?
                     } else {
-                        client.first_scan(&pattern, count).await?
+                        client.first_scan(&pattern, count, key_type).await?
                     };
                     result_keys.extend(keys);
                     // Break if scan cycle finishes
@@ -326,7 +332,7 @@ impl ZedisServerState {
         if key.is_empty() {
             return;
         }
-        self.terminal = false;
+        self.panel = ServerPanel::Editor;
         // only set loading status if the value exists for better performance
         // prevent editor flickering
         if let Some(value) = self.value.as_mut() {
@@ -351,9 +357,9 @@ impl ZedisServerState {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 let (t, ttl): (String, i64) = pipe()
                     .cmd("TYPE")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .cmd("TTL")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .query_async(&mut conn)
                     .await?;
                 // the key does not exist
@@ -377,13 +383,15 @@ impl ZedisServerState {
                     KeyType::Set => first_load_set_value(&mut conn, &key).await,
                     KeyType::Zset => first_load_zset_value(&mut conn, &key, SortOrder::Asc).await,
                     KeyType::Hash => first_load_hash_value(&mut conn, &key).await,
+                    KeyType::Stream => first_load_stream_value(&mut conn, &key, SortOrder::Desc).await,
+                    KeyType::Vectorset => first_load_vector_set_value(&mut conn, &key).await,
                     _ => Err(Error::Invalid {
                         message: "unsupported key type".to_string(),
                     }),
                 }?;
                 if let Ok(memory_usage) = cmd("MEMORY")
                     .arg("USAGE")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .query_async::<u64>(&mut conn)
                     .await
                 {
@@ -445,7 +453,7 @@ impl ZedisServerState {
             ServerTask::DeleteKey,
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
-                let _: () = cmd("DEL").arg(key.as_str()).query_async(&mut conn).await?;
+                let _: () = cmd("DEL").arg(RedisKey::from(&key)).query_async(&mut conn).await?;
                 Ok(())
             },
             move |this, result, cx| {
@@ -482,7 +490,7 @@ impl ZedisServerState {
                 // Use pipeline for batch deletion
                 let mut pipeline = pipe();
                 for key in &keys_to_delete {
-                    pipeline.cmd("DEL").arg(key.as_str());
+                    pipeline.cmd("DEL").arg(RedisKey::from(&key));
                 }
                 let _: Vec<i64> = pipeline.query_async(&mut conn).await?;
                 Ok(keys_to_delete)
@@ -553,7 +561,7 @@ impl ZedisServerState {
                 }
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 let _: () = cmd("EXPIRE")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(new_ttl.as_secs())
                     .query_async(&mut conn)
                     .await?;
@@ -575,6 +583,7 @@ impl ZedisServerState {
     /// Renames a key using the Redis RENAME command.
     /// Caller is expected to pass a trimmed, validated `new_key`.
     pub fn rename_key(&mut self, old_key: SharedString, new_key: SharedString, cx: &mut Context<Self>) {
+        let new_key = RedisKey::from(&new_key).escaped();
         if new_key.is_empty() || old_key == new_key {
             return;
         }
@@ -587,8 +596,8 @@ impl ZedisServerState {
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 let _: () = cmd("RENAME")
-                    .arg(old_key.as_str())
-                    .arg(new_key.as_str())
+                    .arg(RedisKey::from(&old_key))
+                    .arg(RedisKey::from(&new_key))
                     .query_async(&mut conn)
                     .await?;
                 Ok(())
@@ -608,6 +617,7 @@ impl ZedisServerState {
     }
 
     pub fn add_key(&mut self, category: SharedString, key: SharedString, ttl: SharedString, cx: &mut Context<Self>) {
+        let key = RedisKey::from(&key).escaped();
         let server_id = self.server_id.clone();
         let db = self.db;
         let key_type = KeyType::from(category.to_lowercase().as_str());
@@ -616,7 +626,7 @@ impl ZedisServerState {
             ServerTask::AddKey,
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
-                let exists: bool = cmd("EXISTS").arg(key.as_str()).query_async(&mut conn).await?;
+                let exists: bool = cmd("EXISTS").arg(RedisKey::from(&key)).query_async(&mut conn).await?;
                 let ttl_duration = if ttl.is_empty() {
                     None
                 } else if let Ok(secs) = ttl.parse::<u64>() {
@@ -633,25 +643,29 @@ impl ZedisServerState {
                 }
                 match key_type {
                     KeyType::String => {
-                        let _: () = cmd("SET").arg(key.as_str()).arg("").query_async(&mut conn).await?;
+                        let _: () = cmd("SET")
+                            .arg(RedisKey::from(&key))
+                            .arg("")
+                            .query_async(&mut conn)
+                            .await?;
                     }
                     KeyType::List => {
                         let _: () = cmd("LPUSH")
-                            .arg(key.as_str())
+                            .arg(RedisKey::from(&key))
                             .arg("list item 1")
                             .query_async(&mut conn)
                             .await?;
                     }
                     KeyType::Set => {
                         let _: () = cmd("SADD")
-                            .arg(key.as_str())
+                            .arg(RedisKey::from(&key))
                             .arg("set item 1")
                             .query_async(&mut conn)
                             .await?;
                     }
                     KeyType::Zset => {
                         let _: () = cmd("ZADD")
-                            .arg(key.as_str())
+                            .arg(RedisKey::from(&key))
                             .arg(1.0)
                             .arg("zset item 1")
                             .query_async(&mut conn)
@@ -659,7 +673,7 @@ impl ZedisServerState {
                     }
                     KeyType::Hash => {
                         let _: () = cmd("HSET")
-                            .arg(key.as_str())
+                            .arg(RedisKey::from(&key))
                             .arg("field1")
                             .arg("value1")
                             .query_async(&mut conn)
@@ -673,7 +687,7 @@ impl ZedisServerState {
                 };
                 if let Some(ttl_duration) = ttl_duration {
                     let _: () = cmd("EXPIRE")
-                        .arg(key.as_str())
+                        .arg(RedisKey::from(&key))
                         .arg(ttl_duration.as_secs())
                         .query_async(&mut conn)
                         .await?;
@@ -712,8 +726,8 @@ impl ZedisServerState {
                 // Batch all DUMP + PTTL commands into a single pipeline
                 let mut pipeline = pipe();
                 for key in &keys {
-                    pipeline.cmd("DUMP").arg(key.as_str());
-                    pipeline.cmd("PTTL").arg(key.as_str());
+                    pipeline.cmd("DUMP").arg(RedisKey::from(&key));
+                    pipeline.cmd("PTTL").arg(RedisKey::from(&key));
                 }
                 let results: Vec<redis::Value> = pipeline.query_async(&mut conn).await?;
 
@@ -732,7 +746,7 @@ impl ZedisServerState {
                                 message: format!("Failed to parse DUMP/PTTL result: {}", e),
                             }
                         })?;
-                    let hex_key = bytes_to_compact_hex(key.as_bytes());
+                    let hex_key = bytes_to_compact_hex(RedisKey::from(key).as_bytes());
                     let hex_dump = bytes_to_compact_hex(&dump);
                     lines.push(format!("{},{},{}", hex_key, hex_dump, pttl));
                 }
@@ -797,10 +811,9 @@ impl ZedisServerState {
                             }
                         };
                     let restore_ttl = if pttl < 0 { 0 } else { pttl };
-                    let key = String::from_utf8_lossy(&key_bytes);
                     pipeline
                         .cmd("RESTORE")
-                        .arg(key.as_ref())
+                        .arg(&key_bytes)
                         .arg(restore_ttl)
                         .arg(&dump_bytes)
                         .arg("REPLACE");
@@ -857,16 +870,22 @@ impl ZedisServerState {
                 let dest_key = find_available_key_name(&mut conn, &source_key).await?;
 
                 // Get TTL for the source key
-                let ttl: i64 = cmd("PTTL").arg(source_key.as_str()).query_async(&mut conn).await?;
+                let ttl: i64 = cmd("PTTL")
+                    .arg(RedisKey::from(&source_key))
+                    .query_async(&mut conn)
+                    .await?;
 
                 // Use DUMP + RESTORE for compatibility with older Redis versions
-                let dump: Vec<u8> = cmd("DUMP").arg(source_key.as_str()).query_async(&mut conn).await?;
+                let dump: Vec<u8> = cmd("DUMP")
+                    .arg(RedisKey::from(&source_key))
+                    .query_async(&mut conn)
+                    .await?;
 
                 // Use 0 TTL if key is persistent (-1) or expired (-2)
                 let restore_ttl = if ttl < 0 { 0 } else { ttl };
 
                 let _: () = cmd("RESTORE")
-                    .arg(dest_key.as_str())
+                    .arg(RedisKey::from(&dest_key))
                     .arg(restore_ttl)
                     .arg(&dump)
                     .query_async(&mut conn)
@@ -897,7 +916,7 @@ async fn find_available_key_name<C: redis::aio::ConnectionLike>(
 ) -> Result<SharedString, Error> {
     for i in 1..=1000 {
         let candidate = format!("{}-{}", base_key, i);
-        let exists: bool = cmd("EXISTS").arg(&candidate).query_async(conn).await?;
+        let exists: bool = cmd("EXISTS").arg(RedisKey::from(&candidate)).query_async(conn).await?;
         if !exists {
             return Ok(candidate.into());
         }
@@ -20,6 +20,7 @@ use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
     error::Error,
     helpers::{
+        RedisKey,
         codec::{CompressionFormat, MAX_DECOMPRESS_BYTES, decompress, detect},
         fast_contains_ignore_case,
     },
@@ -96,7 +97,12 @@ fn bytes_to_display_string(bytes: &[u8]) -> String {
 /// Returns a vector of strings. Binary data is lossily converted to UTF-8.
 async fn get_redis_list_value(conn: &mut RedisAsyncConn, key: &str, start: usize, stop: usize) -> Result<Vec<String>> {
     // Fetch raw bytes to handle binary data safely
-    let value: Vec<Vec<u8>> = cmd("LRANGE").arg(key).arg(start).arg(stop).query_async(conn).await?;
+    let value: Vec<Vec<u8>> = cmd("LRANGE")
+        .arg(RedisKey::from(key))
+        .arg(start)
+        .arg(stop)
+        .query_async(conn)
+        .await?;
     if value.is_empty() {
         return Ok(vec![]);
     }
@@ -107,7 +113,7 @@ async fn get_redis_list_value(conn: &mut RedisAsyncConn, key: &str, start: usize
 /// Initial load for a List key.
 /// Fetches the total length (LLEN) and the first 100 items.
 pub(crate) async fn first_load_list_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
-    let size: usize = cmd("LLEN").arg(key).query_async(conn).await?;
+    let size: usize = cmd("LLEN").arg(RedisKey::from(key)).query_async(conn).await?;
     let values = get_redis_list_value(conn, key, 0, LIST_PAGE_SIZE - 1).await?;
     Ok(RedisValue {
         key_type: KeyType::List,
@@ -175,11 +181,11 @@ impl ZedisServerState {
                 let _: () = pipe()
                     .atomic()
                     .cmd("LSET")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(index)
                     .arg(&unique_marker)
                     .cmd("LREM")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(1)
                     .arg(&unique_marker)
                     .query_async(&mut conn)
@@ -245,7 +251,7 @@ impl ZedisServerState {
                     .collect::<Vec<_>>();
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 let mut binding = cmd("EVAL");
-                binding.arg(REMOVE_LIST_VALUES_SCRIPT).arg(1).arg(key.as_str());
+                binding.arg(REMOVE_LIST_VALUES_SCRIPT).arg(1).arg(RedisKey::from(&key));
                 for (index, marker) in indexes_for_task.iter().zip(markers.iter()) {
                     binding.arg(*index).arg(marker);
                 }
@@ -310,7 +316,7 @@ impl ZedisServerState {
                 let cmd_name = if is_lpush { "LPUSH" } else { "RPUSH" };
 
                 let _: () = cmd(cmd_name)
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(new_value.as_str())
                     .query_async(&mut conn)
                     .await?;
@@ -384,7 +390,7 @@ impl ZedisServerState {
                 // 1. Optimistic Lock Check: Get current value as bytes
                 // Use bytes to handle compressed/binary data correctly
                 let current_bytes: Vec<u8> = cmd("LINDEX")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(index)
                     .query_async(&mut conn)
                     .await?;
@@ -403,7 +409,7 @@ impl ZedisServerState {
 
                 // 2. Perform Update
                 let _: () = cmd("LSET")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(index)
                     .arg(new_value_clone.as_str())
                     .query_async(&mut conn)
@@ -552,7 +558,7 @@ impl ZedisServerState {
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 let bytes: Vec<u8> = cmd("LINDEX")
-                    .arg(key_clone.as_str())
+                    .arg(RedisKey::from(&key_clone))
                     .arg(index)
                     .query_async(&mut conn)
                     .await?;
@@ -602,7 +608,7 @@ impl ZedisServerState {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
 
                 let _: () = cmd("LSET")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(index)
                     .arg(new_bytes_vec)
                     .query_async(&mut conn)
@@ -0,0 +1,131 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory analyzer.
+//!
+//! The analysis runs as a bulk operation, so it reports its progress in the status bar
+//! and can be cancelled. The report of the last analysis is kept until another server
+//! is selected.
+
+use super::{ServerTask, ZedisServerState};
+use crate::{
+    connection::{MemoryAnalysisOptions, MemoryReport, get_connection_manager},
+    states::{NotificationAction, ServerEvent, ZedisGlobalStore, i18n_memory},
+};
+use gpui::{SharedString, prelude::*};
+use rust_i18n::t;
+use std::{path::PathBuf, sync::Arc};
+use tracing::info;
+
+impl ZedisServerState {
+    /// Returns the report of the last memory analysis.
+    pub fn memory_report(&self) -> Option<Arc<MemoryReport>> {
+        self.memory_report.clone()
+    }
+
+    /// Whether the memory analysis is running.
+    pub fn is_memory_analyzing(&self) -> bool {
+        self.memory_analyzing
+    }
+
+    /// Analyzes the memory of the keys matching a pattern.
+    ///
+    /// Prefixes are split with the key separator of the key tree.
+    /// Emits `MemoryAnalyzed` with the report, also when the analysis was cancelled.
+    ///
+    /// # Arguments
+    /// * `pattern` - SCAN MATCH pattern, every key when empty
+    /// * `top_keys` - Number of biggest keys kept for each type
+    pub fn analyze_memory(&mut self, pattern: SharedString, top_keys: usize, cx: &mut Context<Self>) {
+        let Some(cancelled) = self.start_bulk_operation(cx) else {
+            return;
+        };
+        let options = MemoryAnalysisOptions {
+            pattern: if pattern.trim().is_empty() {
+                "*".to_string()
+            } else {
+                pattern.trim().to_string()
+            },
+            separator: cx.global::<ZedisGlobalStore>().read(cx).key_separator().to_string(),
+            top_keys,
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let preset_credentials = self.preset_credentials.clone();
+        let (progress_tx, progress_rx) = futures::channel::mpsc::unbounded();
+        self.watch_bulk_progress(progress_rx, cx);
+        self.memory_analyzing = true;
+
+        self.spawn(
+            ServerTask::AnalyzeMemory,
+            move || async move {
+                get_connection_manager()
+                    .analyze_memory(
+                        &server_id,
+                        db,
+                        preset_credentials,
+                        options,
+                        Some(progress_tx),
+                        cancelled,
+                    )
+                    .await
+            },
+            move |this, result, cx| {
+                this.bulk_cancel = None;
+                this.memory_analyzing = false;
+                if let Ok(report) = result {
+                    if report.cancelled {
+                        let message = i18n_memory(cx, "cancelled");
+                        cx.emit(ServerEvent::Notification(NotificationAction::new_warning(message)));
+                    }
+                    let report = Arc::new(report);
+                    this.memory_report = Some(report.clone());
+                    cx.emit(ServerEvent::MemoryAnalyzed(report));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Writes the last memory report to a CSV or JSON file, depending on its extension.
+    pub fn export_memory_report(&mut self, path: PathBuf, cx: &mut Context<Self>) {
+        let Some(report) = self.memory_report.clone() else {
+            return;
+        };
+        self.spawn(
+            ServerTask::ExportMemoryReport,
+            move || async move {
+                report.export(&path)?;
+                info!(path = %path.display(), "Memory report exported");
+                Ok(path)
+            },
+            move |_this, result, cx| {
+                if let Ok(path) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!("memory.exported", path = path.display(), locale = locale).to_string();
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
+                }
+            },
+            cx,
+        );
+    }
+
+    /// Lists the keys starting with a prefix in the key tree.
+    pub fn filter_keys_by_prefix(&mut self, prefix: SharedString, cx: &mut Context<Self>) {
+        cx.emit(ServerEvent::KeyFilterRequested(prefix));
+    }
+}
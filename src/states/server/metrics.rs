@@ -0,0 +1,372 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rolling history of server metrics.
+//!
+//! Every sample keeps the `INFO ALL` metrics of each master node, so the dashboard can
+//! chart a single node or the whole cluster. Counters (keyspace hits/misses, evicted keys)
+//! are turned into per-interval values when a series is built.
+
+use super::{ServerTask, ZedisServerState, stat::RedisInfo};
+use crate::{
+    connection::get_connection_manager,
+    states::{NotificationAction, ServerEvent, ZedisGlobalStore},
+};
+use chrono::{Local, TimeZone, Utc};
+use gpui::{SharedString, prelude::*};
+use redis::cmd;
+use rust_i18n::t;
+use std::{collections::VecDeque, fmt::Write, fs, path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// Maximum number of samples kept in memory
+const MAX_METRICS_SAMPLES: usize = 720;
+
+/// Sampling intervals offered by the dashboard, in seconds
+pub const METRICS_INTERVALS: [u64; 5] = [1, 2, 5, 10, 30];
+
+/// A metric charted by the dashboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    OpsPerSec,
+    UsedMemory,
+    /// Keyspace hit rate (%) over the sampling interval
+    HitRate,
+    ConnectedClients,
+    InputKbps,
+    OutputKbps,
+    /// Evicted keys per second over the sampling interval
+    Evictions,
+    Fragmentation,
+}
+
+impl Metric {
+    pub fn all() -> &'static [Metric] {
+        &[
+            Metric::OpsPerSec,
+            Metric::UsedMemory,
+            Metric::HitRate,
+            Metric::ConnectedClients,
+            Metric::InputKbps,
+            Metric::OutputKbps,
+            Metric::Evictions,
+            Metric::Fragmentation,
+        ]
+    }
+
+    /// The CSV column, also used as i18n key.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Metric::OpsPerSec => "ops_per_sec",
+            Metric::UsedMemory => "used_memory",
+            Metric::HitRate => "hit_rate",
+            Metric::ConnectedClients => "connected_clients",
+            Metric::InputKbps => "input_kbps",
+            Metric::OutputKbps => "output_kbps",
+            Metric::Evictions => "evictions_per_sec",
+            Metric::Fragmentation => "mem_fragmentation_ratio",
+        }
+    }
+}
+
+/// The metrics of a node at one sample.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeMetrics {
+    pub ops_per_sec: u64,
+    pub used_memory: u64,
+    pub keyspace_hits: u64,
+    pub keyspace_misses: u64,
+    pub connected_clients: u64,
+    pub input_kbps: f64,
+    pub output_kbps: f64,
+    pub evicted_keys: u64,
+    pub fragmentation: f64,
+}
+
+impl From<&RedisInfo> for NodeMetrics {
+    fn from(info: &RedisInfo) -> Self {
+        Self {
+            ops_per_sec: info.instantaneous_ops_per_sec,
+            used_memory: info.used_memory,
+            keyspace_hits: info.keyspace_hits,
+            keyspace_misses: info.keyspace_misses,
+            connected_clients: info.connected_clients,
+            input_kbps: info.instantaneous_input_kbps,
+            output_kbps: info.instantaneous_output_kbps,
+            evicted_keys: info.evicted_keys,
+            fragmentation: info.mem_fragmentation_ratio,
+        }
+    }
+}
+
+impl NodeMetrics {
+    /// Sums the metrics of several nodes, keeping the worst fragmentation ratio.
+    fn aggregate<'a>(nodes: impl Iterator<Item = &'a NodeMetrics>) -> Self {
+        nodes.fold(NodeMetrics::default(), |mut total, node| {
+            total.ops_per_sec += node.ops_per_sec;
+            total.used_memory += node.used_memory;
+            total.keyspace_hits += node.keyspace_hits;
+            total.keyspace_misses += node.keyspace_misses;
+            total.connected_clients += node.connected_clients;
+            total.input_kbps += node.input_kbps;
+            total.output_kbps += node.output_kbps;
+            total.evicted_keys += node.evicted_keys;
+            total.fragmentation = total.fragmentation.max(node.fragmentation);
+            total
+        })
+    }
+}
+
+/// The metrics of every node at one point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsSample {
+    /// Unix timestamp in milliseconds
+    pub timestamp: i64,
+    /// Metrics in the order of [`MetricsHistory::nodes`]
+    pub nodes: Vec<NodeMetrics>,
+}
+
+/// Rolling history of samples.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsHistory {
+    /// `host:port` of every master node
+    pub nodes: Vec<SharedString>,
+    pub samples: VecDeque<MetricsSample>,
+}
+
+impl MetricsHistory {
+    /// Appends a sample, starting over when the nodes changed (e.g. cluster failover).
+    fn push(&mut self, nodes: Vec<SharedString>, sample: MetricsSample) {
+        if self.nodes != nodes {
+            self.nodes = nodes;
+            self.samples.clear();
+        }
+        self.samples.push_back(sample);
+        if self.samples.len() > MAX_METRICS_SAMPLES {
+            self.samples.pop_front();
+        }
+    }
+
+    fn node_metrics(sample: &MetricsSample, node: Option<usize>) -> NodeMetrics {
+        match node {
+            Some(index) => sample.nodes.get(index).cloned().unwrap_or_default(),
+            None => NodeMetrics::aggregate(sample.nodes.iter()),
+        }
+    }
+
+    /// Builds the series of a metric.
+    ///
+    /// # Arguments
+    /// * `metric` - The metric to chart
+    /// * `node` - Index of the node, every node combined when `None`
+    ///
+    /// # Returns
+    /// (timestamp in milliseconds, value) for every sample.
+    pub fn series(&self, metric: Metric, node: Option<usize>) -> Vec<(i64, f64)> {
+        let mut series = Vec::with_capacity(self.samples.len());
+        let mut previous: Option<(i64, NodeMetrics)> = None;
+        let mut hit_rate = 0.0;
+        for sample in &self.samples {
+            let current = Self::node_metrics(sample, node);
+            let value = match metric {
+                Metric::OpsPerSec => current.ops_per_sec as f64,
+                Metric::UsedMemory => current.used_memory as f64,
+                Metric::ConnectedClients => current.connected_clients as f64,
+                Metric::InputKbps => current.input_kbps,
+                Metric::OutputKbps => current.output_kbps,
+                Metric::Fragmentation => current.fragmentation,
+                Metric::HitRate => {
+                    let (hits, misses) = match &previous {
+                        Some((_, prev)) => (
+                            current.keyspace_hits.saturating_sub(prev.keyspace_hits),
+                            current.keyspace_misses.saturating_sub(prev.keyspace_misses),
+                        ),
+                        None => (current.keyspace_hits, current.keyspace_misses),
+                    };
+                    // Keep the last rate when there was no lookup during the interval
+                    if hits + misses > 0 {
+                        hit_rate = hits as f64 * 100.0 / (hits + misses) as f64;
+                    }
+                    hit_rate
+                }
+                Metric::Evictions => match &previous {
+                    Some((timestamp, prev)) if sample.timestamp > *timestamp => {
+                        let evicted = current.evicted_keys.saturating_sub(prev.evicted_keys);
+                        evicted as f64 * 1000.0 / (sample.timestamp - timestamp) as f64
+                    }
+                    _ => 0.0,
+                },
+            };
+            series.push((sample.timestamp, value));
+            previous = Some((sample.timestamp, current));
+        }
+        series
+    }
+
+    /// Formats the history as CSV, one row per node and sample.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("time,node");
+        for metric in Metric::all() {
+            out.push(',');
+            out.push_str(metric.as_str());
+        }
+        out.push('\n');
+        let columns: Vec<Vec<Vec<(i64, f64)>>> = (0..self.nodes.len())
+            .map(|node| {
+                Metric::all()
+                    .iter()
+                    .map(|metric| self.series(*metric, Some(node)))
+                    .collect()
+            })
+            .collect();
+        for (index, sample) in self.samples.iter().enumerate() {
+            let time = Local
+                .timestamp_millis_opt(sample.timestamp)
+                .single()
+                .map(|time| time.to_rfc3339())
+                .unwrap_or_default();
+            for (node, series) in self.nodes.iter().zip(&columns) {
+                let _ = write!(out, "{time},{node}");
+                for values in series {
+                    let value = values.get(index).map(|(_, value)| *value).unwrap_or_default();
+                    let _ = write!(out, ",{}", (value * 100.0).round() / 100.0);
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+impl ZedisServerState {
+    /// Returns the collected metrics.
+    pub fn metrics(&self) -> Arc<MetricsHistory> {
+        self.metrics.clone()
+    }
+
+    /// Discards the collected metrics.
+    pub fn clear_metrics(&mut self, cx: &mut Context<Self>) {
+        self.metrics = Arc::new(MetricsHistory::default());
+        cx.notify();
+    }
+
+    /// Collects `INFO ALL` from every master and appends it to the metrics history.
+    pub fn sample_metrics(&mut self, cx: &mut Context<Self>) {
+        if self.server_id.is_empty() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::SampleMetrics,
+            move || async move {
+                let (client, _) = get_connection_manager().get_client(&server_id, db, vec![]).await?;
+                let nodes: Vec<SharedString> = client.master_addrs().into_iter().map(SharedString::from).collect();
+                let list: Vec<String> = client.query_async_masters(vec![cmd("INFO").arg("ALL").clone()]).await?;
+                let sample = MetricsSample {
+                    timestamp: Utc::now().timestamp_millis(),
+                    nodes: list
+                        .iter()
+                        .map(|info| NodeMetrics::from(&RedisInfo::parse(info)))
+                        .collect(),
+                };
+                Ok((server_id, nodes, sample))
+            },
+            move |this, result, cx| {
+                if let Ok((server_id, nodes, sample)) = result
+                    && server_id == this.server_id
+                {
+                    Arc::make_mut(&mut this.metrics).push(nodes, sample);
+                    cx.notify();
+                }
+            },
+            cx,
+        );
+    }
+
+    /// Writes the collected metrics to a CSV file.
+    pub fn export_metrics_csv(&mut self, path: PathBuf, cx: &mut Context<Self>) {
+        let metrics = self.metrics.clone();
+        let count = metrics.samples.len();
+        self.spawn(
+            ServerTask::ExportMetrics,
+            move || async move {
+                fs::write(&path, metrics.to_csv())?;
+                info!(path = %path.display(), count, "Metrics exported");
+                Ok(())
+            },
+            move |_this, result, cx| {
+                if result.is_ok() {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!("metrics.exported", count = count, locale = locale).to_string();
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
+                }
+            },
+            cx,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: i64, nodes: Vec<(u64, u64, u64, f64)>) -> MetricsSample {
+        MetricsSample {
+            timestamp,
+            nodes: nodes
+                .into_iter()
+                .map(|(hits, misses, evicted_keys, fragmentation)| NodeMetrics {
+                    ops_per_sec: 10,
+                    keyspace_hits: hits,
+                    keyspace_misses: misses,
+                    evicted_keys,
+                    fragmentation,
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn builds_interval_series() {
+        let nodes: Vec<SharedString> = vec!["a:6379".into(), "b:6379".into()];
+        let mut history = MetricsHistory::default();
+        history.push(nodes.clone(), sample(0, vec![(50, 50, 0, 1.0), (0, 0, 0, 1.5)]));
+        history.push(nodes.clone(), sample(2000, vec![(140, 60, 10, 1.2), (0, 0, 10, 1.1)]));
+        history.push(nodes.clone(), sample(4000, vec![(140, 60, 10, 1.2), (0, 0, 10, 1.1)]));
+
+        let ops = history.series(Metric::OpsPerSec, None);
+        assert_eq!(ops[0], (0, 20.0));
+        // The first sample uses the counters, then only the interval is considered
+        let hit_rate = history.series(Metric::HitRate, Some(0));
+        assert_eq!(hit_rate, vec![(0, 50.0), (2000, 90.0), (4000, 90.0)]);
+        let evictions = history.series(Metric::Evictions, None);
+        assert_eq!(evictions, vec![(0, 0.0), (2000, 10.0), (4000, 0.0)]);
+        let fragmentation = history.series(Metric::Fragmentation, None);
+        assert_eq!(fragmentation[0], (0, 1.5));
+
+        let csv = history.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert!(lines[0].starts_with("time,node,ops_per_sec,used_memory,hit_rate"));
+        assert!(lines[3].contains(",a:6379,10,0,90,"));
+
+        // A topology change starts a new history
+        history.push(vec!["c:6379".into()], sample(6000, vec![(0, 0, 0, 1.0)]));
+        assert_eq!(history.samples.len(), 1);
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redis Pub/Sub operations module.
+//!
+//! This module provides the server side of the Pub/Sub panel:
+//! - Listing active channels with their subscriber counts (PUBSUB CHANNELS/NUMSUB)
+//! - Publishing test messages (PUBLISH, SPUBLISH)
+//! - Decoding received payloads and exporting the message log as NDJSON
+//!
+//! Subscriptions themselves use dedicated connections, see `connection::PubSubSession`.
+
+use super::{ServerTask, ZedisServerState};
+use crate::{
+    connection::{PubSubMessage, SubscriptionKind, get_connection_manager},
+    helpers::codec::{
+        CompressionFormat, EditFormat, MAX_DECOMPRESS_BYTES, decode_to_text, decompress, detect, suggest_edit_format,
+    },
+    states::{NotificationAction, ServerEvent, ZedisGlobalStore},
+};
+use chrono::{Local, TimeZone};
+use gpui::{SharedString, prelude::*};
+use redis::cmd;
+use rust_i18n::t;
+use serde_json::json;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::info;
+
+/// Maximum number of channels listed in the panel
+const MAX_PUBSUB_CHANNELS: usize = 1000;
+
+/// An active channel and its number of subscribers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PubSubChannel {
+    pub name: SharedString,
+    pub subscribers: u64,
+}
+
+/// Merges the channels and `PUBSUB NUMSUB` replies of every master.
+///
+/// Subscribers are connected to a single node, so the counts of all nodes are summed.
+fn merge_pubsub_channels(names: BTreeSet<String>, counts: Vec<HashMap<String, u64>>) -> Vec<PubSubChannel> {
+    names
+        .into_iter()
+        .map(|name| {
+            let subscribers = counts.iter().filter_map(|count| count.get(&name)).sum();
+            PubSubChannel {
+                name: name.into(),
+                subscribers,
+            }
+        })
+        .collect()
+}
+
+/// Decodes a message payload for display.
+///
+/// Compressed payloads are decompressed first, protobuf payloads are shown as hex
+/// since no schema is attached to a channel.
+///
+/// # Returns
+/// The compression, the format used and the decoded text.
+pub fn decode_pubsub_payload(payload: &[u8]) -> (CompressionFormat, EditFormat, String) {
+    let detection = detect(payload);
+    let compression = detection.compression;
+    let bytes = if compression == CompressionFormat::None {
+        None
+    } else {
+        decompress(payload, compression, MAX_DECOMPRESS_BYTES).ok()
+    };
+    let bytes = bytes.as_deref().unwrap_or(payload);
+    let format = match suggest_edit_format(detection.content, detection.is_utf8) {
+        EditFormat::ProtobufJson => EditFormat::Hex,
+        format => format,
+    };
+    match decode_to_text(bytes, format) {
+        Ok(text) => (compression, format, text),
+        Err(_) => (
+            compression,
+            EditFormat::Text,
+            String::from_utf8_lossy(bytes).to_string(),
+        ),
+    }
+}
+
+/// Formats messages as NDJSON, one object per message.
+fn format_pubsub_log(messages: &[PubSubMessage]) -> String {
+    let mut out = String::new();
+    for message in messages {
+        let (_, format, payload) = decode_pubsub_payload(&message.payload);
+        let time = Local
+            .timestamp_millis_opt(message.received_at)
+            .single()
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_default();
+        let line = json!({
+            "time": time,
+            "kind": message.kind.subscribe_command(),
+            "channel": String::from_utf8_lossy(&message.channel),
+            "pattern": message.pattern.as_deref().map(String::from_utf8_lossy),
+            "format": format.as_str(),
+            "payload": payload,
+        });
+        out.push_str(&line.to_string());
+        out.push('\n');
+    }
+    out
+}
+
+impl ZedisServerState {
+    /// Returns the channels loaded by `load_pubsub_channels`.
+    pub fn pubsub_channels(&self) -> Option<Arc<Vec<PubSubChannel>>> {
+        self.pubsub_channels.clone()
+    }
+
+    /// Loads the active channels matching the pattern from every master.
+    ///
+    /// # Arguments
+    /// * `pattern` - Glob-style pattern, every channel when empty
+    /// * `cx` - GPUI context
+    pub fn load_pubsub_channels(&mut self, pattern: SharedString, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::LoadPubSubChannels,
+            move || async move {
+                let (client, _) = get_connection_manager().get_client(&server_id, db, vec![]).await?;
+                let mut command = cmd("PUBSUB");
+                command.arg("CHANNELS");
+                if !pattern.is_empty() {
+                    command.arg(pattern.as_str());
+                }
+                let channels: Vec<Vec<String>> = client.query_async_masters(vec![command]).await?;
+                let names: BTreeSet<String> = channels.into_iter().flatten().take(MAX_PUBSUB_CHANNELS).collect();
+                if names.is_empty() {
+                    return Ok(vec![]);
+                }
+                let mut command = cmd("PUBSUB");
+                command.arg("NUMSUB").arg(names.iter().collect::<Vec<_>>());
+                let counts: Vec<HashMap<String, u64>> = client.query_async_masters(vec![command]).await?;
+                Ok(merge_pubsub_channels(names, counts))
+            },
+            move |this, result, cx| {
+                if let Ok(channels) = result {
+                    this.pubsub_channels = Some(Arc::new(channels));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Publishes a message, to a sharded channel for [`SubscriptionKind::Shard`].
+    ///
+    /// # Arguments
+    /// * `kind` - Selects PUBLISH or SPUBLISH
+    /// * `channel` - The channel name
+    /// * `message` - The message payload
+    /// * `cx` - GPUI context
+    pub fn publish_message(
+        &mut self,
+        kind: SubscriptionKind,
+        channel: SharedString,
+        message: SharedString,
+        cx: &mut Context<Self>,
+    ) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::PublishMessage,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let receivers: u64 = cmd(kind.publish_command())
+                    .arg(channel.as_str())
+                    .arg(message.as_str())
+                    .query_async(&mut conn)
+                    .await?;
+                info!(channel = %channel, receivers, "Message published");
+                Ok(receivers)
+            },
+            move |_this, result, cx| {
+                if let Ok(receivers) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!("pubsub.published", count = receivers, locale = locale).to_string();
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
+                }
+            },
+            cx,
+        );
+    }
+
+    /// Writes the message log to a NDJSON file.
+    ///
+    /// # Arguments
+    /// * `path` - Destination file
+    /// * `messages` - Messages in the order they were received
+    /// * `cx` - GPUI context
+    pub fn export_pubsub_log(&mut self, path: PathBuf, messages: Vec<PubSubMessage>, cx: &mut Context<Self>) {
+        let count = messages.len();
+        self.spawn(
+            ServerTask::ExportPubSubLog,
+            move || async move {
+                fs::write(&path, format_pubsub_log(&messages))?;
+                info!(path = %path.display(), count, "Pub/Sub log exported");
+                Ok(())
+            },
+            move |_this, result, cx| {
+                if result.is_ok() {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!("pubsub.exported", count = count, locale = locale).to_string();
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
+                }
+            },
+            cx,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_subscriber_counts_of_all_nodes() {
+        let names = BTreeSet::from(["news".to_string(), "orders".to_string()]);
+        let counts = vec![
+            HashMap::from([("news".to_string(), 2), ("orders".to_string(), 0)]),
+            HashMap::from([("news".to_string(), 1)]),
+        ];
+        let channels = merge_pubsub_channels(names, counts);
+        assert_eq!(
+            channels,
+            vec![
+                PubSubChannel {
+                    name: "news".into(),
+                    subscribers: 3,
+                },
+                PubSubChannel {
+                    name: "orders".into(),
+                    subscribers: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn exports_decoded_messages_as_ndjson() {
+        let message = |pattern: Option<&str>, payload: &[u8]| PubSubMessage {
+            kind: if pattern.is_some() {
+                SubscriptionKind::Pattern
+            } else {
+                SubscriptionKind::Channel
+            },
+            channel: b"news".to_vec(),
+            pattern: pattern.map(|p| p.as_bytes().to_vec()),
+            payload: payload.to_vec(),
+            received_at: 0,
+        };
+        let log = format_pubsub_log(&[message(None, br#"{"id":1}"#), message(Some("n*"), &[0xff, 0x00])]);
+        let lines: Vec<serde_json::Value> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("valid json line"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["kind"], "SUBSCRIBE");
+        assert_eq!(lines[0]["format"], "JSON");
+        assert_eq!(lines[0]["pattern"], serde_json::Value::Null);
+        assert_eq!(lines[1]["pattern"], "n*");
+        assert_eq!(lines[1]["format"], "Hex");
+    }
+}
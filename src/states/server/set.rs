@@ -19,6 +19,7 @@ use super::{
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
     error::Error,
+    helpers::RedisKey,
     states::{ServerEvent, i18n_set_editor},
 };
 use gpui::{SharedString, prelude::*};
@@ -53,7 +54,7 @@ async fn get_redis_set_value(
 
     // Execute SSCAN with MATCH and COUNT options
     let (next_cursor, raw_values): (u64, Vec<Vec<u8>>) = cmd("SSCAN")
-        .arg(key)
+        .arg(RedisKey::from(key))
         .arg(cursor)
         .arg("MATCH")
         .arg(pattern)
@@ -89,7 +90,7 @@ async fn get_redis_set_value(
 /// A `RedisValue` containing SET metadata and initial member values
 pub(crate) async fn first_load_set_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
     // Get total number of members in the SET
-    let size: usize = cmd("SCARD").arg(key).query_async(conn).await?;
+    let size: usize = cmd("SCARD").arg(RedisKey::from(key)).query_async(conn).await?;
 
     // Load first batch of values (up to 100 members)
     let (cursor, values) = get_redis_set_value(conn, key, None, 0, 100).await?;
@@ -142,7 +143,7 @@ impl ZedisServerState {
 
                 // SADD returns number of elements added (0 if already exists, 1 if new)
                 let count: usize = cmd("SADD")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(new_value.as_str())
                     .query_async(&mut conn)
                     .await?;
@@ -345,7 +346,7 @@ impl ZedisServerState {
 
                 // SREM returns number of members removed (0 if doesn't exist, 1 if removed)
                 let count: usize = cmd("SREM")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(remove_value.as_str())
                     .query_async(&mut conn)
                     .await?;
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::connection::get_connection_manager;
+use crate::error::Error;
 use crate::states::{ServerEvent, ServerTask, ZedisServerState};
 use gpui::prelude::*;
 use redis::cmd;
@@ -267,6 +268,9 @@ impl ZedisServerState {
                 let start = Instant::now();
                 client.ping().await?;
                 let latency = start.elapsed();
+                if let Some(failover) = client.check_failover().await {
+                    return Err(Error::Invalid { message: failover });
+                }
 
                 let list: Vec<String> = client.query_async_masters(vec![cmd("INFO").arg("ALL").clone()]).await?;
                 let infos: Vec<RedisInfo> = list.iter().map(|info| RedisInfo::parse(info)).collect();
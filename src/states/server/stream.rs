@@ -0,0 +1,609 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redis STREAM data type operations module.
+//!
+//! This module provides functionality for managing Redis STREAM operations including:
+//! - Loading stream entries with ID range pagination (XRANGE/XREVRANGE)
+//! - Appending entries with field-value pairs (XADD)
+//! - Removing entries by ID (XDEL)
+//! - Trimming a stream by length or minimum ID (XTRIM MAXLEN/MINID)
+//! - Filtering loaded entries by ID, field or value
+
+use super::{
+    KeyType, RedisValueData, ServerTask, ZedisServerState,
+    value::{RedisStreamEntry, RedisStreamValue, RedisValue, RedisValueStatus, SortOrder},
+};
+use crate::{
+    connection::{RedisAsyncConn, get_connection_manager},
+    error::Error,
+    helpers::{
+        RedisKey,
+        codec::{CompressionFormat, MAX_DECOMPRESS_BYTES, decompress, detect},
+        fast_contains_ignore_case,
+    },
+    states::{NotificationAction, ServerEvent, i18n_stream_editor},
+};
+use gpui::{SharedString, prelude::*};
+use redis::cmd;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Type alias for XRANGE/XREVRANGE result: vec of (id, flattened field-value bytes)
+type StreamRangeValue = Vec<(String, Vec<Vec<u8>>)>;
+
+/// Strategy used by XTRIM to evict old entries.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum StreamTrimStrategy {
+    /// Keep at most the given number of entries
+    #[default]
+    MaxLen,
+    /// Evict entries with an ID lower than the given ID
+    MinId,
+}
+
+impl StreamTrimStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreamTrimStrategy::MaxLen => "MAXLEN",
+            StreamTrimStrategy::MinId => "MINID",
+        }
+    }
+}
+
+/// Convert raw bytes into the table preview string while preserving compressed data previews.
+fn bytes_to_display_string(bytes: &[u8]) -> String {
+    let detection = detect(bytes);
+    let data = if detection.compression != CompressionFormat::None {
+        decompress(bytes, detection.compression, MAX_DECOMPRESS_BYTES).unwrap_or_else(|_| bytes.to_vec())
+    } else {
+        bytes.to_vec()
+    };
+
+    String::from_utf8_lossy(&data).to_string()
+}
+
+/// Parses a stream entry ID in `<ms>-<seq>` form. A bare `<ms>` means sequence 0.
+fn parse_stream_id(id: &str) -> Option<(u64, u64)> {
+    match id.split_once('-') {
+        Some((ms, seq)) => Some((ms.parse().ok()?, seq.parse().ok()?)),
+        None => Some((id.parse().ok()?, 0)),
+    }
+}
+
+/// Returns the smallest ID strictly greater than `id`, or `None` if `id` is the maximum.
+///
+/// Used instead of the exclusive `(` range syntax so pagination works below Redis 6.2.
+fn next_stream_id(id: &str) -> Option<String> {
+    let (ms, seq) = parse_stream_id(id)?;
+    if let Some(seq) = seq.checked_add(1) {
+        return Some(format!("{ms}-{seq}"));
+    }
+    ms.checked_add(1).map(|ms| format!("{ms}-0"))
+}
+
+/// Returns the largest ID strictly lower than `id`, or `None` if `id` is `0-0`.
+fn prev_stream_id(id: &str) -> Option<String> {
+    let (ms, seq) = parse_stream_id(id)?;
+    if let Some(seq) = seq.checked_sub(1) {
+        return Some(format!("{ms}-{seq}"));
+    }
+    ms.checked_sub(1).map(|ms| format!("{ms}-{}", u64::MAX))
+}
+
+/// Checks whether an entry matches the filter keyword (lowercase) by ID, field or value.
+fn stream_entry_matches(entry: &RedisStreamEntry, keyword_lower: &str) -> bool {
+    fast_contains_ignore_case(entry.id.as_str(), keyword_lower)
+        || entry.fields.iter().any(|(field, value)| {
+            fast_contains_ignore_case(field.as_str(), keyword_lower)
+                || fast_contains_ignore_case(value.as_str(), keyword_lower)
+        })
+}
+
+fn is_current_stream_pagination(
+    stream: &RedisStreamValue,
+    keyword: Option<&SharedString>,
+    cursor: Option<&SharedString>,
+) -> bool {
+    stream.keyword.as_ref() == keyword && stream.cursor.as_ref() == cursor
+}
+
+/// Retrieves a page of stream entries using XRANGE (ascending) or XREVRANGE (descending).
+///
+/// # Arguments
+/// * `conn` - Redis async connection
+/// * `key` - The STREAM key to query
+/// * `sort_order` - Ascending (XRANGE) or Descending (XREVRANGE)
+/// * `cursor` - ID of the last loaded entry, `None` to start from the beginning
+/// * `count` - Maximum number of entries to return
+///
+/// # Returns
+/// A tuple of (next_cursor, entries, done) where done is true when the range is exhausted
+async fn get_redis_stream_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    sort_order: SortOrder,
+    cursor: Option<SharedString>,
+    count: usize,
+) -> Result<(Option<SharedString>, Vec<RedisStreamEntry>, bool)> {
+    // Resolve the start of the next page; no neighbour ID means the range is exhausted
+    let start = match (&cursor, sort_order) {
+        (None, SortOrder::Asc) => "-".to_string(),
+        (None, SortOrder::Desc) => "+".to_string(),
+        (Some(id), SortOrder::Asc) => match next_stream_id(id) {
+            Some(id) => id,
+            None => return Ok((cursor, vec![], true)),
+        },
+        (Some(id), SortOrder::Desc) => match prev_stream_id(id) {
+            Some(id) => id,
+            None => return Ok((cursor, vec![], true)),
+        },
+    };
+
+    let mut binding = if sort_order == SortOrder::Asc {
+        let mut binding = cmd("XRANGE");
+        binding.arg(RedisKey::from(key)).arg(start).arg("+");
+        binding
+    } else {
+        let mut binding = cmd("XREVRANGE");
+        binding.arg(RedisKey::from(key)).arg(start).arg("-");
+        binding
+    };
+    let raw_values: StreamRangeValue = binding.arg("COUNT").arg(count).query_async(conn).await?;
+
+    let done = raw_values.len() < count;
+    let next_cursor = raw_values.last().map(|(id, _)| id.clone().into()).or(cursor);
+
+    // Decode field-value pairs, decompressing values when a codec is detected
+    let values = raw_values
+        .into_iter()
+        .map(|(id, fields)| RedisStreamEntry {
+            id: id.into(),
+            fields: fields
+                .chunks(2)
+                .map(|chunk| {
+                    let field = bytes_to_display_string(&chunk[0]);
+                    let value = chunk.get(1).map(|v| bytes_to_display_string(v)).unwrap_or_default();
+                    (field.into(), value.into())
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok((next_cursor, values, done))
+}
+
+/// Performs initial load of a Redis STREAM value.
+///
+/// Fetches the total number of entries (XLEN) and loads the newest batch of entries
+/// (up to 100). This is called when a STREAM key is first opened in the editor.
+///
+/// # Arguments
+/// * `conn` - Redis async connection
+/// * `key` - The STREAM key to load
+/// * `sort_order` - Order in which entries are paged
+///
+/// # Returns
+/// A `RedisValue` containing STREAM metadata and initial entries
+pub(crate) async fn first_load_stream_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    sort_order: SortOrder,
+) -> Result<RedisValue> {
+    let size: usize = cmd("XLEN").arg(RedisKey::from(key)).query_async(conn).await?;
+    let (cursor, values, done) = get_redis_stream_value(conn, key, sort_order, None, 100).await?;
+
+    Ok(RedisValue {
+        key_type: KeyType::Stream,
+        data: Some(RedisValueData::Stream(Arc::new(RedisStreamValue {
+            cursor,
+            size,
+            values,
+            done,
+            sort_order,
+            ..Default::default()
+        }))),
+        ..Default::default()
+    })
+}
+
+impl ZedisServerState {
+    /// Appends a new entry to the Redis STREAM.
+    ///
+    /// Uses XADD with the given ID (`*` lets the server generate one).
+    /// The new entry is shown immediately when it falls into the loaded range.
+    ///
+    /// # Arguments
+    /// * `id` - Entry ID, or `*` for an auto-generated ID
+    /// * `fields` - Field-value pairs of the entry
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn add_stream_value(
+        &mut self,
+        id: SharedString,
+        fields: Vec<(SharedString, SharedString)>,
+        cx: &mut Context<Self>,
+    ) {
+        if fields.is_empty() {
+            return;
+        }
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let fields_clone = fields.clone();
+        let id = if id.trim().is_empty() { "*".into() } else { id };
+
+        self.spawn(
+            ServerTask::AddStreamValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut binding = cmd("XADD");
+                binding.arg(RedisKey::from(&key)).arg(id.trim());
+                for (field, value) in &fields {
+                    binding.arg(field.as_str()).arg(value.as_str());
+                }
+                let new_id: String = binding.query_async(&mut conn).await?;
+                Ok(new_id)
+            },
+            move |this, result, cx| {
+                let title = i18n_stream_editor(cx, "add_value_success");
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+
+                    if let Ok(new_id) = result
+                        && let Some(RedisValueData::Stream(stream_data)) = value.data.as_mut()
+                    {
+                        let stream = Arc::make_mut(stream_data);
+                        stream.size += 1;
+                        let entry = RedisStreamEntry {
+                            id: new_id.clone().into(),
+                            fields: fields_clone,
+                        };
+                        // A new entry is always the newest one, so it is only visible at the
+                        // head of a descending view or the tail of a fully loaded ascending view
+                        let visible = stream.keyword.is_none();
+                        match stream.sort_order {
+                            SortOrder::Desc if visible => stream.values.insert(0, entry),
+                            SortOrder::Asc if visible && stream.done => {
+                                stream.cursor = Some(entry.id.clone());
+                                stream.values.push(entry);
+                            }
+                            _ => {}
+                        }
+                        info!(key = %key_clone, id = new_id, "Added Redis stream entry");
+                        cx.emit(ServerEvent::Notification(
+                            NotificationAction::new_success(new_id.into()).with_title(title),
+                        ));
+                        cx.emit(ServerEvent::ValueAdded(key_clone));
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Removes entries from the Redis STREAM by ID.
+    ///
+    /// Uses XDEL and updates both the entry count and the local UI state.
+    ///
+    /// # Arguments
+    /// * `ids` - Entry IDs to remove
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn remove_stream_values(&mut self, ids: Vec<SharedString>, cx: &mut Context<Self>) {
+        if ids.is_empty() {
+            debug!("Skip stream entry removal because no entries were selected");
+            return;
+        }
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let ids_for_task = ids.clone();
+
+        self.spawn(
+            ServerTask::RemoveStreamValues,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut binding = cmd("XDEL");
+                binding.arg(RedisKey::from(&key));
+                for id in &ids_for_task {
+                    binding.arg(id.as_str());
+                }
+                let count: usize = binding.query_async(&mut conn).await?;
+                Ok(count)
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    if let Ok(count) = result {
+                        if count != 0
+                            && let Some(RedisValueData::Stream(stream_data)) = value.data.as_mut()
+                        {
+                            let stream = Arc::make_mut(stream_data);
+                            stream.values.retain(|entry| !ids.contains(&entry.id));
+                            stream.size = stream.size.saturating_sub(count);
+                            info!(key = %key_clone, removed = count, "Removed Redis stream entries");
+                        }
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Trims the Redis STREAM with XTRIM and reloads the first page.
+    ///
+    /// # Arguments
+    /// * `strategy` - MAXLEN keeps the newest N entries, MINID evicts entries below an ID
+    /// * `threshold` - Maximum length or minimum ID, depending on the strategy
+    /// * `approximate` - Use `~` so Redis can trim whole macro nodes efficiently
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn trim_stream(
+        &mut self,
+        strategy: StreamTrimStrategy,
+        threshold: SharedString,
+        approximate: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let sort_order = value.stream_value().map(|stream| stream.sort_order).unwrap_or_default();
+
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+
+        self.spawn(
+            ServerTask::TrimStream,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut binding = cmd("XTRIM");
+                binding.arg(RedisKey::from(&key)).arg(strategy.as_str());
+                if approximate {
+                    binding.arg("~");
+                }
+                let count: usize = binding.arg(threshold.trim()).query_async(&mut conn).await?;
+                let value = first_load_stream_value(&mut conn, &key, sort_order).await?;
+                Ok((count, value))
+            },
+            move |this, result, cx| {
+                if this.key.as_ref() != Some(&key_clone) {
+                    return;
+                }
+                if let Some(value) = this.value.as_mut() {
+                    if let Ok((count, mut new_value)) = result {
+                        // Keep the consumer group panel state across the reload
+                        if let Some(groups) = value.stream_value().and_then(|stream| stream.groups.clone())
+                            && let Some(RedisValueData::Stream(stream_data)) = new_value.data.as_mut()
+                        {
+                            Arc::make_mut(stream_data).groups = Some(groups);
+                        }
+                        value.data = new_value.data;
+                        info!(key = %key_clone, removed = count, "Trimmed Redis stream");
+                        let msg = i18n_stream_editor(cx, "trim_success_tips");
+                        cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                            format!("{msg}: {count}").into(),
+                        )));
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Switches the stream between oldest-first and newest-first order and reloads it.
+    pub fn toggle_stream_sort_order(&mut self, cx: &mut Context<Self>) {
+        let Some((_, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(stream) = value.stream_value() else {
+            return;
+        };
+        let sort_order = if stream.sort_order == SortOrder::Asc {
+            SortOrder::Desc
+        } else {
+            SortOrder::Asc
+        };
+        let new_stream = RedisStreamValue {
+            keyword: stream.keyword.clone(),
+            size: stream.size,
+            sort_order,
+            groups: stream.groups.clone(),
+            ..Default::default()
+        };
+        value.data = Some(RedisValueData::Stream(Arc::new(new_stream)));
+        self.load_more_stream_value(cx);
+    }
+
+    /// Applies a filter to STREAM entries by resetting the pagination state with a keyword.
+    ///
+    /// Entries are matched client-side against their ID, fields and values.
+    ///
+    /// # Arguments
+    /// * `keyword` - The search keyword
+    /// * `cx` - GPUI context for UI updates
+    pub fn filter_stream_value(&mut self, keyword: SharedString, cx: &mut Context<Self>) -> bool {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return false;
+        };
+        let Some(stream) = value.stream_value() else {
+            return false;
+        };
+        let filter_keyword = if keyword.is_empty() {
+            None
+        } else {
+            Some(keyword.clone())
+        };
+
+        let new_stream = RedisStreamValue {
+            keyword: filter_keyword,
+            size: stream.size,
+            sort_order: stream.sort_order,
+            groups: stream.groups.clone(),
+            ..Default::default()
+        };
+        value.data = Some(RedisValueData::Stream(Arc::new(new_stream)));
+        self.remember_value_filter_keyword_for_key(key.as_str(), keyword);
+
+        self.load_more_stream_value(cx);
+        true
+    }
+
+    /// Loads the next page of STREAM entries after the last loaded ID.
+    ///
+    /// When filtering is active, uses larger pages (1000) and keeps loading until
+    /// enough matching entries are found or the stream is exhausted.
+    ///
+    /// # Arguments
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn load_more_stream_value(&mut self, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let (cursor, keyword, sort_order) = match value.stream_value() {
+            Some(stream) => (stream.cursor.clone(), stream.keyword.clone(), stream.sort_order),
+            None => return,
+        };
+        let request_cursor = cursor.clone();
+        let request_keyword = keyword.clone();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        cx.emit(ServerEvent::ValuePaginationStarted(key.clone()));
+
+        let key_clone = key.clone();
+
+        self.spawn(
+            ServerTask::LoadMoreValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let count = if request_keyword.is_some() { 1000 } else { 100 };
+                let (next_cursor, mut values, done) =
+                    get_redis_stream_value(&mut conn, &key, sort_order, request_cursor, count).await?;
+                if let Some(keyword) = request_keyword {
+                    let keyword = keyword.to_lowercase();
+                    values.retain(|entry| stream_entry_matches(entry, &keyword));
+                }
+                Ok((next_cursor, values, done))
+            },
+            move |this, result, cx| {
+                if this.key.as_ref() != Some(&key_clone) {
+                    let current_key = this.key.clone().unwrap_or_default();
+                    debug!(
+                        expected_key = key_clone.as_str(),
+                        current_key = current_key.as_str(),
+                        "Skip stale stream value pagination result"
+                    );
+                    return;
+                }
+
+                let mut should_load_more = false;
+                if let Ok((new_cursor, new_values, done)) = result
+                    && let Some(RedisValueData::Stream(stream_data)) = this.value.as_mut().and_then(|v| v.data.as_mut())
+                {
+                    let stream = Arc::make_mut(stream_data);
+                    if !is_current_stream_pagination(stream, keyword.as_ref(), cursor.as_ref()) {
+                        debug!(
+                            key = key_clone.as_str(),
+                            "Skip stale stream value pagination result because filter state changed"
+                        );
+                        return;
+                    }
+                    stream.cursor = new_cursor;
+                    stream.done = done;
+                    stream.values.extend(new_values);
+                    if !stream.done && stream.values.len() < 50 {
+                        should_load_more = true;
+                    }
+                }
+
+                cx.emit(ServerEvent::ValuePaginationFinished(key_clone));
+
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.notify();
+                if should_load_more {
+                    this.load_more_stream_value(cx);
+                }
+            },
+            cx,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RedisStreamEntry, next_stream_id, parse_stream_id, prev_stream_id, stream_entry_matches};
+    use gpui::SharedString;
+
+    #[test]
+    fn parses_stream_ids() {
+        assert_eq!(parse_stream_id("1526919030474-55"), Some((1526919030474, 55)));
+        assert_eq!(parse_stream_id("42"), Some((42, 0)));
+        assert_eq!(parse_stream_id("abc-1"), None);
+    }
+
+    #[test]
+    fn computes_neighbour_stream_ids() {
+        assert_eq!(next_stream_id("5-1").as_deref(), Some("5-2"));
+        assert_eq!(next_stream_id(&format!("5-{}", u64::MAX)).as_deref(), Some("6-0"));
+        assert_eq!(next_stream_id(&format!("{0}-{0}", u64::MAX)), None);
+
+        assert_eq!(prev_stream_id("5-1").as_deref(), Some("5-0"));
+        assert_eq!(prev_stream_id("5-0"), Some(format!("4-{}", u64::MAX)));
+        assert_eq!(prev_stream_id("0-0"), None);
+    }
+
+    #[test]
+    fn matches_stream_entries_by_id_field_or_value() {
+        let entry = RedisStreamEntry {
+            id: SharedString::from("1-0"),
+            fields: vec![(SharedString::from("Sensor"), SharedString::from("Temperature"))],
+        };
+
+        assert!(stream_entry_matches(&entry, "sensor"));
+        assert!(stream_entry_matches(&entry, "temp"));
+        assert!(stream_entry_matches(&entry, "1-0"));
+        assert!(!stream_entry_matches(&entry, "humidity"));
+    }
+}
@@ -0,0 +1,470 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redis STREAM consumer group operations module.
+//!
+//! This module provides functionality for inspecting and managing consumer groups including:
+//! - Loading groups, consumers and pending entries (XINFO GROUPS/CONSUMERS, XPENDING)
+//! - Creating, repositioning and destroying groups (XGROUP CREATE/SETID/DESTROY)
+//! - Removing consumers (XGROUP DELCONSUMER)
+//! - Acknowledging and claiming pending entries (XACK, XCLAIM, XAUTOCLAIM)
+
+use super::{
+    RedisValueData, ServerTask, ZedisServerState,
+    value::{RedisStreamConsumer, RedisStreamGroup, RedisStreamGroupsValue, RedisStreamPendingEntry, RedisValueStatus},
+};
+use crate::{
+    connection::{RedisAsyncConn, get_connection_manager},
+    error::Error,
+    helpers::{RedisKey, redis_value_to_string},
+    states::{NotificationAction, ServerEvent, i18n_stream_editor},
+};
+use gpui::{SharedString, prelude::*};
+use redis::{Cmd, Value, cmd, from_redis_value_ref};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Type alias for XINFO GROUPS/CONSUMERS result: one field map per item
+type StreamInfoValue = Vec<HashMap<String, Value>>;
+
+/// Type alias for XPENDING extended result: (id, consumer, idle ms, delivery count)
+type StreamPendingValue = Vec<(String, String, u64, u64)>;
+
+/// Maximum number of pending entries listed for the selected group
+const PENDING_ENTRIES_LIMIT: usize = 100;
+
+fn info_u64(info: &HashMap<String, Value>, name: &str) -> Option<u64> {
+    info.get(name).and_then(|value| from_redis_value_ref::<u64>(value).ok())
+}
+
+fn info_string(info: &HashMap<String, Value>, name: &str) -> SharedString {
+    match info.get(name) {
+        Some(Value::Nil) | None => SharedString::default(),
+        Some(value) => redis_value_to_string(value).into(),
+    }
+}
+
+fn parse_stream_group(info: &HashMap<String, Value>) -> RedisStreamGroup {
+    RedisStreamGroup {
+        name: info_string(info, "name"),
+        consumers: info_u64(info, "consumers").unwrap_or_default(),
+        pending: info_u64(info, "pending").unwrap_or_default(),
+        last_delivered_id: info_string(info, "last-delivered-id"),
+        entries_read: info_u64(info, "entries-read"),
+        lag: info_u64(info, "lag"),
+    }
+}
+
+fn parse_stream_consumer(info: &HashMap<String, Value>) -> RedisStreamConsumer {
+    RedisStreamConsumer {
+        name: info_string(info, "name"),
+        pending: info_u64(info, "pending").unwrap_or_default(),
+        idle: info_u64(info, "idle").unwrap_or_default(),
+        inactive: info
+            .get("inactive")
+            .and_then(|value| from_redis_value_ref::<i64>(value).ok()),
+    }
+}
+
+/// Extracts the number of affected entries from a consumer group command reply.
+///
+/// XACK/XGROUP reply with an integer, XCLAIM with the claimed entries and
+/// XAUTOCLAIM with `[next-start, claimed entries, deleted ids]`.
+fn affected_count(task: &ServerTask, value: &Value) -> Option<usize> {
+    match (task, value) {
+        (_, Value::Int(count)) => Some((*count).max(0) as usize),
+        (ServerTask::AutoClaimStreamEntries, Value::Array(items)) => match items.get(1) {
+            Some(Value::Array(claimed)) => Some(claimed.len()),
+            _ => None,
+        },
+        (_, Value::Array(items)) => Some(items.len()),
+        _ => None,
+    }
+}
+
+/// Loads the consumer groups of a stream and the details of one group.
+///
+/// # Arguments
+/// * `conn` - Redis async connection
+/// * `key` - The STREAM key
+/// * `selected_group` - Group whose consumers and pending entries are loaded,
+///   falls back to the first group when missing or no longer existing
+async fn get_stream_groups(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    selected_group: Option<SharedString>,
+) -> Result<RedisStreamGroupsValue> {
+    let raw_groups: StreamInfoValue = cmd("XINFO")
+        .arg("GROUPS")
+        .arg(RedisKey::from(key))
+        .query_async(conn)
+        .await?;
+    let groups: Vec<RedisStreamGroup> = raw_groups.iter().map(parse_stream_group).collect();
+
+    let selected_group = selected_group
+        .filter(|name| groups.iter().any(|group| &group.name == name))
+        .or_else(|| groups.first().map(|group| group.name.clone()));
+    let Some(group) = selected_group.clone() else {
+        return Ok(RedisStreamGroupsValue::default());
+    };
+
+    let raw_consumers: StreamInfoValue = cmd("XINFO")
+        .arg("CONSUMERS")
+        .arg(RedisKey::from(key))
+        .arg(group.as_str())
+        .query_async(conn)
+        .await?;
+    let raw_pending: StreamPendingValue = cmd("XPENDING")
+        .arg(RedisKey::from(key))
+        .arg(group.as_str())
+        .arg("-")
+        .arg("+")
+        .arg(PENDING_ENTRIES_LIMIT)
+        .query_async(conn)
+        .await?;
+
+    Ok(RedisStreamGroupsValue {
+        groups,
+        selected_group,
+        consumers: raw_consumers.iter().map(parse_stream_consumer).collect(),
+        pending: raw_pending
+            .into_iter()
+            .map(|(id, consumer, idle, delivered)| RedisStreamPendingEntry {
+                id: id.into(),
+                consumer: consumer.into(),
+                idle,
+                delivered,
+            })
+            .collect(),
+    })
+}
+
+impl ZedisServerState {
+    /// Returns the group currently selected in the consumer group panel.
+    fn selected_stream_group(&self) -> Option<SharedString> {
+        self.value
+            .as_ref()
+            .and_then(|value| value.stream_value())
+            .and_then(|stream| stream.groups.as_ref())
+            .and_then(|groups| groups.selected_group.clone())
+    }
+
+    /// Stores freshly loaded consumer groups into the current stream value.
+    fn set_stream_groups(&mut self, key: &SharedString, groups: RedisStreamGroupsValue) {
+        if self.key.as_ref() != Some(key) {
+            debug!(key = key.as_str(), "Skip stale stream consumer groups");
+            return;
+        }
+        if let Some(RedisValueData::Stream(stream_data)) = self.value.as_mut().and_then(|v| v.data.as_mut()) {
+            Arc::make_mut(stream_data).groups = Some(groups);
+        }
+    }
+
+    /// Loads consumer groups of the current stream (XINFO GROUPS).
+    ///
+    /// # Arguments
+    /// * `group` - Group to show details for, `None` keeps the current selection
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn load_stream_groups(&mut self, group: Option<SharedString>, cx: &mut Context<Self>) {
+        let selected_group = group.or_else(|| self.selected_stream_group());
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        if value.stream_value().is_none() {
+            return;
+        }
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+
+        self.spawn(
+            ServerTask::LoadStreamGroups,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                get_stream_groups(&mut conn, &key, selected_group).await
+            },
+            move |this, result, cx| {
+                if let Ok(groups) = result {
+                    this.set_stream_groups(&key_clone, groups);
+                }
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.emit(ServerEvent::ValueUpdated(key_clone));
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Executes a consumer group command, then reloads the groups.
+    ///
+    /// # Arguments
+    /// * `task` - Task type used for progress and error reporting
+    /// * `command` - Fully built Redis command (key is already included)
+    /// * `success_key` - i18n key of the success notification
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    fn run_stream_group_command(
+        &mut self,
+        task: ServerTask,
+        command: Cmd,
+        success_key: &'static str,
+        cx: &mut Context<Self>,
+    ) {
+        let selected_group = self.selected_stream_group();
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let task_clone = task.clone();
+
+        self.spawn(
+            task,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let reply: Value = command.query_async(&mut conn).await?;
+                let groups = get_stream_groups(&mut conn, &key, selected_group).await?;
+                Ok((reply, groups))
+            },
+            move |this, result, cx| {
+                if let Ok((reply, groups)) = result {
+                    let count = affected_count(&task_clone, &reply);
+                    info!(key = %key_clone, task = task_clone.as_str(), count, "Stream consumer group command finished");
+                    this.set_stream_groups(&key_clone, groups);
+                    let msg = i18n_stream_editor(cx, success_key);
+                    let msg = match count {
+                        Some(count) => format!("{msg}: {count}").into(),
+                        None => msg,
+                    };
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(msg)));
+                }
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.emit(ServerEvent::ValueUpdated(key_clone));
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Creates a consumer group starting at the given ID (XGROUP CREATE).
+    ///
+    /// # Arguments
+    /// * `group` - Name of the new group
+    /// * `id` - Last delivered ID, `$` for new entries only or `0` for the whole stream
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn create_stream_group(&mut self, group: SharedString, id: SharedString, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let mut command = cmd("XGROUP");
+        command
+            .arg("CREATE")
+            .arg(RedisKey::from(&key))
+            .arg(group.as_str())
+            .arg(id.as_str());
+        self.run_stream_group_command(ServerTask::UpdateStreamGroup, command, "create_group_success", cx);
+    }
+
+    /// Moves the last delivered ID of a consumer group (XGROUP SETID).
+    pub fn set_stream_group_id(&mut self, group: SharedString, id: SharedString, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let mut command = cmd("XGROUP");
+        command
+            .arg("SETID")
+            .arg(RedisKey::from(&key))
+            .arg(group.as_str())
+            .arg(id.as_str());
+        self.run_stream_group_command(ServerTask::UpdateStreamGroup, command, "set_group_id_success", cx);
+    }
+
+    /// Destroys a consumer group and its pending entries list (XGROUP DESTROY).
+    pub fn destroy_stream_group(&mut self, group: SharedString, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let mut command = cmd("XGROUP");
+        command.arg("DESTROY").arg(RedisKey::from(&key)).arg(group.as_str());
+        self.run_stream_group_command(ServerTask::UpdateStreamGroup, command, "destroy_group_success", cx);
+    }
+
+    /// Removes a consumer from a group; its pending entries are dropped (XGROUP DELCONSUMER).
+    pub fn delete_stream_consumer(&mut self, group: SharedString, consumer: SharedString, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let mut command = cmd("XGROUP");
+        command
+            .arg("DELCONSUMER")
+            .arg(RedisKey::from(&key))
+            .arg(group.as_str())
+            .arg(consumer.as_str());
+        self.run_stream_group_command(ServerTask::UpdateStreamGroup, command, "delete_consumer_success", cx);
+    }
+
+    /// Acknowledges pending entries of a group (XACK).
+    pub fn ack_stream_entries(&mut self, group: SharedString, ids: Vec<SharedString>, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        if ids.is_empty() {
+            return;
+        }
+        let mut command = cmd("XACK");
+        command.arg(RedisKey::from(&key)).arg(group.as_str());
+        for id in &ids {
+            command.arg(id.as_str());
+        }
+        self.run_stream_group_command(ServerTask::AckStreamEntries, command, "ack_success", cx);
+    }
+
+    /// Transfers ownership of pending entries to another consumer (XCLAIM).
+    ///
+    /// # Arguments
+    /// * `group` - Consumer group name
+    /// * `consumer` - Consumer that takes over the entries
+    /// * `min_idle_time` - Only claim entries idle for at least this many milliseconds
+    /// * `ids` - Pending entry IDs to claim
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn claim_stream_entries(
+        &mut self,
+        group: SharedString,
+        consumer: SharedString,
+        min_idle_time: u64,
+        ids: Vec<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        if ids.is_empty() {
+            return;
+        }
+        let mut command = cmd("XCLAIM");
+        command
+            .arg(RedisKey::from(&key))
+            .arg(group.as_str())
+            .arg(consumer.as_str())
+            .arg(min_idle_time);
+        for id in &ids {
+            command.arg(id.as_str());
+        }
+        command.arg("JUSTID");
+        self.run_stream_group_command(ServerTask::ClaimStreamEntries, command, "claim_success", cx);
+    }
+
+    /// Claims up to `count` idle pending entries of a group for a consumer (XAUTOCLAIM, Redis 6.2+).
+    pub fn autoclaim_stream_entries(
+        &mut self,
+        group: SharedString,
+        consumer: SharedString,
+        min_idle_time: u64,
+        count: usize,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let mut command = cmd("XAUTOCLAIM");
+        command
+            .arg(RedisKey::from(&key))
+            .arg(group.as_str())
+            .arg(consumer.as_str())
+            .arg(min_idle_time)
+            .arg("0-0")
+            .arg("COUNT")
+            .arg(count)
+            .arg("JUSTID");
+        self.run_stream_group_command(ServerTask::AutoClaimStreamEntries, command, "claim_success", cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ServerTask, affected_count, parse_stream_consumer, parse_stream_group};
+    use redis::Value;
+    use std::collections::HashMap;
+
+    fn bulk(value: &str) -> Value {
+        Value::BulkString(value.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn parses_stream_group_info() {
+        let info = HashMap::from([
+            ("name".to_string(), bulk("workers")),
+            ("consumers".to_string(), Value::Int(2)),
+            ("pending".to_string(), Value::Int(5)),
+            ("last-delivered-id".to_string(), bulk("1700000000000-3")),
+            ("entries-read".to_string(), Value::Nil),
+            ("lag".to_string(), Value::Int(7)),
+        ]);
+        let group = parse_stream_group(&info);
+
+        assert_eq!(group.name.as_str(), "workers");
+        assert_eq!(group.consumers, 2);
+        assert_eq!(group.pending, 5);
+        assert_eq!(group.last_delivered_id.as_str(), "1700000000000-3");
+        assert_eq!(group.entries_read, None);
+        assert_eq!(group.lag, Some(7));
+    }
+
+    #[test]
+    fn parses_stream_consumer_without_inactive_field() {
+        let info = HashMap::from([
+            ("name".to_string(), bulk("alice")),
+            ("pending".to_string(), Value::Int(1)),
+            ("idle".to_string(), Value::Int(1500)),
+        ]);
+        let consumer = parse_stream_consumer(&info);
+
+        assert_eq!(consumer.name.as_str(), "alice");
+        assert_eq!(consumer.idle, 1500);
+        assert_eq!(consumer.inactive, None);
+    }
+
+    #[test]
+    fn counts_affected_entries_by_reply_shape() {
+        assert_eq!(affected_count(&ServerTask::AckStreamEntries, &Value::Int(3)), Some(3));
+        assert_eq!(
+            affected_count(
+                &ServerTask::ClaimStreamEntries,
+                &Value::Array(vec![bulk("1-0"), bulk("2-0")])
+            ),
+            Some(2)
+        );
+        assert_eq!(
+            affected_count(
+                &ServerTask::AutoClaimStreamEntries,
+                &Value::Array(vec![bulk("0-0"), Value::Array(vec![bulk("1-0")]), Value::Array(vec![])])
+            ),
+            Some(1)
+        );
+        assert_eq!(affected_count(&ServerTask::UpdateStreamGroup, &Value::Okay), None);
+    }
+}
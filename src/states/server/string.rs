@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::value::{DataFormat, KeyType, RedisBytesValue, RedisValue, RedisValueData, detect_format};
-use crate::helpers::{decode_raw_to_json, decompress_zstd};
+use crate::helpers::{RedisKey, decode_raw_to_json, decompress_zstd};
 use crate::{connection::RedisAsyncConn, error::Error};
 use bytes::Bytes;
 use flate2::read::GzDecoder;
@@ -151,7 +151,7 @@ pub(crate) async fn get_redis_value(
     key: &str,
     max_truncate_length: usize,
 ) -> Result<RedisValue> {
-    let value_bytes: Vec<u8> = cmd("GET").arg(key).query_async(conn).await?;
+    let value_bytes: Vec<u8> = cmd("GET").arg(RedisKey::from(key)).query_async(conn).await?;
     let size = value_bytes.len();
     let mut data = RedisBytesValue {
         format: DataFormat::Text,
@@ -13,12 +13,12 @@
 // limitations under the License.
 
 use super::{ServerEvent, ServerTask, ZedisServerState};
-use crate::connection::get_connection_manager;
-use crate::helpers::is_likely_protobuf;
+use crate::connection::{RedisAsyncConn, get_connection_manager, length_command};
+use crate::helpers::{RedisKey, is_likely_protobuf};
 use bytes::Bytes;
 use chrono::Local;
 use gpui::{Action, Hsla, SharedString, prelude::*};
-use redis::cmd;
+use redis::{Value, cmd};
 use schemars::JsonSchema;
 use serde::Deserialize;
 use std::io::Cursor;
@@ -232,6 +232,8 @@ pub enum RedisValueData {
     Set(Arc<RedisSetValue>),
     Zset(Arc<RedisZsetValue>),
     Hash(Arc<RedisHashValue>),
+    Stream(Arc<RedisStreamValue>),
+    VectorSet(Arc<RedisVectorSetValue>),
 }
 
 /// Redis Set value structure with pagination support
@@ -273,6 +275,109 @@ pub struct RedisHashValue {
     pub values: Vec<(SharedString, SharedString)>,
 }
 
+/// A single Redis Stream entry with its decoded field-value pairs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RedisStreamEntry {
+    pub id: SharedString,
+    pub fields: Vec<(SharedString, SharedString)>,
+}
+
+/// Consumer group of a Redis Stream (XINFO GROUPS)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RedisStreamGroup {
+    pub name: SharedString,
+    pub consumers: u64,
+    pub pending: u64,
+    pub last_delivered_id: SharedString,
+    /// Logical read counter, only reported by Redis 7.0+
+    pub entries_read: Option<u64>,
+    /// Number of entries not yet delivered, only reported by Redis 7.0+
+    pub lag: Option<u64>,
+}
+
+/// Consumer of a Redis Stream consumer group (XINFO CONSUMERS)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RedisStreamConsumer {
+    pub name: SharedString,
+    pub pending: u64,
+    /// Milliseconds since the consumer last interacted with the server
+    pub idle: u64,
+    /// Milliseconds since the last successful read, only reported by Redis 7.2+
+    pub inactive: Option<i64>,
+}
+
+/// Pending entry of a Redis Stream consumer group (XPENDING extended form)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RedisStreamPendingEntry {
+    pub id: SharedString,
+    pub consumer: SharedString,
+    /// Milliseconds since the entry was last delivered
+    pub idle: u64,
+    pub delivered: u64,
+}
+
+/// Consumer groups of a Redis Stream with the details of the selected group
+#[derive(Debug, Clone, Default)]
+pub struct RedisStreamGroupsValue {
+    pub groups: Vec<RedisStreamGroup>,
+    pub selected_group: Option<SharedString>,
+    pub consumers: Vec<RedisStreamConsumer>,
+    pub pending: Vec<RedisStreamPendingEntry>,
+}
+
+/// Redis Stream value structure with ID range pagination support
+#[derive(Debug, Clone, Default)]
+pub struct RedisStreamValue {
+    pub keyword: Option<SharedString>,
+    /// ID of the last scanned entry, the next page starts right after it
+    pub cursor: Option<SharedString>,
+    pub size: usize,
+    pub values: Vec<RedisStreamEntry>,
+    pub done: bool,
+    pub sort_order: SortOrder,
+    /// Consumer groups, loaded on demand by the consumer group panel
+    pub groups: Option<RedisStreamGroupsValue>,
+}
+
+/// A single element of a Redis vector set with its JSON attributes (VGETATTR)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RedisVectorSetElement {
+    pub name: SharedString,
+    pub attributes: Option<SharedString>,
+}
+
+/// Scored element returned by a VSIM similarity search
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RedisVectorSetMatch {
+    pub name: SharedString,
+    pub score: f64,
+    pub attributes: Option<SharedString>,
+}
+
+/// Result of the last VSIM similarity search
+#[derive(Debug, Clone, Default)]
+pub struct RedisVectorSetSearch {
+    /// Element name or vector the search was run with
+    pub query: SharedString,
+    pub matches: Vec<RedisVectorSetMatch>,
+}
+
+/// Redis vector set value structure with VRANDMEMBER sampling support
+#[derive(Debug, Clone, Default)]
+pub struct RedisVectorSetValue {
+    pub keyword: Option<SharedString>,
+    /// Size of the last VRANDMEMBER sample, the next page samples more elements
+    pub sample_size: usize,
+    pub size: usize,
+    /// Vector dimension (VDIM)
+    pub dim: usize,
+    /// Field-value pairs reported by VINFO, such as quant-type and max-level
+    pub info: Vec<(SharedString, SharedString)>,
+    pub values: Vec<RedisVectorSetElement>,
+    pub done: bool,
+    pub search: Option<RedisVectorSetSearch>,
+}
+
 /// Redis List value structure
 #[derive(Debug, Clone, Default)]
 pub struct RedisListValue {
@@ -358,6 +463,22 @@ impl RedisValue {
         }
         None
     }
+
+    /// Returns the stream value if the data is a Stream type
+    pub fn stream_value(&self) -> Option<&Arc<RedisStreamValue>> {
+        if let Some(RedisValueData::Stream(data)) = self.data.as_ref() {
+            return Some(data);
+        }
+        None
+    }
+
+    /// Returns the vector set value if the data is a Vectorset type
+    pub fn vector_set_value(&self) -> Option<&Arc<RedisVectorSetValue>> {
+        if let Some(RedisValueData::VectorSet(data)) = self.data.as_ref() {
+            return Some(data);
+        }
+        None
+    }
 }
 
 /// Redis key types: string, list, set, zset, hash, stream, and vectorset
@@ -374,6 +495,33 @@ pub enum KeyType {
     Vectorset,
 }
 impl KeyType {
+    /// Returns every type a key can have
+    pub fn known_types() -> [KeyType; 7] {
+        [
+            KeyType::String,
+            KeyType::List,
+            KeyType::Set,
+            KeyType::Zset,
+            KeyType::Hash,
+            KeyType::Stream,
+            KeyType::Vectorset,
+        ]
+    }
+
+    /// Returns the type name reported by `TYPE` and accepted by `SCAN ... TYPE`
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            KeyType::String => "string",
+            KeyType::List => "list",
+            KeyType::Set => "set",
+            KeyType::Zset => "zset",
+            KeyType::Hash => "hash",
+            KeyType::Stream => "stream",
+            KeyType::Vectorset => "vectorset",
+            KeyType::Unknown => "",
+        }
+    }
+
     /// Returns the abbreviated string representation of the key type
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -412,6 +560,37 @@ pub enum RedisValueStatus {
     Updating,
 }
 
+/// Hash fields whose TTL is read by the key inspector at most
+const MAX_INSPECTED_FIELDS: usize = 100;
+
+/// Internal metadata of a key shown by the key inspector
+///
+/// Every entry is `None` when the server does not support the command, e.g. `OBJECT FREQ`
+/// without an LFU policy or `CLUSTER KEYSLOT` outside a cluster.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RedisKeyMetadata {
+    /// `OBJECT ENCODING`, e.g. listpack or hashtable
+    pub encoding: Option<String>,
+    /// `OBJECT REFCOUNT`
+    pub refcount: Option<i64>,
+    /// `OBJECT IDLETIME` in seconds, not available with an LFU policy
+    pub idle_seconds: Option<u64>,
+    /// `OBJECT FREQ`, only available with an LFU policy
+    pub frequency: Option<u64>,
+    /// `MEMORY USAGE SAMPLES 0`, every element is measured
+    pub memory_usage: Option<u64>,
+    /// `CLUSTER KEYSLOT`
+    pub slot: Option<u16>,
+    /// `host:port` of the master serving the slot
+    pub node: Option<String>,
+    /// Number of elements, the length for strings
+    pub elements: Option<u64>,
+    /// Number of hash fields whose TTL was read
+    pub inspected_fields: usize,
+    /// Hash fields with a TTL in seconds (`HTTL`, Redis 7.4)
+    pub field_ttls: Option<Vec<(SharedString, i64)>>,
+}
+
 /// Redis value with metadata including type, data, expiration, and status
 #[derive(Debug, Clone, Default)]
 pub struct RedisValue {
@@ -420,6 +599,8 @@ pub struct RedisValue {
     pub(crate) data: Option<RedisValueData>,
     pub(crate) expire_at: Option<i64>,
     pub(crate) size: usize,
+    /// Loaded on demand by the key inspector
+    pub(crate) metadata: Option<Arc<RedisKeyMetadata>>,
 }
 
 impl RedisValue {
@@ -490,6 +671,104 @@ impl RedisValue {
     pub fn is_expired(&self) -> bool {
         self.expire_at.is_some_and(|expire_at| expire_at == -2)
     }
+
+    /// Returns the metadata read by the key inspector
+    pub fn metadata(&self) -> Option<Arc<RedisKeyMetadata>> {
+        self.metadata.clone()
+    }
+}
+
+/// Returns the `host:port` of the master serving a slot from a `CLUSTER SLOTS` reply.
+fn slot_owner(slots: &Value, slot: u16) -> Option<String> {
+    let Value::Array(ranges) = slots else {
+        return None;
+    };
+    ranges.iter().find_map(|range| {
+        let Value::Array(items) = range else {
+            return None;
+        };
+        let (Some(Value::Int(start)), Some(Value::Int(end)), Some(Value::Array(master))) =
+            (items.first(), items.get(1), items.get(2))
+        else {
+            return None;
+        };
+        if !(*start..=*end).contains(&i64::from(slot)) {
+            return None;
+        }
+        let host = match master.first()? {
+            Value::BulkString(host) => String::from_utf8_lossy(host).to_string(),
+            Value::SimpleString(host) => host.clone(),
+            _ => return None,
+        };
+        let Value::Int(port) = master.get(1)? else {
+            return None;
+        };
+        Some(format!("{host}:{port}"))
+    })
+}
+
+/// Reads the internal metadata of a key.
+///
+/// The commands are sent one by one, so a command the server rejects only leaves its
+/// entry empty.
+///
+/// # Arguments
+/// * `key_type` - Type of the key, used to read its length
+/// * `fields` - Loaded hash fields whose TTL is read
+async fn load_key_metadata(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    key_type: KeyType,
+    fields: Vec<SharedString>,
+) -> RedisKeyMetadata {
+    let redis_key = RedisKey::from(key);
+    let object = |subcommand: &str| cmd("OBJECT").arg(subcommand).arg(&redis_key).clone();
+    let mut metadata = RedisKeyMetadata {
+        encoding: object("ENCODING").query_async(conn).await.ok(),
+        refcount: object("REFCOUNT").query_async(conn).await.ok(),
+        idle_seconds: object("IDLETIME").query_async(conn).await.ok(),
+        frequency: object("FREQ").query_async(conn).await.ok(),
+        memory_usage: cmd("MEMORY")
+            .arg("USAGE")
+            .arg(&redis_key)
+            .arg("SAMPLES")
+            .arg(0)
+            .query_async(conn)
+            .await
+            .ok(),
+        ..Default::default()
+    };
+    if let Some(length) = length_command(key_type.type_name(), redis_key.as_bytes()) {
+        metadata.elements = length.query_async(conn).await.ok();
+    }
+
+    metadata.slot = cmd("CLUSTER")
+        .arg("KEYSLOT")
+        .arg(&redis_key)
+        .query_async(conn)
+        .await
+        .ok();
+    if let Some(slot) = metadata.slot
+        && let Ok(slots) = cmd("CLUSTER").arg("SLOTS").query_async::<Value>(conn).await
+    {
+        metadata.node = slot_owner(&slots, slot);
+    }
+
+    if key_type == KeyType::Hash && !fields.is_empty() {
+        let fields: Vec<SharedString> = fields.into_iter().take(MAX_INSPECTED_FIELDS).collect();
+        let ttls: Option<Vec<i64>> = cmd("HTTL")
+            .arg(&redis_key)
+            .arg("FIELDS")
+            .arg(fields.len())
+            .arg(fields.iter().map(|field| field.as_str()).collect::<Vec<_>>())
+            .query_async(conn)
+            .await
+            .ok();
+        metadata.inspected_fields = fields.len();
+        // -1 is returned for the fields without TTL, -2 for the deleted ones
+        metadata.field_ttls = ttls.map(|ttls| fields.into_iter().zip(ttls).filter(|(_, ttl)| *ttl >= 0).collect());
+    }
+    metadata
 }
 
 /// Converts a string representation to a KeyType
@@ -548,7 +827,7 @@ impl ZedisServerState {
                     .await?;
                 let mut conn = client.connection();
                 let mut binding = cmd("SET");
-                let mut cmd = binding.arg(key.as_str()).arg(new_value.as_str());
+                let mut cmd = binding.arg(RedisKey::from(&key)).arg(new_value.as_str());
                 // keep ttl if the version is at least 6.0.0
                 cmd = if client.is_at_least_version("6.0.0") {
                     cmd.arg("KEEPTTL")
@@ -615,7 +894,7 @@ impl ZedisServerState {
                     .await?;
                 let mut conn = client.connection();
                 let mut binding = cmd("SET");
-                let mut set_cmd = binding.arg(key.as_str()).arg(bytes_for_save.as_ref());
+                let mut set_cmd = binding.arg(RedisKey::from(&key)).arg(bytes_for_save.as_ref());
                 // keep ttl if the version is at least 6.0.0
                 set_cmd = if client.is_at_least_version("6.0.0") {
                     set_cmd.arg("KEEPTTL")
@@ -650,6 +929,49 @@ impl ZedisServerState {
         );
     }
 
+    /// Reads the internal metadata of the selected key for the key inspector.
+    ///
+    /// The metadata is stored in the value, so it is cleared when the key is reloaded.
+    pub fn inspect_key(&mut self, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let Some(value) = self.value.as_ref() else {
+            return;
+        };
+        if value.is_expired() || value.key_type == KeyType::Unknown {
+            return;
+        }
+        let key_type = value.key_type;
+        let fields = value
+            .hash_value()
+            .map(|hash| hash.values.iter().map(|(field, _)| field.clone()).collect())
+            .unwrap_or_default();
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let current_key = key.clone();
+        self.spawn(
+            ServerTask::InspectKey,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                Ok(load_key_metadata(&mut conn, &key, key_type, fields).await)
+            },
+            move |this, result, cx| {
+                // The selection changed while the metadata was read
+                if this.key.as_ref() != Some(&current_key) {
+                    return;
+                }
+                if let Ok(metadata) = result
+                    && let Some(value) = this.value.as_mut()
+                {
+                    value.metadata = Some(Arc::new(metadata));
+                    cx.notify();
+                }
+            },
+            cx,
+        );
+    }
+
     pub fn update_bytes_value_view_mode(&mut self, view_mode: SharedString, cx: &mut Context<Self>) {
         let Some(value) = self.value.as_mut() else {
             return;
@@ -665,3 +987,35 @@ impl ZedisServerState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(host: &str, port: i64) -> Value {
+        Value::Array(vec![
+            Value::BulkString(host.as_bytes().to_vec()),
+            Value::Int(port),
+            Value::BulkString(b"node-id".to_vec()),
+        ])
+    }
+
+    #[test]
+    fn finds_slot_owner() {
+        let slots = Value::Array(vec![
+            Value::Array(vec![
+                Value::Int(0),
+                Value::Int(5460),
+                node("10.0.0.1", 7000),
+                node("10.0.0.4", 7003),
+            ]),
+            Value::Array(vec![Value::Int(5461), Value::Int(10922), node("10.0.0.2", 7001)]),
+            Value::Array(vec![Value::Int(10923), Value::Int(16383), node("10.0.0.3", 7002)]),
+        ]);
+        assert_eq!(slot_owner(&slots, 0), Some("10.0.0.1:7000".to_string()));
+        assert_eq!(slot_owner(&slots, 5461), Some("10.0.0.2:7001".to_string()));
+        assert_eq!(slot_owner(&slots, 16383), Some("10.0.0.3:7002".to_string()));
+        assert_eq!(slot_owner(&Value::Array(vec![]), 42), None);
+        assert_eq!(slot_owner(&Value::Nil, 42), None);
+    }
+}
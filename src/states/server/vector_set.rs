@@ -0,0 +1,690 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redis vector set (Redis 8) data type operations module.
+//!
+//! This module provides functionality for managing vector sets including:
+//! - Loading metadata (VCARD/VDIM/VINFO) and sampling elements (VRANDMEMBER)
+//! - Reading element attributes (VGETATTR)
+//! - Similarity search by element or by vector (VSIM)
+//! - Adding elements, removing them and updating their attributes (VADD/VREM/VSETATTR)
+
+use super::{
+    KeyType, RedisValueData, ServerTask, ZedisServerState,
+    value::{
+        RedisValue, RedisValueStatus, RedisVectorSetElement, RedisVectorSetMatch, RedisVectorSetSearch,
+        RedisVectorSetValue,
+    },
+};
+use crate::{
+    connection::{RedisAsyncConn, get_connection_manager},
+    error::Error,
+    helpers::{RedisKey, fast_contains_ignore_case, redis_value_to_string},
+    states::{NotificationAction, ServerEvent, i18n_vector_set_editor},
+};
+use gpui::{SharedString, prelude::*};
+use redis::{Value, cmd, pipe};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Number of elements sampled per page without a filter
+const PAGE_SIZE: usize = 100;
+/// Number of elements sampled per page while filtering
+const FILTER_PAGE_SIZE: usize = 1000;
+
+/// Query of a VSIM similarity search.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VectorSetQuery {
+    /// Find elements similar to an existing element (VSIM ELE)
+    Element(SharedString),
+    /// Find elements similar to the given vector (VSIM VALUES)
+    Vector(Vec<f64>),
+}
+
+impl VectorSetQuery {
+    /// Returns a short description of the query shown above the result table.
+    fn label(&self) -> SharedString {
+        match self {
+            VectorSetQuery::Element(element) => element.clone(),
+            VectorSetQuery::Vector(values) => {
+                let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                format!("[{}]", values.join(", ")).into()
+            }
+        }
+    }
+}
+
+/// Parses a vector from a JSON array (`[0.1, 0.2]`) or comma / whitespace separated numbers.
+pub fn parse_vector(input: &str) -> Option<Vec<f64>> {
+    let input = input.trim().trim_start_matches('[').trim_end_matches(']');
+    let values = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<f64>().ok().filter(|v| v.is_finite()))
+        .collect::<Option<Vec<f64>>>()?;
+    (!values.is_empty()).then_some(values)
+}
+
+/// Parses the VINFO reply (a flat array in RESP2, a map in RESP3) into field-value pairs.
+fn parse_vector_set_info(value: &Value) -> Vec<(SharedString, SharedString)> {
+    match value {
+        Value::Map(items) => items
+            .iter()
+            .map(|(field, value)| (redis_value_to_string(field).into(), redis_value_to_string(value).into()))
+            .collect(),
+        Value::Array(items) => items
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [field, value] => Some((redis_value_to_string(field).into(), redis_value_to_string(value).into())),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Parses the `VSIM ... WITHSCORES` reply into (element, score) pairs.
+///
+/// RESP2 replies with a flat array of element and score strings, RESP3 with a map.
+fn parse_vector_set_matches(value: &Value) -> Vec<(String, f64)> {
+    let score = |value: &Value| match value {
+        Value::Double(score) => *score,
+        value => redis_value_to_string(value).parse().unwrap_or_default(),
+    };
+    match value {
+        Value::Map(items) => items
+            .iter()
+            .map(|(element, value)| (redis_value_to_string(element), score(value)))
+            .collect(),
+        Value::Array(items) => items
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [element, value] => Some((redis_value_to_string(element), score(value))),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Checks whether an element matches the filter keyword (lowercase) by name or attributes.
+fn vector_set_element_matches(element: &RedisVectorSetElement, keyword_lower: &str) -> bool {
+    fast_contains_ignore_case(element.name.as_str(), keyword_lower)
+        || element
+            .attributes
+            .as_ref()
+            .is_some_and(|attributes| fast_contains_ignore_case(attributes.as_str(), keyword_lower))
+}
+
+/// Fetches the JSON attributes of the given elements with pipelined VGETATTR calls.
+async fn get_vector_set_attributes(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    elements: &[String],
+) -> Result<Vec<Option<String>>> {
+    if elements.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut pipeline = pipe();
+    for element in elements {
+        pipeline.cmd("VGETATTR").arg(RedisKey::from(key)).arg(element);
+    }
+    let attributes: Vec<Option<String>> = pipeline.query_async(conn).await?;
+    Ok(attributes)
+}
+
+/// Samples distinct elements of a vector set with VRANDMEMBER and loads their attributes.
+///
+/// Vector sets have no cursor based iteration, so pages are random samples of a growing
+/// size. A sample of at least VCARD elements always returns the whole set.
+///
+/// # Arguments
+/// * `conn` - Redis async connection
+/// * `key` - The vector set key
+/// * `count` - Number of distinct elements to sample
+async fn get_redis_vector_set_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    count: usize,
+) -> Result<Vec<RedisVectorSetElement>> {
+    let names: Vec<String> = cmd("VRANDMEMBER")
+        .arg(RedisKey::from(key))
+        .arg(count)
+        .query_async(conn)
+        .await?;
+    let attributes = get_vector_set_attributes(conn, key, &names).await?;
+
+    Ok(names
+        .into_iter()
+        .zip(attributes)
+        .map(|(name, attributes)| RedisVectorSetElement {
+            name: name.into(),
+            attributes: attributes.map(SharedString::from),
+        })
+        .collect())
+}
+
+/// Performs initial load of a Redis vector set.
+///
+/// Fetches the cardinality (VCARD), dimension (VDIM) and VINFO details, then samples
+/// the first batch of elements (up to 100).
+///
+/// # Arguments
+/// * `conn` - Redis async connection
+/// * `key` - The vector set key to load
+///
+/// # Returns
+/// A `RedisValue` containing vector set metadata and the first sampled elements
+pub(crate) async fn first_load_vector_set_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
+    let (size, dim, info): (usize, usize, Value) = pipe()
+        .cmd("VCARD")
+        .arg(RedisKey::from(key))
+        .cmd("VDIM")
+        .arg(RedisKey::from(key))
+        .cmd("VINFO")
+        .arg(RedisKey::from(key))
+        .query_async(conn)
+        .await?;
+    let values = get_redis_vector_set_value(conn, key, PAGE_SIZE).await?;
+    let done = values.len() >= size;
+
+    Ok(RedisValue {
+        key_type: KeyType::Vectorset,
+        data: Some(RedisValueData::VectorSet(Arc::new(RedisVectorSetValue {
+            sample_size: PAGE_SIZE,
+            size,
+            dim,
+            info: parse_vector_set_info(&info),
+            values,
+            done,
+            ..Default::default()
+        }))),
+        ..Default::default()
+    })
+}
+
+impl ZedisServerState {
+    /// Adds an element to the vector set, or updates its vector when it already exists.
+    ///
+    /// # Arguments
+    /// * `element` - Element name
+    /// * `vector` - Vector values, must match the dimension of the set
+    /// * `attributes` - Optional JSON attributes stored with VADD SETATTR
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn add_vector_set_value(
+        &mut self,
+        element: SharedString,
+        vector: Vec<f64>,
+        attributes: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let element_clone = element.clone();
+        let attributes_clone = attributes.clone();
+
+        self.spawn(
+            ServerTask::AddVectorSetValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut binding = cmd("VADD");
+                binding.arg(RedisKey::from(&key)).arg("VALUES").arg(vector.len());
+                for v in &vector {
+                    binding.arg(*v);
+                }
+                binding.arg(element.as_str());
+                if let Some(attributes) = &attributes {
+                    binding.arg("SETATTR").arg(attributes.as_str());
+                }
+                let added: bool = binding.query_async(&mut conn).await?;
+                Ok(added)
+            },
+            move |this, result, cx| {
+                let Some(value) = this.value.as_mut() else {
+                    return;
+                };
+                value.status = RedisValueStatus::Idle;
+
+                if let Ok(added) = result
+                    && let Some(RedisValueData::VectorSet(vector_set_data)) = value.data.as_mut()
+                {
+                    let vector_set = Arc::make_mut(vector_set_data);
+                    if let Some(element) = vector_set.values.iter_mut().find(|e| e.name == element_clone) {
+                        element.attributes = attributes_clone;
+                    } else if vector_set.keyword.is_none() {
+                        vector_set.values.push(RedisVectorSetElement {
+                            name: element_clone.clone(),
+                            attributes: attributes_clone,
+                        });
+                    }
+                    if added {
+                        vector_set.size += 1;
+                    }
+                    info!(key = %key_clone, element = %element_clone, added, "Added Redis vector set element");
+                    let title = i18n_vector_set_editor(cx, "add_value_success");
+                    cx.emit(ServerEvent::Notification(
+                        NotificationAction::new_success(element_clone).with_title(title),
+                    ));
+                    cx.emit(ServerEvent::ValueAdded(key_clone));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Removes elements from the vector set with pipelined VREM calls.
+    ///
+    /// # Arguments
+    /// * `elements` - Element names to remove
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn remove_vector_set_values(&mut self, elements: Vec<SharedString>, cx: &mut Context<Self>) {
+        if elements.is_empty() {
+            debug!("Skip vector set element removal because no elements were selected");
+            return;
+        }
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let elements_for_task = elements.clone();
+
+        self.spawn(
+            ServerTask::RemoveVectorSetValues,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut pipeline = pipe();
+                for element in &elements_for_task {
+                    pipeline.cmd("VREM").arg(RedisKey::from(&key)).arg(element.as_str());
+                }
+                let removed: Vec<bool> = pipeline.query_async(&mut conn).await?;
+                Ok(removed.into_iter().filter(|removed| *removed).count())
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    if let Ok(count) = result {
+                        if let Some(RedisValueData::VectorSet(vector_set_data)) = value.data.as_mut() {
+                            let vector_set = Arc::make_mut(vector_set_data);
+                            vector_set.values.retain(|element| !elements.contains(&element.name));
+                            if let Some(search) = vector_set.search.as_mut() {
+                                search.matches.retain(|m| !elements.contains(&m.name));
+                            }
+                            vector_set.size = vector_set.size.saturating_sub(count);
+                            info!(key = %key_clone, removed = count, "Removed Redis vector set elements");
+                        }
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Replaces the JSON attributes of an element with VSETATTR.
+    ///
+    /// # Arguments
+    /// * `element` - Element name
+    /// * `attributes` - New JSON attributes, an empty string removes them
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn update_vector_set_attributes(
+        &mut self,
+        element: SharedString,
+        attributes: SharedString,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let element_clone = element.clone();
+        let attributes = attributes.trim().to_string();
+        let new_attributes = (!attributes.is_empty()).then(|| SharedString::from(attributes.clone()));
+
+        self.spawn(
+            ServerTask::UpdateVectorSetAttributes,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let updated: bool = cmd("VSETATTR")
+                    .arg(RedisKey::from(&key))
+                    .arg(element.as_str())
+                    .arg(attributes)
+                    .query_async(&mut conn)
+                    .await?;
+                Ok(updated)
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    if let Ok(true) = result
+                        && let Some(RedisValueData::VectorSet(vector_set_data)) = value.data.as_mut()
+                    {
+                        let vector_set = Arc::make_mut(vector_set_data);
+                        if let Some(element) = vector_set.values.iter_mut().find(|e| e.name == element_clone) {
+                            element.attributes = new_attributes.clone();
+                        }
+                        if let Some(search) = vector_set.search.as_mut()
+                            && let Some(m) = search.matches.iter_mut().find(|m| m.name == element_clone)
+                        {
+                            m.attributes = new_attributes;
+                        }
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Runs a VSIM similarity search and stores the scored matches on the value.
+    ///
+    /// # Arguments
+    /// * `query` - Element or vector to compare against
+    /// * `count` - Maximum number of matches
+    /// * `filter` - Optional filter expression over the element attributes (VSIM FILTER)
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn search_vector_set(
+        &mut self,
+        query: VectorSetQuery,
+        count: usize,
+        filter: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let label = query.label();
+
+        self.spawn(
+            ServerTask::SearchVectorSet,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut binding = cmd("VSIM");
+                binding.arg(RedisKey::from(&key));
+                match &query {
+                    VectorSetQuery::Element(element) => {
+                        binding.arg("ELE").arg(element.as_str());
+                    }
+                    VectorSetQuery::Vector(values) => {
+                        binding.arg("VALUES").arg(values.len());
+                        for v in values {
+                            binding.arg(*v);
+                        }
+                    }
+                }
+                binding.arg("WITHSCORES").arg("COUNT").arg(count);
+                if let Some(filter) = &filter {
+                    binding.arg("FILTER").arg(filter.as_str());
+                }
+                let reply: Value = binding.query_async(&mut conn).await?;
+                let matches = parse_vector_set_matches(&reply);
+
+                let names: Vec<String> = matches.iter().map(|(name, _)| name.clone()).collect();
+                let attributes = get_vector_set_attributes(&mut conn, &key, &names).await?;
+                Ok(matches
+                    .into_iter()
+                    .zip(attributes)
+                    .map(|((name, score), attributes)| RedisVectorSetMatch {
+                        name: name.into(),
+                        score,
+                        attributes: attributes.map(SharedString::from),
+                    })
+                    .collect::<Vec<_>>())
+            },
+            move |this, result, cx| {
+                if this.key.as_ref() != Some(&key_clone) {
+                    return;
+                }
+                if let Some(value) = this.value.as_mut() {
+                    if let Ok(matches) = result
+                        && let Some(RedisValueData::VectorSet(vector_set_data)) = value.data.as_mut()
+                    {
+                        debug!(key = %key_clone, matches = matches.len(), "Vector set similarity search finished");
+                        Arc::make_mut(vector_set_data).search = Some(RedisVectorSetSearch { query: label, matches });
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Drops the result of the last similarity search.
+    pub fn clear_vector_set_search(&mut self, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        if let Some(RedisValueData::VectorSet(vector_set_data)) = value.data.as_mut() {
+            Arc::make_mut(vector_set_data).search = None;
+            cx.emit(ServerEvent::ValueUpdated(key));
+        }
+        cx.notify();
+    }
+
+    /// Applies a filter to the sampled elements by resetting the sampling state with a keyword.
+    ///
+    /// Elements are matched client-side against their name and attributes.
+    ///
+    /// # Arguments
+    /// * `keyword` - The search keyword
+    /// * `cx` - GPUI context for UI updates
+    pub fn filter_vector_set_value(&mut self, keyword: SharedString, cx: &mut Context<Self>) -> bool {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return false;
+        };
+        let Some(vector_set) = value.vector_set_value() else {
+            return false;
+        };
+        let filter_keyword = if keyword.is_empty() {
+            None
+        } else {
+            Some(keyword.clone())
+        };
+
+        let new_vector_set = RedisVectorSetValue {
+            keyword: filter_keyword,
+            size: vector_set.size,
+            dim: vector_set.dim,
+            info: vector_set.info.clone(),
+            search: vector_set.search.clone(),
+            ..Default::default()
+        };
+        value.data = Some(RedisValueData::VectorSet(Arc::new(new_vector_set)));
+        self.remember_value_filter_keyword_for_key(key.as_str(), keyword);
+
+        self.load_more_vector_set_value(cx);
+        true
+    }
+
+    /// Loads more elements by sampling a larger batch with VRANDMEMBER.
+    ///
+    /// Already loaded elements are skipped, so each page only adds new ones. When filtering,
+    /// larger samples (1000) are used until enough matches are found or the set is exhausted.
+    ///
+    /// # Arguments
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn load_more_vector_set_value(&mut self, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let (sample_size, keyword) = match value.vector_set_value() {
+            Some(vector_set) => (vector_set.sample_size, vector_set.keyword.clone()),
+            None => return,
+        };
+        let page_size = if keyword.is_some() { FILTER_PAGE_SIZE } else { PAGE_SIZE };
+        let count = sample_size + page_size;
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        cx.emit(ServerEvent::ValuePaginationStarted(key.clone()));
+
+        let key_clone = key.clone();
+
+        self.spawn(
+            ServerTask::LoadMoreValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                get_redis_vector_set_value(&mut conn, &key, count).await
+            },
+            move |this, result, cx| {
+                if this.key.as_ref() != Some(&key_clone) {
+                    debug!(key = key_clone.as_str(), "Skip stale vector set pagination result");
+                    return;
+                }
+
+                let mut should_load_more = false;
+                if let Ok(elements) = result
+                    && let Some(RedisValueData::VectorSet(vector_set_data)) =
+                        this.value.as_mut().and_then(|v| v.data.as_mut())
+                {
+                    let vector_set = Arc::make_mut(vector_set_data);
+                    if vector_set.keyword != keyword || vector_set.sample_size != sample_size {
+                        debug!(key = key_clone.as_str(), "Skip stale vector set pagination result");
+                        return;
+                    }
+                    // A sample smaller than requested means the whole set has been returned
+                    vector_set.done = elements.len() < count;
+                    vector_set.sample_size = count;
+                    let keyword_lower = keyword.as_ref().map(|k| k.to_lowercase());
+                    for element in elements {
+                        if keyword_lower
+                            .as_ref()
+                            .is_some_and(|k| !vector_set_element_matches(&element, k))
+                        {
+                            continue;
+                        }
+                        if !vector_set.values.iter().any(|e| e.name == element.name) {
+                            vector_set.values.push(element);
+                        }
+                    }
+                    if keyword.is_none() && vector_set.values.len() >= vector_set.size {
+                        vector_set.done = true;
+                    }
+                    if keyword.is_some() && !vector_set.done && vector_set.values.len() < 50 {
+                        should_load_more = true;
+                    }
+                }
+
+                cx.emit(ServerEvent::ValuePaginationFinished(key_clone));
+
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.notify();
+                if should_load_more {
+                    this.load_more_vector_set_value(cx);
+                }
+            },
+            cx,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        RedisVectorSetElement, parse_vector, parse_vector_set_info, parse_vector_set_matches,
+        vector_set_element_matches,
+    };
+    use gpui::SharedString;
+    use redis::Value;
+
+    #[test]
+    fn parses_vectors_from_json_or_separated_numbers() {
+        assert_eq!(parse_vector("[0.1, 0.2, 3]"), Some(vec![0.1, 0.2, 3.0]));
+        assert_eq!(parse_vector("1 2,3"), Some(vec![1.0, 2.0, 3.0]));
+        assert_eq!(parse_vector(""), None);
+        assert_eq!(parse_vector("1 abc"), None);
+        assert_eq!(parse_vector("1 NaN"), None);
+    }
+
+    #[test]
+    fn parses_similarity_replies_from_resp2_and_resp3() {
+        let resp2 = Value::Array(vec![
+            Value::BulkString(b"a".to_vec()),
+            Value::BulkString(b"0.98".to_vec()),
+            Value::BulkString(b"b".to_vec()),
+            Value::BulkString(b"0.5".to_vec()),
+        ]);
+        assert_eq!(
+            parse_vector_set_matches(&resp2),
+            vec![("a".to_string(), 0.98), ("b".to_string(), 0.5)]
+        );
+
+        let resp3 = Value::Map(vec![(Value::BulkString(b"a".to_vec()), Value::Double(0.75))]);
+        assert_eq!(parse_vector_set_matches(&resp3), vec![("a".to_string(), 0.75)]);
+    }
+
+    #[test]
+    fn parses_vector_set_info_pairs() {
+        let info = Value::Array(vec![
+            Value::SimpleString("quant-type".to_string()),
+            Value::SimpleString("int8".to_string()),
+            Value::SimpleString("vector-dim".to_string()),
+            Value::Int(300),
+        ]);
+        let pairs = parse_vector_set_info(&info);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0], (SharedString::from("quant-type"), SharedString::from("int8")));
+        assert_eq!(pairs[1], (SharedString::from("vector-dim"), SharedString::from("300")));
+    }
+
+    #[test]
+    fn matches_vector_set_elements_by_name_or_attributes() {
+        let element = RedisVectorSetElement {
+            name: SharedString::from("Movie:1"),
+            attributes: Some(SharedString::from(r#"{"genre":"Drama"}"#)),
+        };
+        assert!(vector_set_element_matches(&element, "movie"));
+        assert!(vector_set_element_matches(&element, "drama"));
+        assert!(!vector_set_element_matches(&element, "comedy"));
+    }
+}
@@ -29,6 +29,7 @@ use super::{
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
     error::Error,
+    helpers::RedisKey,
     states::{NotificationAction, ServerEvent, i18n_zset_editor},
 };
 use gpui::{SharedString, prelude::*};
@@ -67,7 +68,7 @@ async fn get_redis_zset_value(
 
     // Execute range query with scores
     let raw_values: Vec<(Vec<u8>, f64)> = cmd(cmd_name)
-        .arg(key)
+        .arg(RedisKey::from(key))
         .arg(start)
         .arg(stop)
         .arg("WITHSCORES")
@@ -114,7 +115,7 @@ async fn search_redis_zset_value(
 ) -> Result<(u64, Vec<(SharedString, f64)>)> {
     // Execute ZSCAN with MATCH and COUNT options
     let (next_cursor, raw_values): (u64, Vec<Vec<u8>>) = cmd("ZSCAN")
-        .arg(key)
+        .arg(RedisKey::from(key))
         .arg(cursor)
         .arg("MATCH")
         .arg(pattern)
@@ -164,7 +165,7 @@ pub(crate) async fn first_load_zset_value(
     sort_order: SortOrder,
 ) -> Result<RedisValue> {
     // Get total number of members in the ZSET
-    let size: usize = cmd("ZCARD").arg(key).query_async(conn).await?;
+    let size: usize = cmd("ZCARD").arg(RedisKey::from(key)).query_async(conn).await?;
 
     // Load first batch (ranks 0-99, i.e., 100 members)
     let values = get_redis_zset_value(conn, key, sort_order, 0, 99).await?;
@@ -231,7 +232,7 @@ impl ZedisServerState {
 
                 // ZADD returns number of new elements added (0 if updating existing)
                 let count: usize = cmd("ZADD")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(score)
                     .arg(new_value.as_str())
                     .query_async(&mut conn)
@@ -481,7 +482,7 @@ impl ZedisServerState {
 
                 // ZREM removes the member and returns success
                 let _: () = cmd("ZREM")
-                    .arg(key.as_str())
+                    .arg(RedisKey::from(&key))
                     .arg(remove_value.as_str())
                     .query_async(&mut conn)
                     .await?;
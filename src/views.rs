@@ -14,34 +14,54 @@
 
 mod about;
 mod bytes_editor;
+mod clients_panel;
+mod console;
 mod content;
+mod diagnostics_panel;
 mod editor;
 mod hash_editor;
+mod hot_keys_panel;
 mod key_tree;
 mod kv_table;
 mod list_editor;
+mod memory_panel;
+mod metrics_panel;
+mod pubsub_panel;
 mod servers;
 mod set_editor;
 mod setting_editor;
 mod sidebar;
 mod status_bar;
+mod stream_editor;
+mod stream_group_panel;
 mod title_bar;
 mod update_dialog;
+mod vector_set_editor;
 mod zset_editor;
 
 pub use about::open_about_window;
 pub use bytes_editor::ZedisBytesEditor;
+pub use clients_panel::ZedisClientsPanel;
+pub use console::ZedisConsole;
 pub use content::ZedisContent;
+pub use diagnostics_panel::ZedisDiagnosticsPanel;
 pub use editor::ZedisEditor;
 pub use hash_editor::ZedisHashEditor;
+pub use hot_keys_panel::ZedisHotKeysPanel;
 pub use key_tree::ZedisKeyTree;
 pub use kv_table::{KvTableColumn, KvTableColumnType, ZedisKvTable};
 pub use list_editor::ZedisListEditor;
+pub use memory_panel::ZedisMemoryPanel;
+pub use metrics_panel::ZedisMetricsPanel;
+pub use pubsub_panel::ZedisPubSubPanel;
 pub use servers::ZedisServers;
 pub use set_editor::ZedisSetEditor;
 pub use setting_editor::ZedisSettingEditor;
 pub use sidebar::ZedisSidebar;
 pub use status_bar::ZedisStatusBar;
+pub use stream_editor::ZedisStreamEditor;
+pub use stream_group_panel::ZedisStreamGroupPanel;
 pub use title_bar::ZedisTitleBar;
 pub use update_dialog::open_update_dialog;
+pub use vector_set_editor::ZedisVectorSetEditor;
 pub use zset_editor::ZedisZsetEditor;
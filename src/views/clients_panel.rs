@@ -0,0 +1,451 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Connected clients panel.
+//!
+//! Lists the `CLIENT LIST` entries of every node, filtered by keyword and sorted by a
+//! column, with actions to kill a client, toggle no-evict mode and pause the clients.
+//! Every action asks for confirmation first.
+
+use crate::{
+    components::{FormDialog, FormField, open_add_form_dialog},
+    helpers::get_font_family,
+    states::{ClientSort, ServerEvent, ServerPanel, ZedisGlobalStore, ZedisServerState, filter_clients, i18n_clients},
+};
+use gpui::{AnyElement, App, Entity, SharedString, Subscription, Window, div, prelude::*, px, uniform_list};
+use gpui_component::{
+    ActiveTheme, Disableable, IconName, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    v_flex,
+};
+use rust_i18n::t;
+use std::rc::Rc;
+
+const ROW_HEIGHT: f32 = 28.0;
+const NODE_WIDTH: f32 = 140.0;
+const ADDR_WIDTH: f32 = 150.0;
+const NAME_WIDTH: f32 = 110.0;
+const USER_WIDTH: f32 = 80.0;
+const DB_WIDTH: f32 = 36.0;
+const AGE_WIDTH: f32 = 70.0;
+const IDLE_WIDTH: f32 = 70.0;
+const FLAGS_WIDTH: f32 = 60.0;
+const MEMORY_WIDTH: f32 = 80.0;
+const ACTION_WIDTH: f32 = 32.0;
+/// Default pause duration in milliseconds
+const DEFAULT_PAUSE_TIMEOUT: u64 = 5000;
+
+/// Formats a duration in seconds as `1d2h`, `3h4m`, `5m6s` or `7s`.
+fn format_seconds(seconds: u64) -> SharedString {
+    let (days, hours, minutes) = (seconds / 86400, seconds % 86400 / 3600, seconds % 3600 / 60);
+    if days > 0 {
+        format!("{days}d{hours}h").into()
+    } else if hours > 0 {
+        format!("{hours}h{minutes}m").into()
+    } else if minutes > 0 {
+        format!("{minutes}m{}s", seconds % 60).into()
+    } else {
+        format!("{seconds}s").into()
+    }
+}
+
+pub struct ZedisClientsPanel {
+    server_state: Entity<ZedisServerState>,
+    keyword_input: Entity<InputState>,
+    sort: ClientSort,
+    descending: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisClientsPanel {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let keyword_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_clients(cx, "keyword_placeholder")));
+        let subscriptions = vec![
+            cx.subscribe(&keyword_input, |_, _, event, cx| {
+                if let InputEvent::Change = event {
+                    cx.notify();
+                }
+            }),
+            cx.subscribe(&server_state, |_, server_state, event, cx| {
+                // Clients are loaded every time the panel is opened, they change quickly
+                if let ServerEvent::PanelToggled(ServerPanel::Clients) = event {
+                    server_state.update(cx, |state, cx| {
+                        state.load_clients(cx);
+                    });
+                }
+            }),
+            cx.observe(&server_state, |_, _, cx| cx.notify()),
+        ];
+        Self {
+            server_state,
+            keyword_input,
+            sort: ClientSort::Idle,
+            descending: true,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Focuses the filter input.
+    pub fn focus(&self, window: &mut Window, cx: &mut Context<Self>) {
+        self.keyword_input.update(cx, |state, cx| state.focus(window, cx));
+    }
+
+    /// Sorts by the column, clicking the current column reverses the order.
+    fn set_sort(&mut self, sort: ClientSort, cx: &mut Context<Self>) {
+        if self.sort == sort {
+            self.descending = !self.descending;
+        } else {
+            self.sort = sort;
+            self.descending = sort != ClientSort::Addr;
+        }
+        cx.notify();
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.server_state.update(cx, |state, cx| {
+            state.load_clients(cx);
+        });
+    }
+
+    fn handle_kill(
+        server_state: Entity<ZedisServerState>,
+        node: SharedString,
+        id: u64,
+        addr: SharedString,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let message = t!(
+            "clients.kill_prompt",
+            id = id,
+            addr = addr,
+            node = node,
+            locale = locale
+        )
+        .to_string();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let server_state = server_state.clone();
+            let node = node.clone();
+            dialog.confirm().child(message.clone()).on_ok(move |_, window, cx| {
+                let node = node.clone();
+                server_state.update(cx, |state, cx| {
+                    state.kill_client(node, id, cx);
+                });
+                window.close_dialog(cx);
+                true
+            })
+        });
+    }
+
+    fn handle_no_evict(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let enabled = !server_state.read(cx).client_no_evict();
+        let prompt = if enabled {
+            "no_evict_on_prompt"
+        } else {
+            "no_evict_off_prompt"
+        };
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let server_state = server_state.clone();
+            dialog
+                .confirm()
+                .child(i18n_clients(cx, prompt))
+                .on_ok(move |_, window, cx| {
+                    server_state.update(cx, |state, cx| {
+                        state.set_client_no_evict(enabled, cx);
+                    });
+                    window.close_dialog(cx);
+                    true
+                })
+        });
+    }
+
+    fn handle_pause(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let [timeout, mode] = values.as_slice() else {
+                return false;
+            };
+            let timeout = if timeout.trim().is_empty() {
+                DEFAULT_PAUSE_TIMEOUT
+            } else {
+                let Ok(timeout) = timeout.trim().parse::<u64>() else {
+                    return false;
+                };
+                timeout
+            };
+            // The first option pauses write commands only
+            let write_only = mode == "0";
+            server_state.update(cx, |state, cx| {
+                state.pause_clients(timeout, write_only, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+        let fields = vec![
+            FormField::new(i18n_clients(cx, "pause_timeout"))
+                .with_placeholder(DEFAULT_PAUSE_TIMEOUT.to_string().into())
+                .with_focus()
+                .with_validate(|s| s.trim().is_empty() || s.trim().parse::<u64>().is_ok()),
+            FormField::new(i18n_clients(cx, "pause_mode"))
+                .with_options(vec![i18n_clients(cx, "pause_write"), i18n_clients(cx, "pause_all")]),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_clients(cx, "pause_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn unpause(&mut self, cx: &mut Context<Self>) {
+        self.server_state.update(cx, |state, cx| {
+            state.unpause_clients(cx);
+        });
+    }
+
+    fn render_toolbar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let state = self.server_state.read(cx);
+        let loading = state.is_clients_loading();
+        let no_evict = state.client_no_evict();
+        let count = state.clients().map(|clients| clients.len()).unwrap_or_default();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        h_flex()
+            .p_2()
+            .gap_2()
+            .items_center()
+            .child(
+                div()
+                    .flex_1()
+                    .child(Input::new(&self.keyword_input).small().cleanable(true)),
+            )
+            .child(
+                Label::new(t!("clients.count", count = count, locale = locale).to_string())
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground),
+            )
+            .child(
+                Button::new("clients-reload")
+                    .outline()
+                    .small()
+                    .icon(IconName::Redo)
+                    .tooltip(i18n_clients(cx, "reload"))
+                    .loading(loading)
+                    .disabled(loading)
+                    .on_click(cx.listener(|this, _, _, cx| this.reload(cx))),
+            )
+            .child(
+                Button::new("clients-no-evict")
+                    .outline()
+                    .small()
+                    .label(if no_evict {
+                        i18n_clients(cx, "no_evict_off")
+                    } else {
+                        i18n_clients(cx, "no_evict_on")
+                    })
+                    .tooltip(i18n_clients(cx, "no_evict_tooltip"))
+                    .on_click(cx.listener(|this, _, window, cx| this.handle_no_evict(window, cx))),
+            )
+            .child(
+                Button::new("clients-pause")
+                    .outline()
+                    .small()
+                    .label(i18n_clients(cx, "pause"))
+                    .on_click(cx.listener(|this, _, window, cx| this.handle_pause(window, cx))),
+            )
+            .child(
+                Button::new("clients-unpause")
+                    .outline()
+                    .small()
+                    .label(i18n_clients(cx, "unpause"))
+                    .on_click(cx.listener(|this, _, _, cx| this.unpause(cx))),
+            )
+    }
+
+    /// Renders a header cell, sortable when `sort` is set.
+    fn render_header_cell(
+        &self,
+        label: &str,
+        width: Option<f32>,
+        sort: Option<ClientSort>,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let label = i18n_clients(cx, label);
+        let cell = match width {
+            Some(width) => h_flex().w(px(width)),
+            None => h_flex().flex_1(),
+        };
+        let Some(sort) = sort else {
+            return cell.child(Label::new(label).text_xs()).into_any_element();
+        };
+        let icon = if self.descending {
+            IconName::SortDescending
+        } else {
+            IconName::SortAscending
+        };
+        let button = Button::new(SharedString::from(format!("clients-sort-{label}")))
+            .ghost()
+            .xsmall()
+            .label(label)
+            .when(self.sort == sort, |this| this.icon(icon))
+            .on_click(cx.listener(move |this, _, _, cx| this.set_sort(sort, cx)));
+        cell.child(button).into_any_element()
+    }
+
+    fn render_header(&self, multiple_nodes: bool, cx: &Context<Self>) -> impl IntoElement {
+        h_flex()
+            .h(px(ROW_HEIGHT))
+            .px_2()
+            .gap_2()
+            .w_full()
+            .items_center()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .when(multiple_nodes, |this| {
+                this.child(self.render_header_cell("node", Some(NODE_WIDTH), None, cx))
+            })
+            .child(self.render_header_cell("addr", Some(ADDR_WIDTH), Some(ClientSort::Addr), cx))
+            .child(self.render_header_cell("name", Some(NAME_WIDTH), None, cx))
+            .child(self.render_header_cell("user", Some(USER_WIDTH), None, cx))
+            .child(self.render_header_cell("db", Some(DB_WIDTH), None, cx))
+            .child(self.render_header_cell("age", Some(AGE_WIDTH), Some(ClientSort::Age), cx))
+            .child(self.render_header_cell("idle", Some(IDLE_WIDTH), Some(ClientSort::Idle), cx))
+            .child(self.render_header_cell("cmd", None, None, cx))
+            .child(self.render_header_cell("flags", Some(FLAGS_WIDTH), None, cx))
+            .child(self.render_header_cell("memory", Some(MEMORY_WIDTH), Some(ClientSort::Memory), cx))
+            .child(div().w(px(ACTION_WIDTH)))
+    }
+
+    fn render_clients(&self, cx: &Context<Self>) -> AnyElement {
+        let muted = cx.theme().muted_foreground;
+        let clients = self.server_state.read(cx).clients().unwrap_or_default();
+        let keyword = self.keyword_input.read(cx).value();
+        let indexes = filter_clients(&clients, keyword.trim(), self.sort, self.descending);
+        let multiple_nodes = clients.iter().any(|client| client.node != clients[0].node);
+        let header = self.render_header(multiple_nodes, cx);
+        if indexes.is_empty() {
+            return v_flex()
+                .flex_1()
+                .child(header)
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .items_center()
+                        .justify_center()
+                        .child(Label::new(i18n_clients(cx, "no_clients")).text_sm().text_color(muted)),
+                )
+                .into_any_element();
+        }
+        let node_color = cx.theme().blue;
+        let font_family: SharedString = get_font_family().into();
+        let kill_tooltip = i18n_clients(cx, "kill");
+        let server_state = self.server_state.clone();
+        let list = uniform_list("clients-list", indexes.len(), move |range, _window, _cx| {
+            range
+                .filter_map(|row| {
+                    indexes
+                        .get(row)
+                        .and_then(|index| clients.get(*index))
+                        .map(|client| (row, client))
+                })
+                .map(|(row, client)| {
+                    let server_state = server_state.clone();
+                    let (node, id, addr) = (client.node.clone(), client.id, client.addr.clone());
+                    h_flex()
+                        .h(px(ROW_HEIGHT))
+                        .px_2()
+                        .gap_2()
+                        .w_full()
+                        .items_center()
+                        .when(multiple_nodes, |this| {
+                            this.child(
+                                Label::new(client.node.clone())
+                                    .text_xs()
+                                    .text_color(node_color)
+                                    .w(px(NODE_WIDTH))
+                                    .truncate(),
+                            )
+                        })
+                        .child(
+                            Label::new(client.addr.clone())
+                                .text_sm()
+                                .font_family(font_family.clone())
+                                .w(px(ADDR_WIDTH))
+                                .truncate(),
+                        )
+                        .child(Label::new(client.name.clone()).text_sm().w(px(NAME_WIDTH)).truncate())
+                        .child(
+                            Label::new(client.user.clone())
+                                .text_xs()
+                                .text_color(muted)
+                                .w(px(USER_WIDTH))
+                                .truncate(),
+                        )
+                        .child(Label::new(client.db.to_string()).text_xs().w(px(DB_WIDTH)))
+                        .child(Label::new(format_seconds(client.age)).text_xs().w(px(AGE_WIDTH)))
+                        .child(Label::new(format_seconds(client.idle)).text_xs().w(px(IDLE_WIDTH)))
+                        .child(
+                            Label::new(client.cmd.clone())
+                                .text_sm()
+                                .font_family(font_family.clone())
+                                .flex_1()
+                                .truncate(),
+                        )
+                        .child(
+                            Label::new(client.flags.clone())
+                                .text_xs()
+                                .text_color(muted)
+                                .w(px(FLAGS_WIDTH)),
+                        )
+                        .child(
+                            Label::new(humansize::format_size(client.memory, humansize::DECIMAL))
+                                .text_xs()
+                                .w(px(MEMORY_WIDTH)),
+                        )
+                        .child(
+                            Button::new(("clients-kill", row))
+                                .ghost()
+                                .xsmall()
+                                .icon(IconName::CircleX)
+                                .tooltip(kill_tooltip.clone())
+                                .on_click(move |_, window, cx| {
+                                    Self::handle_kill(server_state.clone(), node.clone(), id, addr.clone(), window, cx);
+                                }),
+                        )
+                })
+                .collect()
+        })
+        .flex_1()
+        .w_full();
+        v_flex().flex_1().child(header).child(list).into_any_element()
+    }
+}
+
+impl Render for ZedisClientsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w_full()
+            .h_full()
+            .child(self.render_toolbar(cx))
+            .child(self.render_clients(cx))
+    }
+}
@@ -0,0 +1,620 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Interactive redis-cli style console.
+//!
+//! This module provides the terminal shown in place of the value editor:
+//! - redis-cli compatible argument parsing (quotes and `\x` escapes)
+//! - Typed reply rendering, including RESP3 maps, sets and attributes
+//! - Persistent per-server history with up/down navigation and reverse search (Ctrl+R)
+//! - Tab completion of command names, subcommands, tokens and key names
+//! - Cluster node targeting (all masters, all nodes or a single node) with per-node replies
+
+use crate::{
+    connection::{NodeRedirect, NodeReply, NodeRole, NodeTarget},
+    helpers::{ConsoleAction, format_error, format_reply, get_font_family, split_args},
+    states::{
+        ConsoleHistory, ServerEvent, ServerPanel, ZedisGlobalStore, ZedisServerState, common_prefix, complete,
+        execute_console_command, find_command_doc, i18n_console, next_history_sequence, save_console_history,
+    },
+};
+use gpui::{AnyElement, App, Corner, Entity, ScrollHandle, SharedString, Subscription, Window, div, prelude::*};
+use gpui_component::{
+    ActiveTheme, Sizable,
+    button::{Button, ButtonVariants, DropdownButton},
+    h_flex,
+    input::{Escape, IndentInline, Input, InputEvent, InputState, MoveDown, MoveUp},
+    label::Label,
+    menu::PopupMenuItem,
+    v_flex,
+};
+use rust_i18n::t;
+use tracing::error;
+
+const CMD_LABEL: &str = "$";
+const CMD_CLEAR: &str = "clear";
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Maximum number of lines rendered for a single reply
+const MAX_REPLY_LINES: usize = 2000;
+/// Maximum number of output blocks kept in the console
+const MAX_OUTPUTS: usize = 300;
+
+const ZEDIS_LOGO: &str = r#" __________ ____ ___ ____
+|__  / ____|  _ \_ _/ ___|
+  / /|  _| | | | | |\___ \    ZEDIS Native Redis GUI v{VERSION}
+ / /_| |___| |_| | | ___) |
+/____|_____|____/___|____/
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputKind {
+    Banner,
+    Command,
+    Reply,
+    Error,
+    /// Header of a node's reply when the command was sent to cluster nodes
+    Node,
+    /// Explanation added by the console (redirections)
+    Info,
+}
+
+/// A block of console output (the echoed command, a reply or an error).
+#[derive(Debug, Clone)]
+struct ConsoleOutput {
+    kind: OutputKind,
+    lines: Vec<SharedString>,
+}
+
+/// Completion candidates shown below the output while cycling with Tab.
+#[derive(Debug, Clone)]
+struct CompletionState {
+    /// Input text before the completed word
+    base: String,
+    candidates: Vec<SharedString>,
+    selected: Option<usize>,
+}
+
+/// Reverse history search (Ctrl+R); the input holds the search query while active.
+#[derive(Debug, Clone)]
+struct ReverseSearch {
+    /// Input text before the search started, restored on cancel
+    draft: SharedString,
+    /// Index of the matched history entry
+    matched: Option<usize>,
+}
+
+pub struct ZedisConsole {
+    server_state: Entity<ZedisServerState>,
+    input_state: Entity<InputState>,
+    scroll_handle: ScrollHandle,
+    outputs: Vec<ConsoleOutput>,
+
+    /// Command history of `history_server_id`
+    history: ConsoleHistory,
+    history_server_id: SharedString,
+
+    completion: Option<CompletionState>,
+    reverse_search: Option<ReverseSearch>,
+    /// Cluster nodes the commands are sent to
+    target: NodeTarget,
+
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisConsole {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut subscriptions = Vec::new();
+        let input_state = cx.new(|cx| InputState::new(window, cx).placeholder(i18n_console(cx, "placeholder")));
+        subscriptions.push(
+            cx.subscribe_in(&input_state, window, |this, state, event, window, cx| match event {
+                InputEvent::PressEnter { .. } => {
+                    if this.reverse_search.is_some() {
+                        this.finish_reverse_search(true, window, cx);
+                        return;
+                    }
+                    let command = state.read(cx).value();
+                    state.update(cx, |state, cx| {
+                        state.set_value(SharedString::default(), window, cx);
+                    });
+                    this.execute_command(command, cx);
+                }
+                InputEvent::Change => {
+                    this.completion = None;
+                    if this.reverse_search.is_some() {
+                        this.update_reverse_search(None, cx);
+                    } else {
+                        this.history.reset_navigation();
+                    }
+                    cx.notify();
+                }
+                _ => {}
+            }),
+        );
+        subscriptions.push(
+            cx.subscribe(&server_state, |this, server_state, event, cx| match event {
+                ServerEvent::PanelToggled(ServerPanel::Terminal) => {
+                    this.ensure_history(cx);
+                    server_state.update(cx, |state, cx| {
+                        state.load_command_docs(cx);
+                    });
+                }
+                ServerEvent::ServerSelected(_, _) => {
+                    this.reset(cx);
+                    this.ensure_history(cx);
+                }
+                _ => {}
+            }),
+        );
+
+        let mut this = Self {
+            server_state,
+            input_state,
+            scroll_handle: ScrollHandle::new(),
+            outputs: Vec::with_capacity(8),
+            history: ConsoleHistory::default(),
+            history_server_id: SharedString::default(),
+            completion: None,
+            reverse_search: None,
+            target: NodeTarget::Auto,
+            _subscriptions: subscriptions,
+        };
+        this.reset(cx);
+        this
+    }
+
+    /// Focuses the command input.
+    pub fn focus(&self, window: &mut Window, cx: &mut Context<Self>) {
+        self.input_state.update(cx, |state, cx| state.focus(window, cx));
+    }
+
+    /// Clears the output and shows the banner.
+    fn reset(&mut self, cx: &mut Context<Self>) {
+        self.outputs.clear();
+        self.push_output(OutputKind::Banner, &ZEDIS_LOGO.replace("{VERSION}", VERSION), cx);
+        self.completion = None;
+        self.reverse_search = None;
+        self.target = NodeTarget::Auto;
+        self.scroll_handle = ScrollHandle::new();
+    }
+
+    /// Loads the history when the console is used for a different server.
+    fn ensure_history(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_state.read(cx).server_id().to_string();
+        if server_id.is_empty() || self.history_server_id == server_id {
+            return;
+        }
+        self.history = ConsoleHistory::load(&server_id);
+        self.history_server_id = server_id.into();
+    }
+
+    fn push_output(&mut self, kind: OutputKind, text: &str, cx: &mut Context<Self>) {
+        let mut lines: Vec<SharedString> = text
+            .lines()
+            .take(MAX_REPLY_LINES)
+            .map(|line| SharedString::from(line.to_string()))
+            .collect();
+        let total = text.lines().count();
+        if total > MAX_REPLY_LINES {
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!("console.more_lines", count = total - MAX_REPLY_LINES, locale = locale);
+            lines.push(message.to_string().into());
+        }
+        self.outputs.push(ConsoleOutput { kind, lines });
+        if self.outputs.len() > MAX_OUTPUTS {
+            let overflow = self.outputs.len() - MAX_OUTPUTS;
+            self.outputs.drain(..overflow);
+        }
+    }
+
+    fn scroll_to_bottom(&self, cx: &mut Context<Self>) {
+        let scroll_handle = self.scroll_handle.clone();
+        cx.notify();
+        cx.defer(move |_cx| {
+            scroll_handle.scroll_to_bottom();
+        });
+    }
+
+    fn execute_command(&mut self, command: SharedString, cx: &mut Context<Self>) {
+        let command = command.trim().to_string();
+        if command.is_empty() {
+            return;
+        }
+        self.completion = None;
+        self.ensure_history(cx);
+        if self.history.push(&command) {
+            let server_id = self.history_server_id.to_string();
+            let entries = self.history.entries().to_vec();
+            let sequence = next_history_sequence();
+            cx.background_spawn(async move {
+                if let Err(e) = save_console_history(&server_id, &entries, sequence) {
+                    error!(error = %e, "Failed to save console history");
+                }
+            })
+            .detach();
+        }
+
+        if command.eq_ignore_ascii_case(CMD_CLEAR) {
+            self.reset(cx);
+            cx.notify();
+            return;
+        }
+        self.push_output(OutputKind::Command, &format!("{CMD_LABEL} {command}"), cx);
+        let args = match split_args(&command) {
+            Ok(args) => args,
+            Err(e) => {
+                self.push_output(OutputKind::Error, &format_error(&e), cx);
+                self.scroll_to_bottom(cx);
+                return;
+            }
+        };
+        let server_state = self.server_state.read(cx);
+        let server_id = server_state.server_id().to_string();
+        let db = server_state.db();
+        let target = self.target.clone();
+        cx.spawn(async move |handle, cx| {
+            let task = cx.background_spawn(async move { execute_console_command(&server_id, db, target, args).await });
+            let result = task.await;
+            handle.update(cx, |this, cx| {
+                match result {
+                    Ok(replies) => this.push_replies(replies, cx),
+                    Err(e) => this.push_output(OutputKind::Error, &format_error(&e), cx),
+                }
+                this.scroll_to_bottom(cx);
+            })
+        })
+        .detach();
+        self.scroll_to_bottom(cx);
+    }
+
+    /// Shows the replies, grouped under a header per node when cluster nodes were targeted.
+    fn push_replies(&mut self, replies: Vec<NodeReply>, cx: &mut Context<Self>) {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale().to_string();
+        for reply in replies {
+            if let Some(node) = &reply.node {
+                let header = format!("{} ({})", node.host_port(), role_label(&node.role));
+                self.push_output(OutputKind::Node, &header, cx);
+            }
+            if let Some(redirect) = &reply.redirect {
+                let message = t!(
+                    "console.redirected",
+                    slot = redirect.slot,
+                    addr = redirect.addr,
+                    locale = locale
+                );
+                self.push_output(OutputKind::Info, &message, cx);
+            }
+            match reply.result {
+                Ok(value) => self.push_output(OutputKind::Reply, &format_reply(&value), cx),
+                Err(e) => {
+                    self.push_output(OutputKind::Error, &format_error(&e), cx);
+                    if let Some(redirect) = NodeRedirect::from_error(&e) {
+                        let key = if redirect.ask {
+                            "console.ask_hint"
+                        } else {
+                            "console.moved_hint"
+                        };
+                        let message = t!(key, slot = redirect.slot, addr = redirect.addr, locale = locale);
+                        self.push_output(OutputKind::Info, &message, cx);
+                    }
+                }
+            }
+        }
+    }
+
+    fn set_target(&mut self, target: NodeTarget, cx: &mut Context<Self>) {
+        self.target = target;
+        cx.notify();
+    }
+
+    /// Renders the node selector, only shown for clusters.
+    fn render_target_selector(&self, cx: &Context<Self>) -> Option<AnyElement> {
+        let nodes = self.server_state.read(cx).cluster_nodes();
+        if nodes.is_empty() {
+            return None;
+        }
+        let label: SharedString = match &self.target {
+            NodeTarget::Auto => i18n_console(cx, "target_auto"),
+            NodeTarget::AllMasters => i18n_console(cx, "target_all_masters"),
+            NodeTarget::AllNodes => i18n_console(cx, "target_all_nodes"),
+            NodeTarget::Node(addr) => addr.clone().into(),
+        };
+        let current = self.target.clone();
+        let console = cx.entity().downgrade();
+        let dropdown = DropdownButton::new("console-target-dropdown")
+            .button(
+                Button::new("console-target-btn")
+                    .ghost()
+                    .small()
+                    .label(label)
+                    .tooltip(i18n_console(cx, "target_tooltip")),
+            )
+            .dropdown_menu_with_anchor(Corner::BottomRight, move |menu, _, cx| {
+                let mut targets = vec![
+                    (NodeTarget::Auto, i18n_console(cx, "target_auto")),
+                    (NodeTarget::AllMasters, i18n_console(cx, "target_all_masters")),
+                    (NodeTarget::AllNodes, i18n_console(cx, "target_all_nodes")),
+                ];
+                targets.extend(nodes.iter().map(|node| {
+                    let label = format!("{} ({})", node.host_port(), role_label(&node.role));
+                    (NodeTarget::Node(node.host_port()), label.into())
+                }));
+                targets.into_iter().fold(menu, |menu, (target, label)| {
+                    let console = console.clone();
+                    let checked = target == current;
+                    menu.item(PopupMenuItem::new(label).checked(checked).on_click(move |_, _, cx| {
+                        let _ = console.update(cx, |this, cx| this.set_target(target.clone(), cx));
+                    }))
+                })
+            });
+        Some(dropdown.into_any_element())
+    }
+
+    fn set_input(&mut self, value: impl Into<SharedString>, window: &mut Window, cx: &mut Context<Self>) {
+        let value = value.into();
+        self.input_state.update(cx, |state, cx| {
+            state.set_value(value, window, cx);
+        });
+    }
+
+    fn history_previous(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.ensure_history(cx);
+        let current = self.input_state.read(cx).value();
+        if let Some(entry) = self.history.previous(&current).map(String::from) {
+            self.completion = None;
+            self.set_input(entry, window, cx);
+        }
+    }
+
+    fn history_next(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(entry) = self.history.next().map(String::from) {
+            self.completion = None;
+            self.set_input(entry, window, cx);
+        }
+    }
+
+    /// Completes the word at the end of the input, cycling through candidates on repeated Tab.
+    fn complete(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(completion) = self.completion.as_mut() {
+            let index = completion
+                .selected
+                .map(|index| (index + 1) % completion.candidates.len())
+                .unwrap_or(0);
+            completion.selected = Some(index);
+            let value = format!("{}{}", completion.base, completion.candidates[index]);
+            self.set_input(value, window, cx);
+            cx.notify();
+            return;
+        }
+        let value = self.input_state.read(cx).value();
+        let server_state = self.server_state.read(cx);
+        let docs = server_state.command_docs().unwrap_or_default();
+        let result = complete(&value, &docs, server_state.keys().keys());
+        let base = value[..result.start].to_string();
+        match result.candidates.len() {
+            0 => {}
+            1 => {
+                let value = format!("{base}{} ", result.candidates[0]);
+                self.set_input(value, window, cx);
+            }
+            _ => {
+                let prefix = common_prefix(&result.candidates);
+                if prefix.len() > value.len() - result.start {
+                    let value = format!("{base}{prefix}");
+                    self.set_input(value, window, cx);
+                }
+                self.completion = Some(CompletionState {
+                    base,
+                    candidates: result.candidates,
+                    selected: None,
+                });
+            }
+        }
+        cx.notify();
+    }
+
+    /// Starts a reverse search, or moves to the next older match when already searching.
+    fn reverse_search(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.ensure_history(cx);
+        self.completion = None;
+        if let Some(search) = &self.reverse_search {
+            let before = search.matched.unwrap_or(self.history.entries().len());
+            self.update_reverse_search(Some(before), cx);
+        } else {
+            let draft = self.input_state.read(cx).value();
+            self.reverse_search = Some(ReverseSearch { draft, matched: None });
+            self.set_input(SharedString::default(), window, cx);
+        }
+        cx.notify();
+    }
+
+    fn update_reverse_search(&mut self, before: Option<usize>, cx: &mut Context<Self>) {
+        let query = self.input_state.read(cx).value();
+        let before = before.unwrap_or(self.history.entries().len());
+        let matched = if query.is_empty() {
+            None
+        } else {
+            self.history.search(&query, before).map(|(index, _)| index)
+        };
+        if let Some(search) = self.reverse_search.as_mut()
+            && (matched.is_some() || before == self.history.entries().len())
+        {
+            search.matched = matched;
+        }
+    }
+
+    /// Leaves reverse search, putting the match (accept) or the previous input (cancel) back.
+    fn finish_reverse_search(&mut self, accept: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(search) = self.reverse_search.take() else {
+            return;
+        };
+        let value = match search.matched.and_then(|index| self.history.entries().get(index)) {
+            Some(entry) if accept => entry.clone().into(),
+            _ => search.draft,
+        };
+        self.set_input(value, window, cx);
+        cx.notify();
+    }
+
+    fn render_hint(&self, font_family: &SharedString, cx: &App) -> Option<AnyElement> {
+        let muted = cx.theme().muted_foreground;
+        if let Some(search) = &self.reverse_search {
+            let text = match search.matched.and_then(|index| self.history.entries().get(index)) {
+                Some(entry) => SharedString::from(entry.clone()),
+                None => i18n_console(cx, "no_match"),
+            };
+            return Some(
+                h_flex()
+                    .px_2()
+                    .child(Label::new(text).font_family(font_family.clone()).text_color(muted))
+                    .into_any_element(),
+            );
+        }
+        if let Some(completion) = &self.completion {
+            let selected_color = cx.theme().primary;
+            return Some(
+                h_flex()
+                    .px_2()
+                    .gap_3()
+                    .flex_wrap()
+                    .children(completion.candidates.iter().enumerate().map(|(index, candidate)| {
+                        let color = if completion.selected == Some(index) {
+                            selected_color
+                        } else {
+                            muted
+                        };
+                        Label::new(candidate.clone())
+                            .font_family(font_family.clone())
+                            .text_color(color)
+                    }))
+                    .into_any_element(),
+            );
+        }
+        let value = self.input_state.read(cx).value();
+        let words: Vec<&str> = value.split_whitespace().collect();
+        let docs = self.server_state.read(cx).command_docs()?;
+        let (doc, _) = find_command_doc(&docs, &words)?;
+        let mut hint = doc.syntax();
+        if !doc.summary.is_empty() {
+            hint.push_str("  — ");
+            hint.push_str(&doc.summary);
+        }
+        Some(
+            h_flex()
+                .px_2()
+                .child(Label::new(hint).font_family(font_family.clone()).text_color(muted))
+                .into_any_element(),
+        )
+    }
+}
+
+fn role_label(role: &NodeRole) -> &'static str {
+    match role {
+        NodeRole::Master => "master",
+        NodeRole::Slave => "replica",
+        NodeRole::Fail => "fail",
+        NodeRole::Unknown => "unknown",
+    }
+}
+
+impl Render for ZedisConsole {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let font_family: SharedString = get_font_family().into();
+        let hint = self.render_hint(&font_family, cx);
+        let prompt = if self.reverse_search.is_some() {
+            i18n_console(cx, "reverse_search")
+        } else {
+            CMD_LABEL.into()
+        };
+        let target_selector = self.render_target_selector(cx);
+        let theme = cx.theme();
+        let (error_color, node_color, info_color, foreground) =
+            (theme.red, theme.blue, theme.muted_foreground, theme.foreground);
+        let outputs: Vec<_> = self
+            .outputs
+            .iter()
+            .map(|output| {
+                let color = match output.kind {
+                    OutputKind::Error => error_color,
+                    OutputKind::Node => node_color,
+                    OutputKind::Info => info_color,
+                    _ => foreground,
+                };
+                v_flex().children(output.lines.iter().map(|line| {
+                    Label::new(line.clone())
+                        .font_family(font_family.clone())
+                        .text_color(color)
+                }))
+            })
+            .collect();
+
+        v_flex()
+            .key_context("ZedisConsole")
+            .w_full()
+            .h_full()
+            .capture_action(cx.listener(|this, _: &MoveUp, window, cx| {
+                if this.reverse_search.is_some() {
+                    this.finish_reverse_search(true, window, cx);
+                } else {
+                    this.history_previous(window, cx);
+                }
+                cx.stop_propagation();
+            }))
+            .capture_action(cx.listener(|this, _: &MoveDown, window, cx| {
+                if this.reverse_search.is_some() {
+                    this.finish_reverse_search(true, window, cx);
+                } else {
+                    this.history_next(window, cx);
+                }
+                cx.stop_propagation();
+            }))
+            .capture_action(cx.listener(|this, _: &IndentInline, window, cx| {
+                if this.reverse_search.is_some() {
+                    this.finish_reverse_search(true, window, cx);
+                }
+                this.complete(window, cx);
+                cx.stop_propagation();
+            }))
+            .capture_action(cx.listener(|this, _: &Escape, window, cx| {
+                if this.reverse_search.is_some() {
+                    this.finish_reverse_search(false, window, cx);
+                    cx.stop_propagation();
+                } else if this.completion.take().is_some() {
+                    cx.notify();
+                    cx.stop_propagation();
+                }
+            }))
+            .on_action(cx.listener(|this, action: &ConsoleAction, window, cx| match action {
+                ConsoleAction::ReverseSearch => this.reverse_search(window, cx),
+            }))
+            .child(
+                div()
+                    .id("cmd-output-scrollable-container")
+                    .track_scroll(&self.scroll_handle)
+                    .flex_1()
+                    .w_full()
+                    .overflow_y_scroll()
+                    .child(v_flex().p_2().gap_1().children(outputs)),
+            )
+            .children(hint)
+            .child(
+                div().w_full().border_t_1().border_color(cx.theme().border).child(
+                    Input::new(&self.input_state)
+                        .font_family(font_family)
+                        .prefix(Label::new(prompt).text_color(cx.theme().yellow))
+                        .when_some(target_selector, |this, selector| this.suffix(selector))
+                        .appearance(false),
+                ),
+            )
+    }
+}
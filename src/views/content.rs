@@ -14,37 +14,27 @@
 
 use crate::{
     components::SkeletonLoading,
-    connection::get_connection_manager,
-    error::Error,
-    helpers::{EditorAction, ServersAction, get_font_family, get_key_tree_widths, redis_value_to_string},
-    states::{Route, ServerEvent, ZedisGlobalStore, ZedisServerState, save_app_state},
-    views::{ZedisEditor, ZedisKeyTree, ZedisServers, ZedisSettingEditor, ZedisStatusBar},
+    connection::SshHostKey,
+    helpers::{EditorAction, ServersAction, get_key_tree_widths},
+    states::{Route, ServerEvent, ServerPanel, ZedisGlobalStore, ZedisServerState, i18n_servers, save_app_state},
+    views::{
+        ZedisClientsPanel, ZedisConsole, ZedisDiagnosticsPanel, ZedisEditor, ZedisHotKeysPanel, ZedisKeyTree,
+        ZedisMemoryPanel, ZedisMetricsPanel, ZedisPubSubPanel, ZedisServers, ZedisSettingEditor, ZedisStatusBar,
+    },
 };
-use gpui::{Entity, FocusHandle, Pixels, ScrollHandle, SharedString, Subscription, Window, div, prelude::*, px};
+use gpui::{Entity, FocusHandle, Pixels, Subscription, Window, div, prelude::*, px};
 use gpui_component::{
-    ActiveTheme,
-    input::{Input, InputEvent, InputState},
-    label::Label,
+    WindowExt,
     resizable::{ResizableState, h_resizable, resizable_panel},
     v_flex,
 };
-use redis::cmd;
+use rust_i18n::t;
+use std::sync::Arc;
 use tracing::{debug, error, info};
-type Result<T, E = Error> = std::result::Result<T, E>;
 
 // Constants for UI dimensions
 const LOADING_SKELETON_WIDTH: f32 = 600.0;
 const SERVERS_MARGIN: f32 = 8.0;
-const CMD_LABEL: &str = "$";
-const CMD_CLEAR: &str = "clear";
-const VERSION: &str = env!("CARGO_PKG_VERSION");
-
-const ZEDIS_LOGO: &str = r#" __________ ____ ___ ____  
-|__  / ____|  _ \_ _/ ___| 
-  / /|  _| | | | | |\___ \    ZEDIS Native Redis GUI v{VERSION}
- / /_| |___| |_| | | ___) |
-/____|_____|____/___|____/ 
-"#;
 
 /// Main content area component for the Zedis application
 ///
@@ -64,9 +54,20 @@ pub struct ZedisContent {
     value_editor: Option<Entity<ZedisEditor>>,
     key_tree: Option<Entity<ZedisKeyTree>>,
     status_bar: Entity<ZedisStatusBar>,
-    cmd_output_scroll_handle: ScrollHandle,
-    cmd_input_state: Entity<InputState>,
-    cmd_outputs: Vec<SharedString>,
+    /// redis-cli style console shown in place of the value editor
+    console: Entity<ZedisConsole>,
+    /// Pub/Sub panel, kept alive so subscriptions survive toggling panels
+    pubsub: Entity<ZedisPubSubPanel>,
+    /// Slow log and latency diagnostics
+    diagnostics: Entity<ZedisDiagnosticsPanel>,
+    /// Live metrics dashboard, kept alive so sampling continues in the background
+    metrics: Entity<ZedisMetricsPanel>,
+    /// Connected clients of every node
+    clients: Entity<ZedisClientsPanel>,
+    /// Memory analyzer, kept alive so the report survives toggling panels
+    memory: Entity<ZedisMemoryPanel>,
+    /// Hottest and coldest keys of every node
+    hot_keys: Entity<ZedisHotKeysPanel>,
 
     /// Persisted width of the key tree panel (resizable by user)
     key_tree_width: Pixels,
@@ -121,21 +122,26 @@ impl ZedisContent {
             cx.notify();
         }));
 
-        subscriptions.push(
-            cx.subscribe(&server_state, |this, _server_state, event, cx| match event {
-                ServerEvent::TerminalToggled(terminal) => {
-                    this.should_focus = Some(true);
-                    if *terminal {
-                        this.should_focus_cmd_input = Some(true);
-                    } else {
-                        this.should_focus_cmd_input = None;
-                    }
-                    cx.notify();
+        subscriptions.push(cx.subscribe(&server_state, |this, _server_state, event, cx| {
+            if let ServerEvent::PanelToggled(panel) = event {
+                this.should_focus = Some(true);
+                if *panel != ServerPanel::Editor {
+                    this.should_focus_cmd_input = Some(true);
+                } else {
+                    this.should_focus_cmd_input = None;
                 }
-                ServerEvent::ServerSelected(_, _) => {
-                    this.reset_cmd_state(cx);
+                cx.notify();
+            }
+        }));
+
+        subscriptions.push(
+            cx.subscribe_in(&server_state, window, |this, _server_state, event, window, cx| {
+                // Several tasks may fail on the same key at once
+                if let ServerEvent::HostKeyPrompt(key) = event
+                    && !window.has_active_dialog(cx)
+                {
+                    this.prompt_host_key(key.clone(), window, cx);
                 }
-                _ => {}
             }),
         );
 
@@ -143,18 +149,13 @@ impl ZedisContent {
         let global_store = cx.global::<ZedisGlobalStore>().read(cx);
         let key_tree_width = global_store.key_tree_width();
         let route = global_store.route();
-        let cmd_input_state = cx.new(|cx| InputState::new(window, cx));
-        subscriptions.push(
-            cx.subscribe_in(&cmd_input_state, window, |this, state, event, window, cx| {
-                if let InputEvent::PressEnter { .. } = event {
-                    let cmd = state.read(cx).value();
-                    state.update(cx, |state, cx| {
-                        state.set_value(SharedString::default(), window, cx);
-                    });
-                    this.execute_command(cmd, cx);
-                }
-            }),
-        );
+        let console = cx.new(|cx| ZedisConsole::new(server_state.clone(), window, cx));
+        let pubsub = cx.new(|cx| ZedisPubSubPanel::new(server_state.clone(), window, cx));
+        let diagnostics = cx.new(|cx| ZedisDiagnosticsPanel::new(server_state.clone(), window, cx));
+        let metrics = cx.new(|cx| ZedisMetricsPanel::new(server_state.clone(), cx));
+        let clients = cx.new(|cx| ZedisClientsPanel::new(server_state.clone(), window, cx));
+        let memory = cx.new(|cx| ZedisMemoryPanel::new(server_state.clone(), window, cx));
+        let hot_keys = cx.new(|cx| ZedisHotKeysPanel::new(server_state.clone(), window, cx));
         info!("Creating new content view");
 
         Self {
@@ -165,70 +166,49 @@ impl ZedisContent {
             value_editor: None,
             settings: None,
             key_tree: None,
-            cmd_outputs: Vec::with_capacity(5),
+            console,
+            pubsub,
+            diagnostics,
+            metrics,
+            clients,
+            memory,
+            hot_keys,
             key_tree_width,
-            cmd_input_state,
             should_focus: None,
             should_focus_cmd_input: None,
-            cmd_output_scroll_handle: ScrollHandle::new(),
             focus_handle,
             _subscriptions: subscriptions,
         }
     }
-    fn reset_cmd_state(&mut self, _cx: &mut Context<Self>) {
-        self.cmd_outputs.clear();
-        self.cmd_outputs.extend(
-            ZEDIS_LOGO
-                .replace("{VERSION}", VERSION)
-                .lines()
-                .map(|line| line.to_string().into()),
-        );
-        self.cmd_output_scroll_handle = ScrollHandle::new();
-    }
-    fn execute_command(&mut self, command: SharedString, cx: &mut Context<Self>) {
-        if command.is_empty() {
-            return;
-        }
-        if command == CMD_CLEAR {
-            self.reset_cmd_state(cx);
-            return;
-        }
-        let server_state = self.server_state.read(cx);
-        let server_id = server_state.server_id().to_string();
-        let db = server_state.db();
-        cx.spawn(async move |handle, cx| {
-            let command_clone = command.clone();
-            let task = cx.background_spawn(async move {
-                let parts: Vec<_> = command.split_whitespace().map(|s| s.to_string()).collect();
-                if parts.is_empty() {
-                    return Ok(SharedString::default());
-                }
-                let cmd_name = parts[0].clone();
-                let args = parts[1..].to_vec();
-                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
-                let data: redis::Value = cmd(&cmd_name).arg(&args).query_async(&mut conn).await?;
-                Ok(redis_value_to_string(&data).into())
-            });
-            let result: Result<SharedString> = task.await;
-            let content: SharedString = match result {
-                Ok(result) => result,
-                Err(e) => e.to_string().into(),
-            };
 
-            handle.update(cx, |this, cx| {
-                this.cmd_outputs.extend(vec![
-                    format!("{CMD_LABEL} {command_clone}").into(),
-                    content,
-                    SharedString::default(),
-                ]);
-                let scroll_handle = this.cmd_output_scroll_handle.clone();
-                cx.notify();
-                cx.defer(move |_cx| {
-                    scroll_handle.scroll_to_bottom();
-                });
-            })
-        })
-        .detach();
+    /// Asks whether to trust the key of an SSH server that no known_hosts file records.
+    fn prompt_host_key(&mut self, key: Arc<SshHostKey>, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let message = t!(
+            "servers.host_key_prompt",
+            host = format!("{}:{}", key.host, key.port),
+            algorithm = key.algorithm,
+            fingerprint = key.fingerprint,
+            locale = locale
+        )
+        .to_string();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let server_state = server_state.clone();
+            let key = key.clone();
+            dialog
+                .title(i18n_servers(cx, "host_key_prompt_title"))
+                .confirm()
+                .child(message.clone())
+                .on_ok(move |_, window, cx| {
+                    let key = key.clone();
+                    server_state.update(cx, |state, cx| {
+                        state.trust_host_key(key, cx);
+                    });
+                    window.close_dialog(cx);
+                    true
+                })
+        });
     }
     /// Render the server management view (home page)
     ///
@@ -320,39 +300,46 @@ impl ZedisContent {
             right_panel = right_panel.size(content_width);
         }
         let (key_tree_width, min_width, max_width) = get_key_tree_widths(self.key_tree_width);
-        let right_panel_content =
-            if server_state.read(cx).is_terminal() {
+        let right_panel_content = match server_state.read(cx).panel() {
+            ServerPanel::Terminal => {
                 if let Some(true) = self.should_focus_cmd_input.take() {
-                    self.cmd_input_state.update(cx, |this, cx| this.focus(window, cx));
+                    self.console.update(cx, |console, cx| console.focus(window, cx));
                 }
-                let font_family: SharedString = get_font_family().into();
-
-                v_flex()
-                    .w_full()
-                    .h_full()
-                    .child(
-                        div()
-                            .id("cmd-output-scrollable-container")
-                            .track_scroll(&self.cmd_output_scroll_handle)
-                            .flex_1()
-                            .w_full()
-                            .overflow_y_scroll()
-                            .child(
-                                v_flex().p_2().gap_1().children(self.cmd_outputs.iter().map(|line| {
-                                    div().child(Label::new(line.clone()).font_family(font_family.clone()))
-                                })),
-                            ),
-                    )
-                    .child(
-                        div().w_full().border_t_1().border_color(cx.theme().border).child(
-                            Input::new(&self.cmd_input_state)
-                                .font_family(font_family)
-                                .prefix(Label::new(CMD_LABEL).text_color(cx.theme().yellow))
-                                .appearance(false),
-                        ),
-                    )
-                    .into_any_element()
-            } else {
+                self.console.clone().into_any_element()
+            }
+            ServerPanel::PubSub => {
+                if let Some(true) = self.should_focus_cmd_input.take() {
+                    self.pubsub.update(cx, |pubsub, cx| pubsub.focus(window, cx));
+                }
+                self.pubsub.clone().into_any_element()
+            }
+            ServerPanel::Diagnostics => {
+                if let Some(true) = self.should_focus_cmd_input.take() {
+                    self.diagnostics
+                        .update(cx, |diagnostics, cx| diagnostics.focus(window, cx));
+                }
+                self.diagnostics.clone().into_any_element()
+            }
+            ServerPanel::Metrics => self.metrics.clone().into_any_element(),
+            ServerPanel::Clients => {
+                if let Some(true) = self.should_focus_cmd_input.take() {
+                    self.clients.update(cx, |clients, cx| clients.focus(window, cx));
+                }
+                self.clients.clone().into_any_element()
+            }
+            ServerPanel::Memory => {
+                if let Some(true) = self.should_focus_cmd_input.take() {
+                    self.memory.update(cx, |memory, cx| memory.focus(window, cx));
+                }
+                self.memory.clone().into_any_element()
+            }
+            ServerPanel::HotKeys => {
+                if let Some(true) = self.should_focus_cmd_input.take() {
+                    self.hot_keys.update(cx, |hot_keys, cx| hot_keys.focus(window, cx));
+                }
+                self.hot_keys.clone().into_any_element()
+            }
+            ServerPanel::Editor => {
                 let value_editor = self
                     .value_editor
                     .get_or_insert_with(|| {
@@ -361,7 +348,8 @@ impl ZedisContent {
                     })
                     .clone();
                 value_editor.into_any_element()
-            };
+            }
+        };
 
         h_resizable("editor-container")
             .child(
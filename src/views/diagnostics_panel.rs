@@ -0,0 +1,379 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Slow log and latency diagnostics panel.
+//!
+//! Shows the merged `SLOWLOG GET` entries of every master with their node, filtered by
+//! command and minimum duration and sorted by duration or time, next to the latency
+//! events (`LATENCY LATEST`/`HISTORY`) and the `LATENCY DOCTOR` reports.
+
+use crate::{
+    helpers::get_font_family,
+    states::{ServerEvent, ServerPanel, SlowLogSort, ZedisServerState, filter_slowlog, i18n_diagnostics},
+};
+use chrono::{Local, TimeZone};
+use gpui::{AnyElement, Entity, SharedString, Subscription, Window, div, prelude::*, px, uniform_list};
+use gpui_component::{
+    ActiveTheme, Disableable, IconName, Selectable, Sizable, WindowExt,
+    button::Button,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    v_flex,
+};
+
+const ROW_HEIGHT: f32 = 28.0;
+const TIME_WIDTH: f32 = 150.0;
+const NODE_WIDTH: f32 = 150.0;
+const DURATION_WIDTH: f32 = 90.0;
+const DETAILS_HEIGHT: f32 = 240.0;
+/// Maximum number of history samples shown for a latency event
+const MAX_HISTORY_SAMPLES: usize = 20;
+
+/// Formats a duration in microseconds with a readable unit.
+fn format_micros(micros: u64) -> SharedString {
+    if micros < 1000 {
+        format!("{micros}µs").into()
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1000.0).into()
+    } else {
+        format!("{:.2}s", micros as f64 / 1_000_000.0).into()
+    }
+}
+
+fn format_timestamp(timestamp: i64) -> SharedString {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+        .into()
+}
+
+pub struct ZedisDiagnosticsPanel {
+    server_state: Entity<ZedisServerState>,
+    keyword_input: Entity<InputState>,
+    min_duration_input: Entity<InputState>,
+    sort: SlowLogSort,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisDiagnosticsPanel {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let keyword_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_diagnostics(cx, "keyword_placeholder")));
+        let min_duration_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_diagnostics(cx, "min_duration_placeholder")));
+        let subscriptions = vec![
+            cx.subscribe(&keyword_input, |_, _, event, cx| {
+                if let InputEvent::Change = event {
+                    cx.notify();
+                }
+            }),
+            cx.subscribe(&min_duration_input, |_, _, event, cx| {
+                if let InputEvent::Change = event {
+                    cx.notify();
+                }
+            }),
+            cx.subscribe(&server_state, |_, server_state, event, cx| {
+                // Diagnostics are loaded on demand the first time the panel is opened
+                if let ServerEvent::PanelToggled(ServerPanel::Diagnostics) = event
+                    && server_state.read(cx).diagnostics().is_none()
+                {
+                    server_state.update(cx, |state, cx| {
+                        state.load_diagnostics(cx);
+                    });
+                }
+            }),
+            cx.observe(&server_state, |_, _, cx| cx.notify()),
+        ];
+        Self {
+            server_state,
+            keyword_input,
+            min_duration_input,
+            sort: SlowLogSort::Duration,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Focuses the command filter input.
+    pub fn focus(&self, window: &mut Window, cx: &mut Context<Self>) {
+        self.keyword_input.update(cx, |state, cx| state.focus(window, cx));
+    }
+
+    fn set_sort(&mut self, sort: SlowLogSort, cx: &mut Context<Self>) {
+        self.sort = sort;
+        cx.notify();
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.server_state.update(cx, |state, cx| {
+            state.load_diagnostics(cx);
+        });
+    }
+
+    fn handle_reset_slowlog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let server_state = server_state.clone();
+            dialog
+                .confirm()
+                .child(i18n_diagnostics(cx, "reset_slowlog_prompt"))
+                .on_ok(move |_, window, cx| {
+                    server_state.update(cx, |state, cx| {
+                        state.reset_slowlog(cx);
+                    });
+                    window.close_dialog(cx);
+                    true
+                })
+        });
+    }
+
+    fn render_toolbar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let loading = self.server_state.read(cx).is_diagnostics_loading();
+        h_flex()
+            .p_2()
+            .gap_2()
+            .items_center()
+            .child(
+                div()
+                    .flex_1()
+                    .child(Input::new(&self.keyword_input).small().cleanable(true)),
+            )
+            .child(div().w(px(160.)).child(Input::new(&self.min_duration_input).small()))
+            .child(
+                Button::new("diagnostics-sort-duration")
+                    .outline()
+                    .small()
+                    .label(i18n_diagnostics(cx, "sort_duration"))
+                    .selected(self.sort == SlowLogSort::Duration)
+                    .on_click(cx.listener(|this, _, _, cx| this.set_sort(SlowLogSort::Duration, cx))),
+            )
+            .child(
+                Button::new("diagnostics-sort-time")
+                    .outline()
+                    .small()
+                    .label(i18n_diagnostics(cx, "sort_time"))
+                    .selected(self.sort == SlowLogSort::Time)
+                    .on_click(cx.listener(|this, _, _, cx| this.set_sort(SlowLogSort::Time, cx))),
+            )
+            .child(
+                Button::new("diagnostics-reload")
+                    .outline()
+                    .small()
+                    .icon(IconName::Redo)
+                    .tooltip(i18n_diagnostics(cx, "reload"))
+                    .loading(loading)
+                    .disabled(loading)
+                    .on_click(cx.listener(|this, _, _, cx| this.reload(cx))),
+            )
+            .child(
+                Button::new("diagnostics-reset-slowlog")
+                    .outline()
+                    .small()
+                    .icon(IconName::Delete)
+                    .label(i18n_diagnostics(cx, "reset_slowlog"))
+                    .disabled(loading)
+                    .on_click(cx.listener(|this, _, window, cx| this.handle_reset_slowlog(window, cx))),
+            )
+    }
+
+    fn render_slowlog(&self, font_family: &SharedString, cx: &Context<Self>) -> AnyElement {
+        let muted = cx.theme().muted_foreground;
+        let Some(diagnostics) = self.server_state.read(cx).diagnostics() else {
+            return v_flex().flex_1().into_any_element();
+        };
+        let keyword = self.keyword_input.read(cx).value();
+        // The minimum duration is entered in milliseconds
+        let min_duration = self
+            .min_duration_input
+            .read(cx)
+            .value()
+            .trim()
+            .parse::<f64>()
+            .map(|ms| (ms * 1000.0) as u64)
+            .unwrap_or_default();
+        let indexes = filter_slowlog(&diagnostics.slowlog, keyword.trim(), min_duration, self.sort);
+        if indexes.is_empty() {
+            return v_flex()
+                .flex_1()
+                .items_center()
+                .justify_center()
+                .child(
+                    Label::new(i18n_diagnostics(cx, "no_slowlog"))
+                        .text_sm()
+                        .text_color(muted),
+                )
+                .into_any_element();
+        }
+        let node_color = cx.theme().blue;
+        let font_family = font_family.clone();
+        uniform_list("diagnostics-slowlog", indexes.len(), move |range, _window, _cx| {
+            range
+                .filter_map(|row| indexes.get(row).and_then(|index| diagnostics.slowlog.get(*index)))
+                .map(|entry| {
+                    h_flex()
+                        .h(px(ROW_HEIGHT))
+                        .px_2()
+                        .gap_2()
+                        .w_full()
+                        .items_center()
+                        .child(
+                            Label::new(format_timestamp(entry.timestamp))
+                                .text_xs()
+                                .text_color(muted)
+                                .w(px(TIME_WIDTH)),
+                        )
+                        .child(
+                            Label::new(entry.node.clone())
+                                .text_xs()
+                                .text_color(node_color)
+                                .w(px(NODE_WIDTH))
+                                .truncate(),
+                        )
+                        .child(
+                            Label::new(format_micros(entry.duration))
+                                .text_sm()
+                                .font_family(font_family.clone())
+                                .w(px(DURATION_WIDTH)),
+                        )
+                        .child(
+                            Label::new(entry.command.clone())
+                                .text_sm()
+                                .font_family(font_family.clone())
+                                .flex_1()
+                                .truncate(),
+                        )
+                        .child(Label::new(entry.client.clone()).text_xs().text_color(muted))
+                })
+                .collect()
+        })
+        .flex_1()
+        .w_full()
+        .into_any_element()
+    }
+
+    fn render_latency(&self, font_family: &SharedString, cx: &Context<Self>) -> impl IntoElement {
+        let muted = cx.theme().muted_foreground;
+        let diagnostics = self.server_state.read(cx).diagnostics().unwrap_or_default();
+        let content: AnyElement = if diagnostics.latency.is_empty() {
+            Label::new(i18n_diagnostics(cx, "no_latency"))
+                .text_sm()
+                .text_color(muted)
+                .into_any_element()
+        } else {
+            v_flex()
+                .gap_1()
+                .children(diagnostics.latency.iter().map(|event| {
+                    let history = event
+                        .history
+                        .iter()
+                        .rev()
+                        .take(MAX_HISTORY_SAMPLES)
+                        .rev()
+                        .map(|(_, latency)| latency.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    v_flex()
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(Label::new(event.event.clone()).text_sm())
+                                .child(Label::new(event.node.clone()).text_xs().text_color(cx.theme().blue))
+                                .child(
+                                    Label::new(format!("{}ms / {}ms", event.latest, event.max))
+                                        .text_sm()
+                                        .font_family(font_family.clone()),
+                                )
+                                .child(
+                                    Label::new(format_timestamp(event.timestamp))
+                                        .text_xs()
+                                        .text_color(muted),
+                                ),
+                        )
+                        .when(!history.is_empty(), |this| {
+                            this.child(
+                                Label::new(history)
+                                    .text_xs()
+                                    .font_family(font_family.clone())
+                                    .text_color(muted),
+                            )
+                        })
+                }))
+                .into_any_element()
+        };
+        v_flex()
+            .id("diagnostics-latency")
+            .flex_1()
+            .h_full()
+            .p_2()
+            .gap_2()
+            .overflow_y_scroll()
+            .child(Label::new(i18n_diagnostics(cx, "latency")).text_sm())
+            .child(content)
+    }
+
+    fn render_reports(&self, font_family: &SharedString, cx: &Context<Self>) -> impl IntoElement {
+        let diagnostics = self.server_state.read(cx).diagnostics().unwrap_or_default();
+        let multiple = diagnostics.reports.len() > 1;
+        v_flex()
+            .id("diagnostics-doctor")
+            .flex_1()
+            .h_full()
+            .p_2()
+            .gap_2()
+            .border_l_1()
+            .border_color(cx.theme().border)
+            .overflow_y_scroll()
+            .child(Label::new(i18n_diagnostics(cx, "doctor")).text_sm())
+            .children(diagnostics.reports.iter().map(|report| {
+                v_flex()
+                    .when(multiple, |this| {
+                        this.child(Label::new(report.node.clone()).text_xs().text_color(cx.theme().blue))
+                    })
+                    .children(
+                        report
+                            .report
+                            .lines()
+                            .map(|line| Label::new(line.to_string()).text_xs().font_family(font_family.clone())),
+                    )
+            }))
+    }
+}
+
+impl Render for ZedisDiagnosticsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let font_family: SharedString = get_font_family().into();
+        v_flex()
+            .w_full()
+            .h_full()
+            .child(self.render_toolbar(cx))
+            .child(
+                h_flex()
+                    .px_2()
+                    .pb_1()
+                    .child(Label::new(i18n_diagnostics(cx, "slowlog")).text_sm()),
+            )
+            .child(self.render_slowlog(&font_family, cx))
+            .child(
+                h_flex()
+                    .h(px(DETAILS_HEIGHT))
+                    .w_full()
+                    .border_t_1()
+                    .border_color(cx.theme().border)
+                    .child(self.render_latency(&font_family, cx))
+                    .child(self.render_reports(&font_family, cx)),
+            )
+    }
+}
@@ -16,12 +16,15 @@ use crate::{
     assets::CustomIconName,
     components::{EditValueDialogParams, SelectableTextState, open_edit_value_dialog},
     helpers::{EditorAction, format_duration, humanize_keystroke, validate_ttl},
-    states::{KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_editor},
-    views::{ZedisBytesEditor, ZedisHashEditor, ZedisListEditor, ZedisSetEditor, ZedisZsetEditor},
+    states::{KeyType, RedisKeyMetadata, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_editor},
+    views::{
+        ZedisBytesEditor, ZedisHashEditor, ZedisListEditor, ZedisSetEditor, ZedisStreamEditor, ZedisVectorSetEditor,
+        ZedisZsetEditor,
+    },
 };
 use gpui::{App, ClipboardItem, Entity, FocusHandle, SharedString, Subscription, Window, div, prelude::*, px};
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, WindowExt,
+    ActiveTheme, Disableable, Icon, IconName, Selectable, WindowExt,
     button::Button,
     dialog::DialogButtonProps,
     h_flex,
@@ -39,6 +42,7 @@ use tracing::{debug, info};
 // Constants
 const RECENTLY_SELECTED_THRESHOLD_MS: u64 = 300;
 const TTL_INPUT_MAX_WIDTH: f32 = 100.0;
+const INSPECTOR_FIELDS_MAX_HEIGHT: f32 = 96.0;
 
 /// Main editor component for displaying and editing Redis key values
 /// Supports different key types (String, List, etc.) with type-specific editors
@@ -52,6 +56,8 @@ pub struct ZedisEditor {
     set_editor: Option<Entity<ZedisSetEditor>>,
     zset_editor: Option<Entity<ZedisZsetEditor>>,
     hash_editor: Option<Entity<ZedisHashEditor>>,
+    stream_editor: Option<Entity<ZedisStreamEditor>>,
+    vector_set_editor: Option<Entity<ZedisVectorSetEditor>>,
 
     /// Selectable text state for key name display
     key_text_state: Entity<SelectableTextState>,
@@ -68,6 +74,9 @@ pub struct ZedisEditor {
     /// Track when a key was selected to handle loading states smoothly
     selected_key_at: Option<Instant>,
 
+    /// Whether the key inspector is shown below the key info bar
+    show_inspector: bool,
+
     /// Focus handle for tracking focus within this component
     focus_handle: FocusHandle,
 
@@ -117,6 +126,12 @@ impl ZedisEditor {
                         state.set_text(key.clone());
                     });
                 }
+                // The metadata is dropped with the previous value
+                ServerEvent::ValueLoaded(_) if this.show_inspector => {
+                    server_state.update(cx, |state, cx| {
+                        state.inspect_key(cx);
+                    });
+                }
                 ServerEvent::EditonActionTriggered(action) => match action {
                     EditorAction::UpdateTtl => {
                         this.should_enter_ttl_edit_mode = Some(true);
@@ -166,6 +181,8 @@ impl ZedisEditor {
             set_editor: None,
             zset_editor: None,
             hash_editor: None,
+            stream_editor: None,
+            vector_set_editor: None,
             key_text_state,
             ttl_edit_mode: false,
             ttl_input_state,
@@ -175,6 +192,7 @@ impl ZedisEditor {
             focus_handle,
             _subscriptions: subscriptions,
             selected_key_at: None,
+            show_inspector: false,
         }
     }
 
@@ -187,7 +205,7 @@ impl ZedisEditor {
     }
 
     /// Focuses the keyword filter input field in the current type-specific editor.
-    /// Only works for List, Set, Zset, and Hash editors (BytesEditor has no filter).
+    /// Only works for List, Set, Zset, Hash, Stream and vector set editors (BytesEditor has no filter).
     pub fn focus_keyword(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(editor) = &self.list_editor {
             editor.update(cx, |e, cx| e.focus_keyword(window, cx));
@@ -197,6 +215,10 @@ impl ZedisEditor {
             editor.update(cx, |e, cx| e.focus_keyword(window, cx));
         } else if let Some(editor) = &self.hash_editor {
             editor.update(cx, |e, cx| e.focus_keyword(window, cx));
+        } else if let Some(editor) = &self.stream_editor {
+            editor.update(cx, |e, cx| e.focus_keyword(window, cx));
+        } else if let Some(editor) = &self.vector_set_editor {
+            editor.update(cx, |e, cx| e.focus_keyword(window, cx));
         }
         // bytes_editor has no keyword filter functionality
     }
@@ -347,6 +369,15 @@ impl ZedisEditor {
             state.select_key(key, cx);
         });
     }
+    fn toggle_inspector(&mut self, cx: &mut Context<Self>) {
+        self.show_inspector = !self.show_inspector;
+        if self.show_inspector {
+            self.server_state.update(cx, |state, cx| {
+                state.inspect_key(cx);
+            });
+        }
+        cx.notify();
+    }
     fn save(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
         let server_state = self.server_state.read(cx);
         let is_busy = server_state.value().map(|v| v.is_busy()).unwrap_or(false);
@@ -371,8 +402,7 @@ impl ZedisEditor {
         let Some(value) = server_state.value() else {
             return;
         };
-        let is_busy = value.is_busy();
-        if is_busy {
+        if value.is_busy() || server_state.is_readonly() {
             return;
         }
         let ttl: SharedString = value.ttl().unwrap_or_default().to_string().into();
@@ -395,7 +425,7 @@ impl ZedisEditor {
             return;
         };
         let is_busy = server_state.value().map(|v| v.is_busy()).unwrap_or(false);
-        if is_busy {
+        if is_busy || server_state.is_readonly() {
             return;
         }
         // Lazily create the InputState entity and subscribe to its events
@@ -459,6 +489,8 @@ impl ZedisEditor {
         };
 
         let mut is_busy = false;
+        // a replica browsed read-only refuses writes
+        let readonly = server_state.is_readonly();
         let mut btns = vec![];
         let mut ttl = SharedString::default();
         let mut size = SharedString::default();
@@ -492,7 +524,7 @@ impl ZedisEditor {
                 Button::new("zedis-editor-edit-key")
                     .ml_2()
                     .outline()
-                    .disabled(should_show_loading)
+                    .disabled(readonly || should_show_loading)
                     .tooltip(i18n_editor(cx, "edit_key_tooltip"))
                     .icon(CustomIconName::FilePenLine)
                     .on_click(cx.listener(move |this, _event, window, cx| {
@@ -517,8 +549,8 @@ impl ZedisEditor {
         if let Some(bytes_editor) = &self.bytes_editor {
             let state = bytes_editor.read(cx);
             let value_modified = state.is_value_modified();
-            let readonly = state.is_readonly();
-            let mut tooltip = if readonly {
+            let value_readonly = state.is_readonly();
+            let mut tooltip = if value_readonly {
                 i18n_editor(cx, "can_not_edit_value")
             } else {
                 i18n_editor(cx, "save_data_tooltip")
@@ -528,7 +560,7 @@ impl ZedisEditor {
             btns.push(
                 Button::new("zedis-editor-save-key")
                     .ml_2()
-                    .disabled(readonly || !value_modified || should_show_loading)
+                    .disabled(readonly || value_readonly || !value_modified || should_show_loading)
                     .outline()
                     .label(i18n_common(cx, "save"))
                     .tooltip(tooltip)
@@ -567,7 +599,7 @@ impl ZedisEditor {
                     .ml_2()
                     .outline()
                     .w(px(TTL_INPUT_MAX_WIDTH))
-                    .disabled(should_show_loading)
+                    .disabled(readonly || should_show_loading)
                     .tooltip(ttl_tooltip)
                     .label(ttl.clone())
                     .icon(CustomIconName::Clock3)
@@ -585,6 +617,21 @@ impl ZedisEditor {
             humanize_keystroke("secondary-r")
         )
         .into();
+        // key inspector
+        btns.push(
+            Button::new("zedis-editor-inspect-key")
+                .ml_2()
+                .outline()
+                .selected(self.show_inspector)
+                .disabled(should_show_loading)
+                .tooltip(i18n_editor(cx, "inspect_key_tooltip"))
+                .icon(IconName::Info)
+                .on_click(cx.listener(move |this, _event, _window, cx| {
+                    this.toggle_inspector(cx);
+                }))
+                .into_any_element(),
+        );
+
         // reload
         btns.push(
             Button::new("zedis-editor-reload-key")
@@ -604,7 +651,7 @@ impl ZedisEditor {
             Button::new("zedis-editor-delete-key")
                 .ml_2()
                 .outline()
-                .disabled(should_show_loading)
+                .disabled(readonly || should_show_loading)
                 .tooltip(i18n_editor(cx, "delete_key_tooltip"))
                 .icon(IconName::CircleX)
                 .on_click(cx.listener(move |this, _event, window, cx| {
@@ -659,6 +706,93 @@ impl ZedisEditor {
             )
             .children(btns)
     }
+    /// Render the internal metadata of the key: encoding, access statistics, memory,
+    /// cluster slot and the TTL of the hash fields
+    fn render_inspector(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let metadata = self.server_state.read(cx).value().and_then(|value| value.metadata());
+        let muted = cx.theme().muted_foreground;
+        let container = v_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().border);
+        let Some(metadata) = metadata else {
+            return container.child(Label::new(i18n_editor(cx, "inspecting")).text_xs().text_color(muted));
+        };
+        let RedisKeyMetadata {
+            encoding,
+            refcount,
+            idle_seconds,
+            frequency,
+            memory_usage,
+            slot,
+            node,
+            elements,
+            inspected_fields,
+            field_ttls,
+        } = metadata.as_ref();
+        let unknown = || SharedString::from("--");
+        let items = [
+            ("encoding", encoding.clone().map(SharedString::from)),
+            ("refcount", refcount.map(|value| value.to_string().into())),
+            (
+                "idle",
+                idle_seconds.map(|value| format_duration(Duration::from_secs(value)).into()),
+            ),
+            ("frequency", frequency.map(|value| value.to_string().into())),
+            (
+                "memory_usage",
+                memory_usage.map(|value| format_size(value, DECIMAL).into()),
+            ),
+            ("elements", elements.map(|value| value.to_string().into())),
+            ("slot", slot.map(|value| value.to_string().into())),
+            ("node", node.clone().map(SharedString::from)),
+        ];
+        let summary = h_flex()
+            .flex_wrap()
+            .gap_x_4()
+            .children(items.into_iter().map(|(label, value)| {
+                h_flex()
+                    .gap_1()
+                    .child(Label::new(i18n_editor(cx, label)).text_xs().text_color(muted))
+                    .child(Label::new(value.unwrap_or_else(unknown)).text_xs())
+            }));
+        let value_type = self.server_state.read(cx).value().map(|value| value.key_type());
+        if value_type != Some(KeyType::Hash) {
+            return container.child(summary);
+        }
+
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let field_ttls_label = match field_ttls {
+            Some(field_ttls) => t!(
+                "editor.field_ttls",
+                count = field_ttls.len(),
+                total = inspected_fields,
+                locale = locale
+            )
+            .to_string(),
+            None => i18n_editor(cx, "field_ttls_unsupported").to_string(),
+        };
+        container
+            .child(summary)
+            .child(Label::new(field_ttls_label).text_xs().text_color(muted))
+            .child(
+                h_flex()
+                    .id("zedis-editor-field-ttls")
+                    .flex_wrap()
+                    .gap_x_4()
+                    .max_h(px(INSPECTOR_FIELDS_MAX_HEIGHT))
+                    .overflow_y_scroll()
+                    .children(field_ttls.iter().flatten().map(|(field, ttl)| {
+                        h_flex().gap_1().child(Label::new(field.clone()).text_xs()).child(
+                            Label::new(format_duration(Duration::from_secs(*ttl as u64)))
+                                .text_xs()
+                                .text_color(muted),
+                        )
+                    })),
+            )
+    }
     /// Clean up unused editors when switching between key types
     fn reset_editors(&mut self, key_type: KeyType) {
         if key_type != KeyType::String {
@@ -676,6 +810,12 @@ impl ZedisEditor {
         if key_type != KeyType::Hash {
             let _ = self.hash_editor.take();
         }
+        if key_type != KeyType::Stream {
+            let _ = self.stream_editor.take();
+        }
+        if key_type != KeyType::Vectorset {
+            let _ = self.vector_set_editor.take();
+        }
     }
 
     /// Render the appropriate editor based on the key type
@@ -723,6 +863,22 @@ impl ZedisEditor {
                 });
                 editor.clone().into_any_element()
             }
+            KeyType::Stream => {
+                self.reset_editors(KeyType::Stream);
+                let editor = self.stream_editor.get_or_insert_with(|| {
+                    debug!("Creating new stream editor");
+                    cx.new(|cx| ZedisStreamEditor::new(self.server_state.clone(), window, cx))
+                });
+                editor.clone().into_any_element()
+            }
+            KeyType::Vectorset => {
+                self.reset_editors(KeyType::Vectorset);
+                let editor = self.vector_set_editor.get_or_insert_with(|| {
+                    debug!("Creating new vector set editor");
+                    cx.new(|cx| ZedisVectorSetEditor::new(self.server_state.clone(), window, cx))
+                });
+                editor.clone().into_any_element()
+            }
             _ => {
                 // Default to bytes editor for String type and other types
                 self.reset_editors(KeyType::String);
@@ -755,6 +911,7 @@ impl Render for ZedisEditor {
             .h_full()
             .track_focus(&self.focus_handle)
             .child(self.render_select_key(cx))
+            .when(self.show_inspector, |this| this.child(self.render_inspector(cx)))
             .child(self.render_editor(window, cx))
             .on_action(cx.listener(move |this, event: &EditorAction, window, cx| match event {
                 EditorAction::Save => {
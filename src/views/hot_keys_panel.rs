@@ -0,0 +1,439 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hot keys panel.
+//!
+//! Ranks the keys matching a pattern by their access frequency (LFU policies) or idle
+//! time, per node. The hottest or coldest keys are filtered by keyword and sorted by a
+//! column; clicking a key opens it in the editor.
+
+use crate::{
+    connection::{HotKey, HotKeyMode, HotKeyReport, NodeHotKeys},
+    helpers::{format_duration, get_font_family},
+    states::{
+        DEFAULT_HOT_KEYS, HotKeyCategory, HotKeySort, ServerEvent, ZedisGlobalStore, ZedisServerState, filter_hot_keys,
+        i18n_hot_keys,
+    },
+};
+use gpui::{AnyElement, App, Entity, SharedString, Subscription, Window, div, prelude::*, px, uniform_list};
+use gpui_component::{
+    ActiveTheme, Disableable, IconName, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    v_flex,
+};
+use rust_i18n::t;
+use std::time::Duration;
+
+const ROW_HEIGHT: f32 = 28.0;
+const LIMIT_INPUT_WIDTH: f32 = 72.0;
+const NODE_WIDTH: f32 = 140.0;
+const VALUE_WIDTH: f32 = 90.0;
+const RANK_WIDTH: f32 = 50.0;
+const ACTION_WIDTH: f32 = 32.0;
+
+/// Formats the statistic of a key: the access counter or the idle time.
+fn format_value(mode: HotKeyMode, value: u64) -> SharedString {
+    match mode {
+        HotKeyMode::Frequency => value.to_string().into(),
+        HotKeyMode::Idle => format_duration(Duration::from_secs(value)).into(),
+    }
+}
+
+/// Returns the i18n key of the value column, `value` when the nodes use different modes.
+fn value_label(report: &HotKeyReport) -> &'static str {
+    let mut modes = report.nodes.iter().map(|node| node.mode);
+    let Some(first) = modes.next() else {
+        return "value";
+    };
+    if modes.any(|mode| mode != first) {
+        "value"
+    } else if first == HotKeyMode::Frequency {
+        "frequency"
+    } else {
+        "idle"
+    }
+}
+
+/// Describes the eviction policy of a node and the statistic its keys are ranked by.
+fn node_description(node: &NodeHotKeys, cx: &App) -> String {
+    let mode = match node.mode {
+        HotKeyMode::Frequency => i18n_hot_keys(cx, "mode_frequency"),
+        HotKeyMode::Idle => i18n_hot_keys(cx, "mode_idle"),
+    };
+    // CONFIG is often disabled on managed services
+    let policy = if node.policy.is_empty() {
+        "?"
+    } else {
+        node.policy.as_str()
+    };
+    format!("{} · {policy} · {mode}", node.node)
+}
+
+pub struct ZedisHotKeysPanel {
+    server_state: Entity<ZedisServerState>,
+    pattern_input: Entity<InputState>,
+    limit_input: Entity<InputState>,
+    keyword_input: Entity<InputState>,
+    category: HotKeyCategory,
+    sort: HotKeySort,
+    descending: bool,
+    /// Whether the last report covers several nodes, the node column is hidden otherwise
+    multiple_nodes: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisHotKeysPanel {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let pattern_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_hot_keys(cx, "pattern_placeholder")));
+        let limit_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_hot_keys(cx, "limit"))
+                .default_value(DEFAULT_HOT_KEYS.to_string())
+        });
+        let keyword_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_hot_keys(cx, "keyword_placeholder")));
+        let subscriptions = vec![
+            cx.subscribe(&keyword_input, |_, _, event, cx| {
+                if let InputEvent::Change = event {
+                    cx.notify();
+                }
+            }),
+            cx.subscribe(&server_state, |this, _, event, cx| match event {
+                // A new report starts with the ranking of each node
+                ServerEvent::HotKeysAnalyzed(report) => {
+                    this.sort = HotKeySort::Rank;
+                    this.descending = false;
+                    this.multiple_nodes = report.nodes.len() > 1;
+                    cx.notify();
+                }
+                ServerEvent::ServerSelected(_, _) => {
+                    this.multiple_nodes = false;
+                    cx.notify();
+                }
+                _ => {}
+            }),
+            cx.observe(&server_state, |_, _, cx| cx.notify()),
+        ];
+        Self {
+            server_state,
+            pattern_input,
+            limit_input,
+            keyword_input,
+            category: HotKeyCategory::Hottest,
+            sort: HotKeySort::Rank,
+            descending: false,
+            multiple_nodes: false,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Focuses the pattern input.
+    pub fn focus(&self, window: &mut Window, cx: &mut Context<Self>) {
+        self.pattern_input.update(cx, |state, cx| state.focus(window, cx));
+    }
+
+    fn analyze(&mut self, cx: &mut Context<Self>) {
+        let pattern = self.pattern_input.read(cx).value();
+        let limit = self
+            .limit_input
+            .read(cx)
+            .value()
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_HOT_KEYS);
+        self.server_state.update(cx, |state, cx| {
+            state.analyze_hot_keys(pattern, limit, cx);
+        });
+    }
+
+    fn set_category(&mut self, category: HotKeyCategory, cx: &mut Context<Self>) {
+        self.category = category;
+        cx.notify();
+    }
+
+    /// Sorts by the column, clicking the current column reverses the order.
+    fn set_sort(&mut self, sort: HotKeySort, cx: &mut Context<Self>) {
+        if self.sort == sort {
+            self.descending = !self.descending;
+        } else {
+            self.sort = sort;
+            self.descending = false;
+        }
+        cx.notify();
+    }
+
+    fn render_toolbar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let analyzing = self.server_state.read(cx).is_hot_keys_analyzing();
+        let category_button = |id: &'static str, category: HotKeyCategory, label: &str| {
+            let button = Button::new(id)
+                .small()
+                .label(i18n_hot_keys(cx, label))
+                .on_click(cx.listener(move |this, _, _, cx| this.set_category(category, cx)));
+            if self.category == category {
+                button.primary()
+            } else {
+                button.outline()
+            }
+        };
+        h_flex()
+            .p_2()
+            .gap_2()
+            .items_center()
+            .child(
+                div()
+                    .flex_1()
+                    .child(Input::new(&self.pattern_input).small().cleanable(true)),
+            )
+            .child(
+                div()
+                    .w(px(LIMIT_INPUT_WIDTH))
+                    .child(Input::new(&self.limit_input).small()),
+            )
+            .child(
+                Button::new("hot-keys-analyze")
+                    .outline()
+                    .small()
+                    .icon(IconName::ChartPie)
+                    .label(i18n_hot_keys(cx, "analyze"))
+                    .loading(analyzing)
+                    .disabled(analyzing)
+                    .on_click(cx.listener(|this, _, _, cx| this.analyze(cx))),
+            )
+            .child(category_button("hot-keys-hottest", HotKeyCategory::Hottest, "hottest"))
+            .child(category_button("hot-keys-coldest", HotKeyCategory::Coldest, "coldest"))
+    }
+
+    /// Renders the totals and the statistic used by each node.
+    fn render_summary(&self, report: &HotKeyReport, cx: &Context<Self>) -> impl IntoElement {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let muted = cx.theme().muted_foreground;
+        let summary = t!(
+            "hot_keys.summary",
+            keys = report.scanned(),
+            pattern = report.pattern,
+            nodes = report.nodes.len(),
+            locale = locale
+        )
+        .to_string();
+        h_flex()
+            .px_2()
+            .pb_2()
+            .gap_3()
+            .flex_wrap()
+            .items_center()
+            .child(Label::new(summary).text_sm())
+            .when(report.cancelled, |this| {
+                this.child(
+                    Label::new(i18n_hot_keys(cx, "partial"))
+                        .text_xs()
+                        .text_color(cx.theme().warning),
+                )
+            })
+            .children(
+                report
+                    .nodes
+                    .iter()
+                    .map(|node| Label::new(node_description(node, cx)).text_xs().text_color(muted)),
+            )
+    }
+
+    /// Renders a sortable header cell.
+    fn render_header_cell(&self, label: &str, width: Option<f32>, sort: HotKeySort, cx: &Context<Self>) -> AnyElement {
+        let label = i18n_hot_keys(cx, label);
+        let cell = match width {
+            Some(width) => h_flex().w(px(width)),
+            None => h_flex().flex_1(),
+        };
+        let icon = if self.descending {
+            IconName::SortDescending
+        } else {
+            IconName::SortAscending
+        };
+        let button = Button::new(SharedString::from(format!("hot-keys-sort-{label}")))
+            .ghost()
+            .xsmall()
+            .label(label)
+            .when(self.sort == sort, |this| this.icon(icon))
+            .on_click(cx.listener(move |this, _, _, cx| this.set_sort(sort, cx)));
+        cell.child(button).into_any_element()
+    }
+
+    fn render_header(&self, report: &HotKeyReport, cx: &Context<Self>) -> impl IntoElement {
+        h_flex()
+            .h(px(ROW_HEIGHT))
+            .px_2()
+            .gap_2()
+            .w_full()
+            .items_center()
+            .border_t_1()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(self.render_header_cell("rank", Some(RANK_WIDTH), HotKeySort::Rank, cx))
+            .when(self.multiple_nodes, |this| {
+                this.child(self.render_header_cell("node", Some(NODE_WIDTH), HotKeySort::Node, cx))
+            })
+            .child(self.render_header_cell("key", None, HotKeySort::Key, cx))
+            .child(self.render_header_cell(value_label(report), Some(VALUE_WIDTH), HotKeySort::Value, cx))
+            .child(div().w(px(ACTION_WIDTH)))
+    }
+
+    fn render_keys(&self, report: &HotKeyReport, cx: &Context<Self>) -> AnyElement {
+        let muted = cx.theme().muted_foreground;
+        let keyword = self.keyword_input.read(cx).value();
+        // Rows own their keys, the list renders them lazily
+        let items: Vec<(usize, HotKey, HotKeyMode)> =
+            filter_hot_keys(report, self.category, keyword.trim(), self.sort, self.descending)
+                .into_iter()
+                .map(|(rank, item)| {
+                    let mode = report
+                        .nodes
+                        .iter()
+                        .find(|node| node.node == item.node)
+                        .map(|node| node.mode)
+                        .unwrap_or_default();
+                    (rank, item.clone(), mode)
+                })
+                .collect();
+        let header = self.render_header(report, cx);
+        if items.is_empty() {
+            return v_flex()
+                .flex_1()
+                .child(header)
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .items_center()
+                        .justify_center()
+                        .child(Label::new(i18n_hot_keys(cx, "no_keys")).text_sm().text_color(muted)),
+                )
+                .into_any_element();
+        }
+        let multiple_nodes = self.multiple_nodes;
+        let node_color = cx.theme().blue;
+        let hover_bg = cx.theme().list_hover;
+        let font_family: SharedString = get_font_family().into();
+        let show_keys_tooltip = i18n_hot_keys(cx, "show_keys");
+        let server_state = self.server_state.clone();
+        let list = uniform_list("hot-keys-list", items.len(), move |range, _window, _cx| {
+            range
+                .filter_map(|row| items.get(row).map(|item| (row, item)))
+                .map(|(row, (rank, item, mode))| {
+                    let key = SharedString::from(item.key.clone());
+                    let select_state = server_state.clone();
+                    let filter_state = server_state.clone();
+                    let filter_key = key.clone();
+                    h_flex()
+                        .id(("hot-keys-row", row))
+                        .h(px(ROW_HEIGHT))
+                        .px_2()
+                        .gap_2()
+                        .w_full()
+                        .items_center()
+                        .cursor_pointer()
+                        .hover(move |this| this.bg(hover_bg))
+                        .on_click(move |_, _, cx| {
+                            let key = key.clone();
+                            select_state.update(cx, |state, cx| {
+                                state.select_key(key, cx);
+                            });
+                        })
+                        .child(
+                            Label::new((rank + 1).to_string())
+                                .text_xs()
+                                .text_color(muted)
+                                .w(px(RANK_WIDTH)),
+                        )
+                        .when(multiple_nodes, |this| {
+                            this.child(
+                                Label::new(item.node.clone())
+                                    .text_xs()
+                                    .text_color(node_color)
+                                    .w(px(NODE_WIDTH))
+                                    .truncate(),
+                            )
+                        })
+                        .child(
+                            Label::new(item.key.clone())
+                                .text_sm()
+                                .font_family(font_family.clone())
+                                .flex_1()
+                                .truncate(),
+                        )
+                        .child(Label::new(format_value(*mode, item.value)).text_xs().w(px(VALUE_WIDTH)))
+                        .child(
+                            Button::new(("hot-keys-show", row))
+                                .ghost()
+                                .xsmall()
+                                .icon(IconName::Search)
+                                .tooltip(show_keys_tooltip.clone())
+                                .on_click(move |_, _, cx| {
+                                    cx.stop_propagation();
+                                    let key = filter_key.clone();
+                                    filter_state.update(cx, |state, cx| {
+                                        state.filter_keys_by_prefix(key, cx);
+                                    });
+                                }),
+                        )
+                })
+                .collect()
+        })
+        .flex_1()
+        .w_full();
+        v_flex().flex_1().child(header).child(list).into_any_element()
+    }
+
+    fn render_report(&self, cx: &Context<Self>) -> AnyElement {
+        let Some(report) = self.server_state.read(cx).hot_key_report() else {
+            let message = if self.server_state.read(cx).is_hot_keys_analyzing() {
+                i18n_hot_keys(cx, "analyzing")
+            } else {
+                i18n_hot_keys(cx, "no_report")
+            };
+            return v_flex()
+                .flex_1()
+                .items_center()
+                .justify_center()
+                .child(Label::new(message).text_sm().text_color(cx.theme().muted_foreground))
+                .into_any_element();
+        };
+        v_flex()
+            .flex_1()
+            .w_full()
+            .child(self.render_summary(&report, cx))
+            .child(
+                div()
+                    .px_2()
+                    .pb_2()
+                    .child(Input::new(&self.keyword_input).small().cleanable(true)),
+            )
+            .child(self.render_keys(&report, cx))
+            .into_any_element()
+    }
+}
+
+impl Render for ZedisHotKeysPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w_full()
+            .h_full()
+            .child(self.render_toolbar(cx))
+            .child(self.render_report(cx))
+    }
+}
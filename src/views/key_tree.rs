@@ -15,9 +15,12 @@
 use crate::{
     assets::CustomIconName,
     components::{FormDialog, FormField, SkeletonLoading, open_add_form_dialog},
-    connection::QueryMode,
-    helpers::{EditorAction, KeyTreeAction, validate_long_string, validate_ttl},
-    states::{KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_key_tree},
+    connection::{
+        BulkKeys, KeyConflictPolicy, KeyDeleteSummary, KeyExportFormat, KeyTtlSummary, QueryMode, RedisServer,
+        ScriptImportSummary, TtlAction, TtlCondition,
+    },
+    helpers::{EditorAction, KeyTreeAction, parse_ttl, validate_long_string, validate_ttl},
+    states::{KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_bulk, i18n_common, i18n_key_tree},
 };
 use ahash::{AHashMap, AHashSet};
 use gpui::{
@@ -38,7 +41,7 @@ use gpui_component::{
     v_flex,
 };
 use rust_i18n::t;
-use std::rc::Rc;
+use std::{rc::Rc, sync::Arc};
 use tracing::{debug, info};
 
 // Constants for tree layout and behavior
@@ -51,6 +54,37 @@ const KEY_TYPE_BORDER_FADE_ALPHA: f32 = 0.5; // Border transparency for key type
 const STRIPE_BACKGROUND_ALPHA_DARK: f32 = 0.1; // Odd row background alpha for dark theme
 const STRIPE_BACKGROUND_ALPHA_LIGHT: f32 = 0.03; // Odd row background alpha for light theme
 
+/// Mode of a bulk TTL update.
+#[derive(Clone, Copy)]
+enum BulkTtlMode {
+    Set(TtlCondition),
+    Extend,
+    Shorten,
+    Persist,
+}
+
+// Radio options of the bulk dialogs, in display order, with their `bulk` label keys
+const BULK_TTL_MODES: [(BulkTtlMode, &str); 8] = [
+    (BulkTtlMode::Set(TtlCondition::Always), "ttl_mode_set"),
+    (BulkTtlMode::Extend, "ttl_mode_extend"),
+    (BulkTtlMode::Shorten, "ttl_mode_shorten"),
+    (BulkTtlMode::Set(TtlCondition::Gt), "ttl_mode_gt"),
+    (BulkTtlMode::Set(TtlCondition::Lt), "ttl_mode_lt"),
+    (BulkTtlMode::Set(TtlCondition::Nx), "ttl_mode_nx"),
+    (BulkTtlMode::Set(TtlCondition::Xx), "ttl_mode_xx"),
+    (BulkTtlMode::Persist, "ttl_mode_persist"),
+];
+const CONFLICT_POLICIES: [(KeyConflictPolicy, &str); 3] = [
+    (KeyConflictPolicy::Skip, "copy_conflict_skip"),
+    (KeyConflictPolicy::Replace, "copy_conflict_replace"),
+    (KeyConflictPolicy::Rename, "copy_conflict_rename"),
+];
+const EXPORT_FORMATS: [((KeyExportFormat, &str), &str); 3] = [
+    ((KeyExportFormat::Json, "json"), "format_json"),
+    ((KeyExportFormat::Ndjson, "ndjson"), "format_ndjson"),
+    ((KeyExportFormat::Commands, "redis"), "format_commands"),
+];
+
 #[derive(Default)]
 struct KeyTreeState {
     /// Primary keyword used for Redis SCAN.
@@ -213,6 +247,118 @@ fn visible_key_range(
     Some((start, end, keys))
 }
 
+/// Shows the result of a delete by pattern dry run and deletes the keys once confirmed.
+fn confirm_delete_by_pattern(
+    summary: Arc<KeyDeleteSummary>,
+    server_state: Entity<ZedisServerState>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    if summary.matched == 0 || summary.cancelled {
+        return;
+    }
+    info!(
+        pattern = summary.pattern,
+        matched = summary.matched,
+        "Showing confirmation before deleting keys by pattern"
+    );
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let message = t!(
+            "bulk.delete_prompt",
+            count = summary.matched,
+            pattern = summary.pattern,
+            locale = locale
+        )
+        .to_string();
+        let server_state = server_state.clone();
+        let summary = summary.clone();
+        let samples = summary.samples.clone();
+
+        dialog
+            .confirm()
+            .title(i18n_bulk(cx, "delete_title"))
+            .button_props(
+                DialogButtonProps::default()
+                    .ok_text(i18n_common(cx, "confirm"))
+                    .cancel_text(i18n_common(cx, "cancel")),
+            )
+            .child(
+                v_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(message)
+                    .child(Label::new(i18n_bulk(cx, "samples")).text_sm())
+                    .child(
+                        v_flex()
+                            .w_full()
+                            .max_h(px(200.0))
+                            .overflow_y_scrollbar()
+                            .children(samples.into_iter().map(|key| Label::new(key).text_xs())),
+                    ),
+            )
+            .on_ok(move |_, window, cx| {
+                let pattern: SharedString = summary.pattern.clone().into();
+                let total = summary.matched;
+                server_state.update(cx, move |state, cx| {
+                    state.delete_by_pattern(pattern, total, cx);
+                });
+                window.close_dialog(cx);
+                true
+            })
+    });
+}
+
+/// Lists the keys that no longer existed when their TTL was updated.
+fn show_missing_ttl_keys(summary: Arc<KeyTtlSummary>, window: &mut Window, cx: &mut App) {
+    if summary.missing == 0 {
+        return;
+    }
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let message = t!("bulk.ttl_missing_prompt", count = summary.missing, locale = locale).to_string();
+        dialog.alert().title(i18n_bulk(cx, "ttl_title")).child(
+            v_flex().w_full().gap_2().child(message).child(
+                v_flex().w_full().max_h(px(200.0)).overflow_y_scrollbar().children(
+                    summary
+                        .missing_samples
+                        .iter()
+                        .map(|key| Label::new(key.clone()).text_xs()),
+                ),
+            ),
+        )
+    });
+}
+
+/// Lists the lines of a command script that could not be parsed or failed.
+fn show_script_errors(summary: Arc<ScriptImportSummary>, window: &mut Window, cx: &mut App) {
+    if summary.errors.is_empty() {
+        return;
+    }
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let message = t!(
+            "bulk.script_errors_prompt",
+            count = summary.failed,
+            path = summary.file_path,
+            locale = locale
+        )
+        .to_string();
+        dialog.alert().title(i18n_bulk(cx, "import_script")).child(
+            v_flex().w_full().gap_2().child(message).child(
+                v_flex()
+                    .w_full()
+                    .max_h(px(200.0))
+                    .overflow_y_scrollbar()
+                    .children(summary.errors.iter().map(|(line, error)| {
+                        let text = t!("bulk.script_error_line", line = line, error = error, locale = locale);
+                        Label::new(text).text_xs()
+                    })),
+            ),
+        )
+    });
+}
+
 fn confirm_delete_selected_keys(
     keys: Vec<SharedString>,
     server_state: Entity<ZedisServerState>,
@@ -535,6 +681,28 @@ impl ZedisKeyTree {
             }),
         );
 
+        // Confirm the deletion once the dry run of a delete by pattern has counted the keys,
+        // list the keys that no longer existed after a bulk TTL update or the lines of
+        // a command script that failed, and filter the keys of a prefix picked in the
+        // memory analyzer
+        subscriptions.push(
+            cx.subscribe_in(&server_state, window, |this, _, event, window, cx| match event {
+                ServerEvent::KeyDeletePreviewed(summary) => {
+                    confirm_delete_by_pattern(summary.clone(), this.server_state.clone(), window, cx);
+                }
+                ServerEvent::KeysTtlUpdated(summary) => {
+                    show_missing_ttl_keys(summary.clone(), window, cx);
+                }
+                ServerEvent::ScriptImported(summary) => {
+                    show_script_errors(summary.clone(), window, cx);
+                }
+                ServerEvent::KeyFilterRequested(prefix) => {
+                    this.filter_by_prefix(prefix.clone(), window, cx);
+                }
+                _ => {}
+            }),
+        );
+
         // Initialize keyword search input with placeholder
         let keyword_state = cx.new(|cx| {
             InputState::new(window, cx)
@@ -787,6 +955,24 @@ impl ZedisKeyTree {
         });
     }
 
+    /// Lists the keys starting with the prefix, e.g. a prefix of the memory analyzer.
+    ///
+    /// An exact query would look for a key named like the prefix, so the keys are scanned
+    /// instead.
+    fn filter_by_prefix(&mut self, prefix: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        self.keyword_state.update(cx, |state, cx| {
+            state.set_value(prefix.clone(), window, cx);
+        });
+        if self.state.query_mode != QueryMode::Exact {
+            self.handle_filter(cx);
+            return;
+        }
+        self.state.keyword = prefix.clone();
+        self.server_state.update(cx, |state, cx| {
+            state.scan(prefix, cx);
+        });
+    }
+
     fn select_all_visible_keys(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let input_focus = self.keyword_state.read(cx).focus_handle(cx);
         if input_focus.is_focused(window) {
@@ -1153,6 +1339,7 @@ impl ZedisKeyTree {
                         .upgrade()
                         .map(|v| v.read(cx).server_state.read(cx).selected_keys_count())
                         .unwrap_or(0);
+                    let readonly = server_state.read(cx).is_readonly();
 
                     if selected_count > 1 {
                         // Multi-selection: show export and batch delete options
@@ -1176,6 +1363,7 @@ impl ZedisKeyTree {
                                 i18n_key_tree(cx, "delete_selected"),
                                 selected_count
                             ))
+                            .disabled(readonly)
                             .on_click(move |_, window, cx| {
                                 let keys: Vec<SharedString> =
                                     ss_delete.read(cx).selected_keys().iter().cloned().collect();
@@ -1197,19 +1385,23 @@ impl ZedisKeyTree {
                             }
                         }))
                         .item(
-                            PopupMenuItem::new(i18n_key_tree(cx, "duplicate_key")).on_click(move |_, _window, cx| {
-                                ss_dup.update(cx, |state, cx| {
-                                    state.duplicate_key(key_dup.clone(), cx);
-                                });
-                            }),
+                            PopupMenuItem::new(i18n_key_tree(cx, "duplicate_key"))
+                                .disabled(readonly)
+                                .on_click(move |_, _window, cx| {
+                                    ss_dup.update(cx, |state, cx| {
+                                        state.duplicate_key(key_dup.clone(), cx);
+                                    });
+                                }),
                         )
                         .separator()
                         .item(
-                            PopupMenuItem::new(i18n_key_tree(cx, "delete_key")).on_click(move |_, _window, cx| {
-                                ss_del.update(cx, |state, cx| {
-                                    state.delete_key(key_del.clone(), cx);
-                                });
-                            }),
+                            PopupMenuItem::new(i18n_key_tree(cx, "delete_key"))
+                                .disabled(readonly)
+                                .on_click(move |_, _window, cx| {
+                                    ss_del.update(cx, |state, cx| {
+                                        state.delete_key(key_del.clone(), cx);
+                                    });
+                                }),
                         )
                     } else {
                         menu
@@ -1268,6 +1460,38 @@ impl ZedisKeyTree {
                     |_, cx| Label::new(i18n_key_tree(cx, "query_mode_exact")).ml_2().text_xs(),
                 )
             });
+        // Key type filter, sent to the server as SCAN ... TYPE
+        let key_type_filter = self.server_state.read(cx).key_type_filter();
+        let all_types_label = i18n_key_tree(cx, "key_type_all");
+        let server_state = self.server_state.downgrade();
+        let key_type_dropdown = DropdownButton::new("key-tree-key-type-dropdown")
+            .button(
+                Button::new("key-tree-key-type-btn")
+                    .ghost()
+                    .px_2()
+                    .tooltip(i18n_key_tree(cx, "key_type_tooltip"))
+                    .when_some(key_type_filter, |this, key_type| {
+                        this.child(Label::new(key_type.as_str()).text_xs().text_color(key_type.color()))
+                    })
+                    .when(key_type_filter.is_none(), |this| this.icon(IconName::Menu)),
+            )
+            .dropdown_menu_with_anchor(Corner::TopRight, move |menu, _, _| {
+                let options = std::iter::once((None, all_types_label.clone())).chain(
+                    KeyType::known_types()
+                        .into_iter()
+                        .map(|key_type| (Some(key_type), key_type.type_name().into())),
+                );
+                options.fold(menu, |menu, (key_type, label)| {
+                    let server_state = server_state.clone();
+                    menu.item(PopupMenuItem::new(label).checked(key_type == key_type_filter).on_click(
+                        move |_, _, cx| {
+                            let _ = server_state.update(cx, |state, cx| {
+                                state.set_key_type_filter(key_type, cx);
+                            });
+                        },
+                    ))
+                })
+            });
         // Search button (shows loading spinner during scan)
         let search_btn = Button::new("key-tree-search-btn")
             .ghost()
@@ -1285,7 +1509,7 @@ impl ZedisKeyTree {
             .px_0()
             .mr_2()
             .prefix(query_mode_dropdown)
-            .suffix(search_btn)
+            .suffix(h_flex().items_center().child(key_type_dropdown).child(search_btn))
             .cleanable(true);
         let local_filter_inputs = self
             .local_filters
@@ -1325,6 +1549,7 @@ impl ZedisKeyTree {
             .on_click(cx.listener(|this, _, _, cx| {
                 this.handle_refresh(cx);
             }));
+        let readonly = self.server_state.read(cx).is_readonly();
         h_flex()
             .p_2()
             .border_b_1()
@@ -1336,6 +1561,7 @@ impl ZedisKeyTree {
             .child(
                 Button::new("key-tree-add-btn")
                     .outline()
+                    .disabled(readonly)
                     .icon(CustomIconName::FilePlusCorner)
                     .on_click(cx.listener(|this, _, window, cx| {
                         this.handle_add_key(window, cx);
@@ -1345,12 +1571,405 @@ impl ZedisKeyTree {
                 Button::new("key-tree-import-btn")
                     .outline()
                     .tooltip(i18n_key_tree(cx, "import_keys_tooltip"))
+                    .disabled(readonly)
                     .icon(CustomIconName::FileInput)
                     .on_click(cx.listener(|this, _, _, cx| {
                         this.handle_import_keys(cx);
                     })),
             )
+            .child(self.render_bulk_menu(cx))
+    }
+
+    /// Renders the menu of the operations on every key matching a pattern.
+    fn render_bulk_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity().downgrade();
+        let bulk_running = self.server_state.read(cx).is_bulk_running();
+        // a replica browsed read-only can still be exported and copied elsewhere
+        let readonly = self.server_state.read(cx).is_readonly();
+        let servers = self.server_state.read(cx).servers().unwrap_or_default().to_vec();
+        DropdownButton::new("key-tree-bulk-dropdown")
+            .button(
+                Button::new("key-tree-bulk-btn")
+                    .outline()
+                    .tooltip(i18n_bulk(cx, "menu_tooltip"))
+                    .icon(IconName::EllipsisVertical)
+                    .disabled(bulk_running),
+            )
+            .dropdown_menu_with_anchor(Corner::TopRight, move |menu, window, cx| {
+                let delete_view = view.clone();
+                let ttl_view = view.clone();
+                let copy_view = view.clone();
+                let export_view = view.clone();
+                let import_view = view.clone();
+                let script_view = view.clone();
+                let servers = servers.clone();
+                menu.item(
+                    PopupMenuItem::new(i18n_bulk(cx, "delete_by_pattern"))
+                        .disabled(readonly)
+                        .on_click(move |_, window, cx| {
+                            let _ = delete_view.update(cx, |this, cx| this.handle_delete_by_pattern(window, cx));
+                        }),
+                )
+                .item(
+                    PopupMenuItem::new(i18n_bulk(cx, "update_ttl"))
+                        .disabled(readonly)
+                        .on_click(move |_, window, cx| {
+                            let _ = ttl_view.update(cx, |this, cx| this.handle_bulk_ttl(window, cx));
+                        }),
+                )
+                .item(
+                    PopupMenuItem::new(i18n_bulk(cx, "export_json")).on_click(move |_, window, cx| {
+                        let _ = export_view.update(cx, |this, cx| this.handle_export_json(window, cx));
+                    }),
+                )
+                .item(
+                    PopupMenuItem::new(i18n_bulk(cx, "import_json"))
+                        .disabled(readonly)
+                        .on_click(move |_, window, cx| {
+                            let _ = import_view.update(cx, |this, cx| this.handle_import_json(window, cx));
+                        }),
+                )
+                .item(
+                    PopupMenuItem::new(i18n_bulk(cx, "import_script"))
+                        .disabled(readonly)
+                        .on_click(move |_, window, cx| {
+                            let _ = script_view.update(cx, |this, cx| this.handle_import_script(window, cx));
+                        }),
+                )
+                .submenu(i18n_bulk(cx, "copy_to"), window, cx, move |menu, _, _| {
+                    servers.iter().fold(menu, |menu, server| {
+                        let view = copy_view.clone();
+                        let server = server.clone();
+                        menu.item(PopupMenuItem::new(server.name.clone()).on_click(move |_, window, cx| {
+                            let server = server.clone();
+                            let _ = view.update(cx, |this, cx| this.handle_copy_keys(server, window, cx));
+                        }))
+                    })
+                })
+            })
+    }
+
+    /// Asks for a pattern and counts the matching keys, the deletion is confirmed
+    /// once the dry run finishes.
+    fn handle_delete_by_pattern(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some(pattern) = values.first().map(|pattern| pattern.trim().to_string()) else {
+                return false;
+            };
+            if pattern.is_empty() {
+                return false;
+            }
+            server_state.update(cx, |state, cx| {
+                state.preview_delete_by_pattern(pattern.into(), cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+        let fields = vec![
+            FormField::new(i18n_bulk(cx, "pattern"))
+                .with_placeholder(i18n_bulk(cx, "pattern_placeholder"))
+                .with_focus()
+                .with_validate(|s| !s.trim().is_empty()),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_bulk(cx, "delete_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
     }
+
+    /// Asks for the keys (selection or pattern), the TTL and the mode of a bulk TTL update.
+    fn handle_bulk_ttl(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let selected_keys: Vec<SharedString> = server_state.read(cx).selected_keys().iter().cloned().collect();
+        let selected_count = selected_keys.len();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some((keys, values)) = parse_bulk_keys(&values, &selected_keys) else {
+                return false;
+            };
+            if values.len() != 3 {
+                return false;
+            }
+            let Some(mode) = radio_choice(&BULK_TTL_MODES, values.get(2)) else {
+                return false;
+            };
+            let action = if let BulkTtlMode::Persist = mode {
+                TtlAction::Persist
+            } else {
+                let Some(ttl_ms) = parse_bulk_ttl_ms(values[0].trim()).filter(|ttl_ms| *ttl_ms > 0) else {
+                    return false;
+                };
+                let jitter = values[1].trim();
+                let jitter_ms = if jitter.is_empty() {
+                    0
+                } else {
+                    let Some(jitter_ms) = parse_bulk_ttl_ms(jitter) else {
+                        return false;
+                    };
+                    jitter_ms
+                };
+                let jitter_ms = jitter_ms as u64;
+                match mode {
+                    BulkTtlMode::Set(condition) => TtlAction::Expire {
+                        ttl_ms: ttl_ms as u64,
+                        jitter_ms,
+                        condition,
+                    },
+                    BulkTtlMode::Extend => TtlAction::Adjust {
+                        delta_ms: ttl_ms,
+                        jitter_ms,
+                    },
+                    BulkTtlMode::Shorten => TtlAction::Adjust {
+                        delta_ms: -ttl_ms,
+                        jitter_ms,
+                    },
+                    BulkTtlMode::Persist => TtlAction::Persist,
+                }
+            };
+            server_state.update(cx, |state, cx| {
+                state.update_keys_ttl(keys, action, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        let mut fields = bulk_keys_fields(selected_count, cx);
+        fields.extend([
+            FormField::new(i18n_bulk(cx, "ttl"))
+                .with_placeholder(i18n_bulk(cx, "ttl_placeholder"))
+                .with_focus()
+                .with_validate(validate_bulk_ttl),
+            FormField::new(i18n_bulk(cx, "ttl_jitter"))
+                .with_placeholder(i18n_bulk(cx, "ttl_jitter_placeholder"))
+                .with_validate(validate_bulk_ttl),
+            FormField::new(i18n_bulk(cx, "ttl_mode")).with_options(radio_labels(&BULK_TTL_MODES, cx)),
+        ]);
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_bulk(cx, "ttl_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Asks for the keys, the database and the conflict policy of a copy to `target`.
+    fn handle_copy_keys(&mut self, target: RedisServer, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let selected_keys: Vec<SharedString> = server_state.read(cx).selected_keys().iter().cloned().collect();
+        let selected_count = selected_keys.len();
+        let target_server_id: SharedString = target.id.clone().into();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some((keys, values)) = parse_bulk_keys(&values, &selected_keys) else {
+                return false;
+            };
+            if values.len() != 2 {
+                return false;
+            }
+            let db = values[0].trim();
+            let Ok(db) = (if db.is_empty() { Ok(0) } else { db.parse::<usize>() }) else {
+                return false;
+            };
+            let Some(policy) = radio_choice(&CONFLICT_POLICIES, values.get(1)) else {
+                return false;
+            };
+            let target_server_id = target_server_id.clone();
+            server_state.update(cx, |state, cx| {
+                state.copy_keys(keys, target_server_id, db, policy, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        let mut fields = bulk_keys_fields(selected_count, cx);
+        fields.extend([
+            FormField::new(i18n_bulk(cx, "copy_db"))
+                .with_placeholder("0".into())
+                .with_focus()
+                .with_validate(|s| s.trim().is_empty() || s.trim().parse::<usize>().is_ok()),
+            FormField::new(i18n_bulk(cx, "copy_conflict")).with_options(radio_labels(&CONFLICT_POLICIES, cx)),
+        ]);
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        open_add_form_dialog(
+            FormDialog {
+                title: t!("bulk.copy_title", server = target.name, locale = locale)
+                    .to_string()
+                    .into(),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Asks for the keys and the layout of a logical export, then for the file.
+    fn handle_export_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let selected_keys: Vec<SharedString> = server_state.read(cx).selected_keys().iter().cloned().collect();
+        let selected_count = selected_keys.len();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some((keys, values)) = parse_bulk_keys(&values, &selected_keys) else {
+                return false;
+            };
+            let Some((format, extension)) = radio_choice(&EXPORT_FORMATS, values.first()) else {
+                return false;
+            };
+            let server_state = server_state.clone();
+            cx.spawn(async move |cx| {
+                let handle = rfd::AsyncFileDialog::new()
+                    .add_filter(extension.to_uppercase(), &[extension])
+                    .set_file_name(format!("redis_keys_export.{extension}"))
+                    .save_file()
+                    .await;
+                if let Some(file) = handle {
+                    let path = file.path().to_string_lossy().to_string();
+                    let _ = server_state.update(cx, |state, cx| {
+                        state.export_keys_logical(keys, path, format, cx);
+                    });
+                }
+            })
+            .detach();
+            window.close_dialog(cx);
+            true
+        });
+
+        let mut fields = bulk_keys_fields(selected_count, cx);
+        fields.push(FormField::new(i18n_bulk(cx, "json_format")).with_options(radio_labels(&EXPORT_FORMATS, cx)));
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_bulk(cx, "export_json_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Asks for a script of Redis commands and runs it.
+    fn handle_import_script(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        cx.spawn(async move |_, cx| {
+            let handle = rfd::AsyncFileDialog::new()
+                .add_filter("Redis", &["redis", "txt"])
+                .pick_file()
+                .await;
+            if let Some(file) = handle {
+                let path = file.path().to_string_lossy().to_string();
+                let _ = server_state.update(cx, |state, cx| {
+                    state.import_keys_script(path, cx);
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Asks how existing keys are handled, then for the JSON or NDJSON file to import.
+    fn handle_import_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some(policy) = radio_choice(&CONFLICT_POLICIES, values.first()) else {
+                return false;
+            };
+            let server_state = server_state.clone();
+            cx.spawn(async move |cx| {
+                let handle = rfd::AsyncFileDialog::new()
+                    .add_filter("JSON", &["json", "ndjson", "jsonl"])
+                    .pick_file()
+                    .await;
+                if let Some(file) = handle {
+                    let path = file.path().to_string_lossy().to_string();
+                    let _ = server_state.update(cx, |state, cx| {
+                        state.import_keys_json(path, policy, cx);
+                    });
+                }
+            })
+            .detach();
+            window.close_dialog(cx);
+            true
+        });
+        let fields =
+            vec![FormField::new(i18n_bulk(cx, "copy_conflict")).with_options(radio_labels(&CONFLICT_POLICIES, cx))];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_bulk(cx, "import_json_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+}
+
+/// Fields choosing the keys of a bulk operation: the selected keys, when there are
+/// some, or a pattern.
+fn bulk_keys_fields(selected_count: usize, cx: &App) -> Vec<FormField> {
+    let mut fields = Vec::with_capacity(2);
+    if selected_count > 0 {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let selection = t!("bulk.selected_keys", count = selected_count, locale = locale).to_string();
+        fields.push(
+            FormField::new(i18n_bulk(cx, "keys_target")).with_options(vec![selection.into(), i18n_bulk(cx, "pattern")]),
+        );
+    }
+    fields.push(FormField::new(i18n_bulk(cx, "pattern")).with_placeholder(i18n_bulk(cx, "pattern_placeholder")));
+    fields
+}
+
+/// TTL of the bulk TTL dialog in milliseconds, `None` above `i64::MAX` ms as the
+/// relative changes negate it.
+fn parse_bulk_ttl_ms(s: &str) -> Option<i64> {
+    let ttl_ms = u64::try_from(parse_ttl(s)?.as_millis()).ok()?;
+    i64::try_from(ttl_ms).ok()
+}
+
+fn validate_bulk_ttl(s: &str) -> bool {
+    let s = s.trim();
+    s.is_empty() || parse_bulk_ttl_ms(s).is_some()
+}
+
+/// Labels of radio options, in display order.
+fn radio_labels<T>(options: &[(T, &str)], cx: &App) -> Vec<SharedString> {
+    options.iter().map(|(_, label)| i18n_bulk(cx, label)).collect()
+}
+
+/// Option picked in a radio field, whose value is the position of the option.
+fn radio_choice<T: Copy>(options: &[(T, &str)], value: Option<&SharedString>) -> Option<T> {
+    let index = value?.parse::<usize>().ok()?;
+    options.get(index).map(|(option, _)| *option)
+}
+
+/// Reads the keys chosen with `bulk_keys_fields`, returned with the values of the
+/// following fields.
+fn parse_bulk_keys<'a>(
+    values: &'a [SharedString],
+    selected_keys: &[SharedString],
+) -> Option<(BulkKeys, &'a [SharedString])> {
+    // The target field is only shown when keys are selected
+    let (use_selection, values) = if selected_keys.is_empty() {
+        (false, values)
+    } else {
+        let (target, values) = values.split_first()?;
+        (target == "0", values)
+    };
+    let (pattern, values) = values.split_first()?;
+    if use_selection {
+        return Some((BulkKeys::Keys(selected_keys.to_vec()), values));
+    }
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return None;
+    }
+    Some((BulkKeys::Pattern(pattern.to_string()), values))
 }
 
 impl Render for ZedisKeyTree {
@@ -1474,4 +2093,33 @@ mod tests {
 
         assert!(visible_key_range(&items, &ss("stale"), &ss("current")).is_none());
     }
+
+    #[test]
+    fn parses_bulk_keys_from_selection_or_pattern() {
+        let selected = vec![ss("a"), ss("b")];
+        let values = [ss("0"), ss(""), ss("10")];
+        let (keys, rest) = parse_bulk_keys(&values, &selected).expect("selection");
+        assert!(matches!(keys, BulkKeys::Keys(keys) if keys.len() == 2));
+        assert_eq!(rest, &[ss("10")]);
+
+        let (keys, _) = parse_bulk_keys(&[ss("1"), ss(" user:* "), ss("10")], &selected).expect("pattern");
+        assert!(matches!(keys, BulkKeys::Pattern(pattern) if pattern == "user:*"));
+
+        // Without selection there is no target field and the pattern is required
+        let values = [ss("user:*"), ss("10")];
+        let (keys, rest) = parse_bulk_keys(&values, &[]).expect("pattern");
+        assert!(matches!(keys, BulkKeys::Pattern(_)));
+        assert_eq!(rest, &[ss("10")]);
+        assert!(parse_bulk_keys(&[ss(""), ss("10")], &[]).is_none());
+    }
+
+    #[test]
+    fn bounds_bulk_ttl() {
+        assert_eq!(parse_bulk_ttl_ms("10"), Some(10_000));
+        assert_eq!(parse_bulk_ttl_ms("1h"), Some(3_600_000));
+        // More than i64::MAX milliseconds would change sign once negated or sent
+        assert_eq!(parse_bulk_ttl_ms(&u64::MAX.to_string()), None);
+        assert!(validate_bulk_ttl(""));
+        assert!(!validate_bulk_ttl(&u64::MAX.to_string()));
+    }
 }
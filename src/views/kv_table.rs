@@ -228,6 +228,7 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
                 | ServerEvent::ValueAdded(_)
                 | ServerEvent::ValueUpdated(_) => {
                     let fetcher = Self::new_values(server_state.clone(), cx);
+                    let readonly = server_state.read(cx).is_readonly();
                     let was_loading = this.loading;
                     this.loading = false;
                     this.done = fetcher.is_done();
@@ -235,6 +236,7 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
                     this.total_count = fetcher.count();
                     this.table_state.update(cx, |state, cx| {
                         state.delegate_mut().set_fetcher(fetcher);
+                        state.delegate_mut().set_readonly(readonly);
                         if matches!(
                             event,
                             ServerEvent::ValueLoaded(_) | ServerEvent::ValueAdded(_) | ServerEvent::ValueUpdated(_)
@@ -287,17 +289,19 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
         }));
 
         // Initialize table data and state
+        let readonly = server_state.read(cx).is_readonly();
         let fetcher = Self::new_values(server_state, cx);
         let done = fetcher.is_done();
         let items_count = fetcher.rows_count();
         let total_count = fetcher.count();
         let content_width = Self::initial_content_width(window, cx);
-        let delegate = ZedisKvDelegate::new(
+        let mut delegate = ZedisKvDelegate::new(
             Self::new_columns(columns.clone(), content_width, cx),
             fetcher,
             window,
             cx,
         );
+        delegate.set_readonly(readonly);
         let table_state = cx.new(|cx| TableState::new(delegate, window, cx));
 
         info!("Creating new key value table view");
@@ -407,6 +411,7 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
             self.suppress_input_filter = false;
         }
 
+        let readonly = self.table_state.read(cx).delegate().is_readonly();
         // Handler for adding new values
         let handle_add_value = cx.listener(|this, _, window, cx| {
             this.table_state.update(cx, |state, cx| {
@@ -473,6 +478,7 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
                             .child(
                                 Button::new("add-value-btn")
                                     .icon(CustomIconName::FilePlusCorner)
+                                    .disabled(readonly)
                                     .tooltip(i18n_kv_table(cx, "add_value_tooltip"))
                                     .on_click(handle_add_value),
                             )
@@ -0,0 +1,534 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory analyzer panel.
+//!
+//! Runs the memory analysis of the keys matching a pattern and shows the memory by type
+//! as a proportional strip, the memory by key prefix as bars that drill down into the
+//! prefix levels, and the biggest keys of each type. Keys open in the value editor and
+//! prefixes can be listed in the key tree. The report is exported as CSV or JSON.
+
+use crate::{
+    connection::{DEFAULT_TOP_KEYS, MemoryReport},
+    helpers::{format_duration, get_font_family},
+    states::{ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_memory},
+};
+use chrono::Local;
+use gpui::{AnyElement, App, Entity, Hsla, SharedString, Subscription, Window, div, prelude::*, px, relative};
+use gpui_component::{
+    ActiveTheme, Disableable, IconName, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    label::Label,
+    v_flex,
+};
+use rust_i18n::t;
+use std::{sync::Arc, time::Duration};
+
+const ROW_HEIGHT: f32 = 28.0;
+const TYPE_STRIP_HEIGHT: f32 = 18.0;
+const TOP_INPUT_WIDTH: f32 = 72.0;
+const NAME_WIDTH: f32 = 220.0;
+const COUNT_WIDTH: f32 = 80.0;
+const MEMORY_WIDTH: f32 = 80.0;
+const TTL_WIDTH: f32 = 60.0;
+/// Prefixes listed at most for a level, the smallest ones are summarized
+const MAX_PREFIX_ROWS: usize = 200;
+
+fn format_memory(bytes: u64) -> SharedString {
+    humansize::format_size(bytes, humansize::DECIMAL).into()
+}
+
+/// Returns the share of `value` in `total`, between 0 and 1.
+fn ratio(value: u64, total: u64) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        (value as f64 / total as f64).clamp(0.0, 1.0) as f32
+    }
+}
+
+fn type_color(index: usize, cx: &App) -> Hsla {
+    let theme = cx.theme();
+    match index % 5 {
+        0 => theme.chart_1,
+        1 => theme.chart_2,
+        2 => theme.chart_3,
+        3 => theme.chart_4,
+        _ => theme.chart_5,
+    }
+}
+
+/// Renders a bar filling `ratio` of the available width.
+fn render_bar(ratio: f32, color: Hsla) -> impl IntoElement {
+    div()
+        .flex_1()
+        .h(px(8.0))
+        .child(div().h_full().w(relative(ratio)).rounded_sm().bg(color))
+}
+
+pub struct ZedisMemoryPanel {
+    server_state: Entity<ZedisServerState>,
+    pattern_input: Entity<InputState>,
+    top_keys_input: Entity<InputState>,
+    /// Prefix whose sub-prefixes are shown, the first level when empty
+    prefix: String,
+    /// Type of the biggest keys shown, the largest type when `None`
+    key_type: Option<String>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisMemoryPanel {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let pattern_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_memory(cx, "pattern_placeholder")));
+        let top_keys_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_memory(cx, "top_keys"))
+                .default_value(DEFAULT_TOP_KEYS.to_string())
+        });
+        let subscriptions = vec![
+            cx.subscribe(&server_state, |this, _, event, cx| match event {
+                // A new report starts at the first prefix level and the largest type
+                ServerEvent::MemoryAnalyzed(report) => {
+                    this.prefix.clear();
+                    this.key_type = report.types.first().map(|item| item.key_type.clone());
+                    cx.notify();
+                }
+                ServerEvent::ServerSelected(_, _) => {
+                    this.prefix.clear();
+                    this.key_type = None;
+                    cx.notify();
+                }
+                _ => {}
+            }),
+            cx.observe(&server_state, |_, _, cx| cx.notify()),
+        ];
+        Self {
+            server_state,
+            pattern_input,
+            top_keys_input,
+            prefix: String::new(),
+            key_type: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Focuses the pattern input.
+    pub fn focus(&self, window: &mut Window, cx: &mut Context<Self>) {
+        self.pattern_input.update(cx, |state, cx| state.focus(window, cx));
+    }
+
+    fn analyze(&mut self, cx: &mut Context<Self>) {
+        let pattern = self.pattern_input.read(cx).value();
+        let top_keys = self
+            .top_keys_input
+            .read(cx)
+            .value()
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_TOP_KEYS);
+        self.server_state.update(cx, |state, cx| {
+            state.analyze_memory(pattern, top_keys, cx);
+        });
+    }
+
+    fn export(&mut self, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let file_name = format!("memory-{}.csv", Local::now().format("%Y%m%d-%H%M%S"));
+        cx.spawn(async move |_, cx| {
+            let handle = rfd::AsyncFileDialog::new()
+                .add_filter("CSV", &["csv"])
+                .add_filter("JSON", &["json"])
+                .set_file_name(file_name)
+                .save_file()
+                .await;
+            if let Some(file) = handle {
+                let path = file.path().to_path_buf();
+                let _ = server_state.update(cx, |state, cx| {
+                    state.export_memory_report(path, cx);
+                });
+            }
+        })
+        .detach();
+    }
+
+    fn set_prefix(&mut self, prefix: String, cx: &mut Context<Self>) {
+        self.prefix = prefix;
+        cx.notify();
+    }
+
+    fn render_toolbar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let state = self.server_state.read(cx);
+        let analyzing = state.is_memory_analyzing();
+        let has_report = state.memory_report().is_some();
+        h_flex()
+            .p_2()
+            .gap_2()
+            .items_center()
+            .child(
+                div()
+                    .flex_1()
+                    .child(Input::new(&self.pattern_input).small().cleanable(true)),
+            )
+            .child(
+                div()
+                    .w(px(TOP_INPUT_WIDTH))
+                    .child(Input::new(&self.top_keys_input).small()),
+            )
+            .child(
+                Button::new("memory-analyze")
+                    .outline()
+                    .small()
+                    .icon(IconName::ChartPie)
+                    .label(i18n_memory(cx, "analyze"))
+                    .loading(analyzing)
+                    .disabled(analyzing)
+                    .on_click(cx.listener(|this, _, _, cx| this.analyze(cx))),
+            )
+            .child(
+                Button::new("memory-export")
+                    .outline()
+                    .small()
+                    .label(i18n_memory(cx, "export"))
+                    .disabled(!has_report || analyzing)
+                    .on_click(cx.listener(|this, _, _, cx| this.export(cx))),
+            )
+    }
+
+    /// Renders the totals and the memory of each type as a proportional strip.
+    fn render_summary(&self, report: &MemoryReport, cx: &Context<Self>) -> impl IntoElement {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let muted = cx.theme().muted_foreground;
+        let summary = t!(
+            "memory.summary",
+            keys = report.keys,
+            memory = format_memory(report.memory),
+            expiring = report.expiring,
+            pattern = report.pattern,
+            locale = locale
+        )
+        .to_string();
+        let mut notes = vec![];
+        if report.cancelled {
+            notes.push(i18n_memory(cx, "partial"));
+        }
+        if report.prefixes_truncated {
+            notes.push(i18n_memory(cx, "prefixes_truncated"));
+        }
+        v_flex()
+            .px_2()
+            .pb_2()
+            .gap_1()
+            .child(
+                h_flex().gap_2().child(Label::new(summary).text_sm()).children(
+                    notes
+                        .into_iter()
+                        .map(|note| Label::new(note).text_xs().text_color(cx.theme().warning)),
+                ),
+            )
+            .child(
+                h_flex()
+                    .h(px(TYPE_STRIP_HEIGHT))
+                    .w_full()
+                    .rounded_sm()
+                    .overflow_hidden()
+                    .bg(cx.theme().muted)
+                    .children(report.types.iter().enumerate().map(|(index, item)| {
+                        div()
+                            .h_full()
+                            .w(relative(ratio(item.memory, report.memory)))
+                            .bg(type_color(index, cx))
+                    })),
+            )
+            .child(
+                h_flex()
+                    .flex_wrap()
+                    .gap_3()
+                    .children(report.types.iter().enumerate().map(|(index, item)| {
+                        h_flex()
+                            .gap_1()
+                            .items_center()
+                            .child(div().size(px(8.0)).rounded_sm().bg(type_color(index, cx)))
+                            .child(
+                                Label::new(format!(
+                                    "{} {} · {}",
+                                    item.key_type,
+                                    format_memory(item.memory),
+                                    item.keys
+                                ))
+                                .text_xs()
+                                .text_color(muted),
+                            )
+                    })),
+            )
+    }
+
+    fn render_prefixes(&self, report: &Arc<MemoryReport>, cx: &Context<Self>) -> impl IntoElement {
+        let muted = cx.theme().muted_foreground;
+        let color = cx.theme().chart_2;
+        let font_family: SharedString = get_font_family().into();
+        let parent_memory = report
+            .prefixes
+            .iter()
+            .find(|item| item.prefix == self.prefix)
+            .map(|item| item.memory)
+            .unwrap_or(report.memory);
+        let children = report.child_prefixes(&self.prefix);
+
+        // Breadcrumb of the parent prefixes, the root lists the first level
+        let mut crumbs = vec![(i18n_memory(cx, "all_keys"), String::new())];
+        if !self.prefix.is_empty() {
+            crumbs.extend(
+                report
+                    .prefixes
+                    .iter()
+                    .filter(|item| self.prefix.starts_with(&item.prefix))
+                    .map(|item| (SharedString::from(item.prefix.clone()), item.prefix.clone())),
+            );
+            crumbs[1..].sort_by_key(|(_, prefix)| prefix.len());
+        }
+        let breadcrumb = h_flex()
+            .gap_1()
+            .items_center()
+            .children(crumbs.into_iter().enumerate().map(|(index, (label, prefix))| {
+                Button::new(("memory-crumb", index))
+                    .ghost()
+                    .xsmall()
+                    .label(label)
+                    .on_click(cx.listener(move |this, _, _, cx| this.set_prefix(prefix.clone(), cx)))
+            }));
+
+        let rows = if children.is_empty() {
+            vec![
+                Label::new(i18n_memory(cx, "no_prefixes"))
+                    .text_sm()
+                    .text_color(muted)
+                    .into_any_element(),
+            ]
+        } else {
+            let hidden = children.len().saturating_sub(MAX_PREFIX_ROWS);
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let mut rows: Vec<AnyElement> = children
+                .into_iter()
+                .take(MAX_PREFIX_ROWS)
+                .enumerate()
+                .map(|(row, item)| {
+                    let prefix = item.prefix.clone();
+                    let filter_prefix = SharedString::from(item.prefix.clone());
+                    let has_children = item.children > 0;
+                    h_flex()
+                        .id(("memory-prefix", row))
+                        .h(px(ROW_HEIGHT))
+                        .px_2()
+                        .gap_2()
+                        .w_full()
+                        .items_center()
+                        .when(has_children, |this| {
+                            this.cursor_pointer()
+                                .hover(|this| this.bg(cx.theme().list_hover))
+                                .on_click(cx.listener(move |this, _, _, cx| this.set_prefix(prefix.clone(), cx)))
+                        })
+                        .child(
+                            Label::new(item.prefix.clone())
+                                .text_sm()
+                                .font_family(font_family.clone())
+                                .w(px(NAME_WIDTH))
+                                .truncate(),
+                        )
+                        .child(render_bar(ratio(item.memory, parent_memory), color))
+                        .child(Label::new(format_memory(item.memory)).text_xs().w(px(MEMORY_WIDTH)))
+                        .child(
+                            Label::new(item.keys.to_string())
+                                .text_xs()
+                                .text_color(muted)
+                                .w(px(COUNT_WIDTH)),
+                        )
+                        .child(
+                            Button::new(("memory-prefix-keys", row))
+                                .ghost()
+                                .xsmall()
+                                .icon(IconName::Search)
+                                .tooltip(i18n_memory(cx, "show_keys"))
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    let prefix = filter_prefix.clone();
+                                    this.server_state.update(cx, |state, cx| {
+                                        state.filter_keys_by_prefix(prefix, cx);
+                                    });
+                                })),
+                        )
+                        .into_any_element()
+                })
+                .collect();
+            if hidden > 0 {
+                let message = t!("memory.more_prefixes", count = hidden, locale = locale).to_string();
+                rows.push(Label::new(message).text_xs().text_color(muted).into_any_element());
+            }
+            rows
+        };
+
+        v_flex()
+            .id("memory-prefixes")
+            .flex_1()
+            .h_full()
+            .p_2()
+            .gap_1()
+            .overflow_y_scroll()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(Label::new(i18n_memory(cx, "prefixes")).text_sm())
+                    .child(breadcrumb),
+            )
+            .children(rows)
+    }
+
+    fn render_top_keys(&self, report: &Arc<MemoryReport>, cx: &Context<Self>) -> impl IntoElement {
+        let muted = cx.theme().muted_foreground;
+        let font_family: SharedString = get_font_family().into();
+        let current_type = self
+            .key_type
+            .clone()
+            .or_else(|| report.types.first().map(|item| item.key_type.clone()))
+            .unwrap_or_default();
+        let type_index = report
+            .types
+            .iter()
+            .position(|item| item.key_type == current_type)
+            .unwrap_or_default();
+        let color = type_color(type_index, cx);
+        let keys = report.top_keys_of(&current_type);
+        let max_memory = keys.first().map(|item| item.memory).unwrap_or_default();
+
+        let tabs = h_flex()
+            .gap_1()
+            .flex_wrap()
+            .children(report.types.iter().enumerate().map(|(index, item)| {
+                let key_type = item.key_type.clone();
+                let button = Button::new(("memory-type", index))
+                    .xsmall()
+                    .label(item.key_type.clone())
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.key_type = Some(key_type.clone());
+                        cx.notify();
+                    }));
+                if item.key_type == current_type {
+                    button.primary()
+                } else {
+                    button.ghost()
+                }
+            }));
+
+        let rows = keys.into_iter().enumerate().map(|(row, item)| {
+            let key = SharedString::from(item.key.clone());
+            let ttl = item
+                .ttl_ms
+                .map(|ttl| format_duration(Duration::from_millis(ttl)))
+                .unwrap_or_default();
+            h_flex()
+                .id(("memory-key", row))
+                .h(px(ROW_HEIGHT))
+                .px_2()
+                .gap_2()
+                .w_full()
+                .items_center()
+                .cursor_pointer()
+                .hover(|this| this.bg(cx.theme().list_hover))
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    let key = key.clone();
+                    this.server_state.update(cx, |state, cx| {
+                        state.select_key(key, cx);
+                    });
+                }))
+                .child(
+                    Label::new(item.key.clone())
+                        .text_sm()
+                        .font_family(font_family.clone())
+                        .w(px(NAME_WIDTH))
+                        .truncate(),
+                )
+                .child(render_bar(ratio(item.memory, max_memory), color))
+                .child(Label::new(format_memory(item.memory)).text_xs().w(px(MEMORY_WIDTH)))
+                .child(
+                    Label::new(item.elements.to_string())
+                        .text_xs()
+                        .text_color(muted)
+                        .w(px(COUNT_WIDTH)),
+                )
+                .child(Label::new(ttl).text_xs().text_color(muted).w(px(TTL_WIDTH)))
+        });
+
+        v_flex()
+            .id("memory-top-keys")
+            .flex_1()
+            .h_full()
+            .p_2()
+            .gap_1()
+            .border_l_1()
+            .border_color(cx.theme().border)
+            .overflow_y_scroll()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(Label::new(i18n_memory(cx, "top_keys_title")).text_sm())
+                    .child(tabs),
+            )
+            .children(rows)
+    }
+
+    fn render_report(&self, cx: &Context<Self>) -> AnyElement {
+        let Some(report) = self.server_state.read(cx).memory_report() else {
+            let message = if self.server_state.read(cx).is_memory_analyzing() {
+                i18n_memory(cx, "analyzing")
+            } else {
+                i18n_memory(cx, "no_report")
+            };
+            return v_flex()
+                .flex_1()
+                .items_center()
+                .justify_center()
+                .child(Label::new(message).text_sm().text_color(cx.theme().muted_foreground))
+                .into_any_element();
+        };
+        v_flex()
+            .flex_1()
+            .w_full()
+            .child(self.render_summary(&report, cx))
+            .child(
+                h_flex()
+                    .flex_1()
+                    .w_full()
+                    .border_t_1()
+                    .border_color(cx.theme().border)
+                    .child(self.render_prefixes(&report, cx))
+                    .child(self.render_top_keys(&report, cx)),
+            )
+            .into_any_element()
+    }
+}
+
+impl Render for ZedisMemoryPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w_full()
+            .h_full()
+            .child(self.render_toolbar(cx))
+            .child(self.render_report(cx))
+    }
+}
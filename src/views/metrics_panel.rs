@@ -0,0 +1,362 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Live metrics dashboard.
+//!
+//! Samples `INFO ALL` of every master at a configurable interval while the dashboard is
+//! running and charts ops/sec, memory, hit rate, clients, network, evictions and
+//! fragmentation for the whole server or a single cluster node.
+
+use crate::states::{
+    METRICS_INTERVALS, Metric, ServerEvent, ServerPanel, ZedisGlobalStore, ZedisServerState, i18n_metrics,
+};
+use chrono::{Local, TimeZone};
+use gpui::{AnyElement, App, Corner, Entity, Hsla, SharedString, Subscription, Task, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Disableable, IconName, Sizable,
+    button::{Button, DropdownButton},
+    chart::LineChart,
+    h_flex,
+    label::Label,
+    menu::PopupMenuItem,
+    v_flex,
+};
+use rust_i18n::t;
+use std::time::Duration;
+
+const CHART_HEIGHT: f32 = 140.0;
+const CHART_MIN_WIDTH: f32 = 320.0;
+/// Number of time labels shown on the x axis
+const X_LABELS: usize = 4;
+
+fn format_metric(metric: Metric, value: f64) -> String {
+    match metric {
+        Metric::UsedMemory => humansize::format_size(value as u64, humansize::DECIMAL),
+        Metric::HitRate => format!("{value:.1}%"),
+        Metric::InputKbps | Metric::OutputKbps => format!("{value:.2} KB/s"),
+        Metric::Evictions => format!("{value:.2}/s"),
+        Metric::Fragmentation => format!("{value:.2}"),
+        Metric::OpsPerSec | Metric::ConnectedClients => format!("{value:.0}"),
+    }
+}
+
+fn chart_color(metric: Metric, cx: &App) -> Hsla {
+    let theme = cx.theme();
+    match metric {
+        Metric::OpsPerSec | Metric::Evictions => theme.chart_1,
+        Metric::UsedMemory | Metric::Fragmentation => theme.chart_2,
+        Metric::HitRate => theme.chart_3,
+        Metric::ConnectedClients => theme.chart_4,
+        Metric::InputKbps | Metric::OutputKbps => theme.chart_5,
+    }
+}
+
+pub struct ZedisMetricsPanel {
+    server_state: Entity<ZedisServerState>,
+    /// Sampling interval in seconds
+    interval: u64,
+    /// Node charted, every node combined when `None`
+    node: Option<usize>,
+    /// Running while metrics are sampled
+    sampling_task: Option<Task<()>>,
+    /// Sampling was paused by the user
+    paused: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisMetricsPanel {
+    pub fn new(server_state: Entity<ZedisServerState>, cx: &mut Context<Self>) -> Self {
+        let subscriptions = vec![
+            cx.subscribe(&server_state, |this, _, event, cx| match event {
+                // Sampling starts the first time the dashboard is opened and keeps running
+                // in the background to fill the history
+                ServerEvent::PanelToggled(ServerPanel::Metrics) if this.sampling_task.is_none() && !this.paused => {
+                    this.start_sampling(cx);
+                }
+                ServerEvent::ServerSelected(_, _) => {
+                    this.sampling_task = None;
+                    this.paused = false;
+                    this.node = None;
+                    cx.notify();
+                }
+                _ => {}
+            }),
+            cx.observe(&server_state, |_, _, cx| cx.notify()),
+        ];
+        Self {
+            server_state,
+            interval: METRICS_INTERVALS[1],
+            node: None,
+            sampling_task: None,
+            paused: false,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn start_sampling(&mut self, cx: &mut Context<Self>) {
+        let interval = Duration::from_secs(self.interval);
+        let server_state = self.server_state.clone();
+        self.sampling_task = Some(cx.spawn(async move |_, cx| {
+            loop {
+                let _ = server_state.update(cx, |state, cx| {
+                    state.sample_metrics(cx);
+                });
+                cx.background_executor().timer(interval).await;
+            }
+        }));
+    }
+
+    fn set_interval(&mut self, interval: u64, cx: &mut Context<Self>) {
+        self.interval = interval;
+        if self.sampling_task.is_some() {
+            self.start_sampling(cx);
+        }
+        cx.notify();
+    }
+
+    fn set_node(&mut self, node: Option<usize>, cx: &mut Context<Self>) {
+        self.node = node;
+        cx.notify();
+    }
+
+    fn toggle_pause(&mut self, cx: &mut Context<Self>) {
+        self.paused = !self.paused;
+        if self.paused {
+            self.sampling_task = None;
+        } else {
+            self.start_sampling(cx);
+        }
+        cx.notify();
+    }
+
+    fn clear(&mut self, cx: &mut Context<Self>) {
+        self.server_state.update(cx, |state, cx| {
+            state.clear_metrics(cx);
+        });
+    }
+
+    fn export(&mut self, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let file_name = format!("metrics-{}.csv", Local::now().format("%Y%m%d-%H%M%S"));
+        cx.spawn(async move |_, cx| {
+            let handle = rfd::AsyncFileDialog::new()
+                .add_filter("CSV", &["csv"])
+                .set_file_name(file_name)
+                .save_file()
+                .await;
+            if let Some(file) = handle {
+                let path = file.path().to_path_buf();
+                let _ = server_state.update(cx, |state, cx| {
+                    state.export_metrics_csv(path, cx);
+                });
+            }
+        })
+        .detach();
+    }
+
+    fn render_interval_selector(&self, cx: &Context<Self>) -> impl IntoElement {
+        let current = self.interval;
+        let panel = cx.entity().downgrade();
+        DropdownButton::new("metrics-interval-dropdown")
+            .button(
+                Button::new("metrics-interval-btn")
+                    .outline()
+                    .small()
+                    .label(format!("{current}s"))
+                    .tooltip(i18n_metrics(cx, "interval_tooltip")),
+            )
+            .dropdown_menu_with_anchor(Corner::TopLeft, move |menu, _, _| {
+                METRICS_INTERVALS.iter().fold(menu, |menu, interval| {
+                    let panel = panel.clone();
+                    let interval = *interval;
+                    menu.item(
+                        PopupMenuItem::new(format!("{interval}s"))
+                            .checked(interval == current)
+                            .on_click(move |_, _, cx| {
+                                let _ = panel.update(cx, |this, cx| this.set_interval(interval, cx));
+                            }),
+                    )
+                })
+            })
+    }
+
+    /// Renders the node selector, only shown when there are several masters.
+    fn render_node_selector(&self, nodes: &[SharedString], cx: &Context<Self>) -> Option<AnyElement> {
+        if nodes.len() < 2 {
+            return None;
+        }
+        let all_nodes = i18n_metrics(cx, "all_nodes");
+        let label = self
+            .node
+            .and_then(|index| nodes.get(index).cloned())
+            .unwrap_or_else(|| all_nodes.clone());
+        let current = self.node;
+        let nodes = nodes.to_vec();
+        let panel = cx.entity().downgrade();
+        let dropdown = DropdownButton::new("metrics-node-dropdown")
+            .button(Button::new("metrics-node-btn").outline().small().label(label))
+            .dropdown_menu_with_anchor(Corner::TopLeft, move |menu, _, _| {
+                let mut targets = vec![(None, all_nodes.clone())];
+                targets.extend(
+                    nodes
+                        .iter()
+                        .enumerate()
+                        .map(|(index, node)| (Some(index), node.clone())),
+                );
+                targets.into_iter().fold(menu, |menu, (node, label)| {
+                    let panel = panel.clone();
+                    menu.item(
+                        PopupMenuItem::new(label)
+                            .checked(node == current)
+                            .on_click(move |_, _, cx| {
+                                let _ = panel.update(cx, |this, cx| this.set_node(node, cx));
+                            }),
+                    )
+                })
+            });
+        Some(dropdown.into_any_element())
+    }
+
+    fn render_chart(&self, metric: Metric, points: Vec<(i64, f64)>, cx: &Context<Self>) -> impl IntoElement {
+        let muted = cx.theme().muted_foreground;
+        let current = points.last().map(|(_, value)| format_metric(metric, *value));
+        let max = points
+            .iter()
+            .map(|(_, value)| *value)
+            .fold(None, |max: Option<f64>, value| {
+                Some(max.map_or(value, |max| max.max(value)))
+            });
+        let tick_margin = (points.len() / X_LABELS).max(1);
+        let color = chart_color(metric, cx);
+        v_flex()
+            .flex_1()
+            .min_w(px(CHART_MIN_WIDTH))
+            .p_2()
+            .gap_1()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(i18n_metrics(cx, metric.as_str())).text_sm().flex_1())
+                    .when_some(current, |this, current| this.child(Label::new(current).text_sm()))
+                    .when_some(max, |this, max| {
+                        this.child(
+                            Label::new(format!("{} {}", i18n_metrics(cx, "max"), format_metric(metric, max)))
+                                .text_xs()
+                                .text_color(muted),
+                        )
+                    }),
+            )
+            .child(
+                div().h(px(CHART_HEIGHT)).w_full().child(
+                    LineChart::new(points)
+                        .x(|(timestamp, _)| {
+                            Local
+                                .timestamp_millis_opt(*timestamp)
+                                .single()
+                                .map(|time| time.format("%H:%M:%S").to_string())
+                                .unwrap_or_default()
+                        })
+                        .y(|(_, value)| *value)
+                        .stroke(color)
+                        .tick_margin(tick_margin),
+                ),
+            )
+    }
+}
+
+impl Render for ZedisMetricsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let metrics = self.server_state.read(cx).metrics();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let samples = t!("metrics.samples", count = metrics.samples.len(), locale = locale).to_string();
+        let node_selector = self.render_node_selector(&metrics.nodes, cx);
+        let pause_label = if self.paused || self.sampling_task.is_none() {
+            i18n_metrics(cx, "resume")
+        } else {
+            i18n_metrics(cx, "pause")
+        };
+        let charts: Vec<_> = Metric::all()
+            .iter()
+            .map(|metric| {
+                let points = metrics.series(*metric, self.node);
+                self.render_chart(*metric, points, cx)
+            })
+            .collect();
+        let content = if metrics.samples.is_empty() {
+            v_flex()
+                .flex_1()
+                .items_center()
+                .justify_center()
+                .child(
+                    Label::new(i18n_metrics(cx, "no_samples"))
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .into_any_element()
+        } else {
+            div()
+                .id("metrics-charts")
+                .flex_1()
+                .overflow_y_scroll()
+                .child(h_flex().p_2().gap_2().flex_wrap().children(charts))
+                .into_any_element()
+        };
+
+        v_flex()
+            .w_full()
+            .h_full()
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .items_center()
+                    .child(self.render_interval_selector(cx))
+                    .children(node_selector)
+                    .child(
+                        Label::new(samples)
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .flex_1(),
+                    )
+                    .child(
+                        Button::new("metrics-pause-btn")
+                            .outline()
+                            .small()
+                            .label(pause_label)
+                            .on_click(cx.listener(|this, _, _, cx| this.toggle_pause(cx))),
+                    )
+                    .child(
+                        Button::new("metrics-clear-btn")
+                            .outline()
+                            .small()
+                            .icon(IconName::Delete)
+                            .label(i18n_metrics(cx, "clear"))
+                            .on_click(cx.listener(|this, _, _, cx| this.clear(cx))),
+                    )
+                    .child(
+                        Button::new("metrics-export-btn")
+                            .outline()
+                            .small()
+                            .icon(IconName::File)
+                            .label(i18n_metrics(cx, "export"))
+                            .disabled(metrics.samples.is_empty())
+                            .on_click(cx.listener(|this, _, _, cx| this.export(cx))),
+                    ),
+            )
+            .child(content)
+    }
+}
@@ -0,0 +1,755 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redis Pub/Sub panel.
+//!
+//! This module provides the monitor shown in place of the value editor:
+//! - SUBSCRIBE/PSUBSCRIBE/SSUBSCRIBE on a dedicated session, also over SSH tunnels
+//! - A virtualised message log with timestamps and decoded payloads
+//! - Pause, clear and NDJSON export of the log
+//! - Active channels (PUBSUB CHANNELS/NUMSUB) and publishing of test messages
+
+use crate::{
+    connection::{PubSubMessage, PubSubSession, SubscriptionKind},
+    error::Error,
+    helpers::{codec::CompressionFormat, get_font_family},
+    states::{NotificationAction, ServerEvent, ZedisGlobalStore, ZedisServerState, decode_pubsub_payload, i18n_pubsub},
+};
+use chrono::{Local, TimeZone};
+use futures::{StreamExt, channel::mpsc::UnboundedReceiver, lock::Mutex};
+use gpui::{
+    AnyElement, App, Corner, Entity, ScrollStrategy, SharedString, Subscription, Task, UniformListScrollHandle, Window,
+    div, prelude::*, px, uniform_list,
+};
+use gpui_component::{
+    ActiveTheme, Disableable, IconName, Selectable, Sizable,
+    button::{Button, ButtonVariants, DropdownButton},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    menu::PopupMenuItem,
+    v_flex,
+};
+use rust_i18n::t;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Maximum number of messages kept in the log (and buffered while paused)
+const MAX_MESSAGES: usize = 10_000;
+/// Maximum number of messages decoded in one batch
+const MAX_BATCH: usize = 500;
+/// Maximum number of characters shown in the single line preview
+const MAX_PREVIEW_CHARS: usize = 200;
+/// Maximum number of lines shown for the selected message
+const MAX_DETAIL_LINES: usize = 500;
+const ROW_HEIGHT: f32 = 28.0;
+const CHANNELS_WIDTH: f32 = 240.0;
+
+/// A received message with its display fields computed once.
+#[derive(Debug, Clone)]
+struct PubSubEntry {
+    message: PubSubMessage,
+    time: SharedString,
+    /// The channel, prefixed by the pattern for pattern subscriptions
+    source: SharedString,
+    format: SharedString,
+    text: SharedString,
+    preview: SharedString,
+}
+
+impl PubSubEntry {
+    fn new(message: PubSubMessage) -> Self {
+        let time = Local
+            .timestamp_millis_opt(message.received_at)
+            .single()
+            .map(|time| time.format("%H:%M:%S%.3f").to_string())
+            .unwrap_or_default();
+        let channel = String::from_utf8_lossy(&message.channel);
+        let source = match &message.pattern {
+            Some(pattern) => format!("{} → {channel}", String::from_utf8_lossy(pattern)),
+            None => channel.to_string(),
+        };
+        let (compression, format, text) = decode_pubsub_payload(&message.payload);
+        let format = if compression == CompressionFormat::None {
+            format.as_str().to_string()
+        } else {
+            format!("{}/{}", compression.as_str(), format.as_str())
+        };
+        let head: String = text.chars().take(MAX_PREVIEW_CHARS * 2).collect();
+        let mut preview = head.split_whitespace().collect::<Vec<_>>().join(" ");
+        if let Some((index, _)) = preview.char_indices().nth(MAX_PREVIEW_CHARS) {
+            preview.truncate(index);
+            preview.push('…');
+        }
+        Self {
+            message,
+            time: time.into(),
+            source: source.into(),
+            format: format.into(),
+            text: text.into(),
+            preview: preview.into(),
+        }
+    }
+}
+
+fn kind_label(kind: SubscriptionKind, cx: &App) -> SharedString {
+    match kind {
+        SubscriptionKind::Channel => i18n_pubsub(cx, "kind_channel"),
+        SubscriptionKind::Pattern => i18n_pubsub(cx, "kind_pattern"),
+        SubscriptionKind::Shard => i18n_pubsub(cx, "kind_shard"),
+    }
+}
+
+/// Appends entries to a capped log.
+///
+/// # Returns
+/// The number of entries dropped from the front.
+fn push_capped(log: &mut VecDeque<PubSubEntry>, entries: Vec<PubSubEntry>) -> usize {
+    log.extend(entries);
+    let overflow = log.len().saturating_sub(MAX_MESSAGES);
+    log.drain(..overflow);
+    overflow
+}
+
+pub struct ZedisPubSubPanel {
+    server_state: Entity<ZedisServerState>,
+    subscribe_input: Entity<InputState>,
+    channel_filter_input: Entity<InputState>,
+    publish_channel_input: Entity<InputState>,
+    publish_message_input: Entity<InputState>,
+    scroll_handle: UniformListScrollHandle,
+
+    /// Subscription type used to subscribe and publish
+    kind: SubscriptionKind,
+    /// Opened on the first subscription, closed when another server is selected
+    session: Option<Arc<Mutex<PubSubSession>>>,
+    subscriptions: Vec<(SubscriptionKind, SharedString)>,
+    /// A subscription request is in flight
+    subscribing: bool,
+
+    messages: VecDeque<PubSubEntry>,
+    /// Messages received while paused
+    pending: VecDeque<PubSubEntry>,
+    paused: bool,
+    selected: Option<usize>,
+
+    _receive_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisPubSubPanel {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut subscriptions = Vec::new();
+        let subscribe_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_pubsub(cx, "subscribe_placeholder")));
+        let channel_filter_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_pubsub(cx, "channels_placeholder")));
+        let publish_channel_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_pubsub(cx, "publish_channel_placeholder")));
+        let publish_message_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_pubsub(cx, "publish_message_placeholder")));
+
+        subscriptions.push(cx.subscribe_in(&subscribe_input, window, |this, _, event, window, cx| {
+            if let InputEvent::PressEnter { .. } = event {
+                this.subscribe(window, cx);
+            }
+        }));
+        subscriptions.push(cx.subscribe(&channel_filter_input, |this, _, event, cx| {
+            if let InputEvent::PressEnter { .. } = event {
+                this.load_channels(cx);
+            }
+        }));
+        subscriptions.push(
+            cx.subscribe_in(&publish_message_input, window, |this, _, event, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    this.publish(window, cx);
+                }
+            }),
+        );
+        subscriptions.push(cx.subscribe(&server_state, |this, _, event, cx| {
+            if let ServerEvent::ServerSelected(_, _) = event {
+                this.reset(cx);
+            }
+        }));
+        subscriptions.push(cx.observe(&server_state, |_, _, cx| cx.notify()));
+
+        Self {
+            server_state,
+            subscribe_input,
+            channel_filter_input,
+            publish_channel_input,
+            publish_message_input,
+            scroll_handle: UniformListScrollHandle::new(),
+            kind: SubscriptionKind::Channel,
+            session: None,
+            subscriptions: Vec::new(),
+            subscribing: false,
+            messages: VecDeque::new(),
+            pending: VecDeque::new(),
+            paused: false,
+            selected: None,
+            _receive_task: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Focuses the subscription input.
+    pub fn focus(&self, window: &mut Window, cx: &mut Context<Self>) {
+        self.subscribe_input.update(cx, |state, cx| state.focus(window, cx));
+    }
+
+    /// Closes the session and clears the log.
+    fn reset(&mut self, cx: &mut Context<Self>) {
+        self.session = None;
+        self._receive_task = None;
+        self.subscriptions.clear();
+        self.subscribing = false;
+        self.messages.clear();
+        self.pending.clear();
+        self.paused = false;
+        self.selected = None;
+        cx.notify();
+    }
+
+    fn notify_error(&self, message: String, cx: &mut Context<Self>) {
+        self.server_state.update(cx, |_, cx| {
+            cx.emit(ServerEvent::Notification(NotificationAction::new_error(message.into())));
+        });
+    }
+
+    /// Forwards the session messages to the log, decoding them in the background.
+    fn start_receiving(&mut self, mut receiver: UnboundedReceiver<PubSubMessage>, cx: &mut Context<Self>) {
+        self._receive_task = Some(cx.spawn(async move |handle, cx| {
+            while let Some(message) = receiver.next().await {
+                let mut batch = vec![message];
+                while batch.len() < MAX_BATCH
+                    && let Ok(Some(message)) = receiver.try_next()
+                {
+                    batch.push(message);
+                }
+                let entries = cx
+                    .background_spawn(async move { batch.into_iter().map(PubSubEntry::new).collect::<Vec<_>>() })
+                    .await;
+                if handle.update(cx, |this, cx| this.push_entries(entries, cx)).is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+
+    fn push_entries(&mut self, entries: Vec<PubSubEntry>, cx: &mut Context<Self>) {
+        if self.paused {
+            push_capped(&mut self.pending, entries);
+            cx.notify();
+            return;
+        }
+        let dropped = push_capped(&mut self.messages, entries);
+        self.selected = self.selected.and_then(|index| index.checked_sub(dropped));
+        if !self.messages.is_empty() {
+            self.scroll_handle
+                .scroll_to_item(self.messages.len() - 1, ScrollStrategy::Bottom);
+        }
+        cx.notify();
+    }
+
+    fn toggle_pause(&mut self, cx: &mut Context<Self>) {
+        self.paused = !self.paused;
+        if !self.paused {
+            let pending: Vec<_> = self.pending.drain(..).collect();
+            self.push_entries(pending, cx);
+        }
+        cx.notify();
+    }
+
+    fn clear(&mut self, cx: &mut Context<Self>) {
+        self.messages.clear();
+        self.pending.clear();
+        self.selected = None;
+        cx.notify();
+    }
+
+    fn export(&mut self, cx: &mut Context<Self>) {
+        if self.messages.is_empty() {
+            return;
+        }
+        let messages: Vec<PubSubMessage> = self.messages.iter().map(|entry| entry.message.clone()).collect();
+        let server_state = self.server_state.clone();
+        let file_name = format!("pubsub-{}.ndjson", Local::now().format("%Y%m%d-%H%M%S"));
+        cx.spawn(async move |_, cx| {
+            let handle = rfd::AsyncFileDialog::new()
+                .add_filter("NDJSON", &["ndjson", "jsonl"])
+                .set_file_name(file_name)
+                .save_file()
+                .await;
+            if let Some(file) = handle {
+                let path = file.path().to_path_buf();
+                let _ = server_state.update(cx, |state, cx| {
+                    state.export_pubsub_log(path, messages, cx);
+                });
+            }
+        })
+        .detach();
+    }
+
+    fn set_kind(&mut self, kind: SubscriptionKind, cx: &mut Context<Self>) {
+        self.kind = kind;
+        cx.notify();
+    }
+
+    fn subscribe(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.subscribing {
+            return;
+        }
+        let value = self.subscribe_input.read(cx).value();
+        let names: Vec<String> = value
+            .split_whitespace()
+            .filter(|name| !self.is_subscribed(self.kind, name))
+            .map(String::from)
+            .collect();
+        self.subscribe_input.update(cx, |state, cx| {
+            state.set_value(SharedString::default(), window, cx);
+        });
+        if names.is_empty() {
+            return;
+        }
+        self.subscribe_names(self.kind, names, cx);
+    }
+
+    fn is_subscribed(&self, kind: SubscriptionKind, name: &str) -> bool {
+        self.subscriptions.iter().any(|(k, n)| *k == kind && n == name)
+    }
+
+    fn subscribe_names(&mut self, kind: SubscriptionKind, names: Vec<String>, cx: &mut Context<Self>) {
+        let server_state = self.server_state.read(cx);
+        let server_id = server_state.server_id().to_string();
+        let db = server_state.db();
+        let session = self.session.clone();
+        self.subscribing = true;
+        cx.notify();
+        cx.spawn(async move |handle, cx| {
+            let current_server_id = server_id.clone();
+            let result = cx
+                .background_spawn(async move {
+                    let (session, receiver) = match session {
+                        Some(session) => (session, None),
+                        None => {
+                            let (session, receiver) = PubSubSession::open(&server_id, db).await?;
+                            (Arc::new(Mutex::new(session)), Some(receiver))
+                        }
+                    };
+                    // A new session is kept even if the subscription fails, its connection is reused
+                    let result = session.lock().await.subscribe(kind, &names).await.map(|_| names);
+                    Ok::<_, Error>((session, receiver, result))
+                })
+                .await;
+            handle.update(cx, |this, cx| {
+                // Another server was selected in the meantime
+                if this.server_state.read(cx).server_id() != current_server_id {
+                    return;
+                }
+                this.subscribing = false;
+                let result = result.and_then(|(session, receiver, result)| {
+                    if let Some(receiver) = receiver {
+                        this.session = Some(session);
+                        this.start_receiving(receiver, cx);
+                    }
+                    result
+                });
+                match result {
+                    Ok(names) => this
+                        .subscriptions
+                        .extend(names.into_iter().map(|name| (kind, SharedString::from(name)))),
+                    Err(e) => this.notify_error(e.to_string(), cx),
+                }
+                cx.notify();
+            })
+        })
+        .detach();
+    }
+
+    fn unsubscribe(&mut self, kind: SubscriptionKind, name: SharedString, cx: &mut Context<Self>) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        self.subscriptions.retain(|(k, n)| !(*k == kind && *n == name));
+        cx.notify();
+        cx.spawn(async move |handle, cx| {
+            let names = [name.to_string()];
+            let result = cx
+                .background_spawn(async move { session.lock().await.unsubscribe(kind, &names).await })
+                .await;
+            if let Err(e) = result {
+                let _ = handle.update(cx, |this, cx| this.notify_error(e.to_string(), cx));
+            }
+        })
+        .detach();
+    }
+
+    fn load_channels(&mut self, cx: &mut Context<Self>) {
+        let pattern = self.channel_filter_input.read(cx).value().trim().to_string();
+        self.server_state.update(cx, |state, cx| {
+            state.load_pubsub_channels(pattern.into(), cx);
+        });
+    }
+
+    fn publish(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let channel = self.publish_channel_input.read(cx).value().trim().to_string();
+        if channel.is_empty() {
+            return;
+        }
+        let message = self.publish_message_input.read(cx).value();
+        self.publish_message_input.update(cx, |state, cx| {
+            state.set_value(SharedString::default(), window, cx);
+        });
+        let kind = self.kind;
+        self.server_state.update(cx, |state, cx| {
+            state.publish_message(kind, channel.into(), message, cx);
+        });
+    }
+
+    fn render_kind_selector(&self, cx: &Context<Self>) -> impl IntoElement {
+        let current = self.kind;
+        let panel = cx.entity().downgrade();
+        DropdownButton::new("pubsub-kind-dropdown")
+            .button(
+                Button::new("pubsub-kind-btn")
+                    .outline()
+                    .small()
+                    .label(kind_label(current, cx))
+                    .tooltip(i18n_pubsub(cx, "kind_tooltip")),
+            )
+            .dropdown_menu_with_anchor(Corner::TopLeft, move |menu, _, cx| {
+                [
+                    SubscriptionKind::Channel,
+                    SubscriptionKind::Pattern,
+                    SubscriptionKind::Shard,
+                ]
+                .into_iter()
+                .fold(menu, |menu, kind| {
+                    let panel = panel.clone();
+                    menu.item(
+                        PopupMenuItem::new(kind_label(kind, cx))
+                            .checked(kind == current)
+                            .on_click(move |_, _, cx| {
+                                let _ = panel.update(cx, |this, cx| this.set_kind(kind, cx));
+                            }),
+                    )
+                })
+            })
+    }
+
+    fn render_subscriptions(&self, cx: &Context<Self>) -> impl IntoElement {
+        let muted = cx.theme().muted_foreground;
+        if self.subscriptions.is_empty() {
+            return h_flex()
+                .px_2()
+                .child(
+                    Label::new(i18n_pubsub(cx, "no_subscriptions"))
+                        .text_sm()
+                        .text_color(muted),
+                )
+                .into_any_element();
+        }
+        let unsubscribe_tooltip = i18n_pubsub(cx, "unsubscribe_tooltip");
+        h_flex()
+            .px_2()
+            .gap_1()
+            .flex_wrap()
+            .children(self.subscriptions.iter().enumerate().map(|(index, (kind, name))| {
+                let (kind, name) = (*kind, name.clone());
+                let prefix = match kind {
+                    SubscriptionKind::Channel => "",
+                    SubscriptionKind::Pattern => "P ",
+                    SubscriptionKind::Shard => "S ",
+                };
+                Button::new(("pubsub-subscription", index))
+                    .outline()
+                    .xsmall()
+                    .label(format!("{prefix}{name}"))
+                    .icon(IconName::Close)
+                    .tooltip(unsubscribe_tooltip.clone())
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.unsubscribe(kind, name.clone(), cx);
+                    }))
+            }))
+            .into_any_element()
+    }
+
+    fn render_toolbar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let status = if self.paused {
+            t!("pubsub.paused", count = self.pending.len(), locale = locale)
+        } else {
+            t!("pubsub.messages", count = self.messages.len(), locale = locale)
+        };
+        let pause_label = if self.paused {
+            i18n_pubsub(cx, "resume")
+        } else {
+            i18n_pubsub(cx, "pause")
+        };
+        h_flex()
+            .px_2()
+            .gap_2()
+            .items_center()
+            .child(
+                Label::new(status.to_string())
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .flex_1(),
+            )
+            .child(
+                Button::new("pubsub-pause-btn")
+                    .outline()
+                    .small()
+                    .selected(self.paused)
+                    .label(pause_label)
+                    .on_click(cx.listener(|this, _, _, cx| this.toggle_pause(cx))),
+            )
+            .child(
+                Button::new("pubsub-clear-btn")
+                    .outline()
+                    .small()
+                    .icon(IconName::Delete)
+                    .label(i18n_pubsub(cx, "clear"))
+                    .on_click(cx.listener(|this, _, _, cx| this.clear(cx))),
+            )
+            .child(
+                Button::new("pubsub-export-btn")
+                    .outline()
+                    .small()
+                    .icon(IconName::File)
+                    .label(i18n_pubsub(cx, "export"))
+                    .disabled(self.messages.is_empty())
+                    .on_click(cx.listener(|this, _, _, cx| this.export(cx))),
+            )
+    }
+
+    fn render_messages(&self, font_family: &SharedString, cx: &Context<Self>) -> AnyElement {
+        if self.messages.is_empty() {
+            return v_flex()
+                .flex_1()
+                .items_center()
+                .justify_center()
+                .child(
+                    Label::new(i18n_pubsub(cx, "no_messages"))
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .into_any_element();
+        }
+        let theme = cx.theme();
+        let (muted, channel_color, selected_bg, hover_bg) =
+            (theme.muted_foreground, theme.blue, theme.list_active, theme.list_hover);
+        let entries: Vec<PubSubEntry> = self.messages.iter().cloned().collect();
+        let selected = self.selected;
+        let font_family = font_family.clone();
+        let view = cx.entity().downgrade();
+        uniform_list("pubsub-messages", entries.len(), move |range, _window, _cx| {
+            range
+                .filter_map(|index| entries.get(index).map(|entry| (index, entry)))
+                .map(|(index, entry)| {
+                    let view = view.clone();
+                    h_flex()
+                        .id(("pubsub-message", index))
+                        .h(px(ROW_HEIGHT))
+                        .px_2()
+                        .gap_2()
+                        .w_full()
+                        .items_center()
+                        .when(selected == Some(index), |this| this.bg(selected_bg))
+                        .hover(|this| this.bg(hover_bg))
+                        .on_click(move |_, _, cx| {
+                            let _ = view.update(cx, |this, cx| {
+                                this.selected = if this.selected == Some(index) {
+                                    None
+                                } else {
+                                    Some(index)
+                                };
+                                cx.notify();
+                            });
+                        })
+                        .child(
+                            Label::new(entry.time.clone())
+                                .text_xs()
+                                .font_family(font_family.clone())
+                                .text_color(muted),
+                        )
+                        .child(
+                            Label::new(entry.source.clone())
+                                .text_sm()
+                                .text_color(channel_color)
+                                .max_w(px(CHANNELS_WIDTH))
+                                .truncate(),
+                        )
+                        .child(Label::new(entry.format.clone()).text_xs().text_color(muted))
+                        .child(
+                            Label::new(entry.preview.clone())
+                                .text_sm()
+                                .font_family(font_family.clone())
+                                .flex_1()
+                                .truncate(),
+                        )
+                })
+                .collect()
+        })
+        .track_scroll(self.scroll_handle.clone())
+        .flex_1()
+        .w_full()
+        .into_any_element()
+    }
+
+    /// Renders the decoded payload of the selected message.
+    fn render_detail(&self, font_family: &SharedString, cx: &Context<Self>) -> Option<AnyElement> {
+        let entry = self.messages.get(self.selected?)?;
+        let lines = entry
+            .text
+            .lines()
+            .take(MAX_DETAIL_LINES)
+            .map(|line| Label::new(line.to_string()).font_family(font_family.clone()));
+        Some(
+            v_flex()
+                .id("pubsub-message-detail")
+                .h(px(200.))
+                .w_full()
+                .p_2()
+                .border_t_1()
+                .border_color(cx.theme().border)
+                .overflow_y_scroll()
+                .child(
+                    Label::new(format!("{}  {}  {}", entry.time, entry.source, entry.format))
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .children(lines)
+                .into_any_element(),
+        )
+    }
+
+    fn render_channels(&self, cx: &Context<Self>) -> impl IntoElement {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale().to_string();
+        let channels = self.server_state.read(cx).pubsub_channels().unwrap_or_default();
+        let muted = cx.theme().muted_foreground;
+        let tooltip = i18n_pubsub(cx, "channel_subscribe_tooltip");
+        let list: AnyElement = if channels.is_empty() {
+            Label::new(i18n_pubsub(cx, "no_channels"))
+                .text_sm()
+                .text_color(muted)
+                .into_any_element()
+        } else {
+            v_flex()
+                .children(channels.iter().enumerate().map(|(index, channel)| {
+                    let name = channel.name.clone();
+                    let subscribers = t!("pubsub.subscribers", count = channel.subscribers, locale = locale);
+                    h_flex()
+                        .id(("pubsub-channel", index))
+                        .px_1()
+                        .gap_2()
+                        .justify_between()
+                        .hover(|this| this.bg(cx.theme().list_hover))
+                        .child(Label::new(name.clone()).text_sm().truncate())
+                        .child(Label::new(subscribers.to_string()).text_xs().text_color(muted))
+                        .tooltip({
+                            let tooltip = tooltip.clone();
+                            move |window, cx| gpui_component::tooltip::Tooltip::new(tooltip.clone()).build(window, cx)
+                        })
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            if this.subscribing || this.is_subscribed(SubscriptionKind::Channel, &name) {
+                                return;
+                            }
+                            this.subscribe_names(SubscriptionKind::Channel, vec![name.to_string()], cx);
+                        }))
+                }))
+                .into_any_element()
+        };
+        v_flex()
+            .w(px(CHANNELS_WIDTH))
+            .h_full()
+            .p_2()
+            .gap_2()
+            .border_l_1()
+            .border_color(cx.theme().border)
+            .child(Label::new(i18n_pubsub(cx, "channels")).text_sm())
+            .child(
+                Input::new(&self.channel_filter_input).small().suffix(
+                    Button::new("pubsub-channels-refresh")
+                        .ghost()
+                        .xsmall()
+                        .icon(IconName::Search)
+                        .tooltip(i18n_pubsub(cx, "refresh"))
+                        .on_click(cx.listener(|this, _, _, cx| this.load_channels(cx))),
+                ),
+            )
+            .child(div().id("pubsub-channels").flex_1().overflow_y_scroll().child(list))
+    }
+}
+
+impl Render for ZedisPubSubPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let font_family: SharedString = get_font_family().into();
+        let messages = self.render_messages(&font_family, cx);
+        let detail = self.render_detail(&font_family, cx);
+
+        v_flex()
+            .w_full()
+            .h_full()
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .child(self.render_kind_selector(cx))
+                    .child(div().flex_1().child(Input::new(&self.subscribe_input).small()))
+                    .child(
+                        Button::new("pubsub-subscribe-btn")
+                            .primary()
+                            .small()
+                            .icon(IconName::Bell)
+                            .label(i18n_pubsub(cx, "subscribe"))
+                            .loading(self.subscribing)
+                            .disabled(self.subscribing)
+                            .on_click(cx.listener(|this, _, window, cx| this.subscribe(window, cx))),
+                    ),
+            )
+            .child(self.render_subscriptions(cx))
+            .child(div().pt_2().child(self.render_toolbar(cx)))
+            .child(
+                h_flex()
+                    .flex_1()
+                    .w_full()
+                    .mt_1()
+                    .border_t_1()
+                    .border_color(cx.theme().border)
+                    .child(v_flex().flex_1().h_full().child(messages).children(detail))
+                    .child(self.render_channels(cx)),
+            )
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .border_t_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        div()
+                            .w(px(CHANNELS_WIDTH))
+                            .child(Input::new(&self.publish_channel_input).small()),
+                    )
+                    .child(div().flex_1().child(Input::new(&self.publish_message_input).small()))
+                    .child(
+                        Button::new("pubsub-publish-btn")
+                            .outline()
+                            .small()
+                            .label(i18n_pubsub(cx, "publish"))
+                            .on_click(cx.listener(|this, _, window, cx| this.publish(window, cx))),
+                    ),
+            )
+    }
+}
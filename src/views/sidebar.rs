@@ -15,7 +15,7 @@
 use crate::connection::get_connection_manager;
 use crate::{
     assets::CustomIconName,
-    connection::RedisServer,
+    connection::{RedisServer, format_jump_hosts, parse_jump_hosts, parse_sentinel_addrs},
     constants::GITHUB_REPOSITORY_URL,
     helpers::{MemuAction, is_development, is_windows, validate_common_string, validate_host, validate_long_string},
     states::{
@@ -186,6 +186,11 @@ impl ZedisSidebar {
                 .validate(|s, _cx| validate_host(s))
         });
         let port_state = cx.new(|cx| InputState::new(window, cx).placeholder(i18n_common(cx, "port_placeholder")));
+        let socket_path_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "socket_path_placeholder"))
+                .validate(|s, _cx| validate_long_string(s))
+        });
         let username_state = cx.new(|cx| {
             InputState::new(window, cx)
                 .placeholder(i18n_common(cx, "username_placeholder"))
@@ -219,6 +224,27 @@ impl ZedisSidebar {
                 .placeholder(i18n_servers(cx, "master_name_placeholder"))
                 .validate(|s, _cx| validate_common_string(s))
         });
+        let sentinel_nodes_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "sentinel_nodes_placeholder"))
+                .validate(|s, _cx| validate_long_string(s))
+        });
+        let sentinel_username_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "sentinel_username_placeholder"))
+                .validate(|s, _cx| validate_common_string(s))
+        });
+        let sentinel_password_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "sentinel_password_placeholder"))
+                .validate(|s, _cx| validate_common_string(s))
+                .masked(true)
+        });
+        let sentinel_replica_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "sentinel_replica_placeholder"))
+                .validate(|s, _cx| validate_host(s))
+        });
         let ssh_addr_state = cx.new(|cx| {
             InputState::new(window, cx)
                 .placeholder(i18n_servers(cx, "ssh_addr_placeholder"))
@@ -240,6 +266,16 @@ impl ZedisSidebar {
                 .auto_grow(cert_min_rows, cert_max_rows)
                 .placeholder(i18n_servers(cx, "ssh_key_placeholder"))
         });
+        let ssh_key_passphrase_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "ssh_key_passphrase_placeholder"))
+                .masked(true)
+        });
+        let ssh_jump_hosts_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "ssh_jump_hosts_placeholder"))
+                .validate(|s, _cx| validate_long_string(s))
+        });
         let description_state = cx.new(|cx| {
             InputState::new(window, cx)
                 .placeholder(i18n_common(cx, "description_placeholder"))
@@ -258,6 +294,9 @@ impl ZedisSidebar {
                 state.set_value(server.port.to_string(), window, cx);
             });
         }
+        socket_path_state.update(cx, |state, cx| {
+            state.set_value(server.socket_path.clone().unwrap_or_default(), window, cx);
+        });
         username_state.update(cx, |state, cx| {
             state.set_value(server.username.clone().unwrap_or_default(), window, cx);
         });
@@ -276,6 +315,18 @@ impl ZedisSidebar {
         master_name_state.update(cx, |state, cx| {
             state.set_value(server.master_name.clone().unwrap_or_default(), window, cx);
         });
+        sentinel_nodes_state.update(cx, |state, cx| {
+            state.set_value(server.sentinel_nodes.clone().unwrap_or_default().join(", "), window, cx);
+        });
+        sentinel_username_state.update(cx, |state, cx| {
+            state.set_value(server.sentinel_username.clone().unwrap_or_default(), window, cx);
+        });
+        sentinel_password_state.update(cx, |state, cx| {
+            state.set_value(server.sentinel_password.clone().unwrap_or_default(), window, cx);
+        });
+        sentinel_replica_state.update(cx, |state, cx| {
+            state.set_value(server.sentinel_replica.clone().unwrap_or_default(), window, cx);
+        });
         ssh_addr_state.update(cx, |state, cx| {
             state.set_value(server.ssh_addr.clone().unwrap_or_default(), window, cx);
         });
@@ -288,6 +339,13 @@ impl ZedisSidebar {
         ssh_key_state.update(cx, |state, cx| {
             state.set_value(server.ssh_key.clone().unwrap_or_default(), window, cx);
         });
+        ssh_key_passphrase_state.update(cx, |state, cx| {
+            state.set_value(server.ssh_key_passphrase.clone().unwrap_or_default(), window, cx);
+        });
+        ssh_jump_hosts_state.update(cx, |state, cx| {
+            let jump_hosts = format_jump_hosts(server.ssh_jump_hosts.as_deref().unwrap_or_default());
+            state.set_value(jump_hosts, window, cx);
+        });
         description_state.update(cx, |state, cx| {
             state.set_value(server.description.clone().unwrap_or_default(), window, cx);
         });
@@ -303,16 +361,23 @@ impl ZedisSidebar {
         let name_state_clone = name_state.clone();
         let host_state_clone = host_state.clone();
         let port_state_clone = port_state.clone();
+        let socket_path_state_clone = socket_path_state.clone();
         let username_state_clone = username_state.clone();
         let password_state_clone = password_state.clone();
         let client_cert_state_clone = client_cert_state.clone();
         let client_key_state_clone = client_key_state.clone();
         let root_cert_state_clone = root_cert_state.clone();
         let master_name_state_clone = master_name_state.clone();
+        let sentinel_nodes_state_clone = sentinel_nodes_state.clone();
+        let sentinel_username_state_clone = sentinel_username_state.clone();
+        let sentinel_password_state_clone = sentinel_password_state.clone();
+        let sentinel_replica_state_clone = sentinel_replica_state.clone();
         let ssh_addr_state_clone = ssh_addr_state.clone();
         let ssh_username_state_clone = ssh_username_state.clone();
         let ssh_password_state_clone = ssh_password_state.clone();
         let ssh_key_state_clone = ssh_key_state.clone();
+        let ssh_key_passphrase_state_clone = ssh_key_passphrase_state.clone();
+        let ssh_jump_hosts_state_clone = ssh_jump_hosts_state.clone();
         let description_state_clone = description_state.clone();
         let server_enable_tls_for_submit = server_enable_tls.clone();
         let server_insecure_tls_for_submit = server_insecure_tls.clone();
@@ -323,8 +388,15 @@ impl ZedisSidebar {
             let name = name_state_clone.read(cx).value();
             let host = host_state_clone.read(cx).value();
             let port = port_state_clone.read(cx).value().parse::<u16>().unwrap_or(6379);
+            let socket_path_val = socket_path_state_clone.read(cx).value();
+            let socket_path = if socket_path_val.is_empty() {
+                None
+            } else {
+                Some(socket_path_val)
+            };
 
-            if name.is_empty() || host.is_empty() {
+            // A unix socket needs no host
+            if name.is_empty() || (host.is_empty() && socket_path.is_none()) {
                 return false;
             }
 
@@ -378,6 +450,15 @@ impl ZedisSidebar {
             } else {
                 Some(master_name_val)
             };
+            let sentinel_nodes = Some(parse_sentinel_addrs(&sentinel_nodes_state_clone.read(cx).value()))
+                .filter(|nodes| !nodes.is_empty());
+            let sentinel_value = |state: &Entity<InputState>| {
+                let value = state.read(cx).value();
+                (!value.is_empty()).then(|| value.to_string())
+            };
+            let sentinel_username = sentinel_value(&sentinel_username_state_clone);
+            let sentinel_password = sentinel_value(&sentinel_password_state_clone);
+            let sentinel_replica = sentinel_value(&sentinel_replica_state_clone);
 
             let desc_val = description_state_clone.read(cx).value();
             let description = if desc_val.is_empty() { None } else { Some(desc_val) };
@@ -407,6 +488,9 @@ impl ZedisSidebar {
             } else {
                 Some(ssh_key_val)
             };
+            // Saved even when empty, a missing passphrase marks the configurations to migrate
+            let ssh_key_passphrase = ssh_key_passphrase_state_clone.read(cx).value();
+            let ssh_jump_hosts_val = ssh_jump_hosts_state_clone.read(cx).value();
 
             // Get current server for preserving non-editable fields
             let current_server = server_state
@@ -421,9 +505,14 @@ impl ZedisSidebar {
                 name: name.to_string(),
                 host: host.to_string(),
                 port,
+                socket_path: socket_path.map(|p| p.to_string()),
                 username: username.map(|u| u.to_string()),
                 password: password.map(|p| p.to_string()),
                 master_name: master_name.map(|m| m.to_string()),
+                sentinel_nodes,
+                sentinel_username,
+                sentinel_password,
+                sentinel_replica,
                 description: description.map(|d| d.to_string()),
                 tls: if enable_tls { Some(enable_tls) } else { None },
                 insecure: insecure_tls,
@@ -435,6 +524,12 @@ impl ZedisSidebar {
                 ssh_username: ssh_username.map(|u| u.to_string()),
                 ssh_password: ssh_password.map(|p| p.to_string()),
                 ssh_key: ssh_key.map(|k| k.to_string()),
+                ssh_key_passphrase: Some(ssh_key_passphrase.to_string()),
+                ssh_jump_hosts: Some(parse_jump_hosts(
+                    &ssh_jump_hosts_val,
+                    current_server.ssh_jump_hosts.as_deref().unwrap_or_default(),
+                ))
+                .filter(|hosts| !hosts.is_empty()),
                 ..current_server
             };
 
@@ -476,6 +571,7 @@ impl ZedisSidebar {
             let name_label = i18n_common(cx, "name");
             let host_label = i18n_common(cx, "host");
             let port_label = i18n_common(cx, "port");
+            let socket_path_label = i18n_servers(cx, "socket_path");
             let username_label = i18n_common(cx, "username");
             let password_label = i18n_common(cx, "password");
             let tls_label = i18n_common(cx, "tls");
@@ -487,10 +583,16 @@ impl ZedisSidebar {
             let root_cert_label = i18n_common(cx, "root_cert");
             let description_label = i18n_common(cx, "description");
             let master_name_label = i18n_servers(cx, "master_name");
+            let sentinel_nodes_label = i18n_servers(cx, "sentinel_nodes");
+            let sentinel_username_label = i18n_servers(cx, "sentinel_username");
+            let sentinel_password_label = i18n_servers(cx, "sentinel_password");
+            let sentinel_replica_label = i18n_servers(cx, "sentinel_replica");
             let ssh_addr_label = i18n_servers(cx, "ssh_addr");
             let ssh_username_label = i18n_servers(cx, "ssh_username");
             let ssh_password_label = i18n_servers(cx, "ssh_password");
             let ssh_key_label = i18n_servers(cx, "ssh_key");
+            let ssh_key_passphrase_label = i18n_servers(cx, "ssh_key_passphrase");
+            let ssh_jump_hosts_label = i18n_servers(cx, "ssh_jump_hosts");
             let ssh_tunnel_label = i18n_servers(cx, "ssh_tunnel");
             let ssh_tunnel_check_label = i18n_servers(cx, "ssh_tunnel_check_label");
 
@@ -508,6 +610,7 @@ impl ZedisSidebar {
                         .child(field().label(name_label).child(Input::new(&name_state)))
                         .child(field().label(host_label).child(Input::new(&host_state)))
                         .child(field().label(port_label).child(NumberInput::new(&port_state)))
+                        .child(field().label(socket_path_label).child(Input::new(&socket_path_state)))
                         .child(field().label(username_label).child(Input::new(&username_state)))
                         .child(
                             field()
@@ -558,12 +661,45 @@ impl ZedisSidebar {
                             .child(field().label(ssh_addr_label).child(Input::new(&ssh_addr_state)))
                             .child(field().label(ssh_username_label).child(Input::new(&ssh_username_state)))
                             .child(field().label(ssh_password_label).child(Input::new(&ssh_password_state)))
-                            .child(field().label(ssh_key_label).child(Input::new(&ssh_key_state)));
+                            .child(field().label(ssh_key_label).child(Input::new(&ssh_key_state)))
+                            .child(
+                                field()
+                                    .label(ssh_key_passphrase_label)
+                                    .child(Input::new(&ssh_key_passphrase_state).mask_toggle()),
+                            )
+                            .child(
+                                field()
+                                    .label(ssh_jump_hosts_label)
+                                    .child(Input::new(&ssh_jump_hosts_state)),
+                            );
                     }
 
-                    form = form
-                        .child(field().label(master_name_label).child(Input::new(&master_name_state)))
-                        .child(field().label(description_label).child(Input::new(&description_state)));
+                    form = form.child(field().label(master_name_label).child(Input::new(&master_name_state)));
+                    // The Sentinel settings only apply with a master name
+                    if !master_name_state.read(cx).value().is_empty() {
+                        form = form
+                            .child(
+                                field()
+                                    .label(sentinel_nodes_label)
+                                    .child(Input::new(&sentinel_nodes_state)),
+                            )
+                            .child(
+                                field()
+                                    .label(sentinel_username_label)
+                                    .child(Input::new(&sentinel_username_state)),
+                            )
+                            .child(
+                                field()
+                                    .label(sentinel_password_label)
+                                    .child(Input::new(&sentinel_password_state).mask_toggle()),
+                            )
+                            .child(
+                                field()
+                                    .label(sentinel_replica_label)
+                                    .child(Input::new(&sentinel_replica_state)),
+                            );
+                    }
+                    form = form.child(field().label(description_label).child(Input::new(&description_state)));
 
                     div()
                         .id("edit-servers-scrollable-container")
@@ -14,16 +14,16 @@
 
 use crate::{
     assets::CustomIconName,
-    connection::{KeyBackupProgress, KeyBackupProgressPhase, RedisClientDescription},
+    connection::{BulkKeyPhase, BulkKeyProgress, KeyBackupProgress, KeyBackupProgressPhase, RedisClientDescription},
     helpers::humanize_keystroke,
     states::{
-        DataFormat, ErrorMessage, ServerEvent, ServerTask, ViewMode, ZedisGlobalStore, ZedisServerState, i18n_common,
-        i18n_sidebar, i18n_status_bar,
+        DataFormat, ErrorMessage, ServerEvent, ServerPanel, ServerTask, ViewMode, ZedisGlobalStore, ZedisServerState,
+        i18n_bulk, i18n_common, i18n_sidebar, i18n_status_bar,
     },
 };
 use chrono::Local;
 use directories::UserDirs;
-use gpui::{Entity, Hsla, SharedString, Subscription, Task, TextAlign, Window, div, prelude::*, px};
+use gpui::{App, Div, Entity, Hsla, SharedString, Subscription, Task, TextAlign, Window, div, prelude::*, px};
 use gpui_component::select::{SearchableVec, Select, SelectEvent, SelectItem, SelectState};
 use gpui_component::{
     ActiveTheme, Disableable, Icon, IconName, IndexPath, Sizable, WindowExt,
@@ -45,6 +45,36 @@ fn format_size(dbsize: Option<u64>, scan_count: usize) -> SharedString {
     }
     .into()
 }
+/// Renders the label and progress bar of a long running operation, the bar stays
+/// empty while the total is unknown.
+fn render_progress(label: SharedString, processed: usize, total: Option<usize>, cx: &App) -> Div {
+    let (ratio, text) = if let Some(total) = total {
+        let ratio = if total > 0 {
+            (processed as f32 / total as f32).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        (ratio, format!("{} {}/{}", label, processed, total))
+    } else {
+        (0.0, format!("{} {}", label, processed))
+    };
+
+    h_flex()
+        .items_center()
+        .mr_3()
+        .gap_2()
+        .child(Label::new(text).text_xs())
+        .child(
+            div().w(px(96.)).h(px(6.)).rounded(px(3.)).bg(cx.theme().muted).child(
+                div()
+                    .h_full()
+                    .w(gpui::relative(ratio))
+                    .rounded(px(3.))
+                    .bg(cx.theme().primary),
+            ),
+        )
+}
+
 /// Formats the latency string and determines the color based on the delay.
 #[inline]
 fn format_latency(latency: Option<Duration>, cx: &Context<ZedisStatusBar>) -> (SharedString, Hsla) {
@@ -79,7 +109,11 @@ fn format_nodes(nodes: (usize, usize), version: &str) -> SharedString {
 #[inline]
 fn format_nodes_description(description: Arc<RedisClientDescription>, cx: &Context<ZedisStatusBar>) -> SharedString {
     let t = i18n_sidebar(cx, "server_type");
-    let master_nodes = i18n_sidebar(cx, "master_nodes");
+    let master_nodes = if description.readonly {
+        i18n_sidebar(cx, "readonly_replica")
+    } else {
+        i18n_sidebar(cx, "master_nodes")
+    };
     let slave_nodes = i18n_sidebar(cx, "slave_nodes");
     let mut messages = Vec::with_capacity(3);
     messages.push(format!("{t}: {}", description.server_type.as_str()));
@@ -165,6 +199,7 @@ struct StatusBarState {
     data_format_type: Option<DataFormat>,
     error: Option<ErrorMessage>,
     key_backup_progress: Option<KeyBackupProgress>,
+    bulk_progress: Option<BulkKeyProgress>,
     protobuf_types: Vec<SharedString>,
 }
 
@@ -214,6 +249,7 @@ impl ZedisStatusBar {
                 ServerEvent::ErrorOccurred(error) => {
                     this.state.error = Some(error.clone());
                     this.state.key_backup_progress = None;
+                    this.state.bulk_progress = None;
                 }
                 ServerEvent::TaskStarted(task) => {
                     // Clear error when a new task starts (except background ping)
@@ -252,6 +288,20 @@ impl ZedisStatusBar {
                 ServerEvent::KeyBackupExported(_, _) | ServerEvent::KeyBackupRestored(_, _) => {
                     this.state.key_backup_progress = None;
                 }
+                ServerEvent::BulkKeyProgress(progress) => {
+                    this.state.bulk_progress = Some(progress.clone());
+                }
+                ServerEvent::KeyDeletePreviewed(_)
+                | ServerEvent::KeysDeleted(_)
+                | ServerEvent::KeysTtlUpdated(_)
+                | ServerEvent::KeysCopied
+                | ServerEvent::KeysExported(_)
+                | ServerEvent::KeysImported(_, _)
+                | ServerEvent::ScriptImported(_)
+                | ServerEvent::MemoryAnalyzed(_)
+                | ServerEvent::HotKeysAnalyzed(_) => {
+                    this.state.bulk_progress = None;
+                }
                 _ => {
                     return;
                 }
@@ -426,6 +476,84 @@ impl ZedisStatusBar {
                     }))
                     .mr_2(),
             )
+            .child(
+                Button::new("zedis-status-bar-server-pubsub")
+                    .outline()
+                    .small()
+                    .tooltip(i18n_status_bar(cx, "toggle_pubsub_tooltip"))
+                    .icon(IconName::Bell)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.toggle_panel(ServerPanel::PubSub, cx);
+                        });
+                    }))
+                    .mr_2(),
+            )
+            .child(
+                Button::new("zedis-status-bar-server-diagnostics")
+                    .outline()
+                    .small()
+                    .tooltip(i18n_status_bar(cx, "toggle_diagnostics_tooltip"))
+                    .icon(CustomIconName::Activity)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.toggle_panel(ServerPanel::Diagnostics, cx);
+                        });
+                    }))
+                    .mr_2(),
+            )
+            .child(
+                Button::new("zedis-status-bar-server-metrics")
+                    .outline()
+                    .small()
+                    .tooltip(i18n_status_bar(cx, "toggle_metrics_tooltip"))
+                    .icon(IconName::ChartPie)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.toggle_panel(ServerPanel::Metrics, cx);
+                        });
+                    }))
+                    .mr_2(),
+            )
+            .child(
+                Button::new("zedis-status-bar-server-clients")
+                    .outline()
+                    .small()
+                    .tooltip(i18n_status_bar(cx, "toggle_clients_tooltip"))
+                    .icon(IconName::CircleUser)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.toggle_panel(ServerPanel::Clients, cx);
+                        });
+                    }))
+                    .mr_2(),
+            )
+            .child(
+                Button::new("zedis-status-bar-server-memory")
+                    .outline()
+                    .small()
+                    .tooltip(i18n_status_bar(cx, "toggle_memory_tooltip"))
+                    .icon(CustomIconName::MemoryStick)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.toggle_panel(ServerPanel::Memory, cx);
+                        });
+                    }))
+                    .mr_2(),
+            )
+            .child(
+                Button::new("zedis-status-bar-server-hot-keys")
+                    .outline()
+                    .small()
+                    .tooltip(i18n_status_bar(cx, "toggle_hot_keys_tooltip"))
+                    .icon(CustomIconName::Zap)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.toggle_panel(ServerPanel::HotKeys, cx);
+                        });
+                    }))
+                    .mr_2(),
+            )
             .when(server_state.supports_db_selection, |this| {
                 this.child(Select::new(&self.db_state).mr_2().mt_1().small())
             })
@@ -539,30 +667,35 @@ impl ZedisStatusBar {
             KeyBackupProgressPhase::Export => i18n_status_bar(cx, "backup_progress"),
             KeyBackupProgressPhase::Restore => i18n_status_bar(cx, "restore_progress"),
         };
-        let (ratio, text) = if let Some(total) = progress.total {
-            let ratio = if total > 0 {
-                (progress.processed as f32 / total as f32).clamp(0.0, 1.0)
-            } else {
-                0.0
-            };
-            (ratio, format!("{} {}/{}", label, progress.processed, total))
-        } else {
-            (0.0, format!("{} {}", label, progress.processed))
-        };
+        render_progress(label, progress.processed, progress.total, cx).into_any_element()
+    }
 
-        h_flex()
-            .items_center()
-            .mr_3()
-            .gap_2()
-            .child(Label::new(text).text_xs())
+    fn render_bulk_progress(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(progress) = &self.state.bulk_progress else {
+            return h_flex().into_any_element();
+        };
+        let label = match progress.phase {
+            BulkKeyPhase::Count => i18n_bulk(cx, "counting_progress"),
+            BulkKeyPhase::Delete => i18n_bulk(cx, "delete_progress"),
+            BulkKeyPhase::Ttl => i18n_bulk(cx, "ttl_progress"),
+            BulkKeyPhase::Copy => i18n_bulk(cx, "copy_progress"),
+            BulkKeyPhase::Export => i18n_bulk(cx, "export_progress"),
+            BulkKeyPhase::Import => i18n_bulk(cx, "import_progress"),
+            BulkKeyPhase::Analyze => i18n_bulk(cx, "analyze_progress"),
+            BulkKeyPhase::HotKeys => i18n_bulk(cx, "hot_keys_progress"),
+        };
+        render_progress(label, progress.processed, progress.total, cx)
             .child(
-                div().w(px(96.)).h(px(6.)).rounded(px(3.)).bg(cx.theme().muted).child(
-                    div()
-                        .h_full()
-                        .w(gpui::relative(ratio))
-                        .rounded(px(3.))
-                        .bg(cx.theme().primary),
-                ),
+                Button::new("zedis-status-bar-cancel-bulk")
+                    .ghost()
+                    .xsmall()
+                    .icon(IconName::Close)
+                    .tooltip(i18n_bulk(cx, "cancel"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.cancel_bulk_operation(cx);
+                        });
+                    })),
             )
             .into_any_element()
     }
@@ -757,6 +890,7 @@ impl Render for ZedisStatusBar {
             .child(self.render_server_status(window, cx))
             .child(self.render_editor_settings(window, cx))
             .child(self.render_key_backup_progress(window, cx))
+            .child(self.render_bulk_progress(window, cx))
             .child(self.render_data_format(window, cx))
             .child(self.render_protobuf_controls(window, cx))
             .child(self.render_viewer_mode(window, cx))
@@ -0,0 +1,382 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redis STREAM editor UI component.
+//!
+//! This module provides a table-based editor for viewing and managing Redis STREAM values.
+//! It supports operations like:
+//! - Viewing stream entries (ID and field-value pairs) page by page
+//! - Switching between oldest-first and newest-first order
+//! - Appending entries via a dialog form (XADD)
+//! - Removing entries (XDEL)
+//! - Trimming the stream by length or minimum ID (XTRIM)
+//! - Filtering loaded entries by ID, field or value
+//! - Inspecting and managing consumer groups
+
+use crate::{
+    assets::CustomIconName,
+    components::{FormDialog, FormField, ZedisKvFetcher, open_add_form_dialog},
+    states::{RedisValue, ServerEvent, SortOrder, StreamTrimStrategy, ZedisServerState, i18n_stream_editor},
+    views::{KvTableColumn, ZedisKvTable, ZedisStreamGroupPanel},
+};
+use gpui::{App, Entity, SharedString, Subscription, Window, div, prelude::*};
+use gpui_component::{ActiveTheme, Disableable, IconName, Selectable, WindowExt, button::Button, h_flex, v_flex};
+use std::rc::Rc;
+
+/// Parses the XADD fields input into field-value pairs.
+///
+/// Accepts either a JSON object (`{"field": "value"}`) or whitespace separated
+/// `field value` pairs. Returns `None` when the input is empty or unbalanced.
+fn parse_stream_fields(input: &str) -> Option<Vec<(SharedString, SharedString)>> {
+    let input = input.trim();
+    if input.starts_with('{') {
+        let serde_json::Value::Object(map) = serde_json::from_str(input).ok()? else {
+            return None;
+        };
+        let fields: Vec<(SharedString, SharedString)> = map
+            .into_iter()
+            .map(|(field, value)| {
+                let value = match value {
+                    serde_json::Value::String(value) => value,
+                    value => value.to_string(),
+                };
+                (field.into(), value.into())
+            })
+            .collect();
+        return (!fields.is_empty()).then_some(fields);
+    }
+
+    let tokens: Vec<&str> = input.split_whitespace().collect();
+    if tokens.is_empty() || !tokens.len().is_multiple_of(2) {
+        return None;
+    }
+    Some(
+        tokens
+            .chunks(2)
+            .map(|pair| (pair[0].to_string().into(), pair[1].to_string().into()))
+            .collect(),
+    )
+}
+
+/// Data adapter for Redis STREAM values to work with the KV table component.
+///
+/// Renders each entry as two columns: the entry ID and its field-value pairs.
+struct ZedisStreamValues {
+    /// Current Redis STREAM value data
+    value: RedisValue,
+    /// Reference to server state for executing Redis operations
+    server_state: Entity<ZedisServerState>,
+}
+
+impl ZedisKvFetcher for ZedisStreamValues {
+    fn new(server_state: Entity<ZedisServerState>, value: RedisValue) -> Self {
+        Self { server_state, value }
+    }
+
+    fn get(&self, row_ix: usize, col_ix: usize) -> Option<SharedString> {
+        let stream = self.value.stream_value()?;
+        let entry = stream.values.get(row_ix)?;
+
+        if col_ix == 0 {
+            return Some(entry.id.clone());
+        }
+        let fields = entry
+            .fields
+            .iter()
+            .map(|(field, value)| format!("{field}: {value}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(fields.into())
+    }
+
+    /// Returns the total number of entries in the STREAM (from Redis XLEN).
+    fn count(&self) -> usize {
+        self.value.stream_value().map_or(0, |v| v.size)
+    }
+
+    fn rows_count(&self) -> usize {
+        self.value.stream_value().map_or(0, |v| v.values.len())
+    }
+
+    fn is_done(&self) -> bool {
+        self.value.stream_value().is_some_and(|v| v.done)
+    }
+
+    fn load_more(&self, _window: &mut Window, cx: &mut App) {
+        self.server_state.update(cx, |this, cx| {
+            this.load_more_stream_value(cx);
+        });
+    }
+
+    /// Removes the entry at the given index with XDEL.
+    fn remove(&self, index: usize, cx: &mut App) {
+        self.remove_many(vec![index], cx);
+    }
+
+    fn can_remove_many(&self) -> bool {
+        true
+    }
+
+    fn remove_many(&self, indexes: Vec<usize>, cx: &mut App) {
+        let Some(stream) = self.value.stream_value() else {
+            return;
+        };
+        let ids: Vec<SharedString> = indexes
+            .into_iter()
+            .filter_map(|index| stream.values.get(index).map(|entry| entry.id.clone()))
+            .collect();
+
+        self.server_state.update(cx, |this, cx| {
+            this.remove_stream_values(ids, cx);
+        });
+    }
+
+    fn filter(&self, keyword: SharedString, cx: &mut App) -> bool {
+        self.server_state
+            .update(cx, |this, cx| this.filter_stream_value(keyword, cx))
+    }
+
+    /// Opens a dialog to append a new entry to the STREAM.
+    fn handle_add_value(&self, window: &mut Window, cx: &mut App) {
+        let server_state = self.server_state.clone();
+
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 2 {
+                return false;
+            }
+            let Some(fields) = parse_stream_fields(&values[1]) else {
+                return false;
+            };
+
+            server_state.update(cx, |this, cx| {
+                this.add_stream_value(values[0].clone(), fields, cx);
+            });
+
+            window.close_dialog(cx);
+            true
+        });
+
+        let fields = vec![
+            FormField::new(i18n_stream_editor(cx, "id")).with_placeholder(i18n_stream_editor(cx, "id_placeholder")),
+            FormField::new(i18n_stream_editor(cx, "fields"))
+                .with_placeholder(i18n_stream_editor(cx, "fields_placeholder"))
+                .with_focus()
+                .with_validate(|s| parse_stream_fields(s).is_some()),
+        ];
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_stream_editor(cx, "add_value_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+}
+
+/// Main STREAM editor view component.
+///
+/// Wraps the generic `ZedisKvTable` with a toolbar for the sort order, XTRIM
+/// and switching to the consumer group panel.
+pub struct ZedisStreamEditor {
+    /// Reference to server state for toolbar actions
+    server_state: Entity<ZedisServerState>,
+    /// The table component that renders the STREAM entries
+    table_state: Entity<ZedisKvTable<ZedisStreamValues>>,
+    /// Consumer group panel, created the first time it is opened
+    group_panel: Option<Entity<ZedisStreamGroupPanel>>,
+    /// Whether the consumer group panel is shown instead of the entries
+    show_groups: bool,
+    /// Event subscriptions to refresh the toolbar
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisStreamEditor {
+    /// Creates a new STREAM editor instance.
+    ///
+    /// # Arguments
+    /// * `server_state` - Reference to the server state for Redis operations
+    /// * `window` - GPUI window handle
+    /// * `cx` - GPUI context for component initialization
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let subscriptions = vec![cx.subscribe(&server_state, |_this, _, event, cx| {
+            if matches!(
+                event,
+                ServerEvent::ValueLoaded(_) | ServerEvent::ValueUpdated(_) | ServerEvent::ValuePaginationFinished(_)
+            ) {
+                cx.notify();
+            }
+        })];
+        let table_state = cx.new(|cx| {
+            ZedisKvTable::<ZedisStreamValues>::new(
+                vec![KvTableColumn::new("ID", Some(220.)), KvTableColumn::new("Fields", None)],
+                server_state.clone(),
+                window,
+                cx,
+            )
+        });
+
+        Self {
+            server_state,
+            table_state,
+            group_panel: None,
+            show_groups: false,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Focuses the keyword filter input field.
+    pub fn focus_keyword(&self, window: &mut Window, cx: &mut Context<Self>) {
+        self.table_state.update(cx, |state, cx| {
+            state.focus_keyword(window, cx);
+        });
+    }
+
+    /// Opens a dialog to trim the stream with XTRIM MAXLEN or MINID.
+    fn handle_trim(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 3 || values[1].trim().is_empty() {
+                return false;
+            }
+            let strategy = if values[0] == "1" {
+                StreamTrimStrategy::MinId
+            } else {
+                StreamTrimStrategy::MaxLen
+            };
+            let approximate = values[2] == "1";
+
+            server_state.update(cx, |this, cx| {
+                this.trim_stream(strategy, values[1].clone(), approximate, cx);
+            });
+
+            window.close_dialog(cx);
+            true
+        });
+
+        let fields = vec![
+            FormField::new(i18n_stream_editor(cx, "trim_strategy")).with_options(vec![
+                StreamTrimStrategy::MaxLen.as_str().into(),
+                StreamTrimStrategy::MinId.as_str().into(),
+            ]),
+            FormField::new(i18n_stream_editor(cx, "trim_threshold"))
+                .with_placeholder(i18n_stream_editor(cx, "trim_threshold_placeholder"))
+                .with_focus(),
+            FormField::new(i18n_stream_editor(cx, "trim_mode")).with_options(vec![
+                i18n_stream_editor(cx, "trim_exact"),
+                i18n_stream_editor(cx, "trim_approximate"),
+            ]),
+        ];
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_stream_editor(cx, "trim_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+}
+
+impl Render for ZedisStreamEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (sort_order, is_busy) = self
+            .server_state
+            .read(cx)
+            .value()
+            .map(|value| {
+                (
+                    value.stream_value().map(|stream| stream.sort_order).unwrap_or_default(),
+                    value.is_busy(),
+                )
+            })
+            .unwrap_or_default();
+        let readonly = self.server_state.read(cx).is_readonly();
+        let (sort_icon, sort_tooltip) = if sort_order == SortOrder::Asc {
+            (IconName::SortAscending, i18n_stream_editor(cx, "sort_asc_tooltip"))
+        } else {
+            (IconName::SortDescending, i18n_stream_editor(cx, "sort_desc_tooltip"))
+        };
+        let groups_tooltip = if self.show_groups {
+            i18n_stream_editor(cx, "entries_tooltip")
+        } else {
+            i18n_stream_editor(cx, "groups_tooltip")
+        };
+
+        let content = if self.show_groups {
+            let server_state = self.server_state.clone();
+            self.group_panel
+                .get_or_insert_with(|| cx.new(|cx| ZedisStreamGroupPanel::new(server_state, cx)))
+                .clone()
+                .into_any_element()
+        } else {
+            self.table_state.clone().into_any_element()
+        };
+
+        v_flex()
+            .size_full()
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .w_full()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        Button::new("stream-editor-sort-btn")
+                            .outline()
+                            .disabled(is_busy || self.show_groups)
+                            .icon(sort_icon)
+                            .tooltip(sort_tooltip)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.server_state.update(cx, |state, cx| {
+                                    state.toggle_stream_sort_order(cx);
+                                });
+                            })),
+                    )
+                    .child(
+                        Button::new("stream-editor-trim-btn")
+                            .outline()
+                            .disabled(is_busy || self.show_groups || readonly)
+                            .icon(CustomIconName::ListChecvronsDownUp)
+                            .tooltip(i18n_stream_editor(cx, "trim_tooltip"))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.handle_trim(window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("stream-editor-groups-btn")
+                            .outline()
+                            .selected(self.show_groups)
+                            .icon(CustomIconName::Network)
+                            .tooltip(groups_tooltip)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.show_groups = !this.show_groups;
+                                // Drop the panel so groups are reloaded the next time it is opened
+                                if !this.show_groups {
+                                    this.group_panel = None;
+                                }
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(div().flex_1().w_full().child(content))
+            .into_any_element()
+    }
+}
@@ -0,0 +1,605 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redis STREAM consumer group panel.
+//!
+//! Shows the consumer groups of the selected stream together with the consumers
+//! and pending entries of one group, and exposes the XGROUP/XACK/XCLAIM actions.
+
+use crate::{
+    assets::CustomIconName,
+    components::{FormDialog, FormField, open_add_form_dialog},
+    helpers::format_duration,
+    states::{RedisStreamGroupsValue, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_stream_editor},
+};
+use gpui::{AnyElement, App, Entity, SharedString, Subscription, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Icon, IconName, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    scroll::ScrollableElement,
+    v_flex,
+};
+use rust_i18n::t;
+use std::{rc::Rc, time::Duration};
+
+const CELL_WIDTH: f32 = 160.0;
+const ID_CELL_WIDTH: f32 = 220.0;
+const DEFAULT_AUTOCLAIM_COUNT: usize = 100;
+
+/// Formats an idle time in milliseconds, keeping sub-second precision for short values.
+fn format_idle(ms: u64) -> SharedString {
+    if ms < 1000 {
+        format!("{ms}ms").into()
+    } else {
+        format_duration(Duration::from_millis(ms)).into()
+    }
+}
+
+fn is_u64(value: &str) -> bool {
+    value.trim().parse::<u64>().is_ok()
+}
+
+/// Consumer group panel shown inside the stream editor.
+pub struct ZedisStreamGroupPanel {
+    server_state: Entity<ZedisServerState>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisStreamGroupPanel {
+    pub fn new(server_state: Entity<ZedisServerState>, cx: &mut Context<Self>) -> Self {
+        let subscriptions = vec![
+            cx.subscribe(&server_state, |this, server_state, event, cx| match event {
+                // Another stream was selected, its groups have not been loaded yet
+                ServerEvent::ValueLoaded(_) => {
+                    if this.groups(cx).is_none() {
+                        server_state.update(cx, |state, cx| {
+                            state.load_stream_groups(None, cx);
+                        });
+                    }
+                    cx.notify();
+                }
+                ServerEvent::ValueUpdated(_) => {
+                    cx.notify();
+                }
+                _ => {}
+            }),
+        ];
+        // Groups are loaded on demand the first time the panel is opened
+        server_state.update(cx, |state, cx| {
+            state.load_stream_groups(None, cx);
+        });
+
+        Self {
+            server_state,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn groups(&self, cx: &App) -> Option<RedisStreamGroupsValue> {
+        self.server_state
+            .read(cx)
+            .value()
+            .and_then(|value| value.stream_value())
+            .and_then(|stream| stream.groups.clone())
+    }
+
+    fn handle_create_group(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 2 || values[0].trim().is_empty() {
+                return false;
+            }
+            let id = if values[1].trim().is_empty() {
+                "$".into()
+            } else {
+                values[1].trim().to_string().into()
+            };
+            server_state.update(cx, |state, cx| {
+                state.create_stream_group(values[0].trim().to_string().into(), id, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_stream_editor(cx, "create_group_title"),
+                fields: vec![
+                    FormField::new(i18n_stream_editor(cx, "group"))
+                        .with_placeholder(i18n_stream_editor(cx, "group_placeholder"))
+                        .with_focus(),
+                    FormField::new(i18n_stream_editor(cx, "start_id"))
+                        .with_placeholder(i18n_stream_editor(cx, "start_id_placeholder")),
+                ],
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn handle_set_group_id(&mut self, group: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some(id) = values.first().filter(|id| !id.trim().is_empty()) else {
+                return false;
+            };
+            let id: SharedString = id.trim().to_string().into();
+            let group = group.clone();
+            server_state.update(cx, |state, cx| {
+                state.set_stream_group_id(group, id, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_stream_editor(cx, "set_group_id_title"),
+                fields: vec![
+                    FormField::new(i18n_stream_editor(cx, "start_id"))
+                        .with_placeholder(i18n_stream_editor(cx, "start_id_placeholder"))
+                        .with_focus(),
+                ],
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn handle_destroy_group(&mut self, group: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!("stream_editor.destroy_group_prompt", group = group, locale = locale).to_string();
+            let server_state = server_state.clone();
+            let group = group.clone();
+            dialog.confirm().child(message).on_ok(move |_, window, cx| {
+                let group = group.clone();
+                server_state.update(cx, |state, cx| {
+                    state.destroy_stream_group(group, cx);
+                });
+                window.close_dialog(cx);
+                true
+            })
+        });
+    }
+
+    fn handle_delete_consumer(
+        &mut self,
+        group: SharedString,
+        consumer: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let server_state = self.server_state.clone();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!(
+                "stream_editor.delete_consumer_prompt",
+                consumer = consumer,
+                locale = locale
+            )
+            .to_string();
+            let server_state = server_state.clone();
+            let group = group.clone();
+            let consumer = consumer.clone();
+            dialog.confirm().child(message).on_ok(move |_, window, cx| {
+                let group = group.clone();
+                let consumer = consumer.clone();
+                server_state.update(cx, |state, cx| {
+                    state.delete_stream_consumer(group, consumer, cx);
+                });
+                window.close_dialog(cx);
+                true
+            })
+        });
+    }
+
+    fn handle_claim(&mut self, group: SharedString, id: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 2 || values[0].trim().is_empty() {
+                return false;
+            }
+            let min_idle_time = values[1].trim().parse::<u64>().unwrap_or_default();
+            let consumer: SharedString = values[0].trim().to_string().into();
+            let group = group.clone();
+            let id = id.clone();
+            server_state.update(cx, |state, cx| {
+                state.claim_stream_entries(group, consumer, min_idle_time, vec![id], cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_stream_editor(cx, "claim_title"),
+                fields: vec![
+                    FormField::new(i18n_stream_editor(cx, "consumer")).with_focus(),
+                    FormField::new(i18n_stream_editor(cx, "min_idle_time"))
+                        .with_placeholder("0".into())
+                        .with_validate(|s| s.is_empty() || is_u64(s)),
+                ],
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn handle_autoclaim(&mut self, group: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 3 || values[0].trim().is_empty() {
+                return false;
+            }
+            let consumer: SharedString = values[0].trim().to_string().into();
+            let min_idle_time = values[1].trim().parse::<u64>().unwrap_or_default();
+            let count = values[2].trim().parse::<usize>().unwrap_or(DEFAULT_AUTOCLAIM_COUNT);
+            let group = group.clone();
+            server_state.update(cx, |state, cx| {
+                state.autoclaim_stream_entries(group, consumer, min_idle_time, count, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_stream_editor(cx, "autoclaim_title"),
+                fields: vec![
+                    FormField::new(i18n_stream_editor(cx, "consumer")).with_focus(),
+                    FormField::new(i18n_stream_editor(cx, "min_idle_time"))
+                        .with_placeholder("0".into())
+                        .with_validate(|s| s.is_empty() || is_u64(s)),
+                    FormField::new(i18n_stream_editor(cx, "count"))
+                        .with_placeholder(DEFAULT_AUTOCLAIM_COUNT.to_string().into())
+                        .with_validate(|s| s.is_empty() || is_u64(s)),
+                ],
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Renders one table row of fixed-width cells followed by action buttons.
+    fn render_row(cells: Vec<(SharedString, f32)>, actions: Vec<AnyElement>, cx: &App) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .children(
+                cells
+                    .into_iter()
+                    .map(|(text, width)| div().w(px(width)).truncate().text_sm().child(text)),
+            )
+            .child(h_flex().gap_1().children(actions))
+    }
+
+    fn render_section_title(title: SharedString, cx: &App) -> impl IntoElement {
+        Label::new(title)
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .px_2()
+            .pt_3()
+            .pb_1()
+    }
+}
+
+impl Render for ZedisStreamGroupPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_busy = self.server_state.read(cx).value().is_some_and(|value| value.is_busy());
+        // a replica browsed read-only can't change the groups
+        let readonly = self.server_state.read(cx).is_readonly();
+        let groups = self.groups(cx).unwrap_or_default();
+
+        // Group selector, create and refresh buttons
+        let mut toolbar = h_flex().p_2().gap_2().w_full().flex_wrap();
+        for (index, group) in groups.groups.iter().enumerate() {
+            let name = group.name.clone();
+            let selected = groups.selected_group.as_ref() == Some(&group.name);
+            let btn = Button::new(("stream-group-select", index))
+                .small()
+                .label(format!("{} ({})", group.name, group.pending))
+                .disabled(is_busy)
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    let name = name.clone();
+                    this.server_state.update(cx, |state, cx| {
+                        state.load_stream_groups(Some(name), cx);
+                    });
+                }));
+            toolbar = toolbar.child(if selected { btn.primary() } else { btn.outline() });
+        }
+        toolbar = toolbar
+            .child(
+                Button::new("stream-group-create")
+                    .small()
+                    .outline()
+                    .icon(IconName::Plus)
+                    .disabled(readonly || is_busy)
+                    .tooltip(i18n_stream_editor(cx, "create_group_tooltip"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_create_group(window, cx);
+                    })),
+            )
+            .child(
+                Button::new("stream-group-refresh")
+                    .small()
+                    .outline()
+                    .icon(CustomIconName::RotateCw)
+                    .disabled(is_busy)
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.load_stream_groups(None, cx);
+                        });
+                    })),
+            );
+
+        let Some(group) = groups
+            .selected_group
+            .as_ref()
+            .and_then(|name| groups.groups.iter().find(|group| &group.name == name))
+            .cloned()
+        else {
+            return v_flex()
+                .size_full()
+                .child(toolbar)
+                .child(
+                    Label::new(i18n_stream_editor(cx, "no_groups"))
+                        .p_2()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .into_any_element();
+        };
+
+        // Selected group summary and group level actions
+        let optional =
+            |value: Option<u64>| -> SharedString { value.map(|v| v.to_string()).unwrap_or("--".into()).into() };
+        let summary = h_flex()
+            .px_2()
+            .gap_4()
+            .flex_wrap()
+            .text_sm()
+            .child(format!(
+                "{}: {}",
+                i18n_stream_editor(cx, "last_delivered_id"),
+                group.last_delivered_id
+            ))
+            .child(format!("{}: {}", i18n_stream_editor(cx, "consumers"), group.consumers))
+            .child(format!("{}: {}", i18n_stream_editor(cx, "pending"), group.pending))
+            .child(format!(
+                "{}: {}",
+                i18n_stream_editor(cx, "entries_read"),
+                optional(group.entries_read)
+            ))
+            .child(format!("{}: {}", i18n_stream_editor(cx, "lag"), optional(group.lag)));
+
+        let group_name = group.name.clone();
+        let group_actions = h_flex()
+            .px_2()
+            .pt_2()
+            .gap_2()
+            .child(
+                Button::new("stream-group-set-id")
+                    .small()
+                    .outline()
+                    .icon(CustomIconName::FilePenLine)
+                    .label(i18n_stream_editor(cx, "set_group_id_title"))
+                    .tooltip(i18n_stream_editor(cx, "set_group_id_tooltip"))
+                    .disabled(readonly || is_busy)
+                    .on_click(cx.listener({
+                        let group = group_name.clone();
+                        move |this, _, window, cx| {
+                            this.handle_set_group_id(group.clone(), window, cx);
+                        }
+                    })),
+            )
+            .child(
+                Button::new("stream-group-autoclaim")
+                    .small()
+                    .outline()
+                    .icon(IconName::User)
+                    .label(i18n_stream_editor(cx, "autoclaim_title"))
+                    .tooltip(i18n_stream_editor(cx, "autoclaim_tooltip"))
+                    .disabled(readonly || is_busy)
+                    .on_click(cx.listener({
+                        let group = group_name.clone();
+                        move |this, _, window, cx| {
+                            this.handle_autoclaim(group.clone(), window, cx);
+                        }
+                    })),
+            )
+            .child(
+                Button::new("stream-group-destroy")
+                    .small()
+                    .outline()
+                    .icon(IconName::CircleX)
+                    .tooltip(i18n_stream_editor(cx, "destroy_group_tooltip"))
+                    .disabled(readonly || is_busy)
+                    .on_click(cx.listener({
+                        let group = group_name.clone();
+                        move |this, _, window, cx| {
+                            this.handle_destroy_group(group.clone(), window, cx);
+                        }
+                    })),
+            );
+
+        // Consumers of the selected group
+        let consumer_header = Self::render_row(
+            vec![
+                (i18n_stream_editor(cx, "consumer"), CELL_WIDTH),
+                (i18n_stream_editor(cx, "pending"), CELL_WIDTH),
+                (i18n_stream_editor(cx, "idle"), CELL_WIDTH),
+                (i18n_stream_editor(cx, "inactive"), CELL_WIDTH),
+            ],
+            vec![],
+            cx,
+        );
+        let consumer_rows: Vec<AnyElement> = groups
+            .consumers
+            .iter()
+            .enumerate()
+            .map(|(index, consumer)| {
+                let group = group_name.clone();
+                let name = consumer.name.clone();
+                let inactive = consumer
+                    .inactive
+                    .filter(|ms| *ms >= 0)
+                    .map(|ms| format_idle(ms as u64))
+                    .unwrap_or("--".into());
+                Self::render_row(
+                    vec![
+                        (consumer.name.clone(), CELL_WIDTH),
+                        (consumer.pending.to_string().into(), CELL_WIDTH),
+                        (format_idle(consumer.idle), CELL_WIDTH),
+                        (inactive, CELL_WIDTH),
+                    ],
+                    vec![
+                        Button::new(("stream-consumer-delete", index))
+                            .small()
+                            .ghost()
+                            .icon(Icon::new(CustomIconName::FileXCorner))
+                            .tooltip(i18n_stream_editor(cx, "delete_consumer_tooltip"))
+                            .disabled(readonly || is_busy)
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.handle_delete_consumer(group.clone(), name.clone(), window, cx);
+                            }))
+                            .into_any_element(),
+                    ],
+                    cx,
+                )
+                .into_any_element()
+            })
+            .collect();
+
+        // Pending entries of the selected group
+        let pending_ids: Vec<SharedString> = groups.pending.iter().map(|entry| entry.id.clone()).collect();
+        let pending_header = Self::render_row(
+            vec![
+                ("ID".into(), ID_CELL_WIDTH),
+                (i18n_stream_editor(cx, "consumer"), CELL_WIDTH),
+                (i18n_stream_editor(cx, "idle"), CELL_WIDTH),
+                (i18n_stream_editor(cx, "delivered"), CELL_WIDTH),
+            ],
+            vec![
+                Button::new("stream-pending-ack-all")
+                    .small()
+                    .ghost()
+                    .icon(IconName::Check)
+                    .tooltip(i18n_stream_editor(cx, "ack_all_tooltip"))
+                    .disabled(readonly || is_busy || pending_ids.is_empty())
+                    .on_click(cx.listener({
+                        let group = group_name.clone();
+                        move |this, _, _, cx| {
+                            let group = group.clone();
+                            let ids = pending_ids.clone();
+                            this.server_state.update(cx, |state, cx| {
+                                state.ack_stream_entries(group, ids, cx);
+                            });
+                        }
+                    }))
+                    .into_any_element(),
+            ],
+            cx,
+        );
+        let pending_rows: Vec<AnyElement> = groups
+            .pending
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let ack_group = group_name.clone();
+                let ack_id = entry.id.clone();
+                let claim_group = group_name.clone();
+                let claim_id = entry.id.clone();
+                Self::render_row(
+                    vec![
+                        (entry.id.clone(), ID_CELL_WIDTH),
+                        (entry.consumer.clone(), CELL_WIDTH),
+                        (format_idle(entry.idle), CELL_WIDTH),
+                        (entry.delivered.to_string().into(), CELL_WIDTH),
+                    ],
+                    vec![
+                        Button::new(("stream-pending-ack", index))
+                            .small()
+                            .ghost()
+                            .icon(IconName::Check)
+                            .tooltip(i18n_stream_editor(cx, "ack_tooltip"))
+                            .disabled(readonly || is_busy)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                let group = ack_group.clone();
+                                let id = ack_id.clone();
+                                this.server_state.update(cx, |state, cx| {
+                                    state.ack_stream_entries(group, vec![id], cx);
+                                });
+                            }))
+                            .into_any_element(),
+                        Button::new(("stream-pending-claim", index))
+                            .small()
+                            .ghost()
+                            .icon(IconName::User)
+                            .tooltip(i18n_stream_editor(cx, "claim_tooltip"))
+                            .disabled(readonly || is_busy)
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.handle_claim(claim_group.clone(), claim_id.clone(), window, cx);
+                            }))
+                            .into_any_element(),
+                    ],
+                    cx,
+                )
+                .into_any_element()
+            })
+            .collect();
+
+        v_flex()
+            .size_full()
+            .child(toolbar)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .w_full()
+                    .overflow_y_scrollbar()
+                    .child(summary)
+                    .child(group_actions)
+                    .child(Self::render_section_title(
+                        format!("{} ({})", i18n_stream_editor(cx, "consumers"), groups.consumers.len()).into(),
+                        cx,
+                    ))
+                    .child(consumer_header)
+                    .children(consumer_rows)
+                    .child(Self::render_section_title(
+                        format!(
+                            "{} ({} / {})",
+                            i18n_stream_editor(cx, "pending"),
+                            groups.pending.len(),
+                            group.pending
+                        )
+                        .into(),
+                        cx,
+                    ))
+                    .child(pending_header)
+                    .children(pending_rows),
+            )
+            .into_any_element()
+    }
+}
@@ -0,0 +1,468 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redis vector set editor UI component.
+//!
+//! This module provides a table-based editor for Redis 8 vector sets.
+//! It supports operations like:
+//! - Browsing sampled elements together with their JSON attributes
+//! - Adding elements with a vector and optional attributes (VADD)
+//! - Editing attributes inline (VSETATTR) and removing elements (VREM)
+//! - Running similarity searches by element or by vector (VSIM)
+
+use crate::{
+    components::{FormDialog, FormField, ZedisKvFetcher, open_add_form_dialog},
+    states::{
+        RedisValue, RedisVectorSetSearch, ServerEvent, VectorSetQuery, ZedisServerState, i18n_vector_set_editor,
+        parse_vector,
+    },
+    views::{KvTableColumn, ZedisKvTable},
+};
+use gpui::{AnyElement, App, Entity, SharedString, Subscription, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Icon, IconName, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    scroll::ScrollableElement,
+    v_flex,
+};
+use std::rc::Rc;
+
+const DEFAULT_SEARCH_COUNT: usize = 10;
+const ELEMENT_CELL_WIDTH: f32 = 240.0;
+const SCORE_CELL_WIDTH: f32 = 100.0;
+
+/// Checks that the attributes input is empty or a JSON object, as required by VSETATTR.
+fn is_valid_attributes(input: &str) -> bool {
+    let input = input.trim();
+    input.is_empty() || matches!(serde_json::from_str(input), Ok(serde_json::Value::Object(_)))
+}
+
+/// Data adapter for Redis vector set values to work with the KV table component.
+///
+/// Renders each element as two columns: the element name and its JSON attributes.
+struct ZedisVectorSetValues {
+    /// Current Redis vector set value data
+    value: RedisValue,
+    /// Reference to server state for executing Redis operations
+    server_state: Entity<ZedisServerState>,
+}
+
+impl ZedisKvFetcher for ZedisVectorSetValues {
+    fn new(server_state: Entity<ZedisServerState>, value: RedisValue) -> Self {
+        Self { server_state, value }
+    }
+
+    fn get(&self, row_ix: usize, col_ix: usize) -> Option<SharedString> {
+        let vector_set = self.value.vector_set_value()?;
+        let element = vector_set.values.get(row_ix)?;
+
+        if col_ix == 1 {
+            return Some(element.attributes.clone().unwrap_or_default());
+        }
+        Some(element.name.clone())
+    }
+
+    /// Returns the total number of elements in the vector set (from Redis VCARD).
+    fn count(&self) -> usize {
+        self.value.vector_set_value().map_or(0, |v| v.size)
+    }
+
+    /// Attributes can be edited inline with VSETATTR.
+    fn can_update(&self) -> bool {
+        true
+    }
+
+    fn is_readonly_column(&self, col_ix: usize) -> bool {
+        col_ix == 0
+    }
+
+    fn rows_count(&self) -> usize {
+        self.value.vector_set_value().map_or(0, |v| v.values.len())
+    }
+
+    fn is_done(&self) -> bool {
+        self.value.vector_set_value().is_some_and(|v| v.done)
+    }
+
+    fn load_more(&self, _window: &mut Window, cx: &mut App) {
+        self.server_state.update(cx, |this, cx| {
+            this.load_more_vector_set_value(cx);
+        });
+    }
+
+    /// Removes the element at the given index with VREM.
+    fn remove(&self, index: usize, cx: &mut App) {
+        self.remove_many(vec![index], cx);
+    }
+
+    fn can_remove_many(&self) -> bool {
+        true
+    }
+
+    fn remove_many(&self, indexes: Vec<usize>, cx: &mut App) {
+        let Some(vector_set) = self.value.vector_set_value() else {
+            return;
+        };
+        let elements: Vec<SharedString> = indexes
+            .into_iter()
+            .filter_map(|index| vector_set.values.get(index).map(|element| element.name.clone()))
+            .collect();
+
+        self.server_state.update(cx, |this, cx| {
+            this.remove_vector_set_values(elements, cx);
+        });
+    }
+
+    fn filter(&self, keyword: SharedString, cx: &mut App) -> bool {
+        self.server_state
+            .update(cx, |this, cx| this.filter_vector_set_value(keyword, cx))
+    }
+
+    /// Handles inline editing of an element's attributes.
+    fn handle_update_value(&self, _row_ix: usize, values: Vec<SharedString>, _window: &mut Window, cx: &mut App) {
+        let Some(element) = values.first() else {
+            return;
+        };
+        let Some(attributes) = values.get(1) else {
+            return;
+        };
+        if !is_valid_attributes(attributes) {
+            return;
+        }
+
+        self.server_state.update(cx, |this, cx| {
+            this.update_vector_set_attributes(element.clone(), attributes.clone(), cx);
+        });
+    }
+
+    /// Opens a dialog to add an element with its vector and optional attributes.
+    fn handle_add_value(&self, window: &mut Window, cx: &mut App) {
+        let server_state = self.server_state.clone();
+        let dim = self.value.vector_set_value().map_or(0, |v| v.dim);
+        let is_valid_vector = move |s: &str| parse_vector(s).is_some_and(|v| dim == 0 || v.len() == dim);
+
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 3 || values[0].trim().is_empty() || !is_valid_attributes(&values[2]) {
+                return false;
+            }
+            let Some(vector) = parse_vector(&values[1]).filter(|v| dim == 0 || v.len() == dim) else {
+                return false;
+            };
+            let attributes = values[2].trim();
+            let attributes = (!attributes.is_empty()).then(|| SharedString::from(attributes.to_string()));
+
+            server_state.update(cx, |this, cx| {
+                this.add_vector_set_value(values[0].clone(), vector, attributes, cx);
+            });
+
+            window.close_dialog(cx);
+            true
+        });
+
+        let fields = vec![
+            FormField::new(i18n_vector_set_editor(cx, "element")).with_focus(),
+            FormField::new(i18n_vector_set_editor(cx, "vector"))
+                .with_placeholder(i18n_vector_set_editor(cx, "vector_placeholder"))
+                .with_validate(is_valid_vector),
+            FormField::new(i18n_vector_set_editor(cx, "attributes"))
+                .with_placeholder(i18n_vector_set_editor(cx, "attributes_placeholder"))
+                .with_validate(is_valid_attributes),
+        ];
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_vector_set_editor(cx, "add_value_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+}
+
+/// Main vector set editor view component.
+///
+/// Wraps the generic `ZedisKvTable` with a toolbar showing the VINFO summary and
+/// a VSIM search, whose scored matches replace the table until they are cleared.
+pub struct ZedisVectorSetEditor {
+    /// Reference to server state for toolbar actions
+    server_state: Entity<ZedisServerState>,
+    /// The table component that renders the vector set elements
+    table_state: Entity<ZedisKvTable<ZedisVectorSetValues>>,
+    /// Event subscriptions to refresh the toolbar and search results
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisVectorSetEditor {
+    /// Creates a new vector set editor instance.
+    ///
+    /// # Arguments
+    /// * `server_state` - Reference to the server state for Redis operations
+    /// * `window` - GPUI window handle
+    /// * `cx` - GPUI context for component initialization
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let subscriptions = vec![cx.subscribe(&server_state, |_this, _, event, cx| {
+            if matches!(
+                event,
+                ServerEvent::ValueLoaded(_) | ServerEvent::ValueUpdated(_) | ServerEvent::ValuePaginationFinished(_)
+            ) {
+                cx.notify();
+            }
+        })];
+        let table_state = cx.new(|cx| {
+            ZedisKvTable::<ZedisVectorSetValues>::new(
+                vec![
+                    KvTableColumn::new("Element", Some(ELEMENT_CELL_WIDTH)),
+                    KvTableColumn::new("Attributes", None),
+                ],
+                server_state.clone(),
+                window,
+                cx,
+            )
+        });
+
+        Self {
+            server_state,
+            table_state,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Focuses the keyword filter input field.
+    pub fn focus_keyword(&self, window: &mut Window, cx: &mut Context<Self>) {
+        self.table_state.update(cx, |state, cx| {
+            state.focus_keyword(window, cx);
+        });
+    }
+
+    /// Runs a similarity search using an existing element as the query.
+    fn search_by_element(&mut self, element: SharedString, cx: &mut Context<Self>) {
+        self.server_state.update(cx, |state, cx| {
+            state.search_vector_set(VectorSetQuery::Element(element), DEFAULT_SEARCH_COUNT, None, cx);
+        });
+    }
+
+    /// Opens a dialog to run a VSIM search by element or by vector.
+    fn handle_search(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 4 || values[1].trim().is_empty() {
+                return false;
+            }
+            let query = if values[0] == "1" {
+                let Some(vector) = parse_vector(&values[1]) else {
+                    return false;
+                };
+                VectorSetQuery::Vector(vector)
+            } else {
+                VectorSetQuery::Element(values[1].trim().to_string().into())
+            };
+            let count = values[2].trim().parse::<usize>().unwrap_or(DEFAULT_SEARCH_COUNT);
+            let filter = values[3].trim();
+            let filter = (!filter.is_empty()).then(|| SharedString::from(filter.to_string()));
+
+            server_state.update(cx, |state, cx| {
+                state.search_vector_set(query, count, filter, cx);
+            });
+
+            window.close_dialog(cx);
+            true
+        });
+
+        let fields = vec![
+            FormField::new(i18n_vector_set_editor(cx, "search_by")).with_options(vec![
+                i18n_vector_set_editor(cx, "element"),
+                i18n_vector_set_editor(cx, "vector"),
+            ]),
+            FormField::new(i18n_vector_set_editor(cx, "query"))
+                .with_placeholder(i18n_vector_set_editor(cx, "query_placeholder"))
+                .with_focus(),
+            FormField::new(i18n_vector_set_editor(cx, "count"))
+                .with_placeholder(DEFAULT_SEARCH_COUNT.to_string().into())
+                .with_validate(|s| s.trim().is_empty() || s.trim().parse::<usize>().is_ok_and(|n| n > 0)),
+            FormField::new(i18n_vector_set_editor(cx, "filter"))
+                .with_placeholder(i18n_vector_set_editor(cx, "filter_placeholder")),
+        ];
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_vector_set_editor(cx, "search_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Renders one row of the similarity result table followed by its actions.
+    fn render_row(cells: Vec<(SharedString, Option<f32>)>, actions: Vec<AnyElement>, cx: &App) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .children(cells.into_iter().map(|(text, width)| {
+                let cell = div().truncate().text_sm().child(text);
+                match width {
+                    Some(width) => cell.w(px(width)),
+                    None => cell.flex_1(),
+                }
+            }))
+            .child(h_flex().gap_1().children(actions))
+    }
+
+    /// Renders the scored matches of the last VSIM search.
+    fn render_search(&self, search: RedisVectorSetSearch, is_busy: bool, cx: &mut Context<Self>) -> impl IntoElement {
+        let header = Self::render_row(
+            vec![
+                (i18n_vector_set_editor(cx, "element"), Some(ELEMENT_CELL_WIDTH)),
+                (i18n_vector_set_editor(cx, "score"), Some(SCORE_CELL_WIDTH)),
+                (i18n_vector_set_editor(cx, "attributes"), None),
+            ],
+            vec![],
+            cx,
+        );
+        let rows: Vec<AnyElement> = search
+            .matches
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let name = item.name.clone();
+                Self::render_row(
+                    vec![
+                        (item.name.clone(), Some(ELEMENT_CELL_WIDTH)),
+                        (format!("{:.6}", item.score).into(), Some(SCORE_CELL_WIDTH)),
+                        (item.attributes.clone().unwrap_or_default(), None),
+                    ],
+                    vec![
+                        Button::new(("vector-set-search-similar", index))
+                            .small()
+                            .ghost()
+                            .icon(IconName::Search)
+                            .tooltip(i18n_vector_set_editor(cx, "search_similar_tooltip"))
+                            .disabled(is_busy)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.search_by_element(name.clone(), cx);
+                            }))
+                            .into_any_element(),
+                    ],
+                    cx,
+                )
+                .into_any_element()
+            })
+            .collect();
+
+        v_flex()
+            .size_full()
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .child(
+                        Label::new(format!(
+                            "{}: {} ({})",
+                            i18n_vector_set_editor(cx, "query"),
+                            search.query,
+                            search.matches.len()
+                        ))
+                        .flex_1()
+                        .truncate()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground),
+                    )
+                    .child(
+                        Button::new("vector-set-search-clear")
+                            .small()
+                            .outline()
+                            .icon(Icon::new(IconName::Close))
+                            .tooltip(i18n_vector_set_editor(cx, "clear_search_tooltip"))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.server_state.update(cx, |state, cx| {
+                                    state.clear_vector_set_search(cx);
+                                });
+                            })),
+                    ),
+            )
+            .child(header)
+            .child(v_flex().flex_1().w_full().overflow_y_scrollbar().children(rows))
+    }
+}
+
+impl Render for ZedisVectorSetEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (vector_set, is_busy) = self
+            .server_state
+            .read(cx)
+            .value()
+            .map(|value| (value.vector_set_value().cloned(), value.is_busy()))
+            .unwrap_or_default();
+        let Some(vector_set) = vector_set else {
+            return div().into_any_element();
+        };
+
+        // VINFO summary, e.g. "Dim: 300  quant-type: int8  max-level: 4"
+        let summary = std::iter::once(format!("{}: {}", i18n_vector_set_editor(cx, "dim"), vector_set.dim))
+            .chain(
+                vector_set
+                    .info
+                    .iter()
+                    .filter(|(field, _)| field != "vector-dim" && field != "size")
+                    .map(|(field, value)| format!("{field}: {value}")),
+            )
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        let content = match vector_set.search.clone() {
+            Some(search) => self.render_search(search, is_busy, cx).into_any_element(),
+            None => self.table_state.clone().into_any_element(),
+        };
+
+        v_flex()
+            .size_full()
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .w_full()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        Button::new("vector-set-editor-search-btn")
+                            .outline()
+                            .disabled(is_busy)
+                            .icon(IconName::Search)
+                            .tooltip(i18n_vector_set_editor(cx, "search_tooltip"))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.handle_search(window, cx);
+                            })),
+                    )
+                    .child(
+                        Label::new(summary)
+                            .flex_1()
+                            .truncate()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground),
+                    ),
+            )
+            .child(div().flex_1().w_full().child(content))
+            .into_any_element()
+    }
+}